@@ -0,0 +1,14 @@
+//! Arbitrary YAML through the config loader and stat derivation:
+//! must error cleanly or produce stats, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sim::{BuildConfig, HunterStats};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    if let Ok(config) = BuildConfig::from_yaml(text) {
+        let _ = HunterStats::from_config(&config);
+    }
+});
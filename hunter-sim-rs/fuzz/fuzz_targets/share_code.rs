@@ -0,0 +1,15 @@
+//! Arbitrary share codes must decode to a valid config or error, and any
+//! decoded config must re-encode to a code that decodes again
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sim::share_code;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(code) = std::str::from_utf8(data) else { return };
+    if let Ok(config) = share_code::decode(code) {
+        let again = share_code::encode(&config).expect("decoded config re-encodes");
+        share_code::decode(&again).expect("re-encoded code decodes");
+    }
+});
@@ -0,0 +1,20 @@
+//! Any config that survives `sanitize()` + `normalize()` must simulate to
+//! finite results. Parsing skips the loader's range checks so the fuzzer
+//! also exercises `sanitize()` on out-of-range values.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sim::simulation::run_simulation_with_seed;
+use rust_sim::BuildConfig;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut config) = serde_json::from_slice::<BuildConfig>(data) else { return };
+    config.sanitize();
+    if config.normalize().is_err() {
+        return;
+    }
+    let result = run_simulation_with_seed(&config, 0);
+    assert!(result.elapsed_time.is_finite(), "non-finite elapsed time");
+    assert!(!result.damage.is_nan(), "NaN damage");
+});
@@ -0,0 +1,50 @@
+/* Hunter Simulator C API. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef HUNTER_SIM_H
+#define HUNTER_SIM_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Message of the last failed call on this thread, or NULL if none failed.
+// The pointer stays valid until the next failing call on the same thread;
+// do not free it.
+const char *hunter_sim_last_error(void);
+
+// Release a string returned by this library. NULL is ignored.
+//
+// # Safety
+// `ptr` must be NULL or a pointer returned by a `hunter_sim_*` function that
+// has not been freed yet.
+void hunter_sim_free(char *ptr);
+
+// Run `num_sims` simulations of a config JSON string and return the
+// aggregated stats as JSON (same as Python's `simulate_json`)
+//
+// # Safety
+// `config_json` must be NULL or a NUL-terminated string.
+char *hunter_sim_simulate_json(const char *config_json, size_t num_sims, bool parallel);
+
+// Every derived hunter stat of a config JSON string, as JSON
+//
+// # Safety
+// `config_json` must be NULL or a NUL-terminated string.
+char *hunter_sim_hunter_stats(const char *config_json);
+
+// Itemized loot multiplier of a config JSON string (same as Python's `explain_loot`)
+//
+// # Safety
+// `config_json` must be NULL or a NUL-terminated string.
+char *hunter_sim_explain_loot(const char *config_json);
+
+// Regular enemy and boss stats of one stage for `hunter` ("borge", "ozzy",
+// "knox"), as JSON
+//
+// # Safety
+// `hunter` must be NULL or a NUL-terminated string.
+char *hunter_sim_enemy_stats(const char *hunter, int32_t stage);
+
+#endif  /* HUNTER_SIM_H */
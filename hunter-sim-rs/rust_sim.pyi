@@ -0,0 +1,349 @@
+"""Type stubs for the `rust_sim` extension module (maintained by hand; keep in
+step with src/python.rs when adding or changing bindings).
+
+Parameters typed `ConfigLike` accept either a config JSON string or a
+`BuildConfig`; stats dicts and strings use the `simulate_json` shape.
+`threads=N` runs a call on its own pool of N workers instead of the global
+rayon pool (e.g. to keep cores free for the GUI during an optimization).
+"""
+
+import asyncio
+from typing import Any, Callable, Mapping, Optional, Sequence, Union
+
+import numpy as np
+import numpy.typing as npt
+
+ConfigLike = Union[str, "BuildConfig"]
+Stats = dict[str, Any]
+ProgressCallback = Callable[[int, int, dict[str, Any]], Any]
+
+class ConfigError(ValueError):
+    """A build config (or talent/attribute definition) could not be read, parsed or used"""
+
+    kind: str  # "io", "parse", "invalid", "share_code", "invalid_definition", "unknown_attribute"
+    field: str  # Key path of the offending input, or "" if none
+
+class SimulationError(RuntimeError):
+    """A valid config failed while simulating (e.g. a derived stat came out NaN)"""
+
+    kind: str  # "missing_config", "invalid_stat", "thread_pool", "serialize", "worker"
+
+class BuildConfig:
+    hunter: str
+    level: int
+    stats: dict[str, int]
+    talents: dict[str, int]
+    attributes: dict[str, int]
+    inscryptions: dict[str, int]
+    mods: dict[str, bool]
+    relics: dict[str, int]
+    gems: dict[str, int]
+    gadgets: dict[str, int]
+    bonuses: dict[str, Any]
+
+    def __init__(
+        self,
+        hunter: str = "borge",
+        level: int = 1,
+        stats: Optional[Mapping[str, int]] = None,
+        talents: Optional[Mapping[str, int]] = None,
+        attributes: Optional[Mapping[str, int]] = None,
+        inscryptions: Optional[Mapping[str, int]] = None,
+        mods: Optional[Mapping[str, bool]] = None,
+        relics: Optional[Mapping[str, int]] = None,
+        gems: Optional[Mapping[str, int]] = None,
+        gadgets: Optional[Mapping[str, int]] = None,
+        bonuses: Optional[Mapping[str, Any]] = None,
+    ) -> None: ...
+    @staticmethod
+    def from_json(json: str) -> BuildConfig: ...
+    @staticmethod
+    def from_dict(config: Mapping[str, Any]) -> BuildConfig: ...
+    @staticmethod
+    def from_file(path: str) -> BuildConfig: ...
+    def to_json(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def validate(self) -> None: ...
+    def apply_overrides(self, overrides: Mapping[str, Any]) -> None: ...
+    def copy(self) -> BuildConfig: ...
+    def __copy__(self) -> BuildConfig: ...
+
+class SimResult:
+    final_stage: int
+    elapsed_time: float
+    kills: int
+    damage: float
+    damage_taken: float
+    total_loot: float
+    loot_common: float
+    loot_uncommon: float
+    loot_rare: float
+    total_xp: float
+    event_loot: float
+    event_xp: float
+    attacks: int
+    crits: int
+    extra_damage_from_crits: float
+    multistrikes: int
+    extra_damage_from_ms: float
+    evades: int
+    enemy_attacks: int
+    regenerated_hp: float
+    lifesteal: float
+    overheal_regen: float
+    overheal_lifesteal: float
+    overheal_procs: float
+    mitigated_damage: float
+    total_mitigated_damage: float
+    effect_procs: int
+    lucky_loot_procs: int
+    stun_duration_inflicted: float
+    nominal_speed: float
+    max_hp: float
+    helltouch_barrier: float
+    helltouch_kills: int
+    trample_kills: int
+    medusa_kills: int
+    trickster_evades: int
+    echo_bullets: int
+    unfair_advantage_healing: float  # Attempted, overheal included
+    life_of_the_hunt_healing: float  # Attempted, overheal included
+    unfair_advantage_healing_effective: float
+    life_of_the_hunt_healing_effective: float
+    ghost_bullets: int
+    extra_salvo_damage: float
+    harden_windows: int
+    harden_ticks: int
+    harden_wasted_damage: float
+    held_multistrikes: int
+    boss_fights: int
+    boss_kills: int
+    enrage_at_boss_kill: int
+    max_enrage_fights: int
+    boss_stages_fought: list[int]
+    boss_stages_cleared: list[int]
+    bands: list[BandResult]
+    loot_tail: list[float]
+    boss_entries: list[BossEntry]
+    sustain: list[PhaseSustain]
+    revives: int
+    end: RunEnd
+    seed: int | None
+    wall_stage: int | None
+    wall_unbounded: bool
+    stage_time_growth: float
+    on_kill_calls: int
+    def to_dict(self) -> dict[str, Any]: ...
+
+class BandResult:
+    """One run's counters for a 100-stage band (`SimResult.bands`)"""
+    start_stage: int
+    kills: int
+    attacks: int
+    killing_blows: int
+    overkill: float
+    attack_time: float
+    stun_time: float
+    fights: int
+    incoming_damage: float
+    damage_taken: float
+    regen: float
+    boss_incoming_damage: float
+    boss_damage_taken: float
+    boss_regen: float
+    loot_common: float
+    loot_uncommon: float
+    loot_rare: float
+
+class RunEnd:
+    """Why a run ended (`SimResult.end`)"""
+    Unfinished: RunEnd
+    EnemyAttack: RunEnd
+    EnemySpecial: RunEnd
+    BossAttack: RunEnd
+    BossSpecial: RunEnd
+    MaxStage: RunEnd
+    StageLimit: RunEnd
+    Abandoned: RunEnd
+    TimedOut: RunEnd
+    Aborted: RunEnd
+    def __int__(self) -> int: ...
+
+class FightPhase:
+    """Kind of fight a `PhaseSustain` covers"""
+    Trash: FightPhase
+    Boss: FightPhase
+    BossEnraged: FightPhase
+    def __int__(self) -> int: ...
+
+class PhaseSustain:
+    """One run's damage taken and healing in one fight phase (`SimResult.sustain`)"""
+    phase: FightPhase
+    seconds: float
+    damage_taken: float
+    regen: float
+    lifesteal: float
+    procs: float
+    overheal: float
+
+class BossEntry:
+    """Hunter state as one boss fight started (`SimResult.boss_entries`)"""
+    stage: int
+    hp: float
+    max_hp: float
+    revives_left: int
+    fires_of_war_buff: float
+    trickster_charges: int
+    empowered_regen: int
+    hundred_souls_stacks: int
+    charge: float
+    empowered_block_regen: int
+
+class SimulationJob:
+    def cancel(self) -> None: ...
+    def cancelled(self) -> bool: ...
+    def done(self) -> bool: ...
+    def progress(self) -> tuple[int, int]: ...
+    def partial_stats(self) -> dict[str, Any]: ...
+    def result(self, timeout: Optional[float] = None) -> str: ...
+
+def simulate(
+    hunter: str,
+    level: int,
+    stats: Mapping[str, int],
+    talents: Mapping[str, int],
+    attributes: Mapping[str, int],
+    inscryptions: Optional[Mapping[str, int]] = None,
+    mods: Optional[Mapping[str, bool]] = None,
+    relics: Optional[Mapping[str, int]] = None,
+    gems: Optional[Mapping[str, int]] = None,
+    gadgets: Optional[Mapping[str, int]] = None,
+    bonuses: Optional[Mapping[str, Any]] = None,
+    num_sims: int = 100,
+    parallel: bool = True,
+    progress_callback: Optional[ProgressCallback] = None,
+    progress_interval: float = 0.25,
+    threads: Optional[int] = None,
+    seed: Optional[int] = None,
+) -> Stats: ...
+def simulate_json(
+    config_json: ConfigLike,
+    num_sims: int,
+    parallel: bool = False,
+    progress_callback: Optional[ProgressCallback] = None,
+    progress_interval: float = 0.25,
+    threads: Optional[int] = None,
+    seed: Optional[int] = None,
+) -> str: ...
+def simulate_from_file(config_path: str, num_sims: int, parallel: bool = False, threads: Optional[int] = None) -> str: ...
+def start_simulation(
+    config_json: ConfigLike, num_sims: int, parallel: bool = True, threads: Optional[int] = None, seed: Optional[int] = None
+) -> SimulationJob: ...
+def simulate_async(
+    config_json: ConfigLike, num_sims: int, parallel: bool = True, threads: Optional[int] = None, seed: Optional[int] = None
+) -> asyncio.Future[str]: ...
+def simulate_detailed(
+    config_json: ConfigLike,
+    num_sims: int,
+    parallel: bool = True,
+    seed: Optional[int] = None,
+    threads: Optional[int] = None,
+) -> list[SimResult]: ...
+def simulate_arrays(
+    config_json: ConfigLike,
+    num_sims: int,
+    fields: Optional[Sequence[str]] = None,
+    parallel: bool = True,
+    seed: Optional[int] = None,
+    threads: Optional[int] = None,
+) -> dict[str, npt.NDArray[Union[np.int64, np.float64]]]: ...
+def simulate_delta(
+    base_config: ConfigLike,
+    overrides: Mapping[str, Any],
+    num_sims: int,
+    seed: int = 42,
+    parallel: bool = True,
+    threads: Optional[int] = None,
+) -> dict[str, Any]: ...
+def clear_delta_cache() -> None: ...
+def sweep(
+    config: ConfigLike,
+    key: str,
+    values: Sequence[Any],
+    num_sims: int = 100,
+    seed: int = 42,
+    parallel: bool = True,
+    threads: Optional[int] = None,
+) -> list[dict[str, Any]]: ...
+def compare(
+    a: ConfigLike,
+    b: ConfigLike,
+    num_sims: int = 100,
+    seed: int = 42,
+    parallel: bool = True,
+    threads: Optional[int] = None,
+) -> dict[str, Any]: ...
+def simulate_batch(
+    config_jsons: Sequence[str], num_sims: int, parallel: bool = False, threads: Optional[int] = None
+) -> list[str]: ...
+def eval_builds(
+    config_jsons: Sequence[str], sims_per_build: int, seed: int = 42, threads: Optional[int] = None
+) -> list[float]: ...
+def eval_builds_np(
+    hunter_type: int,
+    level: int,
+    base_stats: Mapping[str, int],
+    talent_names: Sequence[str],
+    talent_values: npt.NDArray[np.uint16],
+    attribute_names: Sequence[str],
+    attribute_values: npt.NDArray[np.float64],
+    sims_per_build: int,
+    seed: int = 42,
+    threads: Optional[int] = None,
+) -> npt.NDArray[np.float32]: ...
+def create_config(
+    hunter: str,
+    level: int,
+    stats: Mapping[str, int],
+    talents: Mapping[str, int],
+    attributes: Mapping[str, int],
+    inscryptions: Optional[Mapping[str, int]] = None,
+    mods: Optional[Mapping[str, bool]] = None,
+    relics: Optional[Mapping[str, int]] = None,
+    gems: Optional[Mapping[str, int]] = None,
+) -> str: ...
+def set_run_limits(max_sim_time: Optional[int] = None, watchdog_ms: Optional[int] = None) -> None: ...
+def install_boss_scripts(text: str = "", provisional: bool = False) -> None: ...
+def get_thread_count() -> int: ...
+def get_available_cores() -> int: ...
+def get_hunter_stats(config_path: str) -> str: ...
+def generate_builds(
+    level: int,
+    talents: Mapping[str, Mapping[str, int]],
+    attributes: Mapping[str, Mapping[str, Optional[float]]],
+    attribute_dependencies: Mapping[str, Mapping[str, int]],
+    attribute_point_gates: Mapping[str, int],
+    attribute_exclusions: Sequence[tuple[str, str]],
+    count: int,
+) -> list[tuple[dict[str, int], dict[str, int]]]: ...
+def optimize(
+    config: ConfigLike,
+    budget: int,
+    objective: str = "avg_stage",
+    *,
+    talents: Mapping[str, Mapping[str, int]],
+    attributes: Mapping[str, Mapping[str, Optional[float]]],
+    attribute_dependencies: Optional[Mapping[str, Mapping[str, int]]] = None,
+    attribute_point_gates: Optional[Mapping[str, int]] = None,
+    attribute_exclusions: Optional[Sequence[tuple[str, str]]] = None,
+    sims_per_build: int = 100,
+    seed: int = 42,
+    top: Optional[int] = None,
+    threads: Optional[int] = None,
+) -> dict[str, Any]: ...
+def explain_loot(config_json: str) -> str: ...
+def compute_hunter_stats(config: Mapping[str, Any]) -> dict[str, Any]: ...
+def compute_enemy_stats(stage: int, hunter: str) -> dict[str, Any]: ...
+def compute_loot_multiplier(config: Mapping[str, Any]) -> float: ...
+def export_share_code(config_json: str) -> str: ...
+def import_share_code(code: str) -> str: ...
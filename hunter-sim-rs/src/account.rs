@@ -0,0 +1,169 @@
+//! Whole-account simulation: all three hunters farming side by side
+//!
+//! An account file maps each hunter to its build, either as a path (relative
+//! to the account file) or inline:
+//!
+//! ```yaml
+//! borge: borge.yaml
+//! ozzy: builds/ozzy_lvl45.yaml
+//! knox:
+//!   meta: { hunter: Knox, level: 30 }
+//!   stats: { hp: 40, power: 35 }
+//!   talents: {}
+//!   attributes: {}
+//! ```
+//!
+//! Each build is simulated, then every stat is bumped by one point to find the
+//! hunter whose next upgrade buys the largest relative loot/hour gain.
+
+use crate::config::{BuildConfig, HunterType};
+use crate::error::{ConfigError, SimError};
+use crate::simulator::Simulator;
+use crate::stats::AggregatedStats;
+use serde::Serialize;
+use std::path::Path;
+
+/// The builds making up one player's account
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub builds: Vec<BuildConfig>,
+}
+
+impl Account {
+    /// Load an account file (YAML or JSON)
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let root: serde_yaml::Value = serde_yaml::from_str(&text).map_err(|e| ConfigError::Parse {
+            field: "<root>".to_string(),
+            message: e.to_string(),
+        })?;
+        let map = root.as_mapping().ok_or_else(|| ConfigError::Invalid {
+            field: "<root>".to_string(),
+            message: "account file must map hunter names to builds".to_string(),
+        })?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut builds = Vec::new();
+        for (key, value) in map {
+            let key = key.as_str().unwrap_or_default().to_string();
+            let hunter = match key.to_lowercase().as_str() {
+                "borge" => HunterType::Borge,
+                "ozzy" => HunterType::Ozzy,
+                "knox" => HunterType::Knox,
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        field: key,
+                        message: "expected borge, ozzy or knox".to_string(),
+                    })
+                }
+            };
+            let config = match value {
+                serde_yaml::Value::String(rel) => BuildConfig::from_file(base.join(rel))?,
+                inline => {
+                    // Inline builds may leave the hunter implied by their key
+                    let mut inline = inline.clone();
+                    if let Some(m) = inline.as_mapping_mut() {
+                        if !m.contains_key("meta") && !m.contains_key("hunter") {
+                            m.insert("hunter".into(), format!("{:?}", hunter).into());
+                        }
+                    }
+                    let mut config: BuildConfig = serde_path_to_error::deserialize(inline).map_err(|e| ConfigError::Parse {
+                        field: format!("{}.{}", key, e.path()),
+                        message: e.into_inner().to_string(),
+                    })?;
+                    config.normalize()?;
+                    config
+                }
+            };
+            if config.get_hunter_type() != hunter {
+                return Err(ConfigError::Invalid {
+                    field: key,
+                    message: format!("build is for {:?}", config.get_hunter_type()),
+                });
+            }
+            builds.push(config);
+        }
+        Ok(Self { builds })
+    }
+}
+
+/// Best single-point stat upgrade for one hunter
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradeGain {
+    pub stat: String,
+    pub loot_per_hour_gain: f64,  // Relative gain, 0.05 = +5%
+}
+
+/// Results for one hunter in the account
+#[derive(Debug, Clone, Serialize)]
+pub struct HunterSummary {
+    pub hunter: HunterType,
+    pub level: i32,
+    pub avg_stage: f64,
+    pub loot_per_hour: f64,
+    pub xp_per_hour: f64,
+    pub best_upgrade: Option<UpgradeGain>,
+}
+
+/// Combined results for a whole account
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountReport {
+    pub hunters: Vec<HunterSummary>,
+    pub total_loot_per_hour: f64,
+    pub total_xp_per_hour: f64,
+    pub suggested_hunter: Option<HunterType>,  // Hunter whose next stat point gains the most
+}
+
+/// Simulate every hunter in the account and find where the next upgrade helps most.
+/// Every batch shares `seed`, so the +1 comparisons use common random numbers.
+pub fn simulate_account(account: &Account, sims: usize, seed: u64) -> Result<AccountReport, SimError> {
+    let run = |config: &BuildConfig| -> Result<AggregatedStats, SimError> {
+        Ok(Simulator::builder().config(config.clone()).sims(sims).seed(seed).build()?.run())
+    };
+
+    let mut hunters = Vec::new();
+    for config in &account.builds {
+        let base = run(config)?;
+        let base_lph = base.avg_loot_per_hour;
+
+        let mut stats: Vec<&String> = config.stats.keys().collect();
+        stats.sort();
+        let mut best: Option<UpgradeGain> = None;
+        for stat in stats {
+            let mut bumped = config.clone();
+            *bumped.stats.entry(stat.clone()).or_insert(0) += 1;
+            let lph = run(&bumped)?.avg_loot_per_hour;
+            let gain = if base_lph > 0.0 { lph / base_lph - 1.0 } else { 0.0 };
+            if best.as_ref().is_none_or(|b| gain > b.loot_per_hour_gain) {
+                best = Some(UpgradeGain { stat: stat.clone(), loot_per_hour_gain: gain });
+            }
+        }
+
+        hunters.push(HunterSummary {
+            hunter: config.get_hunter_type(),
+            level: config.get_level(),
+            avg_stage: base.avg_stage,
+            loot_per_hour: base_lph,
+            xp_per_hour: if base.avg_time > 0.0 { base.avg_xp / base.avg_time * 3600.0 } else { 0.0 },
+            best_upgrade: best,
+        });
+    }
+
+    let suggested_hunter = hunters
+        .iter()
+        .filter_map(|h| h.best_upgrade.as_ref().map(|u| (h.hunter, u.loot_per_hour_gain)))
+        .filter(|(_, gain)| *gain > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(hunter, _)| hunter);
+
+    Ok(AccountReport {
+        total_loot_per_hour: hunters.iter().map(|h| h.loot_per_hour).sum(),
+        total_xp_per_hour: hunters.iter().map(|h| h.xp_per_hour).sum(),
+        hunters,
+        suggested_hunter,
+    })
+}
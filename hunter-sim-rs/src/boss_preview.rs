@@ -0,0 +1,117 @@
+//! How a build fares against one stage's boss (`boss-preview`)
+//!
+//! Every fight is the real engine's: crits, regen, specials, enrage and the
+//! hunter's procs all play out as they would in a full run. What differs is
+//! the way in: the hunter walks up to the boss straight from the build, with
+//! full HP, every revive, and none of the buffs a run stacks on the way,
+//! unless the build's `start` section says otherwise (its stage aside). For
+//! the fight a run actually arrives at, `snapshot` the run at the boss stage
+//! and `replay` it.
+
+use crate::config::BuildConfig;
+use crate::enemy::{Enemy, EnemyStats};
+use crate::simulation::{self, FastRng};
+use crate::stats::RunEnd;
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// One seeded fight
+#[derive(Debug, Clone, Serialize)]
+pub struct BossFight {
+    pub seed: u64,
+    pub won: bool,
+    pub end: Option<RunEnd>,  // What ended a lost fight
+    pub seconds: f64,
+    pub hp_left: f64,         // Fraction of max HP
+    pub revives_used: i32,
+    pub max_enrage: bool,     // The boss passed 200 stacks
+}
+
+/// Fight lengths over the won or lost fights, in seconds
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct FightTimes {
+    pub avg: f64,
+    pub p10: f64,
+    pub median: f64,
+    pub p90: f64,
+}
+
+impl FightTimes {
+    fn of(mut seconds: Vec<f64>) -> Self {
+        if seconds.is_empty() {
+            return Self::default();
+        }
+        seconds.sort_by(f64::total_cmp);
+        // Nearest rank
+        let at = |q: f64| seconds[((seconds.len() as f64 * q).ceil() as usize).saturating_sub(1)];
+        Self { avg: seconds.iter().sum::<f64>() / seconds.len() as f64, p10: at(0.1), median: at(0.5), p90: at(0.9) }
+    }
+}
+
+/// Fights against one boss, summed up
+#[derive(Debug, Clone, Serialize)]
+pub struct BossPreview {
+    pub stage: i32,
+    pub boss: EnemyStats,
+    pub fights: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub win_seconds: FightTimes,
+    pub loss_seconds: FightTimes,
+    pub avg_hp_left: f64,          // Over wins
+    pub avg_revives_used: f64,
+    pub max_enrage_rate: f64,      // Fights where the boss passed 200 stacks
+    pub losses: Vec<(RunEnd, usize)>,
+}
+
+/// Fight the boss of `stage` `count` times with seeds 0..count
+pub fn preview(config: &BuildConfig, stage: i32, count: usize, parallel: bool) -> BossPreview {
+    let one = |seed: u64| fight(config, stage, seed);
+    let fights: Vec<BossFight> = if parallel {
+        (0..count as u64).into_par_iter().map(one).collect()
+    } else {
+        (0..count as u64).map(one).collect()
+    };
+    summarize(stage, EnemyStats::of(&Enemy::new_boss(stage, config.get_hunter_type())), &fights)
+}
+
+/// One seeded fight against the boss of `stage`
+pub fn fight(config: &BuildConfig, stage: i32, seed: u64) -> BossFight {
+    let (result, hunter) = simulation::play_boss_fight(config, stage, &mut FastRng::new(seed));
+    let won = result.boss_stages_cleared.contains(&stage);
+    BossFight {
+        seed,
+        won,
+        end: (!won).then_some(result.end),
+        seconds: result.elapsed_time,
+        hp_left: (hunter.hp / hunter.max_hp).clamp(0.0, 1.0),
+        revives_used: hunter.revive_count,
+        max_enrage: result.max_enrage_fights > 0,
+    }
+}
+
+fn summarize(stage: i32, boss: EnemyStats, fights: &[BossFight]) -> BossPreview {
+    let n = fights.len().max(1) as f64;
+    let wins: Vec<&BossFight> = fights.iter().filter(|f| f.won).collect();
+    let mut losses: Vec<(RunEnd, usize)> = Vec::new();
+    for end in fights.iter().filter_map(|f| f.end) {
+        match losses.iter_mut().find(|(e, _)| *e == end) {
+            Some((_, count)) => *count += 1,
+            None => losses.push((end, 1)),
+        }
+    }
+    losses.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    BossPreview {
+        stage,
+        boss,
+        fights: fights.len(),
+        wins: wins.len(),
+        win_rate: wins.len() as f64 / n,
+        win_seconds: FightTimes::of(wins.iter().map(|f| f.seconds).collect()),
+        loss_seconds: FightTimes::of(fights.iter().filter(|f| !f.won).map(|f| f.seconds).collect()),
+        avg_hp_left: if wins.is_empty() { 0.0 } else { wins.iter().map(|f| f.hp_left).sum::<f64>() / wins.len() as f64 },
+        avg_revives_used: fights.iter().map(|f| f.revives_used as f64).sum::<f64>() / n,
+        max_enrage_rate: fights.iter().filter(|f| f.max_enrage).count() as f64 / n,
+        losses,
+    }
+}
@@ -0,0 +1,239 @@
+//! Boss secondary attacks as data (`--boss-scripts`, `boss-scripts`)
+//!
+//! A boss script names the ability, how often it fires, and the effects it
+//! applies in order each time it does. The built-in scripts are the
+//! `game_data::BOSS_SECONDARIES` table. A scripts file loaded at startup adds
+//! to them, replacing the built-in script for the same hunter and first stage,
+//! so a new boss or a patched cooldown is an edit to a file, not the engine:
+//!
+//! ```yaml
+//! - hunter: Borge
+//!   min_stage: 200
+//!   name: Gothmorgor
+//!   cooldown: { speed_factor: 1.8 }   # Or { seconds: 45 }
+//!   effects:
+//!     - effect: attack                # A primary attack
+//!     - { effect: enrage, stacks: 1 }
+//! - hunter: Ozzy
+//!   min_stage: 600
+//!   name: Exoscarab Prime
+//!   cooldown: { seconds: 50 }
+//!   effects:
+//!     - { effect: harden, ticks: 8, dr: 0.97, regen_mult: 4, end_enrage: 5 }
+//!     - { effect: hit, damage_mult: 1.5 }    # Non-crit hit for power x 1.5
+//!     - { effect: heal, fraction: 0.02 }     # Of max HP
+//!   verified: false                   # Default; true once checked against the game
+//! ```
+//!
+//! Estimated secondaries for the bosses past stage 300, not yet checked
+//! against the game, ship as `data/provisional_boss_scripts.yaml` (also
+//! `PROVISIONAL`): load it to opt in to them.
+//!
+//! A boss uses the script with the highest `min_stage` it has reached. Boss
+//! stats are built once per process from the scripts in place at the time,
+//! so scripts are installed before the first simulation and only once:
+//! `--boss-scripts` for the CLI and `hunter-sim serve`, or
+//! `install_boss_scripts` from Python. `install` refuses once any boss has
+//! been built.
+
+use crate::config::HunterType;
+use crate::error::ConfigError;
+use crate::game_data::BOSS_SECONDARIES;
+use crate::simulation::STAGE_LIMIT;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Shortest cooldown a script may set, in seconds
+pub const MIN_COOLDOWN: f64 = 0.1;
+
+/// Most enrage stacks one effect may add
+pub const MAX_ENRAGE_STACKS: i32 = 1000;
+
+/// Most regen ticks a harden may last
+pub const MAX_HARDEN_TICKS: i32 = 10_000;
+
+/// How often a boss secondary attack fires; written `{ seconds: 45 }` or
+/// `{ speed_factor: 1.8 }`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(into = "CooldownKeys", try_from = "CooldownKeys")]
+pub enum Cooldown {
+    Seconds(f64),
+    SpeedFactor(f64),  // Multiple of the boss's primary attack time
+}
+
+/// `Cooldown` as a map with exactly one key, which reads the same in YAML
+/// and JSON (serde_yaml would write the enum as a `!tag`)
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CooldownKeys {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speed_factor: Option<f64>,
+}
+
+impl From<Cooldown> for CooldownKeys {
+    fn from(cooldown: Cooldown) -> Self {
+        match cooldown {
+            Cooldown::Seconds(s) => Self { seconds: Some(s), speed_factor: None },
+            Cooldown::SpeedFactor(f) => Self { seconds: None, speed_factor: Some(f) },
+        }
+    }
+}
+
+impl TryFrom<CooldownKeys> for Cooldown {
+    type Error = &'static str;
+
+    fn try_from(keys: CooldownKeys) -> Result<Self, Self::Error> {
+        match (keys.seconds, keys.speed_factor) {
+            (Some(s), None) => Ok(Cooldown::Seconds(s)),
+            (None, Some(f)) => Ok(Cooldown::SpeedFactor(f)),
+            _ => Err("set exactly one of `seconds` and `speed_factor`"),
+        }
+    }
+}
+
+impl Cooldown {
+    /// Seconds between uses for a boss attacking every `speed` seconds
+    pub fn seconds(self, speed: f64) -> f64 {
+        match self {
+            Cooldown::Seconds(secs) => secs,
+            Cooldown::SpeedFactor(factor) => speed * factor,
+        }
+    }
+}
+
+/// A harden window: damage reduction and faster regen for a few regen ticks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Harden {
+    pub ticks: i32,       // Regen ticks it lasts
+    pub dr: f64,          // Damage reduction while hardened, in place of the boss's own
+    pub regen_mult: f64,  // Regen multiplier while hardened
+    #[serde(default)]
+    pub end_enrage: i32,  // Enrage stacks added when it ends
+}
+
+/// One step of a boss script
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "effect", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BossEffect {
+    Attack,                        // A primary attack: crits, procs and enrage power apply
+    Hit { damage_mult: f64 },      // One non-crit hit for primary power x damage_mult
+    Enrage { stacks: i32 },        // Enrage stacks, one at a time so every threshold applies
+    Harden(Harden),
+    Heal { fraction: f64 },        // Restore a fraction of max HP
+}
+
+/// One boss's secondary attack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BossScript {
+    pub hunter: HunterType,
+    pub min_stage: i32,
+    pub name: Cow<'static, str>,
+    pub cooldown: Cooldown,
+    pub effects: Cow<'static, [BossEffect]>,
+    #[serde(default)]
+    pub verified: bool,  // Checked against WASM/APK; false = provisional estimate
+}
+
+impl BossScript {
+    /// Reject scripts the engine can't play; `index` locates errors in the file
+    pub fn validate(&self, index: usize) -> Result<(), ConfigError> {
+        let invalid = |field: &str, message: String| Err(ConfigError::Invalid { field: format!("[{}].{}", index, field), message });
+        if !(1..=STAGE_LIMIT).contains(&self.min_stage) {
+            return invalid("min_stage", format!("{} is outside 1..={}", self.min_stage, STAGE_LIMIT));
+        }
+        match self.cooldown {
+            Cooldown::Seconds(s) | Cooldown::SpeedFactor(s) if !(s.is_finite() && s >= MIN_COOLDOWN) => {
+                return invalid("cooldown", format!("{} is below {} or not a number", s, MIN_COOLDOWN));
+            }
+            _ => {}
+        }
+        if self.effects.is_empty() {
+            return invalid("effects", "a script needs at least one effect".to_string());
+        }
+        let ratio = |x: f64| (0.0..=1.0).contains(&x);
+        let factor = |x: f64| x.is_finite() && x >= 0.0;
+        let stacks = |n: i32| (0..=MAX_ENRAGE_STACKS).contains(&n);
+        for (i, effect) in self.effects.iter().enumerate() {
+            let message = match *effect {
+                BossEffect::Hit { damage_mult } if !factor(damage_mult) => format!("damage_mult {} is negative or not a number", damage_mult),
+                BossEffect::Enrage { stacks: n } if !stacks(n) => format!("stacks {} is outside 0..={}", n, MAX_ENRAGE_STACKS),
+                BossEffect::Harden(h) if !(1..=MAX_HARDEN_TICKS).contains(&h.ticks) => format!("ticks {} is outside 1..={}", h.ticks, MAX_HARDEN_TICKS),
+                BossEffect::Harden(h) if !ratio(h.dr) => format!("dr {} is outside 0..=1", h.dr),
+                BossEffect::Harden(h) if !factor(h.regen_mult) => format!("regen_mult {} is negative or not a number", h.regen_mult),
+                BossEffect::Harden(h) if !stacks(h.end_enrage) => format!("end_enrage {} is outside 0..={}", h.end_enrage, MAX_ENRAGE_STACKS),
+                BossEffect::Heal { fraction } if !ratio(fraction) => format!("fraction {} is outside 0..=1", fraction),
+                _ => continue,
+            };
+            return invalid(&format!("effects[{}]", i), message);
+        }
+        Ok(())
+    }
+}
+
+/// The shipped scripts file of provisional post-300 secondaries
+pub const PROVISIONAL: &str = include_str!("../data/provisional_boss_scripts.yaml");
+
+/// Read a scripts file (a YAML or JSON list) and check every script
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<BossScript>, ConfigError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })?;
+    from_str(&text)
+}
+
+/// Parse and check scripts from YAML or JSON text
+pub fn from_str(text: &str) -> Result<Vec<BossScript>, ConfigError> {
+    let de = serde_yaml::Deserializer::from_str(text);
+    let scripts: Vec<BossScript> = serde_path_to_error::deserialize(de).map_err(ConfigError::from_yaml)?;
+    for (i, script) in scripts.iter().enumerate() {
+        script.validate(i)?;
+        if scripts[..i].iter().any(|s| s.hunter == script.hunter && s.min_stage == script.min_stage) {
+            return Err(ConfigError::Invalid {
+                field: format!("[{}].min_stage", i),
+                message: format!("a {:?} script for stage {} is already listed", script.hunter, script.min_stage),
+            });
+        }
+    }
+    Ok(scripts)
+}
+
+static LOADED: OnceLock<Vec<BossScript>> = OnceLock::new();
+
+/// Put `scripts` on top of the built-in ones for the rest of the process;
+/// fails once installed or once a simulation has built the stage tables
+pub fn install(scripts: Vec<BossScript>) -> Result<(), ConfigError> {
+    let refused = |message: &str| ConfigError::Invalid { field: "<root>".to_string(), message: message.to_string() };
+    if crate::enemy::stage_tables_built() {
+        return Err(refused("boss scripts must be installed before the first simulation"));
+    }
+    LOADED.set(scripts).map_err(|_| refused("boss scripts are already installed"))
+}
+
+/// Every script in effect, per hunter in stage order: the installed ones and
+/// the built-in ones they don't replace
+pub fn scripts() -> Vec<&'static BossScript> {
+    let loaded: &'static [BossScript] = LOADED.get().map_or(&[], Vec::as_slice);
+    let replaced = |b: &BossScript| loaded.iter().any(|s| s.hunter == b.hunter && s.min_stage == b.min_stage);
+    let mut all: Vec<&'static BossScript> = loaded.iter().chain(BOSS_SECONDARIES.iter().filter(|b| !replaced(b))).collect();
+    all.sort_by_key(|s| (s.hunter as u8, s.min_stage));
+    all
+}
+
+/// Script of the boss on `stage` for `hunter`, if it has one
+pub fn for_boss(hunter: HunterType, stage: i32) -> Option<&'static BossScript> {
+    let loaded: &'static [BossScript] = LOADED.get().map_or(&[], Vec::as_slice);
+    // An installed script wins over a built-in one at the same stage
+    loaded
+        .iter()
+        .chain(BOSS_SECONDARIES)
+        .filter(|s| s.hunter == hunter && stage >= s.min_stage)
+        .fold(None, |best: Option<&'static BossScript>, s| match best {
+            Some(b) if b.min_stage >= s.min_stage => Some(b),
+            _ => Some(s),
+        })
+}
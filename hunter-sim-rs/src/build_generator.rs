@@ -1,3 +1,4 @@
+use crate::error::OptimizeError;
 use rand::Rng;
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
@@ -14,6 +15,16 @@ pub struct TalentInfo {
     pub max: i32,
 }
 
+/// Talent points a hunter of `level` has to spend
+pub fn talent_points(level: i32) -> i32 {
+    level
+}
+
+/// Attribute points a hunter of `level` has to spend
+pub fn attribute_points(level: i32) -> i32 {
+    level * 3
+}
+
 #[derive(Debug, Clone)]
 pub struct BuildGenerator {
     pub talent_points: i32,
@@ -36,8 +47,8 @@ impl BuildGenerator {
         attribute_exclusions: Vec<(String, String)>,
     ) -> Self {
         let mut gen = Self {
-            talent_points: level,
-            attribute_points: level * 3,
+            talent_points: talent_points(level),
+            attribute_points: attribute_points(level),
             talents,
             attributes,
             attribute_dependencies,
@@ -50,6 +61,52 @@ impl BuildGenerator {
         gen
     }
     
+    /// Check that costs are usable and every dependency, gate, and exclusion
+    /// refers to a defined attribute
+    pub fn validate(&self) -> Result<(), OptimizeError> {
+        for (name, info) in &self.talents {
+            if info.cost <= 0 {
+                return Err(OptimizeError::InvalidDefinition {
+                    name: name.clone(),
+                    message: format!("cost must be positive, got {}", info.cost),
+                });
+            }
+        }
+        for (name, info) in &self.attributes {
+            if info.cost <= 0 {
+                return Err(OptimizeError::InvalidDefinition {
+                    name: name.clone(),
+                    message: format!("cost must be positive, got {}", info.cost),
+                });
+            }
+        }
+        
+        let check = |name: &str, referenced_by: &str| -> Result<(), OptimizeError> {
+            if self.attributes.contains_key(name) {
+                Ok(())
+            } else {
+                Err(OptimizeError::UnknownAttribute {
+                    name: name.to_string(),
+                    referenced_by: referenced_by.to_string(),
+                })
+            }
+        };
+        for (attr, deps) in &self.attribute_dependencies {
+            check(attr, "attribute_dependencies")?;
+            for dep in deps.keys() {
+                check(dep, attr)?;
+            }
+        }
+        for attr in self.attribute_point_gates.keys() {
+            check(attr, "attribute_point_gates")?;
+        }
+        for (a, b) in &self.attribute_exclusions {
+            check(a, "attribute_exclusions")?;
+            check(b, "attribute_exclusions")?;
+        }
+        Ok(())
+    }
+    
     fn calculate_dynamic_attr_maxes(&mut self) {
         // Find unlimited attributes
         let unlimited_attrs: Vec<String> = self.attributes.iter()
@@ -128,7 +185,9 @@ impl BuildGenerator {
             
             // Pick random and add 1 point
             let chosen = valid_talents[rng.gen_range(0..valid_talents.len())];
-            *result.get_mut(chosen).unwrap() += 1;
+            if let Some(points) = result.get_mut(chosen) {
+                *points += 1;
+            }
             remaining -= 1;
         }
         
@@ -241,7 +300,9 @@ impl BuildGenerator {
                 // Pick random and add 1 point
                 let chosen = &valid_attrs[rng.gen_range(0..valid_attrs.len())];
                 let cost = self.attributes[chosen].cost;
-                *result.get_mut(chosen).unwrap() += 1;
+                if let Some(points) = result.get_mut(chosen) {
+                    *points += 1;
+                }
                 remaining -= cost;
             }
         }
@@ -0,0 +1,196 @@
+//! Calibration of derived stats against values observed in game
+//!
+//! An observation file lists in-game readings, each with the inputs that
+//! differ from the base build:
+//!
+//! ```yaml
+//! observations:
+//!   - stat: speed            # Any numeric HunterStats field
+//!     observed: 1.74         # Attack time shown in game
+//!     inputs: { stats.speed: 54 }
+//!   - stat: speed
+//!     observed: 2.16
+//!     inputs: { stats.speed: 44 }
+//!     tolerance: 0.005       # Relative; defaults to 1%
+//! ```
+//!
+//! Every observation is compared with the engine's derived value. With fitting
+//! enabled, each stat also gets a scale correction, and stats observed at several
+//! levels of a single input get a least-squares line to compare with the engine's
+//! own slope, which is how the hand-tuned "IRL CALIBRATION" constants were found.
+
+use crate::config::BuildConfig;
+use crate::error::ConfigError;
+use crate::hunter::HunterStats;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// One value read off the game client
+#[derive(Debug, Clone, Deserialize)]
+pub struct Observation {
+    pub stat: String,
+    pub observed: f64,
+    #[serde(default)]
+    pub inputs: BTreeMap<String, i32>,  // `section.key` (or `level`) -> value
+    #[serde(default)]
+    pub tolerance: Option<f64>,
+}
+
+/// Contents of an observation file
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObservationSet {
+    pub observations: Vec<Observation>,
+}
+
+impl ObservationSet {
+    /// Load an observation file (YAML or JSON)
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let de = serde_yaml::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(de).map_err(ConfigError::from_yaml)
+    }
+}
+
+/// Engine value vs observed value for one observation
+#[derive(Debug, Clone, Serialize)]
+pub struct Deviation {
+    pub stat: String,
+    pub inputs: BTreeMap<String, i32>,
+    pub observed: f64,
+    pub simulated: f64,
+    pub relative_error: f64,  // (simulated - observed) / |observed|
+    pub within_tolerance: bool,
+}
+
+/// Least-squares line `value = intercept + slope * input`, observed vs engine
+#[derive(Debug, Clone, Serialize)]
+pub struct LinearFit {
+    pub input: String,
+    pub observed_intercept: f64,
+    pub observed_slope: f64,
+    pub simulated_intercept: f64,
+    pub simulated_slope: f64,
+}
+
+/// Suggested correction for one derived stat
+#[derive(Debug, Clone, Serialize)]
+pub struct Correction {
+    pub stat: String,
+    pub samples: usize,
+    pub scale: f64,  // Mean observed / simulated; multiply the formula by this
+    pub linear: Option<LinearFit>,
+}
+
+/// Result of a calibration pass
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationReport {
+    pub deviations: Vec<Deviation>,
+    pub corrections: Vec<Correction>,  // Empty unless fitting was requested
+}
+
+impl CalibrationReport {
+    /// Number of observations outside their tolerance
+    pub fn failures(&self) -> usize {
+        self.deviations.iter().filter(|d| !d.within_tolerance).count()
+    }
+}
+
+/// Compare every observation against the engine's derived stats for `base`
+/// with the observation's inputs applied
+pub fn calibrate(base: &BuildConfig, set: &ObservationSet, fit: bool) -> Result<CalibrationReport, ConfigError> {
+    let mut deviations = Vec::with_capacity(set.observations.len());
+    for (i, obs) in set.observations.iter().enumerate() {
+        let field = |name: &str| format!("observations[{}].{}", i, name);
+        let mut config = base.clone();
+        for (key, value) in &obs.inputs {
+            config.set_input(key, *value).map_err(|e| ConfigError::Invalid {
+                field: format!("{}.{}", field("inputs"), key),
+                message: e.to_string(),
+            })?;
+        }
+        let simulated = derived_stat(&config, &obs.stat).ok_or_else(|| ConfigError::Invalid {
+            field: field("stat"),
+            message: format!("`{}` is not a numeric derived stat", obs.stat),
+        })?;
+        let relative_error = if obs.observed != 0.0 {
+            (simulated - obs.observed) / obs.observed.abs()
+        } else {
+            simulated
+        };
+        deviations.push(Deviation {
+            stat: obs.stat.clone(),
+            inputs: obs.inputs.clone(),
+            observed: obs.observed,
+            simulated,
+            relative_error,
+            within_tolerance: relative_error.abs() <= obs.tolerance.unwrap_or(DEFAULT_TOLERANCE),
+        });
+    }
+
+    let corrections = if fit { fit_corrections(&deviations) } else { Vec::new() };
+    Ok(CalibrationReport { deviations, corrections })
+}
+
+/// Look up a numeric `HunterStats` field by name
+fn derived_stat(config: &BuildConfig, stat: &str) -> Option<f64> {
+    let stats = serde_json::to_value(HunterStats::from_config(config)).ok()?;
+    stats.get(stat)?.as_f64()
+}
+
+fn fit_corrections(deviations: &[Deviation]) -> Vec<Correction> {
+    let mut by_stat: BTreeMap<&str, Vec<&Deviation>> = BTreeMap::new();
+    for d in deviations {
+        by_stat.entry(&d.stat).or_default().push(d);
+    }
+
+    by_stat
+        .into_iter()
+        .map(|(stat, samples)| {
+            let ratios: Vec<f64> = samples
+                .iter()
+                .filter(|d| d.simulated != 0.0)
+                .map(|d| d.observed / d.simulated)
+                .collect();
+            let scale = if ratios.is_empty() { 1.0 } else { ratios.iter().sum::<f64>() / ratios.len() as f64 };
+            Correction {
+                stat: stat.to_string(),
+                samples: samples.len(),
+                scale,
+                linear: linear_fit(&samples),
+            }
+        })
+        .collect()
+}
+
+/// Fit a line when every sample varies the same single input over 2+ distinct levels
+fn linear_fit(samples: &[&Deviation]) -> Option<LinearFit> {
+    let input = samples.first()?.inputs.keys().next()?.clone();
+    if samples.iter().any(|d| d.inputs.len() != 1 || !d.inputs.contains_key(&input)) {
+        return None;
+    }
+    let xs: Vec<f64> = samples.iter().map(|d| d.inputs[&input] as f64).collect();
+    let (observed_intercept, observed_slope) = least_squares(&xs, &samples.iter().map(|d| d.observed).collect::<Vec<_>>())?;
+    let (simulated_intercept, simulated_slope) = least_squares(&xs, &samples.iter().map(|d| d.simulated).collect::<Vec<_>>())?;
+    Some(LinearFit { input, observed_intercept, observed_slope, simulated_intercept, simulated_slope })
+}
+
+/// (intercept, slope) of the least-squares line; None if x doesn't vary
+fn least_squares(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    if sxx == 0.0 {
+        return None;
+    }
+    let sxy: f64 = xs.iter().zip(ys).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let slope = sxy / sxx;
+    Some((mean_y - slope * mean_x, slope))
+}
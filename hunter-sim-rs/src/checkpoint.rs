@@ -0,0 +1,214 @@
+//! Crash-resistant checkpoints for long batches (`--checkpoint`, `--resume`)
+//!
+//! A checkpointed batch runs simulation `i` of every config with seed `i`, the
+//! same as a parallel batch, in chunks. Each finished chunk is appended to the
+//! checkpoint file and synced to disk, so after a crash or reboot `--resume`
+//! picks up at the next unrun seed and the finished batch is identical to one
+//! that was never interrupted. Saving a chunk costs the same however far the
+//! batch has got: nothing already written is written again.
+//!
+//! The file is `HSCK2\n` followed by frames, each a little-endian `u64` byte
+//! length and a deflate-compressed JSON record: first the batch (simulations
+//! per config and the configs), then one frame per chunk of results. A frame
+//! cut short by an interruption is dropped on load and overwritten on resume.
+
+use crate::config::BuildConfig;
+use crate::error::SimError;
+use crate::simulation::run_simulation_with_seed;
+use crate::stats::SimResult;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Header identifying the checkpoint format version
+const MAGIC: &[u8] = b"HSCK2\n";
+
+/// Completed simulations of a checkpointed batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub num_sims: usize,
+    pub runs: Vec<CheckpointRun>,
+    #[serde(skip)]
+    complete_bytes: u64,  // Length of the file's complete frames, where a resume appends
+}
+
+/// One config's share of a checkpoint; result `i` came from seed `i`, so the
+/// next seed to run is `results.len()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointRun {
+    pub config: serde_json::Value,
+    pub results: Vec<SimResult>,
+}
+
+/// First frame: what the batch is
+#[derive(Serialize, Deserialize)]
+struct BatchFrame {
+    num_sims: usize,
+    configs: Vec<serde_json::Value>,
+}
+
+/// Every later frame: results of seeds `start..` for config `run`
+#[derive(Serialize, Deserialize)]
+struct ChunkFrame {
+    run: usize,
+    start: usize,
+    results: Vec<SimResult>,
+}
+
+impl Checkpoint {
+    /// An empty checkpoint for `num_sims` simulations of each config
+    pub fn new(configs: &[BuildConfig], num_sims: usize) -> Result<Self, SimError> {
+        let runs = configs
+            .iter()
+            .map(|config| Ok(CheckpointRun { config: serde_json::to_value(config)?, results: Vec::new() }))
+            .collect::<Result<_, SimError>>()?;
+        Ok(Self { num_sims, runs, complete_bytes: 0 })
+    }
+
+    /// Read a checkpoint file, up to its last complete frame
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SimError> {
+        let path = path.as_ref();
+        let bad = |message: String| SimError::Checkpoint { path: path.to_path_buf(), message };
+
+        let bytes = std::fs::read(path).map_err(|e| bad(e.to_string()))?;
+        if bytes.starts_with(b"HSCK1\n") {
+            return Err(bad("checkpoint is from an older version of the format; start the batch again".to_string()));
+        }
+        let mut rest = bytes.strip_prefix(MAGIC).ok_or_else(|| bad("not a checkpoint file".to_string()))?;
+        let mut frames = Vec::new();
+        while let Some(frame) = next_frame(&mut rest) {
+            let mut json = Vec::new();
+            DeflateDecoder::new(frame)
+                .read_to_end(&mut json)
+                .map_err(|e| bad(format!("corrupt checkpoint: {}", e)))?;
+            frames.push(json);
+        }
+        let complete_bytes = (bytes.len() - rest.len()) as u64;
+
+        let corrupt = |e: serde_json::Error| bad(format!("corrupt checkpoint: {}", e));
+        let batch: BatchFrame = serde_json::from_slice(frames.first().ok_or_else(|| bad("checkpoint has no batch header".to_string()))?).map_err(corrupt)?;
+        let runs = batch.configs.into_iter().map(|config| CheckpointRun { config, results: Vec::new() }).collect();
+        let mut checkpoint = Self { num_sims: batch.num_sims, runs, complete_bytes };
+        for json in &frames[1..] {
+            let chunk: ChunkFrame = serde_json::from_slice(json).map_err(corrupt)?;
+            match checkpoint.runs.get_mut(chunk.run) {
+                Some(run) if run.results.len() == chunk.start => run.results.extend(chunk.results),
+                _ => return Err(bad(format!("corrupt checkpoint: chunk for config {} starts at seed {} out of order", chunk.run, chunk.start))),
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    /// Fail unless this checkpoint is for the same configs and batch size
+    pub fn check_matches(&self, other: &Checkpoint, path: &Path) -> Result<(), SimError> {
+        let bad = |message: &str| SimError::Checkpoint { path: path.to_path_buf(), message: message.to_string() };
+        if self.num_sims != other.num_sims {
+            return Err(bad(&format!("checkpoint is for {} simulations per config, not {}", self.num_sims, other.num_sims)));
+        }
+        if self.runs.len() != other.runs.len() || self.runs.iter().zip(&other.runs).any(|(a, b)| a.config != b.config) {
+            return Err(bad("checkpoint is for different build configs"));
+        }
+        Ok(())
+    }
+
+    /// Simulations completed so far, over all configs
+    pub fn completed(&self) -> usize {
+        self.runs.iter().map(|r| r.results.len()).sum()
+    }
+}
+
+/// Split the next complete frame off `rest`; None at the end or at a frame cut short
+fn next_frame<'a>(rest: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = u64::from_le_bytes(rest.get(..8)?.try_into().ok()?);
+    let frame = rest.get(8..8usize.checked_add(usize::try_from(len).ok()?)?)?;
+    *rest = &rest[8 + frame.len()..];
+    Some(frame)
+}
+
+/// A checkpoint file open for appending chunks
+struct Journal {
+    file: File,
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Start a new file holding just the batch frame, replacing `path` only
+    /// once it is on disk
+    fn create(path: &Path, checkpoint: &Checkpoint) -> Result<Self, SimError> {
+        let bad = |e: std::io::Error| SimError::Checkpoint { path: path.to_path_buf(), message: e.to_string() };
+        let tmp = path.with_extension("tmp");
+        let mut journal = Self { file: File::create(&tmp).map_err(bad)?, path: path.to_path_buf() };
+        journal.file.write_all(MAGIC).map_err(bad)?;
+        let configs = checkpoint.runs.iter().map(|run| run.config.clone()).collect();
+        journal.append(&BatchFrame { num_sims: checkpoint.num_sims, configs })?;
+        std::fs::rename(&tmp, path).map_err(bad)?;
+        Ok(journal)
+    }
+
+    /// Reopen a loaded checkpoint's file after its last complete frame
+    fn resume(path: &Path, checkpoint: &Checkpoint) -> Result<Self, SimError> {
+        let bad = |e: std::io::Error| SimError::Checkpoint { path: path.to_path_buf(), message: e.to_string() };
+        let file = OpenOptions::new().append(true).open(path).map_err(bad)?;
+        file.set_len(checkpoint.complete_bytes).map_err(bad)?;
+        Ok(Self { file, path: path.to_path_buf() })
+    }
+
+    /// Write one frame and sync it to disk
+    fn append(&mut self, record: &impl Serialize) -> Result<(), SimError> {
+        let bad = |e: std::io::Error| SimError::Checkpoint { path: self.path.clone(), message: e.to_string() };
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&serde_json::to_vec(record)?).map_err(bad)?;
+        let frame = encoder.finish().map_err(bad)?;
+        let mut bytes = (frame.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(frame);
+        self.file.write_all(&bytes).map_err(bad)?;
+        self.file.sync_data().map_err(bad)
+    }
+}
+
+/// Run `num_sims` seeded simulations of each config, appending a chunk to the
+/// checkpoint at `path` every `every` simulations. When `resume` is given, its
+/// completed results are kept and only the remaining seeds are run. Returns
+/// each config's results in seed order, held in memory for the batch's stats
+/// as an uncheckpointed batch's are.
+pub fn run_checkpointed(
+    configs: &[BuildConfig],
+    num_sims: usize,
+    parallel: bool,
+    path: &Path,
+    every: usize,
+    resume: Option<Checkpoint>,
+) -> Result<Vec<Vec<SimResult>>, SimError> {
+    let mut checkpoint = Checkpoint::new(configs, num_sims)?;
+    let mut journal = match resume {
+        Some(previous) => {
+            previous.check_matches(&checkpoint, path)?;
+            checkpoint = previous;
+            Journal::resume(path, &checkpoint)?
+        }
+        None => Journal::create(path, &checkpoint)?,
+    };
+
+    let every = every.max(1);
+    for (run, config) in configs.iter().enumerate() {
+        while checkpoint.runs[run].results.len() < num_sims {
+            let start = checkpoint.runs[run].results.len();
+            let end = (start + every).min(num_sims);
+            let results: Vec<SimResult> = if parallel {
+                (start..end).into_par_iter().map(|i| run_simulation_with_seed(config, i as u64)).collect()
+            } else {
+                (start..end).map(|i| run_simulation_with_seed(config, i as u64)).collect()
+            };
+            let chunk = ChunkFrame { run, start, results };
+            journal.append(&chunk)?;
+            checkpoint.runs[run].results.extend(chunk.results);
+        }
+    }
+
+    Ok(checkpoint.runs.into_iter().map(|run| run.results).collect())
+}
@@ -1,9 +1,71 @@
 //! Configuration structures for loading build YAML files
 
+use crate::engine_flags::EngineFlag;
+use crate::error::ConfigError;
+use crate::events::LootEvent;
+use crate::formulas;
+use crate::game_data;
+use crate::logging::LoggingRules;
+use crate::mitigation::MitigationRules;
+use crate::simulation::STAGE_LIMIT;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
+use tracing::{debug, trace, warn};
+
+/// Largest config file or string the loaders accept
+pub const MAX_CONFIG_BYTES: usize = 1 << 20;
+
+/// Largest number of keys accepted in any one section
+pub const MAX_SECTION_KEYS: usize = 4096;
+
+/// Most warm-up runs `persistence` may ask each simulation for
+pub const MAX_WARMUP_RUNS: u32 = 100;
+
+/// Largest value accepted for the level or any integer input. Real builds stay
+/// in the hundreds; the cap keeps the integer formula math far from overflow.
+pub const MAX_INPUT_VALUE: i32 = 1_000_000;
+
+/// Type of value a config getter reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    Int,
+    Float,
+    Bool,
+}
+
+/// A key the engine asked a config for, via one of the `get_*` getters
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct KeyRead {
+    pub section: &'static str,
+    pub key: String,
+    pub kind: ValueKind,
+}
+
+thread_local! {
+    static KEY_READS: RefCell<Option<BTreeSet<KeyRead>>> = const { RefCell::new(None) };
+}
+
+fn note_read(section: &'static str, key: &str, kind: ValueKind) {
+    KEY_READS.with(|reads| {
+        if let Some(reads) = reads.borrow_mut().as_mut() {
+            reads.insert(KeyRead { section, key: key.to_string(), kind });
+        }
+    });
+}
+
+/// Run `f` and return every key the getters were asked for on this thread
+/// meanwhile: the keys the engine recognizes, straight from the code that
+/// reads them. Only reads that happen are seen, so a key read behind a
+/// condition needs a config that meets it.
+pub fn record_key_reads(f: impl FnOnce()) -> BTreeSet<KeyRead> {
+    KEY_READS.with(|reads| *reads.borrow_mut() = Some(BTreeSet::new()));
+    f();
+    KEY_READS.with(|reads| reads.borrow_mut().take().unwrap_or_default())
+}
 
 /// The type of hunter
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -32,6 +94,164 @@ impl<'de> Deserialize<'de> for HunterType {
     }
 }
 
+/// Where runs pick up, from the build's `start` section: mid-run situations
+/// like "stage 180 with 40 souls stacks" instead of a fresh hunter at stage 0.
+/// Unset fields keep the fresh hunter's value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StartState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<i32>,                 // Stage the run starts on, before its enemies spawn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hp_fraction: Option<f64>,           // HP as a fraction of max HP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trickster_charges: Option<i32>,     // Ozzy's banked Trickster's Boon evades
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hundred_souls_stacks: Option<i32>,  // Knox's stacks, up to the build's cap
+}
+
+impl StartState {
+    /// Every key the section takes
+    pub const KEYS: [&'static str; 4] = ["stage", "hp_fraction", "trickster_charges", "hundred_souls_stacks"];
+
+    /// True when nothing is set (the section is then left out of saved builds)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Set one key from a JSON number
+    pub fn set(&mut self, key: &str, value: &serde_json::Value) -> Result<(), ConfigError> {
+        let invalid = |message: String| ConfigError::Invalid { field: format!("start.{}", key), message };
+        let int = || {
+            value
+                .as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .ok_or_else(|| invalid(format!("expected an integer, got {}", value)))
+        };
+        match key {
+            "stage" => self.stage = Some(int()?),
+            "hp_fraction" => self.hp_fraction = Some(value.as_f64().ok_or_else(|| invalid(format!("expected a number, got {}", value)))?),
+            "trickster_charges" => self.trickster_charges = Some(int()?),
+            "hundred_souls_stacks" => self.hundred_souls_stacks = Some(int()?),
+            _ => return Err(invalid(format!("unknown key; expected one of {}", Self::KEYS.join(", ")))),
+        }
+        Ok(())
+    }
+}
+
+/// Warm-up runs each simulation plays when the build's `persistence` section
+/// doesn't say
+pub const DEFAULT_WARMUP_RUNS: u32 = 3;
+
+/// What carries from one run into the next, from the build's `persistence`
+/// section, for steady-state farming instead of cold starts. With a mechanic
+/// on, each simulation first plays warm-up runs, each starting with what the
+/// one before ended with, and reports the run after them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PersistenceRules {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hundred_souls: bool,       // Knox's Hundred Souls stacks
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub trickster_charges: bool,   // Ozzy's banked Trickster's Boon evades
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warmup_runs: Option<u32>,  // Default DEFAULT_WARMUP_RUNS
+}
+
+impl PersistenceRules {
+    /// True when nothing is set (the section is then left out of saved builds)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether any mechanic carries over
+    pub fn any(&self) -> bool {
+        self.hundred_souls || self.trickster_charges
+    }
+
+    /// Runs played before the reported one; none when nothing carries over
+    pub fn warmup_runs(&self) -> u32 {
+        if self.any() { self.warmup_runs.unwrap_or(DEFAULT_WARMUP_RUNS) } else { 0 }
+    }
+
+    /// Set one key: a mechanic from a JSON bool, `warmup_runs` from a number
+    pub fn set(&mut self, key: &str, value: &serde_json::Value) -> Result<(), ConfigError> {
+        let invalid = |message: String| ConfigError::Invalid { field: format!("persistence.{}", key), message };
+        let on = || value.as_bool().ok_or_else(|| invalid(format!("expected a bool, got {}", value)));
+        match key {
+            "hundred_souls" => self.hundred_souls = on()?,
+            "trickster_charges" => self.trickster_charges = on()?,
+            "warmup_runs" => {
+                let n = value.as_u64().and_then(|n| u32::try_from(n).ok());
+                self.warmup_runs = Some(n.ok_or_else(|| invalid(format!("expected a count, got {}", value)))?);
+            }
+            _ => return Err(invalid("unknown key; expected hundred_souls, trickster_charges or warmup_runs".to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// Regen ticks one Vectid Elixir proc empowers
+pub const EMPOWERED_REGEN_TICKS: i32 = 5;
+
+/// How a timed buff's repeat procs combine with what's left of it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackMode {
+    #[default]
+    Accumulate,  // Each proc adds its ticks to the ones left (this engine and the Python sim)
+    Refresh,     // A proc resets the ticks left to one proc's worth
+}
+
+/// How buffs stack, from the build's `stacking` section. Unset keeps the
+/// engine's accumulate-without-limit, which lets a run of kills bank far more
+/// empowered regen than one proc gives; `refresh` or a cap model the game if
+/// it turns out not to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StackingRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empowered_regen: Option<StackMode>,  // Ozzy's Vectid Elixir; default accumulate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empowered_regen_cap: Option<i32>,    // Most empowered ticks banked at once
+}
+
+impl StackingRules {
+    /// True when nothing is set (the section is then left out of saved builds)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Empowered ticks left after a proc adding `ticks` to `left`, and the
+    /// ticks the rules dropped
+    pub fn empowered_regen_after_proc(&self, left: i32, ticks: i32) -> (i32, i32) {
+        let stacked = match self.empowered_regen.unwrap_or_default() {
+            StackMode::Accumulate => left + ticks,
+            StackMode::Refresh => left.max(ticks),
+        };
+        let kept = self.empowered_regen_cap.map_or(stacked, |cap| stacked.min(cap));
+        (kept, left + ticks - kept)
+    }
+
+    /// Set one key: a mode from a JSON string, the cap from a number
+    pub fn set(&mut self, key: &str, value: &serde_json::Value) -> Result<(), ConfigError> {
+        let invalid = |message: String| ConfigError::Invalid { field: format!("stacking.{}", key), message };
+        match key {
+            "empowered_regen" => {
+                let mode = serde_json::from_value(value.clone())
+                    .map_err(|_| invalid(format!("expected accumulate or refresh, got {}", value)))?;
+                self.empowered_regen = Some(mode);
+            }
+            "empowered_regen_cap" => {
+                let n = value.as_i64().and_then(|n| i32::try_from(n).ok());
+                self.empowered_regen_cap = Some(n.ok_or_else(|| invalid(format!("expected an integer, got {}", value)))?);
+            }
+            _ => return Err(invalid("unknown key; expected empowered_regen or empowered_regen_cap".to_string())),
+        }
+        Ok(())
+    }
+}
+
 /// Metadata about the build
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Meta {
@@ -69,6 +289,22 @@ pub struct BuildConfig {
     pub gadgets: HashMap<String, i32>,
     #[serde(default)]
     pub bonuses: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "MitigationRules::is_default")]
+    pub mitigation: MitigationRules,  // Optional DR caps (engine rules, not game inputs)
+    #[serde(default, skip_serializing_if = "LoggingRules::is_default")]
+    pub logging: LoggingRules,  // Optional trace filter (subsystems and stages)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<LootEvent>,  // Optional time-boxed loot/XP multipliers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stage: Option<i32>,  // Optional stage cap, overriding the hunter's from game_data
+    #[serde(default, skip_serializing_if = "StartState::is_default")]
+    pub start: StartState,  // Optional mid-run starting state
+    #[serde(default, skip_serializing_if = "PersistenceRules::is_default")]
+    pub persistence: PersistenceRules,  // Optional carry-over between runs
+    #[serde(default, skip_serializing_if = "StackingRules::is_default")]
+    pub stacking: StackingRules,  // Optional buff stacking rules
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub engine_flags: BTreeMap<String, bool>,  // Optional disputed-mechanic switches, see engine_flags
 }
 
 impl BuildConfig {
@@ -90,64 +326,483 @@ impl BuildConfig {
         }
     }
     
+    /// Stage the build's runs stop at: its `max_stage`, or the hunter's cap
+    pub fn get_max_stage(&self) -> i32 {
+        self.max_stage.unwrap_or_else(|| game_data::max_stage(self.get_hunter_type()))
+    }
+    
+    /// Fold the flat `hunter`/`level` fields into `meta` so every loaded config
+    /// has one canonical shape. GUI exports sometimes carry both formats; if they
+    /// disagree that's an error rather than silently preferring `meta`.
+    pub fn normalize(&mut self) -> Result<(), ConfigError> {
+        if let Some(ref meta) = self.meta {
+            if let Some(hunter) = self.hunter.filter(|h| *h != meta.hunter) {
+                return Err(ConfigError::Invalid {
+                    field: "hunter".to_string(),
+                    message: format!("`meta.hunter` is {:?} but top-level `hunter` is {:?}", meta.hunter, hunter),
+                });
+            }
+            if let Some(level) = self.level.filter(|l| *l != meta.level) {
+                return Err(ConfigError::Invalid {
+                    field: "level".to_string(),
+                    message: format!("`meta.level` is {} but top-level `level` is {}", meta.level, level),
+                });
+            }
+        } else {
+            if self.hunter.is_none() {
+                warn!("config does not name a hunter; defaulting to Borge");
+            }
+            self.meta = Some(Meta {
+                hunter: self.get_hunter_type(),
+                level: self.get_level(),
+            });
+        }
+        self.hunter = None;
+        self.level = None;
+        self.canonicalize_keys()?;
+        self.check_ranges()
+    }
+    
+    /// Reject values the engine can't simulate meaningfully: negative or huge
+    /// levels, non-finite or negative bonus numbers, and oversized sections
+    fn check_ranges(&self) -> Result<(), ConfigError> {
+        let invalid = |field: String, message: String| Err(ConfigError::Invalid { field, message });
+        let level = self.get_level();
+        if !(0..=MAX_INPUT_VALUE).contains(&level) {
+            return invalid("level".to_string(), format!("{} is outside 0..={}", level, MAX_INPUT_VALUE));
+        }
+        for (section, len) in self.section_sizes() {
+            if len > MAX_SECTION_KEYS {
+                return invalid(section.to_string(), format!("{} keys exceeds the limit of {}", len, MAX_SECTION_KEYS));
+            }
+        }
+        for (key, value) in self.numeric_inputs() {
+            if !value.is_finite() || value < 0.0 || value > MAX_INPUT_VALUE as f64 {
+                return invalid(key, format!("{} is outside 0..={}", value, MAX_INPUT_VALUE));
+            }
+        }
+        for (key, cap) in self.mitigation.caps() {
+            if !(0.0..=1.0).contains(&cap) {
+                return invalid(key.to_string(), format!("{} is outside 0..=1", cap));
+            }
+        }
+        if let Some(max_stage) = self.max_stage.filter(|s| !(1..=STAGE_LIMIT).contains(s)) {
+            return invalid("max_stage".to_string(), format!("{} is outside 1..={}", max_stage, STAGE_LIMIT));
+        }
+        for (i, event) in self.events.iter().enumerate() {
+            event.validate(i)?;
+        }
+        if let (Some(min), Some(max)) = (self.logging.min_stage, self.logging.max_stage) {
+            if min > max {
+                return invalid("logging.min_stage".to_string(), format!("{} is above logging.max_stage {}", min, max));
+            }
+        }
+        if let Some(stage) = self.start.stage.filter(|s| !(0..self.get_max_stage()).contains(s)) {
+            return invalid("start.stage".to_string(), format!("{} is outside 0..{} (the max stage)", stage, self.get_max_stage()));
+        }
+        if let Some(fraction) = self.start.hp_fraction.filter(|f| !(*f > 0.0 && *f <= 1.0)) {
+            return invalid("start.hp_fraction".to_string(), format!("{} is outside (0, 1]", fraction));
+        }
+        if let Some(name) = self.engine_flags.keys().find(|name| EngineFlag::parse(name).is_none()) {
+            return invalid(format!("engine_flags.{}", name), format!("unknown flag; expected one of {}", EngineFlag::names()));
+        }
+        if let Some(cap) = self.stacking.empowered_regen_cap.filter(|c| !(1..=MAX_INPUT_VALUE).contains(c)) {
+            return invalid("stacking.empowered_regen_cap".to_string(), format!("{} is outside 1..={}", cap, MAX_INPUT_VALUE));
+        }
+        if self.persistence.warmup_runs.is_some_and(|n| n > MAX_WARMUP_RUNS) {
+            return invalid("persistence.warmup_runs".to_string(), format!("{} is above {}", self.persistence.warmup_runs.unwrap_or(0), MAX_WARMUP_RUNS));
+        }
+        for (key, value) in [("start.trickster_charges", self.start.trickster_charges), ("start.hundred_souls_stacks", self.start.hundred_souls_stacks)] {
+            if let Some(n) = value.filter(|n| !(0..=MAX_INPUT_VALUE).contains(n)) {
+                return invalid(key.to_string(), format!("{} is outside 0..={}", n, MAX_INPUT_VALUE));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Clamp every input into the range `normalize` accepts: levels and integer
+    /// inputs to 0..=MAX_INPUT_VALUE, and non-finite bonus numbers zeroed.
+    /// Returns what was changed, for callers that want to report repairs.
+    pub fn sanitize(&mut self) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        let clamp = |v: i32| v.clamp(0, MAX_INPUT_VALUE);
+        
+        let level = self.get_level();
+        if clamp(level) != level {
+            changes.push(ConfigChange {
+                key: "level".to_string(),
+                before: Some(level.into()),
+                after: Some(clamp(level).into()),
+            });
+            self.set_input("level", clamp(level)).ok();
+        }
+        for section in ["stats", "talents", "attributes", "inscryptions", "relics", "gems", "gadgets"] {
+            if let Some(map) = self.int_section_mut(section) {
+                for (key, value) in map.iter_mut().filter(|(_, v)| clamp(**v) != **v) {
+                    changes.push(ConfigChange {
+                        key: format!("{}.{}", section, key),
+                        before: Some((*value).into()),
+                        after: Some(clamp(*value).into()),
+                    });
+                    *value = clamp(*value);
+                }
+            }
+        }
+        for (key, value) in self.bonuses.iter_mut() {
+            let Some(n) = value.as_f64() else { continue };
+            let fixed = if n.is_finite() { n.clamp(0.0, MAX_INPUT_VALUE as f64) } else { 0.0 };
+            if fixed != n {
+                changes.push(ConfigChange {
+                    key: format!("bonuses.{}", key),
+                    before: Some(value.clone()),
+                    after: Some(fixed.into()),
+                });
+                *value = fixed.into();
+            }
+        }
+        changes.sort_by(|a, b| a.key.cmp(&b.key));
+        changes
+    }
+    
+    fn section_sizes(&self) -> [(&'static str, usize); 9] {
+        [
+            ("stats", self.stats.len()),
+            ("talents", self.talents.len()),
+            ("attributes", self.attributes.len()),
+            ("inscryptions", self.inscryptions.len()),
+            ("mods", self.mods.len()),
+            ("relics", self.relics.len()),
+            ("gems", self.gems.len()),
+            ("gadgets", self.gadgets.len()),
+            ("bonuses", self.bonuses.len()),
+        ]
+    }
+    
+    /// Every integer input and numeric bonus as (`section.key`, value), sorted by key
+    fn numeric_inputs(&self) -> Vec<(String, f64)> {
+        let ints = [
+            ("stats", &self.stats),
+            ("talents", &self.talents),
+            ("attributes", &self.attributes),
+            ("inscryptions", &self.inscryptions),
+            ("relics", &self.relics),
+            ("gems", &self.gems),
+            ("gadgets", &self.gadgets),
+        ];
+        let mut out: Vec<(String, f64)> = ints
+            .iter()
+            .flat_map(|(section, map)| map.iter().map(move |(k, v)| (format!("{}.{}", section, k), *v as f64)))
+            .chain(self.bonuses.iter().filter_map(|(k, v)| v.as_f64().map(|n| (format!("bonuses.{}", k), n))))
+            .collect();
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out
+    }
+    
+    /// Fold every alias in `KEY_ALIASES` into its canonical key. When both
+    /// spellings are present the higher level wins, matching the old `max()` probes.
+    fn canonicalize_keys(&mut self) -> Result<(), ConfigError> {
+        for alias in KEY_ALIASES {
+            let value = if alias.section == "bonuses" {
+                match self.bonuses.remove(alias.alias) {
+                    Some(v) => Some(whole_number(&v).ok_or_else(|| ConfigError::Invalid {
+                        field: format!("bonuses.{}", alias.alias),
+                        message: format!("expected a whole number for {}.{}, got {}", alias.canonical_section, alias.canonical, v),
+                    })?),
+                    None => None,
+                }
+            } else {
+                self.int_section_mut(alias.section).and_then(|s| s.remove(alias.alias))
+            };
+            let Some(value) = value else { continue };
+            debug!(from = alias.alias, to = alias.canonical, "canonicalized config key");
+            if let Some(section) = self.int_section_mut(alias.canonical_section) {
+                let entry = section.entry(alias.canonical.to_string()).or_insert(0);
+                *entry = (*entry).max(value);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Set one integer input addressed as `section.key` (or `level`). Aliases
+    /// are resolved first, so `relics.r7` sets `manifestation_core_titan`.
+    pub fn set_input(&mut self, key: &str, value: i32) -> Result<(), ConfigError> {
+        if key == "level" {
+            match self.meta.as_mut() {
+                Some(meta) => meta.level = value,
+                None => self.level = Some(value),
+            }
+            return Ok(());
+        }
+        let invalid = |message: &str| ConfigError::Invalid { field: key.to_string(), message: message.to_string() };
+        let (section, name) = key.split_once('.').ok_or_else(|| invalid("expected `section.key` or `level`"))?;
+        let (section, name) = KEY_ALIASES
+            .iter()
+            .find(|a| a.section == section && a.alias == name)
+            .map_or((section, name), |a| (a.canonical_section, a.canonical));
+        let map = self.int_section_mut(section).ok_or_else(|| invalid("not an integer section"))?;
+        map.insert(name.to_string(), value);
+        Ok(())
+    }
+    
+    /// Set one input of any type: `hunter`, `level`, `mods.key` (a bool),
+    /// `bonuses.key` (any JSON value) or an integer `section.key` via [`set_input`](Self::set_input)
+    pub fn set_value(&mut self, key: &str, value: &serde_json::Value) -> Result<(), ConfigError> {
+        let invalid = |message: String| ConfigError::Invalid { field: key.to_string(), message };
+        if key == "hunter" {
+            let hunter: HunterType = serde_json::from_value(value.clone()).map_err(|e| invalid(e.to_string()))?;
+            match self.meta.as_mut() {
+                Some(meta) => meta.hunter = hunter,
+                None => self.hunter = Some(hunter),
+            }
+            return Ok(());
+        }
+        match key.split_once('.') {
+            Some(("mods", name)) => {
+                let on = value.as_bool().ok_or_else(|| invalid(format!("expected a bool, got {}", value)))?;
+                self.mods.insert(name.to_string(), on);
+            }
+            Some(("bonuses", name)) => {
+                self.bonuses.insert(name.to_string(), value.clone());
+            }
+            Some(("start", name)) => self.start.set(name, value)?,
+            Some(("persistence", name)) => self.persistence.set(name, value)?,
+            Some(("stacking", name)) => self.stacking.set(name, value)?,
+            Some(("engine_flags", name)) => {
+                let on = value.as_bool().ok_or_else(|| invalid(format!("expected a bool, got {}", value)))?;
+                self.engine_flags.insert(name.to_string(), on);
+            }
+            _ => {
+                let n = value
+                    .as_i64()
+                    .and_then(|n| i32::try_from(n).ok())
+                    .ok_or_else(|| invalid(format!("expected an integer, got {}", value)))?;
+                self.set_input(key, n)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Apply a map of overrides with [`set_value`](Self::set_value). Keys are
+    /// `section.key`, `hunter` or `level`; a section name mapped to an object
+    /// (`{"talents": {"x": 3}}`) sets each of its keys.
+    pub fn apply_overrides(&mut self, overrides: &serde_json::Map<String, serde_json::Value>) -> Result<(), ConfigError> {
+        for (key, value) in overrides {
+            match value {
+                serde_json::Value::Object(inner) if !key.contains('.') => {
+                    for (name, value) in inner {
+                        self.set_value(&format!("{}.{}", key, name), value)?;
+                    }
+                }
+                _ => self.set_value(key, value)?,
+            }
+        }
+        Ok(())
+    }
+    
+    /// Integer-valued section by name (`mods` and `bonuses` aren't integer maps)
+    fn int_section_mut(&mut self, section: &str) -> Option<&mut HashMap<String, i32>> {
+        match section {
+            "stats" => Some(&mut self.stats),
+            "talents" => Some(&mut self.talents),
+            "attributes" => Some(&mut self.attributes),
+            "inscryptions" => Some(&mut self.inscryptions),
+            "relics" => Some(&mut self.relics),
+            "gems" => Some(&mut self.gems),
+            "gadgets" => Some(&mut self.gadgets),
+            _ => None,
+        }
+    }
+    
     /// Load a build configuration from a YAML file
-    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(&path)?;
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let content = read_config_file(path.as_ref())?;
         let path_str = path.as_ref().to_string_lossy().to_lowercase();
         
         // Check if it's JSON or YAML
         if path_str.ends_with(".json") {
-            let config: BuildConfig = serde_json::from_str(&content)?;
-            Ok(config)
+            Self::from_json(&content)
         } else {
-            let config: BuildConfig = serde_yaml::from_str(&content)?;
-            Ok(config)
+            Self::from_yaml(&content)
         }
     }
     
     /// Load from JSON string (for Python interop)
-    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let config: BuildConfig = serde_json::from_str(json)?;
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        check_size(json.len())?;
+        let mut de = serde_json::Deserializer::from_str(json);
+        let mut config: Self = serde_path_to_error::deserialize(&mut de).map_err(ConfigError::from_json)?;
+        config.normalize()?;
         Ok(config)
     }
     
+    /// Load from YAML string
+    pub fn from_yaml(yaml: &str) -> Result<Self, ConfigError> {
+        check_size(yaml.len())?;
+        let de = serde_yaml::Deserializer::from_str(yaml);
+        let mut config: Self = serde_path_to_error::deserialize(de).map_err(ConfigError::from_yaml)?;
+        config.normalize()?;
+        Ok(config)
+    }
+    
+    /// Load one or more configs from a file: either a single YAML/JSON build
+    /// or a JSON array of builds (as written by the GUI for batch runs)
+    pub fn load_all<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, ConfigError> {
+        let content = read_config_file(path.as_ref())?;
+        if content.trim_start().starts_with('[') {
+            let mut de = serde_json::Deserializer::from_str(&content);
+            let mut configs: Vec<Self> = serde_path_to_error::deserialize(&mut de).map_err(ConfigError::from_json)?;
+            for (i, config) in configs.iter_mut().enumerate() {
+                config.normalize().map_err(|e| match e {
+                    ConfigError::Invalid { field, message } => ConfigError::Invalid {
+                        field: format!("[{}].{}", i, field),
+                        message,
+                    },
+                    other => other,
+                })?;
+            }
+            Ok(configs)
+        } else {
+            Self::from_file(path).map(|c| vec![c])
+        }
+    }
+    
+    /// List every input that differs between two builds, keyed as `section.key`.
+    /// Hunter and level are compared after resolving meta vs flat format, and a
+    /// key missing on one side counts as unchanged when the other side is 0/false.
+    pub fn diff_inputs(&self, other: &BuildConfig) -> Vec<ConfigChange> {
+        let before = self.flat_inputs();
+        let after = other.flat_inputs();
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        
+        let is_default = |v: &serde_json::Value| match v {
+            serde_json::Value::Null => true,
+            serde_json::Value::Bool(b) => !b,
+            serde_json::Value::Number(n) => n.as_f64() == Some(0.0),
+            _ => false,
+        };
+        keys.into_iter()
+            .filter_map(|key| {
+                let (b, a) = (before.get(key), after.get(key));
+                let unchanged = match (b, a) {
+                    (Some(b), Some(a)) => b == a,
+                    (Some(v), None) | (None, Some(v)) => is_default(v),
+                    (None, None) => true,
+                };
+                (!unchanged).then(|| ConfigChange {
+                    key: key.clone(),
+                    before: b.cloned(),
+                    after: a.cloned(),
+                })
+            })
+            .collect()
+    }
+    
+    /// Every input as a `section.key` -> value map (hunter/level resolved)
+    fn flat_inputs(&self) -> HashMap<String, serde_json::Value> {
+        let mut out = HashMap::new();
+        out.insert("hunter".to_string(), serde_json::json!(self.get_hunter_type()));
+        out.insert("level".to_string(), serde_json::json!(self.get_level()));
+        let sections: [(&str, serde_json::Value); 12] = [
+            ("stats", serde_json::json!(self.stats)),
+            ("talents", serde_json::json!(self.talents)),
+            ("attributes", serde_json::json!(self.attributes)),
+            ("inscryptions", serde_json::json!(self.inscryptions)),
+            ("mods", serde_json::json!(self.mods)),
+            ("relics", serde_json::json!(self.relics)),
+            ("gems", serde_json::json!(self.gems)),
+            ("gadgets", serde_json::json!(self.gadgets)),
+            ("bonuses", serde_json::json!(self.bonuses)),
+            ("mitigation", serde_json::json!(self.mitigation)),
+            ("stacking", serde_json::json!(self.stacking)),
+            ("engine_flags", serde_json::json!(self.engine_flags)),
+        ];
+        for (section, values) in sections {
+            if let serde_json::Value::Object(map) = values {
+                for (key, value) in map {
+                    out.insert(format!("{}.{}", section, key), value);
+                }
+            }
+        }
+        out
+    }
+    
+    /// Whether `flag` is on: the build's `engine_flags` entry, else its default
+    pub fn get_engine_flag(&self, flag: EngineFlag) -> bool {
+        self.engine_flags.get(flag.name()).copied().unwrap_or(flag.info().default)
+    }
+
+    /// The `mitigation` section with `dr_cap_95` filled in where it sets no DR cap
+    pub fn mitigation_rules(&self) -> MitigationRules {
+        let mut rules = self.mitigation;
+        if rules.dr_cap.is_none() && self.get_engine_flag(EngineFlag::DrCap95) {
+            rules.dr_cap = Some(0.95);
+        }
+        rules
+    }
+
+    /// The `stacking` section with `empowered_regen_refresh` filled in where
+    /// it sets no mode
+    pub fn stacking_rules(&self) -> StackingRules {
+        let mut rules = self.stacking;
+        if rules.empowered_regen.is_none() && self.get_engine_flag(EngineFlag::EmpoweredRegenRefresh) {
+            rules.empowered_regen = Some(StackMode::Refresh);
+        }
+        rules
+    }
+
     /// Get a stat value with default
     pub fn get_stat(&self, key: &str) -> i32 {
+        note_read("stats", key, ValueKind::Int);
         *self.stats.get(key).unwrap_or(&0)
     }
     
     /// Get a talent value with default
     pub fn get_talent(&self, key: &str) -> i32 {
+        note_read("talents", key, ValueKind::Int);
         *self.talents.get(key).unwrap_or(&0)
     }
     
     /// Get an attribute value with default
     pub fn get_attr(&self, key: &str) -> i32 {
+        note_read("attributes", key, ValueKind::Int);
         *self.attributes.get(key).unwrap_or(&0)
     }
     
     /// Get an inscryption value with default
     pub fn get_inscr(&self, key: &str) -> i32 {
+        note_read("inscryptions", key, ValueKind::Int);
         *self.inscryptions.get(key).unwrap_or(&0)
     }
     
     /// Get a relic value with default
     pub fn get_relic(&self, key: &str) -> i32 {
+        note_read("relics", key, ValueKind::Int);
         *self.relics.get(key).unwrap_or(&0)
     }
     
     /// Get a gem value with default
     pub fn get_gem(&self, key: &str) -> i32 {
+        note_read("gems", key, ValueKind::Int);
         *self.gems.get(key).unwrap_or(&0)
     }
     
     /// Get a gadget value with default
     pub fn get_gadget(&self, key: &str) -> i32 {
+        note_read("gadgets", key, ValueKind::Int);
         *self.gadgets.get(key).unwrap_or(&0)
     }
     
+    /// Get a mod flag, off by default
+    pub fn get_mod(&self, key: &str) -> bool {
+        note_read("mods", key, ValueKind::Bool);
+        *self.mods.get(key).unwrap_or(&false)
+    }
+    
     /// Get a bonus integer value with default
     pub fn get_bonus_int(&self, key: &str) -> i32 {
+        note_read("bonuses", key, ValueKind::Int);
         self.bonuses.get(key)
             .and_then(|v| v.as_i64())
             .map(|v| v as i32)
@@ -156,6 +811,7 @@ impl BuildConfig {
     
     /// Get a bonus float value with default
     pub fn get_bonus_float(&self, key: &str) -> f64 {
+        note_read("bonuses", key, ValueKind::Float);
         self.bonuses.get(key)
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0)
@@ -163,6 +819,7 @@ impl BuildConfig {
     
     /// Get a bonus boolean value with default
     pub fn get_bonus_bool(&self, key: &str) -> bool {
+        note_read("bonuses", key, ValueKind::Bool);
         self.bonuses.get(key)
             .and_then(|v| v.as_bool())
             .unwrap_or(false)
@@ -171,36 +828,33 @@ impl BuildConfig {
     /// Calculate the complete loot multiplier from all sources.
     /// This matches the WASM calculation which multiplies all bonuses together.
     pub fn calculate_loot_multiplier(&self, hunter_type: HunterType, effect_chance: f64) -> f64 {
-        let mut mult = 1.0;
-        let debug = std::env::var("DEBUG_LOOT").is_ok();
+        self.loot_multiplier_breakdown(hunter_type, effect_chance).total
+    }
+    
+    /// Itemized loot multiplier: every source that contributes, in the order applied
+    pub fn loot_multiplier_breakdown(&self, hunter_type: HunterType, effect_chance: f64) -> MultiplierBreakdown {
+        let mut steps = MultiplierBreakdown::new("loot");
         
         // === TIMELESS MASTERY (Attribute) ===
         // Different bonus per hunter: Borge +14%, Ozzy +16%, Knox +14% per level
         let timeless = self.get_attr("timeless_mastery");
         if timeless > 0 {
-            let rate = match hunter_type {
-                HunterType::Borge => 0.14,
-                HunterType::Ozzy => 0.16,
-                HunterType::Knox => 0.14,
-            };
-            mult *= 1.0 + (timeless as f64 * rate);
-            if debug { eprintln!("After timeless_mastery({}): {:.4}", timeless, mult); }
+            let rate = game_data::coefficient(hunter_type, "timeless_mastery", "loot").unwrap_or(0.0);
+            steps.add("timeless_mastery", timeless as f64, 1.0 + (timeless as f64 * rate));
         }
         
         // === SHARD MILESTONE #0 ===
         // 1.02^level (unlimited levels!)
         let shard_milestone = self.get_bonus_int("shard_milestone");
         if shard_milestone > 0 {
-            mult *= 1.02_f64.powi(shard_milestone);
-            if debug { eprintln!("After shard_milestone({}): {:.4}", shard_milestone, mult); }
+            steps.add("shard_milestone", shard_milestone as f64, 1.02_f64.powi(shard_milestone));
         }
         
         // === RELIC #7 (Manifestation Core: Titan) ===
         // 1.05^level (max 100)
-        let relic7 = self.get_relic("r7").max(self.get_relic("manifestation_core_titan"));
+        let relic7 = self.get_relic("manifestation_core_titan");
         if relic7 > 0 {
-            mult *= 1.05_f64.powi(relic7);
-            if debug { eprintln!("After r7({}): {:.4}", relic7, mult); }
+            steps.add("r7", relic7 as f64, 1.05_f64.powi(relic7));
         }
         
         // === RESEARCH #81 ===
@@ -216,10 +870,7 @@ impl BuildConfig {
             (6, _) => 1.32,
             _ => 1.0,
         };
-        mult *= research_mult;
-        if research81 > 0 {
-            
-        }
+        steps.add("research81", research81 as f64, research_mult);
         
         // === INSCRYPTIONS (hunter-specific) ===
         match hunter_type {
@@ -227,39 +878,35 @@ impl BuildConfig {
                 // i14: 1.1^level (max 5)
                 let i14 = self.get_inscr("i14");
                 if i14 > 0 { 
-                    mult *= 1.1_f64.powi(i14); 
-                    if debug { eprintln!("After i14({}): {:.4}", i14, mult); }
+                    steps.add("i14", i14 as f64, 1.1_f64.powi(i14));
                 }
                 
                 // i44: 1.08^level (max 10)
                 let i44 = self.get_inscr("i44");
                 if i44 > 0 { 
-                    mult *= 1.08_f64.powi(i44); 
-                    if debug { eprintln!("After i44({}): {:.4}", i44, mult); }
+                    steps.add("i44", i44 as f64, 1.08_f64.powi(i44));
                 }
                 
                 // i60: special multi-power (+3% per level to loot)
                 let i60 = self.get_inscr("i60");
                 if i60 > 0 { 
-                    mult *= 1.0 + (i60 as f64 * 0.03); 
-                    if debug { eprintln!("After i60({}): {:.4}", i60, mult); }
+                    steps.add("i60", i60 as f64, 1.0 + (i60 as f64 * 0.03));
                 }
                 
                 // i80: 1.1^level (max 10)
                 let i80 = self.get_inscr("i80");
                 if i80 > 0 { 
-                    mult *= 1.1_f64.powi(i80); 
-                    if debug { eprintln!("After i80({}): {:.4}", i80, mult); }
+                    steps.add("i80", i80 as f64, 1.1_f64.powi(i80));
                 }
             }
             HunterType::Ozzy => {
                 // i32: 1.5^level (max 8)
                 let i32_val = self.get_inscr("i32");
-                if i32_val > 0 { mult *= 1.5_f64.powi(i32_val); }
+                if i32_val > 0 { steps.add("i32", i32_val as f64, 1.5_f64.powi(i32_val)); }
                 
                 // i81: 1.1^level (max 10)
                 let i81 = self.get_inscr("i81");
-                if i81 > 0 { mult *= 1.1_f64.powi(i81); }
+                if i81 > 0 { steps.add("i81", i81 as f64, 1.1_f64.powi(i81)); }
             }
             HunterType::Knox => {
                 // Knox doesn't have hunter-specific loot inscryptions yet
@@ -269,44 +916,33 @@ impl BuildConfig {
         
         // === GADGETS ===
         // Compound formula: (1 + baseValue)^level * tierMultiplier^(level/tierStep)
-        // wrench/zaptron/anchor: baseValue=0.005, tierStep=10, tierMultiplier=1.02
-        let gadget_loot = |level: i32| -> f64 {
-            if level <= 0 { return 1.0; }
-            let base = 1.005_f64.powi(level);
-            let tier_mult = 1.02_f64.powi(level / 10);
-            base * tier_mult
-        };
+        let gadget_loot = formulas::gadget_loot_multiplier;
         
-        // Wrench (Borge loot) - supports both 'wrench' and 'wrench_of_gore' keys
+        // Wrench of Gore (Borge loot)
         if hunter_type == HunterType::Borge {
-            let wrench_level = self.get_gadget("wrench").max(self.get_gadget("wrench_of_gore"));
-            mult *= gadget_loot(wrench_level);
-            if debug && wrench_level > 0 { eprintln!("After wrench({}): {:.4}", wrench_level, mult); }
+            let wrench_level = self.get_gadget("wrench_of_gore");
+            steps.add("wrench", wrench_level as f64, gadget_loot(wrench_level));
         }
-        // Zaptron (Ozzy loot) - supports both 'zaptron' and 'zaptron_533' keys
+        // Zaptron 533 (Ozzy loot)
         if hunter_type == HunterType::Ozzy {
-            let zaptron_level = self.get_gadget("zaptron").max(self.get_gadget("zaptron_533"));
-            mult *= gadget_loot(zaptron_level);
-            if debug && zaptron_level > 0 { eprintln!("After zaptron({}): {:.4}", zaptron_level, mult); }
+            let zaptron_level = self.get_gadget("zaptron_533");
+            steps.add("zaptron", zaptron_level as f64, gadget_loot(zaptron_level));
         }
-        // Trident (Knox loot) - APK: KnoxLootGadget / Gadget19
+        // Trident of Tides (Knox loot) - APK: KnoxLootGadget / Gadget19
         if hunter_type == HunterType::Knox {
-            let trident_level = self.get_gadget("trident").max(self.get_gadget("gadget19")).max(self.get_gadget("trident_of_tides"));
-            mult *= gadget_loot(trident_level);
-            if debug && trident_level > 0 { eprintln!("After trident({}): {:.4}", trident_level, mult); }
-        }
-        // Anchor (all hunters) - supports both 'anchor' and 'titan_anchor' keys
-        let anchor_level = self.get_gadget("anchor").max(self.get_gadget("anchor_of_ages"));
-        mult *= gadget_loot(anchor_level);
-        if debug && anchor_level > 0 { eprintln!("After anchor({}): {:.4}", anchor_level, mult); }
+            let trident_level = self.get_gadget("trident_of_tides");
+            steps.add("trident", trident_level as f64, gadget_loot(trident_level));
+        }
+        // Anchor of Ages (all hunters)
+        let anchor_level = self.get_gadget("anchor_of_ages");
+        steps.add("anchor", anchor_level as f64, gadget_loot(anchor_level));
         
         // === LOOP MODS ===
         // Scavenger's Advantage: 1.05^level (max 25) - Borge
         if hunter_type == HunterType::Borge {
             let scavenger = self.get_bonus_int("scavenger");
             if scavenger > 0 { 
-                mult *= 1.05_f64.powi(scavenger.min(25)); 
-                if debug { eprintln!("After scavenger({}): {:.4}", scavenger, mult); }
+                steps.add("scavenger", scavenger as f64, 1.05_f64.powi(game_data::clamp_input("bonuses", "scavenger", scavenger)));
             }
             
             // LMOuro1: Base Hunt Loot Rewards Bonus (Borge)
@@ -314,8 +950,7 @@ impl BuildConfig {
             // Formula: exponent^level where exponent ≈ 1.03 (similar to scavenger)
             let lm_ouro1 = self.get_bonus_int("lm_ouro1");
             if lm_ouro1 > 0 { 
-                mult *= 1.03_f64.powi(lm_ouro1); 
-                if debug { eprintln!("After lm_ouro1({}): {:.4}", lm_ouro1, mult); }
+                steps.add("lm_ouro1", lm_ouro1 as f64, 1.03_f64.powi(lm_ouro1));
             }
             
             // LMOuro11 Bonus2: Boon Eternity - Loot Rewards component (Borge)
@@ -323,67 +958,59 @@ impl BuildConfig {
             // This is a prestige-tier loop mod, likely stronger multiplier
             let lm_ouro11 = self.get_bonus_int("lm_ouro11");
             if lm_ouro11 > 0 { 
-                mult *= 1.05_f64.powi(lm_ouro11); 
-                if debug { eprintln!("After lm_ouro11({}): {:.4}", lm_ouro11, mult); }
+                steps.add("lm_ouro11", lm_ouro11 as f64, 1.05_f64.powi(lm_ouro11));
             }
         }
         // Scavenger's Advantage 2: 1.05^level (max 25) - Ozzy
         if hunter_type == HunterType::Ozzy {
             let scavenger2 = self.get_bonus_int("scavenger2");
             if scavenger2 > 0 { 
-                mult *= 1.05_f64.powi(scavenger2.min(25)); 
-                if debug { eprintln!("After scavenger2({}): {:.4}", scavenger2, mult); }
+                steps.add("scavenger2", scavenger2 as f64, 1.05_f64.powi(game_data::clamp_input("bonuses", "scavenger2", scavenger2)));
             }
             
             // LMOuro18: Base Hunt Loot Rewards Bonus (Ozzy)
             // APK: LMOuro18Bonus18Exponent - multiplicative bonus per level
             let lm_ouro18 = self.get_bonus_int("lm_ouro18");
             if lm_ouro18 > 0 { 
-                mult *= 1.03_f64.powi(lm_ouro18); 
-                if debug { eprintln!("After lm_ouro18({}): {:.4}", lm_ouro18, mult); }
+                steps.add("lm_ouro18", lm_ouro18 as f64, 1.03_f64.powi(lm_ouro18));
             }
         }
         
         // === CONSTRUCTION MILESTONES (CMs) ===
         // These are boolean - either unlocked or not
-        if self.get_bonus_bool("cm46") { mult *= 1.03; if debug { eprintln!("After cm46: {:.4}", mult); } }
-        if self.get_bonus_bool("cm47") { mult *= 1.02; if debug { eprintln!("After cm47: {:.4}", mult); } }
-        if self.get_bonus_bool("cm48") { mult *= 1.07; if debug { eprintln!("After cm48: {:.4}", mult); } }
-        if self.get_bonus_bool("cm51") { mult *= 1.05; if debug { eprintln!("After cm51: {:.4}", mult); } }
+        if self.get_bonus_bool("cm46") { steps.add("cm46", 1.0, 1.03); }
+        if self.get_bonus_bool("cm47") { steps.add("cm47", 1.0, 1.02); }
+        if self.get_bonus_bool("cm48") { steps.add("cm48", 1.0, 1.07); }
+        if self.get_bonus_bool("cm51") { steps.add("cm51", 1.0, 1.05); }
         
         // === DIAMOND CARDS ===
         // Gaiden Card: 1.05 loot (Borge)
         if hunter_type == HunterType::Borge && self.get_bonus_bool("gaiden_card") {
-            mult *= 1.05;
-            if debug { eprintln!("After gaiden_card: {:.4}", mult); }
+            steps.add("gaiden_card", 1.0, 1.05);
         }
         // Iridian Card: 1.05 loot (Ozzy)
         if hunter_type == HunterType::Ozzy && self.get_bonus_bool("iridian_card") {
-            mult *= 1.05;
-            if debug { eprintln!("After iridian_card: {:.4}", mult); }
+            steps.add("iridian_card", 1.0, 1.05);
         }
         
         // === DIAMOND SPECIALS ===
         // Hunter Loot Booster: +2.5% per level (max 10)
         let diamond_loot = self.get_bonus_int("diamond_loot");
         if diamond_loot > 0 {
-            mult *= 1.0 + (diamond_loot as f64 * 0.025);
-            if debug { eprintln!("After diamond_loot({}): {:.4}", diamond_loot, mult); }
+            steps.add("diamond_loot", diamond_loot as f64, 1.0 + (diamond_loot as f64 * 0.025));
         }
         
         // === IAP ===
         // Traversal Pack: 1.25x loot
         if self.get_bonus_bool("iap_travpack") {
-            mult *= 1.25;
-            if debug { eprintln!("After iap_travpack: {:.4}", mult); }
+            steps.add("iap_travpack", 1.0, 1.25);
         }
         
         // === ULTIMA ===
         // Direct multiplier (user enters the displayed bonus value)
         let ultima = self.get_bonus_float("ultima_multiplier");
         if ultima > 0.0 {
-            mult *= ultima;
-            if debug { eprintln!("After ultima({}): {:.4}", ultima, mult); }
+            steps.add("ultima", ultima, ultima);
         }
         
         // === GEM NODES (Attraction Gem) ===
@@ -391,58 +1018,37 @@ impl BuildConfig {
         // Formula from WASM: pow(1.07, lootLevel) - max level 50
         // At level 50: 1.07^50 = 29.46x multiplier
         if hunter_type == HunterType::Borge {
-            // Try multiple key variations for attraction_loot_borge
-            let loot_borge = self.get_gem("attraction_loot_borge")
-                .max(self.get_gem("attraction_lootBorge"))
-                .max(self.get_gem("lootBorge"))
-                .max(self.get_bonus_int("attraction_loot_borge"))
-                .max(self.get_bonus_int("attraction_lootBorge"));
+            let loot_borge = self.get_gem("attraction_loot_borge");
             if loot_borge > 0 { 
-                mult *= 1.07_f64.powi(loot_borge.min(50)); 
-                if debug { eprintln!("After attraction_loot_borge({}): {:.4}", loot_borge, mult); }
+                steps.add("attraction_loot_borge", loot_borge as f64, 1.07_f64.powi(game_data::clamp_input("gems", "attraction_loot_borge", loot_borge)));
             }
         }
         if hunter_type == HunterType::Ozzy {
-            // Try multiple key variations for attraction_loot_ozzy
-            let loot_ozzy = self.get_gem("attraction_loot_ozzy")
-                .max(self.get_gem("attraction_lootOzzy"))
-                .max(self.get_gem("lootOzzy"))
-                .max(self.get_bonus_int("attraction_loot_ozzy"))
-                .max(self.get_bonus_int("attraction_lootOzzy"));
+            let loot_ozzy = self.get_gem("attraction_loot_ozzy");
             if loot_ozzy > 0 { 
-                mult *= 1.07_f64.powi(loot_ozzy.min(50)); 
-                if debug { eprintln!("After attraction_loot_ozzy({}): {:.4}", loot_ozzy, mult); }
+                steps.add("attraction_loot_ozzy", loot_ozzy as f64, 1.07_f64.powi(game_data::clamp_input("gems", "attraction_loot_ozzy", loot_ozzy)));
             }
         }
         // APK: AttractionKnoxLootBonusCalc = 1.07^level
         if hunter_type == HunterType::Knox {
-            let loot_knox = self.get_gem("attraction_loot_knox")
-                .max(self.get_gem("attraction_lootKnox"))
-                .max(self.get_gem("lootKnox"))
-                .max(self.get_bonus_int("attraction_loot_knox"))
-                .max(self.get_bonus_int("attraction_lootKnox"));
+            let loot_knox = self.get_gem("attraction_loot_knox");
             if loot_knox > 0 { 
-                mult *= 1.07_f64.powi(loot_knox.min(50)); 
-                if debug { eprintln!("After attraction_loot_knox({}): {:.4}", loot_knox, mult); }
+                steps.add("attraction_loot_knox", loot_knox as f64, 1.07_f64.powi(game_data::clamp_input("gems", "attraction_loot_knox", loot_knox)));
             }
         }
         
         // === ATTRACTION NODE #3 (Gem Bonus) ===
         // All hunters: 1 + 0.25 × level
-        let gem_node_3 = self.get_gem("attraction_node_#3")
-            .max(self.get_gem("attraction_node_3"))
-            .max(self.get_bonus_int("gem_attraction_node3"));
+        let gem_node_3 = self.get_gem("attraction_node_#3");
         if gem_node_3 > 0 {
-            mult *= 1.0 + 0.25 * gem_node_3 as f64;
-            if debug { eprintln!("After attraction_node_#3({}): {:.4}", gem_node_3, mult); }
+            steps.add("attraction_node_#3", gem_node_3 as f64, 1.0 + 0.25 * gem_node_3 as f64);
         }
         
         // === PRESENCE OF GOD (Talent) ===
         // All hunters: 1 + 0.2 × level × effect_chance
         let pog_level = self.get_talent("presence_of_god");
         if pog_level > 0 {
-            mult *= 1.0 + pog_level as f64 * 0.2 * effect_chance;
-            if debug { eprintln!("After presence_of_god({}): {:.4}", pog_level, mult); }
+            steps.add("presence_of_god", pog_level as f64, 1.0 + pog_level as f64 * 0.2 * effect_chance);
         }
         
         // === BLESSINGS OF THE SCARAB (Ozzy attribute) ===
@@ -450,35 +1056,36 @@ impl BuildConfig {
         if hunter_type == HunterType::Ozzy {
             let scarab = self.get_attr("blessings_of_the_scarab");
             if scarab > 0 {
-                mult *= 1.0 + scarab as f64 * 0.05;
-                if debug { eprintln!("After blessings_of_the_scarab({}): {:.4}", scarab, mult); }
+                steps.add("blessings_of_the_scarab", scarab as f64, 1.0 + scarab as f64 * 0.05);
             }
         }
         
-        if debug { eprintln!("Final loot_mult: {:.4}", mult); }
-        mult
+        debug!(mult = steps.total, "final loot multiplier");
+        steps
     }
     
     /// Calculate comprehensive XP multiplier from all sources
     pub fn calculate_xp_multiplier(&self, hunter_type: HunterType) -> f64 {
-        let mut mult = 1.0;
-        let debug = std::env::var("DEBUG_XP").is_ok();
+        self.xp_multiplier_breakdown(hunter_type).total
+    }
+    
+    /// Itemized XP multiplier: every source that contributes, in the order applied
+    pub fn xp_multiplier_breakdown(&self, hunter_type: HunterType) -> MultiplierBreakdown {
+        let mut steps = MultiplierBreakdown::new("xp");
         
         // === RELIC #19 (Book of Mephisto) - Borge only ===
         // 2^level (max 8 levels) = up to 256x XP
         if hunter_type == HunterType::Borge {
-            let r19 = self.get_relic("r19").max(self.get_relic("book_of_mephisto"));
+            let r19 = self.get_relic("book_of_mephisto");
             if r19 > 0 {
-                mult *= 2.0_f64.powi(r19.min(8));
-                if debug { eprintln!("After r19({}): {:.4}", r19, mult); }
+                steps.add("r19", r19 as f64, 2.0_f64.powi(game_data::clamp_input("relics", "book_of_mephisto", r19)));
             }
             
             // POM3: HuntersAttributes XP bonus (Borge) = +10% per level
             // APK: POM3XpBonus with POM3XpBonusExponent
             let pom3 = self.get_bonus_int("pom3");
             if pom3 > 0 {
-                mult *= 1.0 + (pom3 as f64 * 0.10);
-                if debug { eprintln!("After pom3({}): {:.4}", pom3, mult); }
+                steps.add("pom3", pom3 as f64, 1.0 + (pom3 as f64 * 0.10));
             }
         }
         
@@ -487,16 +1094,14 @@ impl BuildConfig {
         if hunter_type == HunterType::Ozzy {
             let i33 = self.get_inscr("i33");
             if i33 > 0 {
-                mult *= 1.75_f64.powi(i33.min(8));
-                if debug { eprintln!("After i33({}): {:.4}", i33, mult); }
+                steps.add("i33", i33 as f64, 1.75_f64.powi(game_data::clamp_input("inscryptions", "i33", i33)));
             }
             
             // POI3: HuntersAttributes XP bonus (Ozzy) = +15% per level
             // APK: POI3XpBonus with POI3XpBonusExponent
             let poi3 = self.get_bonus_int("poi3");
             if poi3 > 0 {
-                mult *= 1.0 + (poi3 as f64 * 0.15);
-                if debug { eprintln!("After poi3({}): {:.4}", poi3, mult); }
+                steps.add("poi3", poi3 as f64, 1.0 + (poi3 as f64 * 0.15));
             }
         }
         
@@ -506,11 +1111,126 @@ impl BuildConfig {
         if hunter_type == HunterType::Knox {
             let pok3 = self.get_bonus_int("pok3");
             if pok3 > 0 {
-                mult *= 1.0 + (pok3 as f64 * 0.15);
-                if debug { eprintln!("After pok3({}): {:.4}", pok3, mult); }
+                steps.add("pok3", pok3 as f64, 1.0 + (pok3 as f64 * 0.15));
             }
         }
         
-        if debug { eprintln!("Final xp_mult: {:.4}", mult); }
-        mult
-    }}
+        debug!(mult = steps.total, "final xp multiplier");
+        steps
+    }
+}
+
+/// Alternate spelling of a config key (relic ids, short gadget names, GUI and
+/// WASM variants). `BuildConfig::normalize` rewrites every alias to its canonical
+/// key, so the formulas only ever read canonical keys.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyAlias {
+    pub section: &'static str,
+    pub alias: &'static str,
+    pub canonical_section: &'static str,
+    pub canonical: &'static str,
+}
+
+const fn alias(section: &'static str, alias: &'static str, canonical_section: &'static str, canonical: &'static str) -> KeyAlias {
+    KeyAlias { section, alias, canonical_section, canonical }
+}
+
+/// Every known alias; canonical keys are the names used by the bundled builds
+pub const KEY_ALIASES: &[KeyAlias] = &[
+    // Relics
+    alias("relics", "r4", "relics", "disk_of_dawn"),
+    alias("relics", "r7", "relics", "manifestation_core_titan"),
+    alias("relics", "r17", "relics", "bee_gone_companion_drone"),
+    alias("relics", "r19", "relics", "book_of_mephisto"),
+    // Gadgets
+    alias("gadgets", "wrench", "gadgets", "wrench_of_gore"),
+    alias("gadgets", "zaptron", "gadgets", "zaptron_533"),
+    alias("gadgets", "anchor", "gadgets", "anchor_of_ages"),
+    alias("gadgets", "titan_anchor", "gadgets", "anchor_of_ages"),
+    alias("gadgets", "trident", "gadgets", "trident_of_tides"),
+    alias("gadgets", "gadget19", "gadgets", "trident_of_tides"),  // APK: Gadget19
+    // Attraction gem nodes (older GUI builds stored some of these under bonuses)
+    alias("gems", "attraction_catch_up", "gems", "attraction_catch-up"),
+    alias("gems", "attraction_node_3", "gems", "attraction_node_#3"),
+    alias("bonuses", "gem_attraction_node3", "gems", "attraction_node_#3"),
+    alias("gems", "attraction_lootBorge", "gems", "attraction_loot_borge"),
+    alias("gems", "lootBorge", "gems", "attraction_loot_borge"),
+    alias("bonuses", "attraction_loot_borge", "gems", "attraction_loot_borge"),
+    alias("bonuses", "attraction_lootBorge", "gems", "attraction_loot_borge"),
+    alias("gems", "attraction_lootOzzy", "gems", "attraction_loot_ozzy"),
+    alias("gems", "lootOzzy", "gems", "attraction_loot_ozzy"),
+    alias("bonuses", "attraction_loot_ozzy", "gems", "attraction_loot_ozzy"),
+    alias("bonuses", "attraction_lootOzzy", "gems", "attraction_loot_ozzy"),
+    alias("gems", "attraction_lootKnox", "gems", "attraction_loot_knox"),
+    alias("gems", "lootKnox", "gems", "attraction_loot_knox"),
+    alias("bonuses", "attraction_loot_knox", "gems", "attraction_loot_knox"),
+    alias("bonuses", "attraction_lootKnox", "gems", "attraction_loot_knox"),
+];
+
+/// A JSON number with no fractional part as an integer input (`3` or `3.0`)
+fn whole_number(value: &serde_json::Value) -> Option<i32> {
+    match value.as_i64() {
+        Some(n) => i32::try_from(n).ok(),
+        None => value.as_f64().filter(|n| n.fract() == 0.0 && (i32::MIN as f64..=i32::MAX as f64).contains(n)).map(|n| n as i32),
+    }
+}
+
+/// One input that differs between two builds (`None` = key absent)
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChange {
+    pub key: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// One multiplicative contribution to the loot or XP multiplier
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiplierSource {
+    pub source: &'static str,
+    pub level: f64,   // Config level (or the raw value for direct multipliers like ultima)
+    pub factor: f64,  // Multiplier this source contributes
+}
+
+/// Loot or XP multiplier itemized by source, in the order the factors are applied
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiplierBreakdown {
+    #[serde(skip)]
+    kind: &'static str,
+    pub total: f64,
+    pub sources: Vec<MultiplierSource>,
+}
+
+impl MultiplierBreakdown {
+    fn new(kind: &'static str) -> Self {
+        Self { kind, total: 1.0, sources: Vec::new() }
+    }
+    
+    /// Apply one source's factor (sources that contribute exactly 1.0 are not listed)
+    fn add(&mut self, source: &'static str, level: f64, factor: f64) {
+        if factor == 1.0 {
+            return;
+        }
+        self.total *= factor;
+        self.sources.push(MultiplierSource { source, level, factor });
+        trace!(kind = self.kind, source, level, mult = self.total, "multiplier step");
+    }
+}
+
+
+/// Read a config file, attaching the path to I/O errors
+fn read_config_file(path: &Path) -> Result<String, ConfigError> {
+    let io_err = |source| ConfigError::Io { path: path.to_path_buf(), source };
+    let len = fs::metadata(path).map_err(io_err)?.len();
+    check_size(usize::try_from(len).unwrap_or(usize::MAX))?;
+    fs::read_to_string(path).map_err(io_err)
+}
+
+fn check_size(len: usize) -> Result<(), ConfigError> {
+    if len > MAX_CONFIG_BYTES {
+        return Err(ConfigError::Invalid {
+            field: "<root>".to_string(),
+            message: format!("config is {} bytes; the limit is {}", len, MAX_CONFIG_BYTES),
+        });
+    }
+    Ok(())
+}
@@ -1,20 +1,77 @@
 //! Enemy and Boss implementations - Updated to match CIFI Tools formulas
 
 use crate::config::HunterType;
-use crate::simulation::FastRng;
+use crate::formulas::{self, BaseEnemyStats};
+use crate::boss_script::{self, BossScript, Harden};
+use crate::game_data::{self, AbilityParams};
+use crate::simulation::SimRng;
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
 
-/// A regular enemy in combat
-/// Secondary attack type for bosses
+/// Special behavior of regular (non-boss) enemies, procced by `effect_chance`
+/// on a landed hit (parameters live in `game_data::ENEMY_ABILITIES`)
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum SecondaryAttackType {
+pub enum EnemyAbility {
     None,
-    Gothmorgor,  // Borge boss: deals damage + adds enrage
-    Exoscarab,   // Ozzy boss: triggers harden (95% DR, 3x regen for 5 ticks, +5 enrage at end)
+    RegenSuppression,  // Cuts hunter regen by a fraction for a few regen ticks
+}
+
+/// Identity of an enemy within a run: its stage and its slot on that stage
+/// (1-10 for regular enemies, 0 for the boss). Only rendered as a name when a
+/// trace or debug path asks for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnemyId {
+    pub stage: i32,
+    pub index: i32,
+}
+
+impl EnemyId {
+    pub fn is_boss(&self) -> bool {
+        self.index == 0
+    }
+}
+
+impl fmt::Display for EnemyId {
+    /// Python-style name: `E{stage:>3}{index:>3}` for enemies, `B{stage:>3}` for bosses
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_boss() {
+            write!(f, "B{:>3}", self.stage)
+        } else {
+            write!(f, "E{:>3}{:>3}", self.stage, self.index)
+        }
+    }
+}
+
+/// Stages covered by the precomputed stat tables; later stages are computed on demand
+const TABLE_STAGES: i32 = 600;
+
+/// Regular enemy and boss of every stage for one hunter type, built on first
+/// use and shared by every simulation in the process. Enemy stats depend only
+/// on (stage, hunter type, boss), so spawning an enemy is a copy of its row.
+struct StageTable {
+    regular: Vec<Enemy>,  // Index stage - 1
+    boss: Vec<Enemy>,     // Index stage - 1 (only boss stages are ever read)
 }
 
+static STAGE_TABLES: [OnceLock<StageTable>; 3] = [const { OnceLock::new() }; 3];
+
+/// Whether any hunter's table has been built, fixing the boss scripts it used
+pub(crate) fn stage_tables_built() -> bool {
+    STAGE_TABLES.iter().any(|table| table.get().is_some())
+}
+
+fn stage_table(hunter_type: HunterType) -> &'static StageTable {
+    STAGE_TABLES[hunter_type as usize].get_or_init(|| StageTable {
+        regular: (1..=TABLE_STAGES).map(|stage| Enemy::compute(1, stage, hunter_type)).collect(),
+        boss: (1..=TABLE_STAGES).map(|stage| Enemy::compute_boss(stage, hunter_type)).collect(),
+    })
+}
+
+/// A regular enemy in combat
 #[derive(Debug, Clone)]
 pub struct Enemy {
-    pub name: String,
+    pub id: EnemyId,
     pub hp: f64,
     pub max_hp: f64,
     pub power: f64,
@@ -37,102 +94,45 @@ pub struct Enemy {
     pub enrage_stacks: i32,
     pub max_enrage: bool,  // True when stacks > 200 (3x power, 100% crit)
     pub has_secondary: bool,
-    pub secondary_type: SecondaryAttackType,
+    pub secondary: Option<&'static BossScript>,  // Played by enemy_attack_special every speed2 seconds
     pub speed2: f64,
     pub base_speed2: f64,
-    // Exoscarab harden mechanic
+    // Harden mechanic (Exoscarab), from the script's harden effect
+    pub harden: Harden,
     pub harden_ticks_left: i32,
+    pub harden_absorbed: f64,  // Damage absorbed by harden DR beyond base DR, drained into SimResult
+    // Regular-enemy ability (Enemy::new only)
+    pub ability: EnemyAbility,
+    pub ability_params: AbilityParams,
 }
 
 impl Enemy {
-    /// Python's multi_wasm scaling function - WASM-verified additive breakpoints
-    /// This MUST match Python's units.py multi_wasm exactly!
-    fn multi_wasm(stage: i32) -> f64 {
-        let s = stage as f64;
-        
-        // WASM formula from multiWasm function (lines 1304-1320 in release.dcmp)
-        // Uses ADDITIVE scaling with many breakpoints
-        let mut result = 1.0;
-        result += 0.0_f64.max((s - 149.0) * 0.006);
-        result += 0.0_f64.max((s - 199.0) * 0.006);
-        result += 0.0_f64.max((s - 249.0) * 0.006);
-        result += 0.0_f64.max((s - 299.0) * 0.006);
-        result += 0.0_f64.max((s - 309.0) * 0.003);
-        result += 0.0_f64.max((s - 319.0) * 0.003);
-        result += 0.0_f64.max((s - 329.0) * 0.004);
-        result += 0.0_f64.max((s - 339.0) * 0.004);
-        result += 0.0_f64.max((s - 349.0) * 0.005);
-        result += 0.0_f64.max((s - 359.0) * 0.005);
-        result += 0.0_f64.max((s - 369.0) * 0.006);
-        result += 0.0_f64.max((s - 379.0) * 0.006);
-        result += 0.0_f64.max((s - 389.0) * 0.007);
-        
-        // WASM applies max(result, 1.0) then multiplies by exponential
-        result = result.max(1.0);
-        result *= 1.01_f64.powi((stage - 350).max(0));
-        
-        result
-    }
-    
-    /// CIFI stage scaling function for Knox (f_o)
-    /// Knox uses MULTIPLICATIVE scaling unlike Borge/Ozzy's additive multi_wasm.
-    /// Below stage 150, returns 1.0 (no scaling).
-    fn knox_scaling(stage: i32) -> f64 {
-        if stage < 150 {
-            return 1.0;
-        }
-        
-        let s = stage as f64;
-        let mut result = 1.0;
-        
-        // First breakpoint at 149
-        if stage > 149 {
-            result *= 1.0 + (s - 149.0) * 0.007;
-        }
-        
-        // Additional breakpoints
-        if stage > 199 {
-            result *= 1.0 + (s - 199.0) * 0.007;
-        }
-        if stage > 249 {
-            result *= 1.0 + (s - 249.0) * 0.007;
-        }
-        if stage > 299 {
-            result *= 1.0 + (s - 299.0) * 0.007;
-        }
-        if stage > 349 {
-            result *= 1.0 + (s - 349.0) * 0.007;
-        }
-        
-        // Breakpoints every 20 stages after 360
-        if stage > 369 {
-            result *= 1.0 + (s - 369.0) * 0.007;
-        }
-        if stage > 389 {
-            result *= 1.0 + (s - 389.0) * 0.007;
-        }
-        if stage > 409 {
-            result *= 1.0 + (s - 409.0) * 0.007;
-        }
-        if stage > 429 {
-            result *= 1.0 + (s - 429.0) * 0.007;
+    /// Create a regular enemy for a given stage (from the stat table when covered)
+    pub fn new(index: i32, stage: i32, hunter_type: HunterType) -> Self {
+        if !(1..=TABLE_STAGES).contains(&stage) {
+            return Self::compute(index, stage, hunter_type);
         }
-        
-        // Exponential scaling after stage 400
-        if stage > 400 {
-            result *= 1.01_f64.powi(stage - 400);
+        let mut enemy = stage_table(hunter_type).regular[stage as usize - 1].clone();
+        enemy.id.index = index;
+        enemy
+    }
+
+    /// Create a boss for a given stage (from the stat table when covered)
+    pub fn new_boss(stage: i32, hunter_type: HunterType) -> Self {
+        if !(1..=TABLE_STAGES).contains(&stage) {
+            return Self::compute_boss(stage, hunter_type);
         }
-        
-        result
+        stage_table(hunter_type).boss[stage as usize - 1].clone()
     }
 
-    /// Create a regular enemy for a given stage - using CIFI formulas
-    pub fn new(index: i32, stage: i32, hunter_type: HunterType) -> Self {
-        let (hp, power, regen, special_chance, special_damage, dr, evade_chance, effect_chance, speed) = 
-            Self::calculate_stats_cifi(stage, hunter_type, false);
+    /// Compute a regular enemy for a given stage - using CIFI formulas
+    fn compute(index: i32, stage: i32, hunter_type: HunterType) -> Self {
+        let BaseEnemyStats { hp, power, regen, special_chance, special_damage, damage_reduction: dr, evade_chance, effect_chance, speed } =
+            formulas::enemy_stats(stage, hunter_type, false);
+        let ability = game_data::enemy_ability(hunter_type, stage);
         
         Self {
-            name: format!("E{:>3}{:>3}", stage, index),
+            id: EnemyId { stage, index },
             hp,
             max_hp: hp,
             power,
@@ -154,33 +154,30 @@ impl Enemy {
             enrage_stacks: 0,
             max_enrage: false,
             has_secondary: false,
-            secondary_type: SecondaryAttackType::None,
+            secondary: None,
             speed2: 0.0,
             base_speed2: 0.0,
+            harden: Harden::default(),
             harden_ticks_left: 0,
+            harden_absorbed: 0.0,
+            ability: ability.map_or(EnemyAbility::None, |a| a.ability),
+            ability_params: ability.map_or_else(AbilityParams::default, |a| a.params),
         }
     }
     
-    /// Create a boss for a given stage - using CIFI formulas
-    pub fn new_boss(stage: i32, hunter_type: HunterType) -> Self {
-        let (hp, power, regen, special_chance, special_damage, dr, evade_chance, effect_chance, speed) = 
-            Self::calculate_stats_cifi(stage, hunter_type, true);
+    /// Compute a boss for a given stage - using CIFI formulas
+    fn compute_boss(stage: i32, hunter_type: HunterType) -> Self {
+        let BaseEnemyStats { hp, power, regen, special_chance, special_damage, damage_reduction: dr, evade_chance, effect_chance, speed } =
+            formulas::enemy_stats(stage, hunter_type, true);
         
-        // Calculate speed2 and secondary type based on hunter type
-        // Ozzy Exoscarab: 60 second cooldown (fixed), no speed reduction from enrage
-        // Borge Gothmorgor: speed2 = base_speed * 2.1 * 1.8, reduced by enrage
-        let (speed2, secondary_type) = if stage >= 200 {
-            match hunter_type {
-                HunterType::Ozzy => (60.0, SecondaryAttackType::Exoscarab),  // WASM: Fixed 60 second cooldown
-                HunterType::Borge => (speed * 1.8, SecondaryAttackType::Gothmorgor),
-                HunterType::Knox => (0.0, SecondaryAttackType::None),  // Knox doesn't have secondary
-            }
-        } else {
-            (0.0, SecondaryAttackType::None)
-        };
+        // Secondary attack and its cooldown come from the boss scripts
+        // Ozzy Exoscarab: 60 second cooldown (fixed)
+        // Borge Gothmorgor: speed2 = base_speed * 1.8, reduced by enrage
+        let secondary = boss_script::for_boss(hunter_type, stage);
+        let speed2 = secondary.map_or(0.0, |b| b.cooldown.seconds(speed));
         
         Self {
-            name: format!("B{:>3}", stage),
+            id: EnemyId { stage, index: 0 },
             hp,
             max_hp: hp,
             power,
@@ -201,146 +198,15 @@ impl Enemy {
             pending_stun_delay: 0.0,
             enrage_stacks: 0,
             max_enrage: false,
-            has_secondary: stage >= 200 && hunter_type != HunterType::Knox,
-            secondary_type,
+            has_secondary: secondary.is_some(),
+            secondary,
             speed2,
             base_speed2: speed2,
+            harden: Harden::default(),
             harden_ticks_left: 0,
-        }
-    }
-    
-    /// Calculate enemy stats using CIFI formulas extracted from WASM
-    fn calculate_stats_cifi(stage: i32, hunter_type: HunterType, is_boss: bool) -> (f64, f64, f64, f64, f64, f64, f64, f64, f64) {
-        // Returns: (hp, power, regen, special_chance, special_damage, dr, evade_chance, effect_chance, speed)
-        let s = stage as f64;
-        let d = ((stage - 1).max(0) as f64 / 100.0).floor() as i32;  // Boss cycles completed
-        let d_f = d as f64;
-        let is_stage_300 = stage == 300;
-        
-        match hunter_type {
-            HunterType::Borge => {
-                let f = Self::multi_wasm(stage);
-                
-                // Match Python Borge formulas (validated against WASM)
-                // Borge uses FLAT 2.85 multiplier for stages > 100
-                let tier_mult = if stage > 100 { 2.85 } else { 1.0 };
-                
-                // HP: (9 + stage * 4) * tier_mult * multi_wasm * boss(90x) * stage300(0.9)
-                let hp = (s * 4.0 + 9.0) * f * tier_mult
-                    * if is_boss { 90.0 } else { 1.0 }
-                    * if is_stage_300 { 0.9 } else { 1.0 };
-                
-                // Power: (2.5 + stage * 0.7) * tier_mult * multi_wasm * boss(3.63x) * stage300(0.9)
-                let power = (s * 0.7 + 2.5) * f * tier_mult
-                    * if is_boss { 3.63 } else { 1.0 }
-                    * if is_stage_300 { 0.9 } else { 1.0 };
-                
-                // Crit chance: 0.0322 + stage * 0.0004 + boss(0.04), capped at 0.25 (APK verified)
-                let special_chance = (s * 0.0004 + 0.0322 + if is_boss { 0.04 } else { 0.0 }).min(0.25);
-                
-                // Crit damage: 1.212 + stage * 0.008 + boss(0.25), capped at 2.5 (APK verified)
-                let special_damage = (s * 0.008 + 1.212 + if is_boss { 0.25 } else { 0.0 }).min(2.5);
-                
-                // Damage reduction (boss only): min(0.05 + stage * 0.0004, 0.25)
-                let actual_dr = if is_boss { (0.05 + s * 0.0004).min(0.25) } else { 0.0 };
-                
-                // Evade: 0.004 if stage > 100
-                let evade = if stage > 100 { 0.004 } else { 0.0 };
-                
-                // Effect chance (not used for Borge enemies in Python)
-                let effect = 0.0;
-                
-                // Regen: (stage-1) * 0.08 * 1.052 (if stage > 100) * multi_wasm * boss(1.92x) (APK verified)
-                let regen_tier = if stage > 100 { 1.052 } else { 1.0 };
-                let regen = if stage > 1 { (s - 1.0) * 0.08 } else { 0.0 } * regen_tier * f
-                    * if is_boss { 1.92 } else { 1.0 };
-                
-                // Speed: (4.53 - stage * 0.006) * boss(2.42x) (APK verified)
-                let speed = (4.53 - s * 0.006) * if is_boss { 2.42 } else { 1.0 };
-                
-                (hp, power, regen, special_chance, special_damage, actual_dr, evade, effect, speed)
-            }
-            HunterType::Ozzy => {
-                let f = Self::multi_wasm(stage);
-                
-                // Match Python Ozzy formulas (validated against WASM)
-                // HP: (11 + stage * 6) * 2.9^tier * multi_wasm * boss(48x) * stage300(0.94)
-                let hp = (s * 6.0 + 11.0) * f * 2.9_f64.powf(d_f)
-                    * if is_boss { 48.0 } else { 1.0 }
-                    * if is_stage_300 { 0.94 } else { 1.0 };
-                
-                // Power: (1.35 + stage * 0.75) * 2.7^tier * multi_wasm * boss(3x) * stage300(0.94)
-                let power = (s * 0.75 + 1.35) * f * 2.7_f64.powf(d_f)
-                    * if is_boss { 3.0 } else { 1.0 }
-                    * if is_stage_300 { 0.94 } else { 1.0 };
-                
-                // Crit chance: 0.0994 + stage * 0.0006 + boss(0.13) (APK verified)
-                let special_chance = (s * 0.0006 + 0.0994 + if is_boss { 0.13 } else { 0.0 }).min(0.25);
-                
-                // Crit damage: min(1.03 + stage * 0.008, 2.5)
-                let special_damage = (s * 0.008 + 1.03).min(2.5);
-                
-                // Damage reduction (boss only from Python)
-                let actual_dr = if is_boss { (0.05 + s * 0.0004).min(0.25) } else { 0.0 };
-                
-                // Evade: max((tier-1)*0.01+0.01, 0) if stage >= 100
-                let evade = if stage >= 100 {
-                    ((d_f - 1.0) * 0.01 + 0.01).max(0.0)
-                } else { 0.0 };
-                
-                // Effect chance (not used in Python Ozzy enemies)
-                let effect = 0.0;
-                
-                // Regen: (stage-1) * 0.1 * 1.25^tier * multi_wasm * boss(6x)
-                let regen = if stage > 0 { (s - 1.0) * 0.1 } else { 0.0 }
-                    * 1.25_f64.powf(d_f) * f
-                    * if is_boss { 6.0 } else { 1.0 };
-                
-                // Speed: (3.20 - stage * 0.004) * boss(2.45)
-                let speed = (3.2 - s * 0.004) * if is_boss { 2.45 } else { 1.0 };
-                
-                (hp, power, regen, special_chance, special_damage, actual_dr, evade, effect, speed)
-            }
-            HunterType::Knox => {
-                let f = Self::knox_scaling(stage);
-                
-                // Match Python Knox formulas (validated against WASM)
-                // HP: (10 + stage * 5) * 2.8 (if stage > 100) * knox_scaling * boss(120x)
-                let post_100_mult = if stage > 100 { 2.8 } else { 1.0 };
-                let hp = (10.0 + s * 5.0) * post_100_mult * f
-                    * if is_boss { 120.0 } else { 1.0 };
-                
-                // Power: (1.5 + stage * 0.65) * 2.6 (if stage > 100) * knox_scaling * boss(4x)
-                let power_100_mult = if stage > 100 { 2.6 } else { 1.0 };
-                let power = (1.5 + s * 0.65) * power_100_mult * f
-                    * if is_boss { 4.0 } else { 1.0 };
-                
-                // Crit chance: 0.075 + stage * 0.00055 + boss_bonus (APK verified: +13%)
-                let special_chance = (s * 0.00055 + 0.075 + if is_boss { 0.13 } else { 0.0 }).min(0.25);
-                
-                // Crit damage: 1.15 + stage * 0.0075 + boss_bonus (APK verified: +0%)
-                let special_damage = (s * 0.0075 + 1.15 + if is_boss { 0.0 } else { 0.0 }).min(2.5);
-                
-                // Damage reduction (boss only)
-                let dr = if is_boss { 0.05 } else { 0.0 };
-                let actual_dr = dr;
-                
-                // Evade: 0.006 if stage > 100, else 0
-                let evade = if stage > 100 { 0.006 } else { 0.0 };
-                
-                // Effect chance: 0.03 + stage * 0.0003
-                let effect = s * 0.0003 + 0.03;
-                
-                // Regen: (stage - 1) * 0.09 * 1.15 (if stage > 100) * knox_scaling * boss(2.0x) (APK verified)
-                let regen_100_mult = if stage > 100 { 1.15 } else { 1.0 };
-                let regen = if stage > 0 { (s - 1.0) * 0.09 } else { 0.0 } * regen_100_mult * f
-                    * if is_boss { 2.0 } else { 1.0 };
-                
-                // Speed: (3.80 - stage * 0.005) * boss(2.85x) (APK verified)
-                let speed = (3.80 - s * 0.005) * if is_boss { 2.85 } else { 1.0 };
-                
-                (hp, power, regen, special_chance, special_damage, actual_dr, evade, effect, speed)
-            }
+            harden_absorbed: 0.0,
+            ability: EnemyAbility::None,
+            ability_params: AbilityParams::default(),
         }
     }
     
@@ -352,44 +218,66 @@ impl Enemy {
     /// Apply damage to the enemy
     pub fn take_damage(&mut self, damage: f64) -> f64 {
         let actual = damage * (1.0 - self.damage_reduction);
+        if self.harden_ticks_left > 0 {
+            self.harden_absorbed += damage * (self.damage_reduction - self.base_dr);
+        }
         self.hp -= actual;
         actual
     }
     
-    /// Apply regeneration - also handles harden mechanic for Exoscarab
+    /// Apply one second of regeneration - also handles harden mechanic for Exoscarab
     pub fn regen_hp(&mut self) {
+        self.regen_hp_over(1.0, 1);
+    }
+    
+    /// Apply `seconds` of regeneration; harden counts down by `whole_seconds`,
+    /// as `Hunter::regen_hp_over`
+    pub fn regen_hp_over(&mut self, seconds: f64, whole_seconds: i32) {
         if self.hp < self.max_hp && self.hp > 0.0 {
             if self.harden_ticks_left > 0 {
-                // Harden effect: 3x regen for 5 ticks
-                self.hp = (self.hp + self.regen * 3.0).min(self.max_hp);
-                self.harden_ticks_left -= 1;
+                // Harden effect: faster regen (Exoscarab: 3x for 5 ticks)
+                self.hp = (self.hp + self.regen * self.harden.regen_mult * seconds).min(self.max_hp);
+                self.harden_ticks_left = (self.harden_ticks_left - whole_seconds).max(0);
                 if self.harden_ticks_left == 0 {
                     // Harden ends: +5 enrage stacks and restore DR
                     self.end_harden();
                 }
             } else {
-                self.hp = (self.hp + self.regen).min(self.max_hp);
+                self.hp = (self.hp + self.regen * seconds).min(self.max_hp);
             }
         }
     }
     
-    /// Start harden effect (Exoscarab boss)
-    pub fn start_harden(&mut self) {
-        self.harden_ticks_left = 5;
-        self.damage_reduction = 0.95;  // 95% DR during harden
+    /// Start a harden window (Exoscarab boss)
+    pub fn start_harden(&mut self, harden: Harden) {
+        self.harden = harden;
+        self.harden_ticks_left = harden.ticks;
+        self.damage_reduction = harden.dr;  // Exoscarab: 95% DR during harden
     }
     
     /// End harden effect (Exoscarab boss)
     pub fn end_harden(&mut self) {
         self.damage_reduction = self.base_dr;  // Restore original DR
         // WASM: +5 enrage stacks added when harden ends
-        for _ in 0..5 {
+        self.add_enrage_stacks(self.harden.end_enrage);
+    }
+    
+    /// Add several enrage stacks, one at a time so every threshold is applied
+    pub fn add_enrage_stacks(&mut self, stacks: i32) {
+        for _ in 0..stacks {
             self.add_enrage();
         }
     }
     
+    /// Siphon: restore `fraction` of max HP
+    pub fn siphon(&mut self, fraction: f64) {
+        if self.hp > 0.0 {
+            self.hp = (self.hp + self.max_hp * fraction).min(self.max_hp);
+        }
+    }
+    
     /// Get attack damage with possible crit - CIFI enrage mechanics
-    pub fn get_attack_damage(&self, rng: &mut FastRng) -> (f64, bool) {
+    pub fn get_attack_damage(&self, rng: &mut impl SimRng) -> (f64, bool) {
         // At 200+ enrage stacks, damage is tripled and always crits
         let power = if self.enrage_stacks > 200 {
             self.base_power * 3.0
@@ -444,3 +332,123 @@ impl Enemy {
         self.speed2
     }
 }
+
+/// Spawn-time combat stats of one enemy, as the stat calculators report them
+#[derive(Debug, Clone, Serialize)]
+pub struct EnemyStats {
+    pub hp: f64,
+    pub power: f64,
+    pub regen: f64,
+    pub damage_reduction: f64,
+    pub evade_chance: f64,
+    pub special_chance: f64,
+    pub special_damage: f64,
+    pub speed: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed2: Option<f64>,       // Bosses only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,  // Bosses only
+}
+
+impl EnemyStats {
+    pub fn of(enemy: &Enemy) -> Self {
+        Self {
+            hp: enemy.max_hp,
+            power: enemy.power,
+            regen: enemy.regen,
+            damage_reduction: enemy.damage_reduction,
+            evade_chance: enemy.evade_chance,
+            special_chance: enemy.special_chance,
+            special_damage: enemy.special_damage,
+            speed: enemy.speed,
+            speed2: enemy.is_boss.then_some(enemy.speed2),
+            secondary: enemy.is_boss.then(|| enemy.secondary.map_or("None".to_string(), |s| s.name.to_string())),
+        }
+    }
+}
+
+/// Regular enemy and boss stats of one stage of a hunter's path
+#[derive(Debug, Clone, Serialize)]
+pub struct StageStats {
+    pub stage: i32,
+    pub enemy: EnemyStats,
+    pub boss: EnemyStats,
+}
+
+impl StageStats {
+    pub fn new(hunter_type: HunterType, stage: i32) -> Self {
+        Self {
+            stage,
+            enemy: EnemyStats::of(&Enemy::new(1, stage, hunter_type)),
+            boss: EnemyStats::of(&Enemy::new_boss(stage, hunter_type)),
+        }
+    }
+
+    /// Every `step`th stage from `from` through `to`
+    pub fn range(hunter_type: HunterType, from: i32, to: i32, step: usize) -> Vec<Self> {
+        (from..=to).step_by(step.max(1)).map(|stage| Self::new(hunter_type, stage)).collect()
+    }
+
+    /// Column names of `csv_row`
+    pub fn csv_header() -> String {
+        let columns = ["hp", "power", "regen", "damage_reduction", "evade_chance", "special_chance", "special_damage", "speed"];
+        let mut header = vec!["stage".to_string()];
+        for who in ["enemy", "boss"] {
+            header.extend(columns.iter().map(|c| format!("{}_{}", who, c)));
+        }
+        header.extend(["boss_speed2".to_string(), "boss_secondary".to_string()]);
+        header.join(",")
+    }
+
+    /// One comma-separated line, at full precision
+    pub fn csv_row(&self) -> String {
+        let mut row = vec![self.stage.to_string()];
+        for s in [&self.enemy, &self.boss] {
+            let values = [s.hp, s.power, s.regen, s.damage_reduction, s.evade_chance, s.special_chance, s.special_damage, s.speed];
+            row.extend(values.iter().map(f64::to_string));
+        }
+        row.push(self.boss.speed2.map_or_else(String::new, |v| v.to_string()));
+        row.push(self.boss.secondary.clone().unwrap_or_default());
+        row.join(",")
+    }
+}
+
+/// Reusable enemy slots for a run: each stage resets the slots it needs in
+/// place instead of building a fresh list, so a run allocates its enemies once
+/// no matter how many stages it clears.
+#[derive(Debug, Clone, Default)]
+pub struct EnemyPool {
+    slots: Vec<Enemy>,
+    active: usize,
+}
+
+impl EnemyPool {
+    /// Enemies on a regular stage
+    pub const SLOTS: usize = 10;
+
+    pub fn new() -> Self {
+        Self { slots: Vec::with_capacity(Self::SLOTS), active: 0 }
+    }
+
+    /// Reset the pool to the enemies of `stage`: the boss on boss stages,
+    /// otherwise `SLOTS` regular enemies
+    pub fn spawn(&mut self, stage: i32, hunter_type: HunterType, is_boss: bool) -> &mut [Enemy] {
+        if is_boss {
+            self.fill(0, Enemy::new_boss(stage, hunter_type));
+            self.active = 1;
+        } else {
+            for i in 0..Self::SLOTS {
+                self.fill(i, Enemy::new(i as i32 + 1, stage, hunter_type));
+            }
+            self.active = Self::SLOTS;
+        }
+        &mut self.slots[..self.active]
+    }
+
+    fn fill(&mut self, slot: usize, enemy: Enemy) {
+        match self.slots.get_mut(slot) {
+            Some(existing) => *existing = enemy,
+            None => self.slots.push(enemy),
+        }
+    }
+}
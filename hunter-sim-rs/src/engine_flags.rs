@@ -0,0 +1,85 @@
+//! Switches for disputed mechanics (`engine_flags`)
+//!
+//! Where the WASM game, the Python sim and this engine disagree, or nobody
+//! knows which is right, a flag picks one reading for a batch so its effect
+//! can be measured before a verdict is in. Flags live in the build's
+//! `engine_flags` map (or `--engine-flag NAME=BOOL` on the command line) and
+//! each one maps onto the engine rule it stands for. An unset flag keeps the
+//! engine's default; a rule set explicitly in its own section (a `mitigation`
+//! cap, a `stacking` mode) wins over the flag.
+
+use serde::Serialize;
+
+/// One disputed mechanic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineFlag {
+    OzzyDoubleOnKill,       // Ozzy's on-kill effects run twice per attack kill (Python sim)
+    DrCap95,                // Main DR capped at 95% (old engine)
+    StunAffectsSpecials,    // Stuns delay the enemy's special attack too
+    EmpoweredRegenRefresh,  // Vectid Elixir procs refresh instead of stacking
+    EnemyAbilities,         // Regular enemies use the provisional abilities from stage 300
+}
+
+/// A flag's name, default and what turning it over does
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FlagInfo {
+    pub flag: EngineFlag,
+    pub name: &'static str,
+    pub default: bool,  // What the engine does with the flag unset
+    pub description: &'static str,
+}
+
+/// Every flag, in the order they're listed
+pub const FLAGS: &[FlagInfo] = &[
+    FlagInfo {
+        flag: EngineFlag::OzzyDoubleOnKill,
+        name: "ozzy_double_on_kill",
+        default: false,
+        description: "Ozzy runs on-kill effects from attack() and on_death() (Python sim), as mods.double_on_kill",
+    },
+    FlagInfo {
+        flag: EngineFlag::DrCap95,
+        name: "dr_cap_95",
+        default: false,
+        description: "Main DR is capped at 95% (old engine), as mitigation.dr_cap: 0.95",
+    },
+    FlagInfo {
+        flag: EngineFlag::StunAffectsSpecials,
+        name: "stun_affects_specials",
+        default: false,
+        description: "Stuns delay the enemy's next special attack as well as its next attack",
+    },
+    FlagInfo {
+        flag: EngineFlag::EmpoweredRegenRefresh,
+        name: "empowered_regen_refresh",
+        default: false,
+        description: "Vectid Elixir procs refresh the buff instead of stacking, as stacking.empowered_regen: refresh",
+    },
+    FlagInfo {
+        flag: EngineFlag::EnemyAbilities,
+        name: "enemy_abilities",
+        default: false,
+        description: "Regular enemies from stage 300 use the unverified abilities in game_data::ENEMY_ABILITIES (regen suppression)",
+    },
+];
+
+impl EngineFlag {
+    pub fn info(self) -> &'static FlagInfo {
+        FLAGS.iter().find(|f| f.flag == self).expect("every flag is listed")
+    }
+
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    /// The flag called `name`
+    pub fn parse(name: &str) -> Option<Self> {
+        FLAGS.iter().find(|f| f.name == name).map(|f| f.flag)
+    }
+
+    /// Every flag name, comma separated, for error messages
+    pub fn names() -> String {
+        FLAGS.iter().map(|f| f.name).collect::<Vec<_>>().join(", ")
+    }
+}
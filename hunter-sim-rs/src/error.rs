@@ -0,0 +1,209 @@
+//! Error types for config loading, simulation, and build optimization
+
+use serde::Serialize;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors from loading or validating a build configuration
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The config file could not be read
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    
+    /// The config text could not be parsed; `field` is the path to the offending value
+    #[error("invalid config at `{field}`: {message}")]
+    Parse { field: String, message: String },
+    
+    /// The config parsed but contains a value the simulator cannot use
+    #[error("invalid value for `{field}`: {message}")]
+    Invalid { field: String, message: String },
+    
+    /// A build share code was malformed or from an unsupported format version
+    #[error("invalid share code: {0}")]
+    ShareCode(String),
+}
+
+impl ConfigError {
+    /// Stable name of the variant, for scripts to branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConfigError::Io { .. } => "config_io",
+            ConfigError::Parse { .. } => "config_parse",
+            ConfigError::Invalid { .. } => "config_invalid",
+            ConfigError::ShareCode(_) => "share_code",
+        }
+    }
+    
+    /// Key path of the offending field (empty for I/O and share-code errors)
+    pub fn field(&self) -> &str {
+        match self {
+            ConfigError::Io { .. } | ConfigError::ShareCode(_) => "",
+            ConfigError::Parse { field, .. } | ConfigError::Invalid { field, .. } => field,
+        }
+    }
+    
+    pub(crate) fn from_json(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        ConfigError::Parse {
+            field: field_path(err.path()),
+            message: err.into_inner().to_string(),
+        }
+    }
+    
+    pub(crate) fn from_yaml(err: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        let field = field_path(err.path());
+        // serde_yaml already prefixes its message with the path; don't repeat it
+        let message = err.into_inner().to_string();
+        let message = message
+            .strip_prefix(&format!("{}: ", field))
+            .map(str::to_string)
+            .unwrap_or(message);
+        ConfigError::Parse { field, message }
+    }
+}
+
+/// Render a serde path, using `<root>` when the error is at the top level
+fn field_path(path: &serde_path_to_error::Path) -> String {
+    let rendered = path.to_string();
+    if rendered == "." { "<root>".to_string() } else { rendered }
+}
+
+/// Errors from running simulations
+#[derive(Debug, Error)]
+pub enum SimError {
+    #[error("number of simulations must be at least 1")]
+    NoSimulations,
+    
+    #[error("no build config was provided")]
+    MissingConfig,
+    
+    /// A derived stat came out NaN, infinite, or negative; `formula` says how it is computed
+    #[error("derived stat `{stat}` is {value} (computed as {formula}); check the inputs feeding it")]
+    InvalidStat { stat: &'static str, value: f64, formula: &'static str },
+    
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    
+    #[error("failed to build thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+    
+    #[error("failed to serialize results: {0}")]
+    Serialize(#[from] serde_json::Error),
+    
+    /// A batch checkpoint could not be read or written, or is for a different batch
+    #[error("checkpoint {}: {message}", path.display())]
+    Checkpoint { path: PathBuf, message: String },
+    
+    /// A `--filter` expression could not be parsed, or matched no runs
+    #[error("filter `{expr}`: {message}")]
+    Filter { expr: String, message: String },
+    
+    /// A ranking objective expression could not be parsed
+    #[error("objective `{expr}`: {message}")]
+    Objective { expr: String, message: String },
+}
+
+impl SimError {
+    /// Stable name of the variant, for scripts to branch on; config errors
+    /// keep their own
+    pub fn code(&self) -> &'static str {
+        match self {
+            SimError::NoSimulations => "no_simulations",
+            SimError::MissingConfig => "missing_config",
+            SimError::InvalidStat { .. } => "invalid_stat",
+            SimError::Config(e) => e.code(),
+            SimError::ThreadPool(_) => "thread_pool",
+            SimError::Serialize(_) => "serialize",
+            SimError::Checkpoint { .. } => "checkpoint",
+            SimError::Filter { .. } => "filter",
+            SimError::Objective { .. } => "objective",
+        }
+    }
+}
+
+/// Errors from build generation and optimization
+#[derive(Debug, Error)]
+pub enum OptimizeError {
+    /// A talent/attribute definition is missing a required field or has an unusable value
+    #[error("invalid definition for `{name}`: {message}")]
+    InvalidDefinition { name: String, message: String },
+    
+    /// A dependency, gate, or exclusion refers to an attribute that is not defined
+    #[error("`{referenced_by}` refers to unknown attribute `{name}`")]
+    UnknownAttribute { name: String, referenced_by: String },
+    
+    #[error(transparent)]
+    Sim(#[from] SimError),
+}
+
+impl OptimizeError {
+    /// Stable name of the variant, for scripts to branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            OptimizeError::InvalidDefinition { .. } => "invalid_definition",
+            OptimizeError::UnknownAttribute { .. } => "unknown_attribute",
+            OptimizeError::Sim(e) => e.code(),
+        }
+    }
+}
+
+/// An error as the CLI reports it under `--output json`: the variant's code,
+/// the message the text output would show, and the key path of the offending
+/// field when there is one
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+impl ErrorReport {
+    /// An error with no typed source, such as a CLI usage mistake
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), field: None }
+    }
+    
+    /// Prefix the message with what was being done, e.g. "loading config"
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+}
+
+impl From<ConfigError> for ErrorReport {
+    fn from(e: ConfigError) -> Self {
+        let field = Some(e.field().to_string()).filter(|f| !f.is_empty());
+        Self { code: e.code(), message: e.to_string(), field }
+    }
+}
+
+impl From<SimError> for ErrorReport {
+    fn from(e: SimError) -> Self {
+        match e {
+            SimError::Config(e) => e.into(),
+            SimError::InvalidStat { stat, .. } => Self { field: Some(stat.to_string()), ..Self::new(e.code(), e.to_string()) },
+            e => Self::new(e.code(), e.to_string()),
+        }
+    }
+}
+
+impl From<OptimizeError> for ErrorReport {
+    fn from(e: OptimizeError) -> Self {
+        match e {
+            OptimizeError::Sim(e) => e.into(),
+            OptimizeError::InvalidDefinition { ref name, .. } => Self { field: Some(name.clone()), ..Self::new(e.code(), e.to_string()) },
+            e => Self::new(e.code(), e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for ErrorReport {
+    fn from(e: std::io::Error) -> Self {
+        Self::new("io", e.to_string())
+    }
+}
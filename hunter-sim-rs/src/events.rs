@@ -0,0 +1,84 @@
+//! Time-boxed loot and XP multipliers (the build's `events` section)
+//!
+//! Weekend events and temporary boosts multiply the loot or XP of the stages
+//! cleared while they're active:
+//!
+//! ```yaml
+//! events:
+//!   - name: weekend event
+//!     loot: 2.0          # Multiplier on every material; default 1
+//!   - name: xp potion
+//!     xp: 1.5            # Default 1
+//!     start: 600         # Seconds into the run; default 0
+//!     duration: 1800     # Seconds; default the rest of the run
+//! ```
+//!
+//! Overlapping events stack multiplicatively. Events only change the loot
+//! model, never combat, so a run's loot without them is exact:
+//! `total_loot - event_loot`.
+
+use crate::error::ConfigError;
+use serde::{Deserialize, Serialize};
+
+/// One loot/XP multiplier and the part of the run it covers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LootEvent {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub loot: f64,
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub xp: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub start: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+fn is_one(v: &f64) -> bool {
+    *v == 1.0
+}
+
+fn is_zero(v: &f64) -> bool {
+    *v == 0.0
+}
+
+impl LootEvent {
+    /// Whether the event covers `elapsed` seconds into the run
+    pub fn active(&self, elapsed: f64) -> bool {
+        elapsed >= self.start && self.duration.is_none_or(|d| elapsed < self.start + d)
+    }
+
+    /// Reject negative or non-finite multipliers and times; `index` locates
+    /// the event in the `events` list
+    pub fn validate(&self, index: usize) -> Result<(), ConfigError> {
+        let checks = [
+            ("loot", self.loot, self.loot >= 0.0, "at least 0"),
+            ("xp", self.xp, self.xp >= 0.0, "at least 0"),
+            ("start", self.start, self.start >= 0.0, "at least 0"),
+            ("duration", self.duration.unwrap_or(1.0), self.duration.is_none_or(|d| d > 0.0), "positive"),
+        ];
+        for (key, value, ok, requirement) in checks {
+            if !value.is_finite() || !ok {
+                return Err(ConfigError::Invalid {
+                    field: format!("events[{}].{}", index, key),
+                    message: format!("{} must be finite and {}", value, requirement),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Combined (loot, xp) multipliers of the events active `elapsed` seconds into the run
+pub fn multipliers(events: &[LootEvent], elapsed: f64) -> (f64, f64) {
+    events
+        .iter()
+        .filter(|e| e.active(elapsed))
+        .fold((1.0, 1.0), |(loot, xp), e| (loot * e.loot, xp * e.xp))
+}
@@ -0,0 +1,105 @@
+//! Reference builds compiled into the binary
+//!
+//! The empty templates list every key the simulator reads for a hunter and are
+//! the starting point for `init` and the game-export importer. The sanity-check
+//! builds are real player builds with known in-game results (see the comments
+//! at the top of each file) and make good first simulations. The files live in
+//! `data/builds/` so the crate packages on its own; the copies there are trimmed
+//! where needed to pass `lint` (the Level 35 Borge build spent one point too
+//! many in each pool).
+
+use crate::config::{BuildConfig, HunterType};
+use crate::error::ConfigError;
+
+/// A bundled example build
+#[derive(Debug, Clone, Copy)]
+pub struct ExampleBuild {
+    pub name: &'static str,
+    pub hunter: HunterType,
+    pub description: &'static str,
+    pub yaml: &'static str,
+}
+
+impl ExampleBuild {
+    pub fn config(&self) -> Result<BuildConfig, ConfigError> {
+        BuildConfig::from_yaml(self.yaml)
+    }
+}
+
+pub const EXAMPLES: &[ExampleBuild] = &[
+    ExampleBuild {
+        name: "empty_borge",
+        hunter: HunterType::Borge,
+        description: "Blank Borge template with every key at 0",
+        yaml: include_str!("../data/builds/empty_borge.yaml"),
+    },
+    ExampleBuild {
+        name: "empty_ozzy",
+        hunter: HunterType::Ozzy,
+        description: "Blank Ozzy template with every key at 0",
+        yaml: include_str!("../data/builds/empty_ozzy.yaml"),
+    },
+    ExampleBuild {
+        name: "empty_knox",
+        hunter: HunterType::Knox,
+        description: "Blank Knox template with every key at 0",
+        yaml: include_str!("../data/builds/empty_knox.yaml"),
+    },
+    ExampleBuild {
+        name: "borge_lvl20",
+        hunter: HunterType::Borge,
+        description: "Level 20 Borge, in-game avg stage 91",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_acd.yaml"),
+    },
+    ExampleBuild {
+        name: "borge_lvl24",
+        hunter: HunterType::Borge,
+        description: "Level 24 Borge, in-game avg stage 100",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_chk.yaml"),
+    },
+    ExampleBuild {
+        name: "borge_lvl35",
+        hunter: HunterType::Borge,
+        description: "Level 35 Borge sanity-check build",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_ut_borge.yaml"),
+    },
+    ExampleBuild {
+        name: "knox_lvl30",
+        hunter: HunterType::Knox,
+        description: "Level 30 Knox, in-game avg stage 100",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_knox.yaml"),
+    },
+    ExampleBuild {
+        name: "ozzy_lvl29",
+        hunter: HunterType::Ozzy,
+        description: "Level 29 Ozzy, in-game avg stage 100",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_gm.yaml"),
+    },
+    ExampleBuild {
+        name: "ozzy_lvl45",
+        hunter: HunterType::Ozzy,
+        description: "Level 45 Ozzy, in-game avg stage 133",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_nw.yaml"),
+    },
+    ExampleBuild {
+        name: "ozzy_lvl45_ut",
+        hunter: HunterType::Ozzy,
+        description: "Level 45 Ozzy sanity-check build (alternate talents)",
+        yaml: include_str!("../data/builds/sanity-checks/sanity_ut_ozzy.yaml"),
+    },
+];
+
+/// Look up a bundled example by name
+pub fn find(name: &str) -> Option<&'static ExampleBuild> {
+    EXAMPLES.iter().find(|e| e.name.eq_ignore_ascii_case(name))
+}
+
+/// Blank template for a hunter (every key present, all zero)
+pub fn template(hunter: HunterType) -> Result<BuildConfig, ConfigError> {
+    let name = match hunter {
+        HunterType::Borge => "empty_borge",
+        HunterType::Ozzy => "empty_ozzy",
+        HunterType::Knox => "empty_knox",
+    };
+    find(name).expect("bundled template present").config()
+}
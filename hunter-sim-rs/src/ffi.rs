@@ -0,0 +1,156 @@
+//! C API for embedding the simulator in non-Python GUIs (`ffi` feature)
+//!
+//! The same JSON-in/JSON-out surface as the Python bindings. Every function
+//! that returns `char*` hands the caller a NUL-terminated UTF-8 string that
+//! must be released with [`hunter_sim_free`]; a NULL return means the call
+//! failed and [`hunter_sim_last_error`] holds the message, including when the
+//! engine panicked (the panic never unwinds into the caller).
+//!
+//! Catching a panic needs the unwind strategy, which every profile keeps
+//! (`panic = "unwind"` in Cargo.toml).
+//!
+//! Building with the feature generates the header into the build's `OUT_DIR`,
+//! and `tests/ffi.rs` fails if the committed `include/hunter_sim.h` differs
+//! from it; refresh the committed one with
+//! `cbindgen --config cbindgen.toml --output include/hunter_sim.h`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, UnwindSafe};
+
+use crate::config::{BuildConfig, HunterType};
+use crate::enemy::StageStats;
+use crate::hunter::HunterStats;
+use crate::simulation::{run_and_aggregate, validate_config};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Read a caller string, failing for NULL or invalid UTF-8
+///
+/// # Safety
+/// `ptr` must be NULL or a NUL-terminated string that outlives the call.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{} is NULL", name));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|e| format!("{} is not valid UTF-8: {}", name, e))
+}
+
+/// Parse and validate a caller's config JSON
+fn read_config(json: &str) -> Result<BuildConfig, String> {
+    let config = BuildConfig::from_json(json).map_err(|e| e.to_string())?;
+    validate_config(&config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Run `body` and hand its result to the caller, or record its error (or
+/// panic) and return NULL
+fn call(body: impl FnOnce() -> Result<String, String> + UnwindSafe) -> *mut c_char {
+    let result = panic::catch_unwind(body).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(format!("internal error: {}", message))
+    });
+    match result.and_then(|s| CString::new(s).map_err(|e| e.to_string())) {
+        Ok(s) => s.into_raw(),
+        Err(message) => {
+            set_error(message);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+fn parse_hunter(name: &str) -> Result<HunterType, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).map_err(|e| e.to_string())
+}
+
+/// Message of the last failed call on this thread, or NULL if none failed.
+/// The pointer stays valid until the next failing call on the same thread;
+/// do not free it.
+#[no_mangle]
+pub extern "C" fn hunter_sim_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(std::ptr::null(), |s| s.as_ptr()))
+}
+
+/// Release a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `ptr` must be NULL or a pointer returned by a `hunter_sim_*` function that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hunter_sim_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
+
+/// Run `num_sims` simulations of a config JSON string and return the
+/// aggregated stats as JSON (same as Python's `simulate_json`)
+///
+/// # Safety
+/// `config_json` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hunter_sim_simulate_json(config_json: *const c_char, num_sims: usize, parallel: bool) -> *mut c_char {
+    call(|| {
+        let config = read_config(read_str(config_json, "config_json")?)?;
+        if num_sims == 0 {
+            return Err(crate::error::SimError::NoSimulations.to_string());
+        }
+        serde_json::to_string(&run_and_aggregate(&config, num_sims, parallel)).map_err(|e| e.to_string())
+    })
+}
+
+/// Every derived hunter stat of a config JSON string, as JSON
+///
+/// # Safety
+/// `config_json` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hunter_sim_hunter_stats(config_json: *const c_char) -> *mut c_char {
+    call(|| {
+        let config = read_config(read_str(config_json, "config_json")?)?;
+        serde_json::to_string(&HunterStats::from_config(&config)).map_err(|e| e.to_string())
+    })
+}
+
+/// Itemized loot multiplier of a config JSON string (same as Python's `explain_loot`)
+///
+/// # Safety
+/// `config_json` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hunter_sim_explain_loot(config_json: *const c_char) -> *mut c_char {
+    call(|| {
+        let config = read_config(read_str(config_json, "config_json")?)?;
+        serde_json::to_string(&HunterStats::from_config(&config).loot_breakdown).map_err(|e| e.to_string())
+    })
+}
+
+/// Regular enemy and boss stats of one stage for `hunter` ("borge", "ozzy",
+/// "knox"), as JSON
+///
+/// # Safety
+/// `hunter` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hunter_sim_enemy_stats(hunter: *const c_char, stage: i32) -> *mut c_char {
+    call(|| {
+        let hunter = parse_hunter(read_str(hunter, "hunter")?)?;
+        serde_json::to_string(&StageStats::new(hunter, stage)).map_err(|e| e.to_string())
+    })
+}
+
+/// Panic inside the C boundary, to check that callers get NULL and an error
+/// rather than an abort. Not part of the header.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn hunter_sim_debug_panic() -> *mut c_char {
+    call(|| panic!("hunter_sim_debug_panic called"))
+}
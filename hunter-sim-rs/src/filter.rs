@@ -0,0 +1,525 @@
+//! Run filters applied before aggregation (`--filter`), and ranking
+//! objectives scored on the aggregates (`--objective`)
+//!
+//! A filter is a boolean expression over a run's numeric `SimResult` fields,
+//! so the stats can be computed over a subset of runs:
+//!
+//! ```text
+//! final_stage < 200
+//! boss_kills >= 2 && damage_taken / max_hp > 10
+//! !(final_stage >= 300 or elapsed_time > 3600)
+//! ```
+//!
+//! Comparisons are `<`, `<=`, `>`, `>=`, `==` and `!=` between arithmetic
+//! (`+ - * /`, parentheses) over fields and numbers. They combine with
+//! `&&`/`and`, `||`/`or` and `!`/`not`. Boolean fields read as 0 or 1.
+//!
+//! An objective is the same arithmetic over a batch's numeric
+//! `AggregatedStats` fields, with no comparison; builds rank by it, highest
+//! first:
+//!
+//! ```text
+//! avg_stage
+//! 0.7 * p10_loot_per_hour / 1e6 + 0.3 * survival_rate
+//! ```
+
+use crate::error::SimError;
+use crate::stats::{AggregatedStats, SimResult};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Deepest an expression may nest parentheses, `!` and unary `-`
+pub const MAX_DEPTH: usize = 64;
+
+/// Most tokens an expression may have, which bounds how deep a long chain of
+/// `+` or `&&` builds its tree
+pub const MAX_TOKENS: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Arith {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// A numeric operand; `F` is a field as parsed (its name) or as resolved
+#[derive(Debug, Clone)]
+enum Operand<F = String> {
+    Number(f64),
+    Field(F),
+    Neg(Box<Operand<F>>),
+    Arith(Arith, Box<Operand<F>>, Box<Operand<F>>),
+}
+
+/// A boolean condition
+#[derive(Debug, Clone)]
+enum Cond<F = String> {
+    Compare(Cmp, Operand<F>, Operand<F>),
+    Not(Box<Cond<F>>),
+    And(Box<Cond<F>>, Box<Cond<F>>),
+    Or(Box<Cond<F>>, Box<Cond<F>>),
+}
+
+/// Reads one numeric field of a run
+type RunField = fn(&SimResult) -> f64;
+
+/// A field's value as a number; booleans read as 0 or 1
+trait Scalar {
+    fn number(self) -> f64;
+}
+
+impl Scalar for i32 {
+    fn number(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for f64 {
+    fn number(self) -> f64 {
+        self
+    }
+}
+
+impl Scalar for bool {
+    fn number(self) -> f64 {
+        self as i32 as f64
+    }
+}
+
+macro_rules! run_fields {
+    ($($field:ident),* $(,)?) => {
+        &[$((stringify!($field), |r: &SimResult| r.$field.number())),*]
+    };
+}
+
+/// The numeric `SimResult` fields a filter can name, resolved once at parse
+/// time so matching a run reads them directly
+const RUN_FIELDS: &[(&str, RunField)] = run_fields!(
+    start_stage, final_stage, elapsed_time, kills, damage, damage_taken, total_loot, loot_common,
+    loot_uncommon, loot_rare, total_xp, event_loot, event_xp, attacks, crits,
+    extra_damage_from_crits, multistrikes, extra_damage_from_ms, evades, enemy_attacks,
+    regenerated_hp, lifesteal, overheal_regen, overheal_lifesteal, overheal_procs, mitigated_damage,
+    total_mitigated_damage, effect_procs, lucky_loot_procs, stun_duration_inflicted, nominal_speed, max_hp,
+    helltouch_barrier, helltouch_kills, trample_kills, medusa_kills, trickster_evades, echo_bullets,
+    unfair_advantage_healing, life_of_the_hunt_healing, unfair_advantage_healing_effective,
+    life_of_the_hunt_healing_effective, ghost_bullets, extra_salvo_damage,
+    harden_windows, harden_ticks, harden_wasted_damage, held_multistrikes, empowered_regen_procs,
+    empowered_regen_dropped, empowered_regen_peak, empowered_regen_seconds, boss_fights, boss_kills,
+    enrage_at_boss_kill, max_enrage_fights, revives, wall_unbounded, stage_time_growth,
+    on_kill_calls,
+);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Cmp(Cmp),
+    Arith(Arith),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Token::Number(n) => return write!(f, "`{}`", n),
+            Token::Ident(name) => return write!(f, "`{}`", name),
+            Token::Cmp(Cmp::Lt) => "<",
+            Token::Cmp(Cmp::Le) => "<=",
+            Token::Cmp(Cmp::Gt) => ">",
+            Token::Cmp(Cmp::Ge) => ">=",
+            Token::Cmp(Cmp::Eq) => "==",
+            Token::Cmp(Cmp::Ne) => "!=",
+            Token::Arith(Arith::Add) => "+",
+            Token::Arith(Arith::Sub) => "-",
+            Token::Arith(Arith::Mul) => "*",
+            Token::Arith(Arith::Div) => "/",
+            Token::And => "&&",
+            Token::Or => "||",
+            Token::Not => "!",
+            Token::Open => "(",
+            Token::Close => ")",
+        };
+        write!(f, "`{}`", symbol)
+    }
+}
+
+/// A parsed `--filter` expression
+#[derive(Debug, Clone)]
+pub struct RunFilter {
+    expr: String,
+    cond: Cond<RunField>,
+}
+
+impl RunFilter {
+    /// Parse an expression, rejecting names that aren't numeric `SimResult` fields
+    pub fn parse(expr: &str) -> Result<Self, SimError> {
+        let bad = |message: String| SimError::Filter { expr: expr.to_string(), message };
+        let tokens = tokenize(expr).map_err(bad)?;
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let cond = parser.or().map_err(bad)?;
+        if let Some(token) = parser.peek() {
+            return Err(bad(format!("unexpected {} after the end of the expression", token)));
+        }
+
+        let cond = cond.resolve(&mut |name| match RUN_FIELDS.iter().find(|(field, _)| *field == name) {
+            Some(&(_, get)) => Ok(get),
+            None => {
+                let mut names: Vec<&str> = RUN_FIELDS.iter().map(|(field, _)| *field).collect();
+                names.sort_unstable();
+                Err(format!("`{}` is not a numeric run field (fields: {})", name, names.join(", ")))
+            }
+        });
+        Ok(Self { expr: expr.to_string(), cond: cond.map_err(bad)? })
+    }
+
+    /// The expression as given
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Whether one run passes the filter
+    pub fn matches(&self, result: &SimResult) -> bool {
+        self.cond.eval(&|get: &RunField| get(result))
+    }
+
+    /// The runs that pass the filter, in order
+    pub fn apply(&self, results: &[SimResult]) -> Vec<SimResult> {
+        results.iter().filter(|r| self.matches(r)).cloned().collect()
+    }
+}
+
+/// A parsed `--objective` expression
+#[derive(Debug, Clone)]
+pub struct Objective {
+    expr: String,
+    operand: Operand,
+}
+
+impl Objective {
+    /// Parse an expression, rejecting names that aren't numeric `AggregatedStats` fields
+    pub fn parse(expr: &str) -> Result<Self, SimError> {
+        let bad = |message: String| SimError::Objective { expr: expr.to_string(), message };
+        let tokens = tokenize(expr).map_err(bad)?;
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let operand = parser.sum().map_err(bad)?;
+        if let Some(token) = parser.peek() {
+            return Err(bad(format!("unexpected {} after the end of the expression", token)));
+        }
+
+        let mut fields = Vec::new();
+        operand.fields(&mut fields);
+        check_fields(&fields, &numeric_fields(&AggregatedStats::default()), "stats").map_err(bad)?;
+        Ok(Self { expr: expr.to_string(), operand })
+    }
+
+    /// The expression as given
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// One batch's score; higher ranks first
+    pub fn score(&self, stats: &AggregatedStats) -> f64 {
+        let fields = numeric_fields(stats);
+        self.operand.eval(&|name: &String| match fields.get(name) {
+            Some(Value::Bool(b)) => *b as i32 as f64,
+            Some(v) => v.as_f64().unwrap_or(f64::NAN),
+            None => f64::NAN,
+        })
+    }
+
+    /// Each batch's index and score, best first; ties keep their order and a
+    /// score that isn't a number (a division by zero) ranks last
+    pub fn rank(&self, stats: &[AggregatedStats]) -> Vec<(usize, f64)> {
+        let mut ranked: Vec<(usize, f64)> = stats.iter().map(|s| self.score(s)).enumerate().collect();
+        let key = |score: f64| if score.is_nan() { f64::NEG_INFINITY } else { score };
+        ranked.sort_by(|a, b| key(b.1).total_cmp(&key(a.1)));
+        ranked
+    }
+}
+
+/// An error naming the first of `fields` missing from `known`, and the names it has
+fn check_fields(fields: &[&String], known: &Map<String, Value>, kind: &str) -> Result<(), String> {
+    let Some(unknown) = fields.iter().find(|f| !known.contains_key(f.as_str())) else {
+        return Ok(());
+    };
+    let mut names: Vec<&str> = known.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    Err(format!("`{}` is not a numeric {} field (fields: {})", unknown, kind, names.join(", ")))
+}
+
+/// A value's top-level numeric and boolean fields by name
+fn numeric_fields(value: &impl Serialize) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(mut fields)) => {
+            fields.retain(|_, v| v.is_number() || v.is_boolean());
+            fields
+        }
+        _ => Map::new(),
+    }
+}
+
+impl<F> Operand<F> {
+    fn eval(&self, field: &impl Fn(&F) -> f64) -> f64 {
+        match self {
+            Operand::Number(n) => *n,
+            Operand::Field(f) => field(f),
+            Operand::Neg(a) => -a.eval(field),
+            Operand::Arith(op, a, b) => {
+                let (a, b) = (a.eval(field), b.eval(field));
+                match op {
+                    Arith::Add => a + b,
+                    Arith::Sub => a - b,
+                    Arith::Mul => a * b,
+                    Arith::Div => a / b,
+                }
+            }
+        }
+    }
+
+    /// The same operand with every field name replaced by `field(name)`
+    fn resolve<G>(self, field: &mut impl FnMut(F) -> Result<G, String>) -> Result<Operand<G>, String> {
+        Ok(match self {
+            Operand::Number(n) => Operand::Number(n),
+            Operand::Field(f) => Operand::Field(field(f)?),
+            Operand::Neg(a) => Operand::Neg(Box::new(a.resolve(field)?)),
+            Operand::Arith(op, a, b) => Operand::Arith(op, Box::new(a.resolve(field)?), Box::new(b.resolve(field)?)),
+        })
+    }
+}
+
+impl Operand {
+    fn fields<'a>(&'a self, out: &mut Vec<&'a String>) {
+        match self {
+            Operand::Number(_) => {}
+            Operand::Field(name) => out.push(name),
+            Operand::Neg(a) => a.fields(out),
+            Operand::Arith(_, a, b) => {
+                a.fields(out);
+                b.fields(out);
+            }
+        }
+    }
+}
+
+impl<F> Cond<F> {
+    fn eval(&self, field: &impl Fn(&F) -> f64) -> bool {
+        match self {
+            Cond::Compare(op, a, b) => {
+                let (a, b) = (a.eval(field), b.eval(field));
+                match op {
+                    Cmp::Lt => a < b,
+                    Cmp::Le => a <= b,
+                    Cmp::Gt => a > b,
+                    Cmp::Ge => a >= b,
+                    Cmp::Eq => a == b,
+                    Cmp::Ne => a != b,
+                }
+            }
+            Cond::Not(c) => !c.eval(field),
+            Cond::And(a, b) => a.eval(field) && b.eval(field),
+            Cond::Or(a, b) => a.eval(field) || b.eval(field),
+        }
+    }
+
+    /// The same condition with every field name replaced by `field(name)`
+    fn resolve<G>(self, field: &mut impl FnMut(F) -> Result<G, String>) -> Result<Cond<G>, String> {
+        Ok(match self {
+            Cond::Compare(op, a, b) => Cond::Compare(op, a.resolve(field)?, b.resolve(field)?),
+            Cond::Not(c) => Cond::Not(Box::new(c.resolve(field)?)),
+            Cond::And(a, b) => Cond::And(Box::new(a.resolve(field)?), Box::new(b.resolve(field)?)),
+            Cond::Or(a, b) => Cond::Or(Box::new(a.resolve(field)?), Box::new(b.resolve(field)?)),
+        })
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('<', Some('=')) => (Token::Cmp(Cmp::Le), 2),
+            ('>', Some('=')) => (Token::Cmp(Cmp::Ge), 2),
+            ('=', Some('=')) => (Token::Cmp(Cmp::Eq), 2),
+            ('!', Some('=')) => (Token::Cmp(Cmp::Ne), 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('<', _) => (Token::Cmp(Cmp::Lt), 1),
+            ('>', _) => (Token::Cmp(Cmp::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('+', _) => (Token::Arith(Arith::Add), 1),
+            ('-', _) => (Token::Arith(Arith::Sub), 1),
+            ('*', _) => (Token::Arith(Arith::Mul), 1),
+            ('/', _) => (Token::Arith(Arith::Div), 1),
+            _ if c.is_ascii_digit() || c == '.' => {
+                let mut end = i;
+                while end < chars.len()
+                    && (chars[end].is_ascii_alphanumeric()
+                        || chars[end] == '.'
+                        || (matches!(chars[end], '+' | '-') && matches!(chars[end - 1], 'e' | 'E')))
+                {
+                    end += 1;
+                }
+                let text: String = chars[i..end].iter().collect();
+                let n = text.parse().map_err(|_| format!("`{}` is not a number", text))?;
+                (Token::Number(n), end - i)
+            }
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = i;
+                while end < chars.len() && (chars[end].is_ascii_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let word: String = chars[i..end].iter().collect();
+                let token = match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(word),
+                };
+                (token, end - i)
+            }
+            _ => return Err(format!("unexpected `{}`", c)),
+        };
+        if tokens.len() == MAX_TOKENS {
+            return Err(format!("more than {} tokens", MAX_TOKENS));
+        }
+        tokens.push(token);
+        i += len;
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: or > and > not > comparison > sum > product > unary
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,  // Parentheses, `!` and unary `-` open around the current token
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Run `parse` one nesting level deeper, failing past MAX_DEPTH so a
+    /// hostile expression can't overflow the stack
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("nests more than {} levels deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn or(&mut self) -> Result<Cond, String> {
+        let mut cond = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            cond = Cond::Or(Box::new(cond), Box::new(self.and()?));
+        }
+        Ok(cond)
+    }
+
+    fn and(&mut self) -> Result<Cond, String> {
+        let mut cond = self.not()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            cond = Cond::And(Box::new(cond), Box::new(self.not()?));
+        }
+        Ok(cond)
+    }
+
+    fn not(&mut self) -> Result<Cond, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Cond::Not(Box::new(self.nested(Self::not)?)));
+        }
+        // A parenthesized condition; parentheses around arithmetic are left to the operand
+        if self.peek() == Some(&Token::Open) {
+            let start = self.pos;
+            self.pos += 1;
+            if let Ok(cond) = self.nested(Self::or) {
+                if self.next() == Some(Token::Close) {
+                    return Ok(cond);
+                }
+            }
+            self.pos = start;
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Cond, String> {
+        let left = self.sum()?;
+        match self.next() {
+            Some(Token::Cmp(op)) => Ok(Cond::Compare(op, left, self.sum()?)),
+            Some(token) => Err(format!("expected a comparison, found {}", token)),
+            None => Err("expected a comparison like `final_stage < 200`".to_string()),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Operand, String> {
+        let mut operand = self.product()?;
+        while let Some(&Token::Arith(op @ (Arith::Add | Arith::Sub))) = self.peek() {
+            self.pos += 1;
+            operand = Operand::Arith(op, Box::new(operand), Box::new(self.product()?));
+        }
+        Ok(operand)
+    }
+
+    fn product(&mut self) -> Result<Operand, String> {
+        let mut operand = self.unary()?;
+        while let Some(&Token::Arith(op @ (Arith::Mul | Arith::Div))) = self.peek() {
+            self.pos += 1;
+            operand = Operand::Arith(op, Box::new(operand), Box::new(self.unary()?));
+        }
+        Ok(operand)
+    }
+
+    fn unary(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Operand::Number(n)),
+            Some(Token::Ident(name)) => Ok(Operand::Field(name)),
+            Some(Token::Arith(Arith::Sub)) => Ok(Operand::Neg(Box::new(self.nested(Self::unary)?))),
+            Some(Token::Open) => {
+                let operand = self.nested(Self::sum)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(operand),
+                    _ => Err("missing `)`".to_string()),
+                }
+            }
+            Some(token) => Err(format!("expected a field or number, found {}", token)),
+            None => Err("expression ends early".to_string()),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Number formatting for human-readable reports
+//!
+//! Loot and XP span 15+ orders of magnitude between hunters, so text output can
+//! render large values with the same K/M/B/T/Qa... suffixes the GUI uses.
+
+use clap::ValueEnum;
+
+/// Suffixes for successive powers of 1000 (matches the GUI's `_format_number`)
+const SUFFIXES: [&str; 12] = ["", "K", "M", "B", "T", "Qa", "Qi", "Sx", "Sp", "Oc", "No", "Dc"];
+
+/// How to render numbers in text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum NumberFormat {
+    /// Plain decimal, e.g. 1640000000000
+    #[default]
+    Raw,
+    /// Short suffixes, e.g. 1.64T
+    Suffix,
+    /// Scientific notation, e.g. 1.64e12
+    Scientific,
+}
+
+impl NumberFormat {
+    /// Format `value`; `decimals` is the precision used for raw output and for
+    /// values below 1000, which are never abbreviated
+    pub fn format(self, value: f64, decimals: usize) -> String {
+        let small = !value.is_finite() || value.abs() < 1000.0;
+        match self {
+            NumberFormat::Raw => format!("{:.*}", decimals, value),
+            _ if small => format!("{:.*}", decimals, value),
+            NumberFormat::Scientific => format!("{:.2e}", value),
+            NumberFormat::Suffix => {
+                let mut tier = (value.abs().log10() / 3.0).floor() as usize;
+                let mut mantissa = value / 1000_f64.powi(tier as i32);
+                // 999.996K should read 1.00M, not 1000.00K
+                if mantissa.abs() >= 999.995 {
+                    tier += 1;
+                    mantissa /= 1000.0;
+                }
+                match SUFFIXES.get(tier) {
+                    Some(suffix) => format!("{:.2}{}", mantissa, suffix),
+                    None => format!("{:.2e}", value),  // Beyond Dc
+                }
+            }
+        }
+    }
+}
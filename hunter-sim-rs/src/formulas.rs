@@ -0,0 +1,331 @@
+//! Game formulas shared by the hunter constructors, the enemy constructors and
+//! the multiplier calculators. Each is a small pure function of levels or a
+//! stage, so it can be checked against the WASM/APK on its own.
+
+use crate::config::{BuildConfig, HunterType};
+
+/// Gadgets every hunter's HP, power and regen scale with
+pub const STAT_GADGETS: [&str; 3] = ["wrench_of_gore", "zaptron_533", "anchor_of_ages"];
+
+/// HP/power/regen factor of one gadget: +0.3% per level, and x1.002 for every
+/// 10 levels (WASM: `(1 + level * 0.003) * 1.002 ** (level // 10)`)
+pub fn gadget_multiplier(level: i32) -> f64 {
+    (1.0 + level as f64 * 0.003) * 1.002_f64.powf((level / 10) as f64)
+}
+
+/// Combined HP/power/regen factor of a build's `STAT_GADGETS`
+pub fn gadget_stat_multiplier(config: &BuildConfig) -> f64 {
+    STAT_GADGETS.iter().map(|g| gadget_multiplier(config.get_gadget(g))).product()
+}
+
+/// Loot factor of a hunter's own gadget: 1.005^level, and x1.02 for every 10
+/// levels (APK: baseValue 0.005, tierStep 10, tierMultiplier 1.02)
+pub fn gadget_loot_multiplier(level: i32) -> f64 {
+    if level <= 0 {
+        return 1.0;
+    }
+    1.005_f64.powi(level) * 1.02_f64.powi(level / 10)
+}
+
+/// Base max HP of a hunter with `hp` levels in the HP stat
+pub fn base_hp(hunter: HunterType, hp: f64) -> f64 {
+    match hunter {
+        HunterType::Borge => 43.0 + hp * (2.50 + 0.01 * (hp / 5.0).floor()),
+        HunterType::Ozzy => 16.0 + hp * (2.0 + 0.03 * (hp / 5.0).floor()),
+        HunterType::Knox => 20.0 + hp * (2.0 + hp / 50.0),
+    }
+}
+
+/// Base power of a hunter with `power` levels in the power stat
+pub fn base_power(hunter: HunterType, power: f64) -> f64 {
+    match hunter {
+        HunterType::Borge => 3.0 + power * (0.5 + 0.01 * (power / 10.0).floor()),
+        HunterType::Ozzy => 2.0 + power * (0.3 + 0.01 * (power / 10.0).floor()),
+        HunterType::Knox => 1.2 + power * (0.06 + power / 1000.0),
+    }
+}
+
+/// Base regen per second of a hunter with `regen` levels in the regen stat
+pub fn base_regen(hunter: HunterType, regen: f64) -> f64 {
+    match hunter {
+        HunterType::Borge => 0.02 + regen * (0.03 + 0.01 * (regen / 30.0).floor()),
+        HunterType::Ozzy => 0.1 + regen * (0.05 + 0.01 * (regen / 30.0).floor()),
+        HunterType::Knox => 0.05 + regen * (0.01 + regen * 0.00075),
+    }
+}
+
+/// Ozzy's power factor from hunter level: x1.001 per level, and x1.02 for
+/// every 10 levels (Python: `1.001 ** level * 1.02 ** (level // 10)`)
+pub fn ozzy_level_multiplier(level: i32) -> f64 {
+    1.001_f64.powi(level) * 1.02_f64.powi(level / 10)
+}
+
+/// Borge's creation node #3 gem factor: `rate` per gem level for every hunter
+/// level past 39
+pub fn creation_node_scaler(rate: f64, level: i32, gem: i32) -> f64 {
+    1.0 + (rate * (level - 39) as f64) * gem as f64
+}
+
+/// Knox's seconds between salvos with `reload_time` levels. Calibrated in
+/// game (base 8.0, -0.08 per level, 6.40s at 20) rather than taken from the
+/// Python port's 4.0 and 0.02
+pub fn knox_reload_interval(reload_time: i32) -> f64 {
+    8.0 - reload_time as f64 * 0.08
+}
+
+/// Python's multi_wasm scaling function - WASM-verified additive breakpoints
+/// This MUST match Python's units.py multi_wasm exactly!
+pub fn stage_scaling(stage: i32) -> f64 {
+    let s = stage as f64;
+    
+    // WASM formula from multiWasm function (lines 1304-1320 in release.dcmp)
+    // Uses ADDITIVE scaling with many breakpoints
+    let mut result = 1.0;
+    result += 0.0_f64.max((s - 149.0) * 0.006);
+    result += 0.0_f64.max((s - 199.0) * 0.006);
+    result += 0.0_f64.max((s - 249.0) * 0.006);
+    result += 0.0_f64.max((s - 299.0) * 0.006);
+    result += 0.0_f64.max((s - 309.0) * 0.003);
+    result += 0.0_f64.max((s - 319.0) * 0.003);
+    result += 0.0_f64.max((s - 329.0) * 0.004);
+    result += 0.0_f64.max((s - 339.0) * 0.004);
+    result += 0.0_f64.max((s - 349.0) * 0.005);
+    result += 0.0_f64.max((s - 359.0) * 0.005);
+    result += 0.0_f64.max((s - 369.0) * 0.006);
+    result += 0.0_f64.max((s - 379.0) * 0.006);
+    result += 0.0_f64.max((s - 389.0) * 0.007);
+    
+    // WASM applies max(result, 1.0) then multiplies by exponential
+    result = result.max(1.0);
+    result *= 1.01_f64.powi((stage - 350).max(0));
+    
+    result
+}
+
+/// CIFI stage scaling function for Knox (f_o)
+/// Knox uses MULTIPLICATIVE scaling unlike Borge/Ozzy's additive multi_wasm.
+/// Below stage 150, returns 1.0 (no scaling).
+pub fn knox_stage_scaling(stage: i32) -> f64 {
+    if stage < 150 {
+        return 1.0;
+    }
+    
+    let s = stage as f64;
+    let mut result = 1.0;
+    
+    // First breakpoint at 149
+    if stage > 149 {
+        result *= 1.0 + (s - 149.0) * 0.007;
+    }
+    
+    // Additional breakpoints
+    if stage > 199 {
+        result *= 1.0 + (s - 199.0) * 0.007;
+    }
+    if stage > 249 {
+        result *= 1.0 + (s - 249.0) * 0.007;
+    }
+    if stage > 299 {
+        result *= 1.0 + (s - 299.0) * 0.007;
+    }
+    if stage > 349 {
+        result *= 1.0 + (s - 349.0) * 0.007;
+    }
+    
+    // Breakpoints every 20 stages after 360
+    if stage > 369 {
+        result *= 1.0 + (s - 369.0) * 0.007;
+    }
+    if stage > 389 {
+        result *= 1.0 + (s - 389.0) * 0.007;
+    }
+    if stage > 409 {
+        result *= 1.0 + (s - 409.0) * 0.007;
+    }
+    if stage > 429 {
+        result *= 1.0 + (s - 429.0) * 0.007;
+    }
+    
+    // Exponential scaling after stage 400
+    if stage > 400 {
+        result *= 1.01_f64.powi(stage - 400);
+    }
+    
+    result
+}
+
+/// A regular enemy's or boss's stats straight from the stage formulas
+#[derive(Debug, Clone, Copy)]
+pub struct BaseEnemyStats {
+    pub hp: f64,
+    pub power: f64,
+    pub regen: f64,
+    pub special_chance: f64,
+    pub special_damage: f64,
+    pub damage_reduction: f64,
+    pub evade_chance: f64,
+    pub effect_chance: f64,
+    pub speed: f64,
+}
+
+/// How a hunter's boss differs from that stage's regular enemy: factors on
+/// HP/power/regen/speed, and bonuses added to crit chance and damage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BossMultipliers {
+    pub hp: f64,
+    pub power: f64,
+    pub regen: f64,
+    pub speed: f64,
+    pub special_chance: f64,
+    pub special_damage: f64,
+}
+
+impl BossMultipliers {
+    /// A regular enemy's
+    pub const NONE: Self = Self { hp: 1.0, power: 1.0, regen: 1.0, speed: 1.0, special_chance: 0.0, special_damage: 0.0 };
+}
+
+/// Boss multipliers per hunter (APK verified)
+pub fn boss_multipliers(hunter: HunterType) -> BossMultipliers {
+    match hunter {
+        HunterType::Borge => BossMultipliers { hp: 90.0, power: 3.63, regen: 1.92, speed: 2.42, special_chance: 0.04, special_damage: 0.25 },
+        HunterType::Ozzy => BossMultipliers { hp: 48.0, power: 3.0, regen: 6.0, speed: 2.45, special_chance: 0.13, special_damage: 0.0 },
+        HunterType::Knox => BossMultipliers { hp: 120.0, power: 4.0, regen: 2.0, speed: 2.85, special_chance: 0.13, special_damage: 0.0 },
+    }
+}
+
+/// Stats of a regular enemy or the boss on `stage`, using CIFI formulas
+/// extracted from WASM (before the caps `Enemy` applies to crits)
+pub fn enemy_stats(stage: i32, hunter_type: HunterType, is_boss: bool) -> BaseEnemyStats {
+    let s = stage as f64;
+    let boss = if is_boss { boss_multipliers(hunter_type) } else { BossMultipliers::NONE };
+    let d = ((stage - 1).max(0) as f64 / 100.0).floor() as i32;  // Boss cycles completed
+    let d_f = d as f64;
+    let is_stage_300 = stage == 300;
+    
+    match hunter_type {
+        HunterType::Borge => {
+            let f = stage_scaling(stage);
+            
+            // Match Python Borge formulas (validated against WASM)
+            // Borge uses FLAT 2.85 multiplier for stages > 100
+            let tier_mult = if stage > 100 { 2.85 } else { 1.0 };
+            
+            // HP: (9 + stage * 4) * tier_mult * multi_wasm * boss(90x) * stage300(0.9)
+            let hp = (s * 4.0 + 9.0) * f * tier_mult
+                * boss.hp
+                * if is_stage_300 { 0.9 } else { 1.0 };
+            
+            // Power: (2.5 + stage * 0.7) * tier_mult * multi_wasm * boss(3.63x) * stage300(0.9)
+            let power = (s * 0.7 + 2.5) * f * tier_mult
+                * boss.power
+                * if is_stage_300 { 0.9 } else { 1.0 };
+            
+            // Crit chance: 0.0322 + stage * 0.0004 + boss(0.04), capped at 0.25 (APK verified)
+            let special_chance = (s * 0.0004 + 0.0322 + boss.special_chance).min(0.25);
+            
+            // Crit damage: 1.212 + stage * 0.008 + boss(0.25), capped at 2.5 (APK verified)
+            let special_damage = (s * 0.008 + 1.212 + boss.special_damage).min(2.5);
+            
+            // Damage reduction (boss only): min(0.05 + stage * 0.0004, 0.25)
+            let actual_dr = if is_boss { (0.05 + s * 0.0004).min(0.25) } else { 0.0 };
+            
+            // Evade: 0.004 if stage > 100
+            let evade = if stage > 100 { 0.004 } else { 0.0 };
+            
+            // Effect chance (not used for Borge enemies in Python)
+            let effect = 0.0;
+            
+            // Regen: (stage-1) * 0.08 * 1.052 (if stage > 100) * multi_wasm * boss(1.92x) (APK verified)
+            let regen_tier = if stage > 100 { 1.052 } else { 1.0 };
+            let regen = if stage > 1 { (s - 1.0) * 0.08 } else { 0.0 } * regen_tier * f
+                * boss.regen;
+            
+            // Speed: (4.53 - stage * 0.006) * boss(2.42x) (APK verified)
+            let speed = (4.53 - s * 0.006) * boss.speed;
+            
+            BaseEnemyStats { hp, power, regen, special_chance, special_damage, damage_reduction: actual_dr, evade_chance: evade, effect_chance: effect, speed }
+        }
+        HunterType::Ozzy => {
+            let f = stage_scaling(stage);
+            
+            // Match Python Ozzy formulas (validated against WASM)
+            // HP: (11 + stage * 6) * 2.9^tier * multi_wasm * boss(48x) * stage300(0.94)
+            let hp = (s * 6.0 + 11.0) * f * 2.9_f64.powf(d_f)
+                * boss.hp
+                * if is_stage_300 { 0.94 } else { 1.0 };
+            
+            // Power: (1.35 + stage * 0.75) * 2.7^tier * multi_wasm * boss(3x) * stage300(0.94)
+            let power = (s * 0.75 + 1.35) * f * 2.7_f64.powf(d_f)
+                * boss.power
+                * if is_stage_300 { 0.94 } else { 1.0 };
+            
+            // Crit chance: 0.0994 + stage * 0.0006 + boss(0.13) (APK verified)
+            let special_chance = (s * 0.0006 + 0.0994 + boss.special_chance).min(0.25);
+            
+            // Crit damage: min(1.03 + stage * 0.008, 2.5)
+            let special_damage = (s * 0.008 + 1.03).min(2.5);
+            
+            // Damage reduction (boss only from Python)
+            let actual_dr = if is_boss { (0.05 + s * 0.0004).min(0.25) } else { 0.0 };
+            
+            // Evade: max((tier-1)*0.01+0.01, 0) if stage >= 100
+            let evade = if stage >= 100 {
+                ((d_f - 1.0) * 0.01 + 0.01).max(0.0)
+            } else { 0.0 };
+            
+            // Effect chance (not used in Python Ozzy enemies)
+            let effect = 0.0;
+            
+            // Regen: (stage-1) * 0.1 * 1.25^tier * multi_wasm * boss(6x)
+            let regen = if stage > 0 { (s - 1.0) * 0.1 } else { 0.0 }
+                * 1.25_f64.powf(d_f) * f
+                * boss.regen;
+            
+            // Speed: (3.20 - stage * 0.004) * boss(2.45)
+            let speed = (3.2 - s * 0.004) * boss.speed;
+            
+            BaseEnemyStats { hp, power, regen, special_chance, special_damage, damage_reduction: actual_dr, evade_chance: evade, effect_chance: effect, speed }
+        }
+        HunterType::Knox => {
+            let f = knox_stage_scaling(stage);
+            
+            // Match Python Knox formulas (validated against WASM)
+            // HP: (10 + stage * 5) * 2.8 (if stage > 100) * knox_scaling * boss(120x)
+            let post_100_mult = if stage > 100 { 2.8 } else { 1.0 };
+            let hp = (10.0 + s * 5.0) * post_100_mult * f
+                * boss.hp;
+            
+            // Power: (1.5 + stage * 0.65) * 2.6 (if stage > 100) * knox_scaling * boss(4x)
+            let power_100_mult = if stage > 100 { 2.6 } else { 1.0 };
+            let power = (1.5 + s * 0.65) * power_100_mult * f
+                * boss.power;
+            
+            // Crit chance: 0.075 + stage * 0.00055 + boss_bonus (APK verified: +13%)
+            let special_chance = (s * 0.00055 + 0.075 + boss.special_chance).min(0.25);
+            
+            // Crit damage: 1.15 + stage * 0.0075 + boss_bonus (APK verified: +0%)
+            let special_damage = (s * 0.0075 + 1.15).min(2.5);
+            
+            // Damage reduction (boss only)
+            let dr = if is_boss { 0.05 } else { 0.0 };
+            let actual_dr = dr;
+            
+            // Evade: 0.006 if stage > 100, else 0
+            let evade = if stage > 100 { 0.006 } else { 0.0 };
+            
+            // Effect chance: 0.03 + stage * 0.0003
+            let effect = s * 0.0003 + 0.03;
+            
+            // Regen: (stage - 1) * 0.09 * 1.15 (if stage > 100) * knox_scaling * boss(2.0x) (APK verified)
+            let regen_100_mult = if stage > 100 { 1.15 } else { 1.0 };
+            let regen = if stage > 0 { (s - 1.0) * 0.09 } else { 0.0 } * regen_100_mult * f
+                * boss.regen;
+            
+            // Speed: (3.80 - stage * 0.005) * boss(2.85x) (APK verified)
+            let speed = (3.80 - s * 0.005) * boss.speed;
+            
+            BaseEnemyStats { hp, power, regen, special_chance, special_damage, damage_reduction: actual_dr, evade_chance: evade, effect_chance: effect, speed }
+        }
+    }
+}
@@ -0,0 +1,1050 @@
+//! Static game data tables read by the engine
+//!
+//! Boss secondary attacks are listed per hunter with the first stage they
+//! appear on, each a `boss_script` of effects; a boss uses the last entry
+//! whose `min_stage` it has reached, and a scripts file can add or replace
+//! entries at runtime.
+//! Only secondaries checked against the game are built in. Provisional
+//! estimates for the post-300 bosses ship as a scripts file
+//! (`boss_script::PROVISIONAL`) that a batch loads to opt in; move a script
+//! here once its real values are known.
+//!
+//! Regular-enemy abilities follow the same layout: a row applies from its
+//! `min_stage` on, and the latest row a stage has reached wins. None is
+//! verified yet, so they only act with `engine_flags.enemy_abilities` on.
+//!
+//! Stage caps are the highest stage the game lets each hunter reach; raise
+//! them here when the game does. A build's `max_stage` overrides them.
+//!
+//! Talents and attributes are a registry: each row has the cost and level
+//! cap from the Python hunters' `costs` tables (which the build generator is
+//! fed; a `max` of None is uncapped), and every effect the simulator models,
+//! with its per-level coefficient. The hunter constructors and the loot
+//! calculator read their coefficients from here through `coefficient`, and
+//! so does the fight: each hunter carries the coefficients of the effects on
+//! combat mechanics (procs, reflects, enemy debuffs) as `CombatCoefficients`,
+//! read once when it's built.
+//!
+//! Other inputs with a level cap are in `INPUT_CAPS`, marking the ones whose
+//! formula clamps to it (points past it are wasted) apart from the ones only
+//! the game enforces.
+//!
+//! The relic and gadget catalog lists every item a build can carry, what the
+//! simulator applies of it for each hunter, and what it knows the game does
+//! that isn't simulated. Move an effect from `unmodeled` to `modeled` when the
+//! engine learns it.
+
+use crate::boss_script::{BossEffect, BossScript, Cooldown::{Seconds, SpeedFactor}, Harden};
+use crate::config::HunterType;
+use crate::enemy::EnemyAbility;
+use serde::Serialize;
+use std::borrow::Cow;
+use PointPool::{Attribute, Talent};
+
+/// Every built-in boss secondary attack, per hunter in stage order (see
+/// `boss_script` for the format and for loading more at runtime)
+pub const BOSS_SECONDARIES: &[BossScript] = &[
+    // === Borge ===
+    // Gothmorgor: extra attack + 1 enrage, speed2 = speed * 1.8 (Python units.py)
+    BossScript {
+        hunter: HunterType::Borge,
+        min_stage: 200,
+        name: Cow::Borrowed("Gothmorgor"),
+        cooldown: SpeedFactor(1.8),
+        effects: Cow::Borrowed(&[BossEffect::Attack, BossEffect::Enrage { stacks: 1 }]),
+        verified: true,
+    },
+    // === Ozzy ===
+    // Exoscarab: harden (95% DR, 3x regen for 5 ticks, +5 enrage at end), WASM: fixed 60s cooldown
+    BossScript {
+        hunter: HunterType::Ozzy,
+        min_stage: 200,
+        name: Cow::Borrowed("Exoscarab"),
+        cooldown: Seconds(60.0),
+        effects: Cow::Borrowed(&[BossEffect::Harden(Harden { ticks: 5, dr: 0.95, regen_mult: 3.0, end_enrage: 5 })]),
+        verified: true,
+    },
+];
+
+/// Tunable parameters of a regular-enemy ability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbilityParams {
+    pub min_effect_chance: f64,  // Proc chance floor per landed hit (Borge/Ozzy enemies have no effect_chance formula)
+    pub ticks: i32,              // Hunter regen ticks the debuff lasts
+    pub strength: f64,           // RegenSuppression: fraction of hunter regen removed
+}
+
+impl Default for AbilityParams {
+    fn default() -> Self {
+        Self { min_effect_chance: 0.0, ticks: 0, strength: 0.0 }
+    }
+}
+
+/// One row of the regular-enemy ability table
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyAbilityRow {
+    pub hunter: HunterType,
+    pub min_stage: i32,
+    pub ability: EnemyAbility,
+    pub params: AbilityParams,
+    pub verified: bool,
+}
+
+/// Every regular-enemy ability, per hunter in stage order
+pub const ENEMY_ABILITIES: &[EnemyAbilityRow] = &[
+    // Regen suppression from stage 300, where the enemy effect chance starts (enemy.rs)
+    EnemyAbilityRow {
+        hunter: HunterType::Borge,
+        min_stage: 300,
+        ability: EnemyAbility::RegenSuppression,
+        params: AbilityParams { min_effect_chance: 0.05, ticks: 3, strength: 0.5 },
+        verified: false,
+    },
+    EnemyAbilityRow {
+        hunter: HunterType::Ozzy,
+        min_stage: 300,
+        ability: EnemyAbility::RegenSuppression,
+        params: AbilityParams { min_effect_chance: 0.05, ticks: 3, strength: 0.5 },
+        verified: false,
+    },
+    EnemyAbilityRow {
+        hunter: HunterType::Knox,
+        min_stage: 300,
+        ability: EnemyAbility::RegenSuppression,
+        params: AbilityParams { min_effect_chance: 0.0, ticks: 3, strength: 0.5 },
+        verified: false,
+    },
+];
+
+/// Ability of regular enemies on `stage` for `hunter`, if they have one
+pub fn enemy_ability(hunter: HunterType, stage: i32) -> Option<&'static EnemyAbilityRow> {
+    ENEMY_ABILITIES
+        .iter()
+        .filter(|r| r.hunter == hunter && stage >= r.min_stage)
+        .max_by_key(|r| r.min_stage)
+}
+
+/// Highest stage each hunter can reach; a run that clears it ends there
+pub const MAX_STAGES: &[(HunterType, i32)] = &[
+    (HunterType::Borge, 300),
+    (HunterType::Ozzy, 210),
+    (HunterType::Knox, 100),
+];
+
+/// Stage cap of `hunter` in the current game version
+pub fn max_stage(hunter: HunterType) -> i32 {
+    MAX_STAGES.iter().find(|(h, _)| *h == hunter).map_or(0, |&(_, stage)| stage)
+}
+
+/// Which of a hunter's point pools an upgrade is bought from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointPool {
+    Talent,
+    Attribute,
+}
+
+/// One per-level effect of a talent or attribute
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct UpgradeEffect {
+    pub target: &'static str,       // Derived stat, or the combat mechanic it drives
+    pub per_level: f64,
+    pub description: &'static str,
+}
+
+/// One row of the talent and attribute registry
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Upgrade {
+    pub hunter: HunterType,
+    pub pool: PointPool,
+    pub key: &'static str,
+    pub cost: i32,         // Points per level
+    pub max: Option<i32>,  // Level cap; None is uncapped
+    pub effects: &'static [UpgradeEffect],  // Empty when the simulator doesn't model it
+}
+
+/// Every talent and attribute, per hunter in the order the game lists them
+pub const UPGRADES: &[Upgrade] = &[
+    // Borge
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "death_is_my_companion",
+        cost: 1,
+        max: Some(2),
+        effects: &[
+            UpgradeEffect { target: "max_revives", per_level: 1.0, description: "+1 revive per level (back at 80% HP)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "life_of_the_hunt",
+        cost: 1,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "heal_on_proc", per_level: 0.06, description: "Effect-chance proc on hit heals 6% of the damage dealt per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "unfair_advantage",
+        cost: 1,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "heal_on_proc", per_level: 0.02, description: "Effect-chance proc on kill heals 2% of max HP per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "impeccable_impacts",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "power", per_level: 2.0, description: "+2 flat power per level" },
+            UpgradeEffect { target: "stun", per_level: 0.1, description: "Effect-chance proc on hit stuns for 0.1s per level (scaled on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "omen_of_defeat",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "enemy_regen", per_level: 0.08, description: "-8% enemy regen per level (half on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "call_me_lucky_loot",
+        cost: 1,
+        max: Some(12),
+        effects: &[
+            UpgradeEffect { target: "lucky_loot_proc", per_level: 1.0, description: "Effect-chance proc on non-boss kills for extra loot" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "presence_of_god",
+        cost: 1,
+        max: Some(15),
+        effects: &[
+            UpgradeEffect { target: "enemy_starting_hp", per_level: 0.04, description: "Enemies spawn with -4% HP per level (half on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "fires_of_war",
+        cost: 1,
+        max: Some(15),
+        effects: &[
+            UpgradeEffect { target: "attack_speed_buff", per_level: 0.1, description: "Effect-chance proc on hit cuts the next attack time by 0.1s per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Talent,
+        key: "legacy_of_ultima",
+        cost: 1,
+        max: Some(50),
+        effects: &[
+            UpgradeEffect { target: "max_hp", per_level: 0.01, description: "+1% max HP per level" },
+            UpgradeEffect { target: "power", per_level: 0.01, description: "+1% power per level" },
+            UpgradeEffect { target: "regen", per_level: 0.01, description: "+1% regen per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "soul_of_ares",
+        cost: 1,
+        max: None,
+        effects: &[
+            UpgradeEffect { target: "max_hp", per_level: 0.01, description: "+1% max HP per level" },
+            UpgradeEffect { target: "power", per_level: 0.002, description: "+0.2% power per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "essence_of_ylith",
+        cost: 1,
+        max: None,
+        effects: &[
+            UpgradeEffect { target: "regen_flat", per_level: 0.04, description: "+0.04 flat regen per level" },
+            UpgradeEffect { target: "regen", per_level: 0.009, description: "+0.9% regen per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "spartan_lineage",
+        cost: 2,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "damage_reduction", per_level: 0.015, description: "+1.5% damage reduction per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "timeless_mastery",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "loot", per_level: 0.14, description: "+14% loot per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "helltouch_barrier",
+        cost: 2,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "reflect", per_level: 0.08, description: "Reflects 8% of damage taken per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "lifedrain_inhalers",
+        cost: 2,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "missing_hp_regen", per_level: 0.0008, description: "Regen ticks heal +0.08% of missing HP per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "explosive_punches",
+        cost: 3,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "special_chance", per_level: 0.044, description: "+4.4% crit chance per level" },
+            UpgradeEffect { target: "special_damage", per_level: 0.08, description: "+8% crit damage per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "book_of_baal",
+        cost: 3,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "lifesteal", per_level: 0.0111, description: "+1.11% lifesteal per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "superior_sensors",
+        cost: 2,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "evade_chance", per_level: 0.016, description: "+1.6% evade chance per level" },
+            UpgradeEffect { target: "effect_chance", per_level: 0.012, description: "+1.2% effect chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "atlas_protocol",
+        cost: 3,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "boss_effect_chance", per_level: 0.014, description: "+1.4% effect chance against bosses per level" },
+            UpgradeEffect { target: "boss_special_chance", per_level: 0.025, description: "+2.5% crit chance against bosses per level" },
+            UpgradeEffect { target: "boss_speed", per_level: 0.04, description: "-4% attack time against bosses per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "weakspot_analysis",
+        cost: 2,
+        max: Some(6),
+        effects: &[
+            UpgradeEffect { target: "enemy_special_damage", per_level: 0.11, description: "-11% enemy crit damage per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "born_for_battle",
+        cost: 5,
+        max: Some(3),
+        effects: &[
+            UpgradeEffect { target: "missing_hp_power", per_level: 0.001, description: "+0.1% power per 1% of HP missing, per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "soul_of_athena",
+        cost: 15,
+        max: Some(1),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "soul_of_hermes",
+        cost: 2,
+        max: Some(20),
+        effects: &[
+            UpgradeEffect { target: "damage_reduction", per_level: 0.002, description: "+0.2% damage reduction per level" },
+            UpgradeEffect { target: "special_chance", per_level: 0.004, description: "+0.4% crit chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Borge,
+        pool: Attribute,
+        key: "soul_of_the_minotaur",
+        cost: 2,
+        max: Some(20),
+        effects: &[
+            UpgradeEffect { target: "power", per_level: 0.01, description: "+1% power per level" },
+            UpgradeEffect { target: "minotaur_dr", per_level: 0.01, description: "+1% multiplicative damage reduction per level" },
+        ],
+    },
+    // Ozzy
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "death_is_my_companion",
+        cost: 1,
+        max: Some(2),
+        effects: &[
+            UpgradeEffect { target: "max_revives", per_level: 1.0, description: "+1 revive per level (back at 80% HP)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "tricksters_boon",
+        cost: 1,
+        max: Some(1),
+        effects: &[
+            UpgradeEffect { target: "trickster_charge", per_level: 1.0, description: "Half-effect-chance proc on attack grants an evade charge" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "unfair_advantage",
+        cost: 1,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "heal_on_proc", per_level: 0.02, description: "Effect-chance proc on kill heals 2% of max HP per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "thousand_needles",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "speed", per_level: 0.06, description: "-0.06s attack time per level" },
+            UpgradeEffect { target: "stun", per_level: 0.05, description: "Effect-chance proc stuns for 0.05s per level (scaled on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "omen_of_decay",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "damage_on_proc", per_level: 0.03, description: "Half-effect-chance proc on hit adds 3% damage per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "call_me_lucky_loot",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "lucky_loot_proc", per_level: 1.0, description: "Effect-chance proc on non-boss kills for extra loot" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "crippling_shots",
+        cost: 1,
+        max: Some(15),
+        effects: &[
+            UpgradeEffect { target: "decay_stacks", per_level: 1.0, description: "Effect-chance proc on hit adds 1 decay stack per level; each stack deals 0.8% of enemy HP on the next attack" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "echo_bullets",
+        cost: 1,
+        max: Some(20),
+        effects: &[
+            UpgradeEffect { target: "echo_damage", per_level: 0.05, description: "Half-effect-chance proc on hit fires an echo for 5% of power per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Talent,
+        key: "legacy_of_ultima",
+        cost: 1,
+        max: Some(50),
+        effects: &[
+            UpgradeEffect { target: "max_hp", per_level: 0.01, description: "+1% max HP per level" },
+            UpgradeEffect { target: "power", per_level: 0.01, description: "+1% power per level" },
+            UpgradeEffect { target: "regen", per_level: 0.01, description: "+1% regen per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "living_off_the_land",
+        cost: 1,
+        max: None,
+        effects: &[
+            UpgradeEffect { target: "max_hp", per_level: 0.02, description: "+2% max HP per level" },
+            UpgradeEffect { target: "regen", per_level: 0.02, description: "+2% regen per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "exo_piercers",
+        cost: 1,
+        max: None,
+        effects: &[
+            UpgradeEffect { target: "power", per_level: 0.012, description: "+1.2% power per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "timeless_mastery",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "loot", per_level: 0.16, description: "+16% loot per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "shimmering_scorpion",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "lifesteal", per_level: 0.033, description: "+3.3% lifesteal per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "wings_of_ibu",
+        cost: 2,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "damage_reduction", per_level: 0.026, description: "+2.6% damage reduction per level" },
+            UpgradeEffect { target: "evade_chance", per_level: 0.005, description: "+0.5% evade chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "extermination_protocol",
+        cost: 2,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "effect_chance", per_level: 0.028, description: "+2.8% effect chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "soul_of_snek",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "enemy_regen", per_level: 0.088, description: "-8.8% enemy regen per level" },
+            UpgradeEffect { target: "empowered_lifesteal", per_level: 0.15, description: "+15% lifesteal per level while Vectid Elixir empowers regen" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "vectid_elixir",
+        cost: 2,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "empowered_regen", per_level: 5.0, description: "An Unfair Advantage heal empowers the next 5 regen ticks" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "cycle_of_death",
+        cost: 3,
+        max: Some(5),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "gift_of_medusa",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "enemy_regen", per_level: 0.06, description: "Enemy regen reduced by 6% of the hunter's regen per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "deal_with_death",
+        cost: 5,
+        max: Some(3),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "dance_of_dashes",
+        cost: 3,
+        max: Some(4),
+        effects: &[
+            UpgradeEffect { target: "evade_charge_on_enemy_crit", per_level: 0.05, description: "5% chance per level, when an enemy crit lands, for an evade charge" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "blessings_of_the_cat",
+        cost: 2,
+        max: Some(20),
+        effects: &[
+            UpgradeEffect { target: "power", per_level: 0.02, description: "+2% power per level" },
+            UpgradeEffect { target: "speed", per_level: 0.004, description: "-0.4% attack time per level (multiplicative)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "blessings_of_the_scarab",
+        cost: 2,
+        max: Some(20),
+        effects: &[
+            UpgradeEffect { target: "scarab_dr", per_level: 0.01, description: "+1% multiplicative damage reduction per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Ozzy,
+        pool: Attribute,
+        key: "blessings_of_the_sisters",
+        cost: 15,
+        max: Some(1),
+        effects: &[
+            UpgradeEffect { target: "max_revives", per_level: 1.0, description: "+1 revive per level" },
+        ],
+    },
+    // Knox
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "death_is_my_companion",
+        cost: 1,
+        max: Some(2),
+        effects: &[
+            UpgradeEffect { target: "max_revives", per_level: 1.0, description: "+1 revive per level (back at 80% HP)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "calypsos_advantage",
+        cost: 1,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "hundred_souls", per_level: 1.0, description: "2.5x-effect-chance proc on kill adds a Hundred Souls stack" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "unfair_advantage",
+        cost: 1,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "heal_on_proc", per_level: 0.02, description: "Effect-chance proc on kill heals 2% of max HP per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "ghost_bullets",
+        cost: 1,
+        max: Some(15),
+        effects: &[
+            UpgradeEffect { target: "ghost_chance", per_level: 0.0667, description: "6.67% chance per level for an extra bullet" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "omen_of_defeat",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "enemy_regen", per_level: 0.08, description: "-8% enemy regen per level (half on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "call_me_lucky_loot",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "lucky_loot_proc", per_level: 1.0, description: "Effect-chance proc on non-boss kills for extra loot" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "presence_of_god",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "enemy_starting_hp", per_level: 0.04, description: "Enemies spawn with -4% HP per level (half on bosses)" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "finishing_move",
+        cost: 1,
+        max: Some(15),
+        effects: &[
+            UpgradeEffect { target: "special_damage", per_level: 0.2, description: "+20% finisher damage per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Talent,
+        key: "legacy_of_ultima",
+        cost: 1,
+        max: Some(50),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "release_the_kraken",
+        cost: 1,
+        max: None,
+        effects: &[
+            UpgradeEffect { target: "max_hp", per_level: 0.005, description: "+0.5% max HP per level" },
+            UpgradeEffect { target: "power", per_level: 0.005, description: "+0.5% power per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "space_pirate_armory",
+        cost: 2,
+        max: Some(50),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "soul_amplification",
+        cost: 1,
+        max: Some(100),
+        effects: &[
+            UpgradeEffect { target: "hundred_souls_cap", per_level: 10.0, description: "+10 maximum Hundred Souls stacks per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "serious_efficiency",
+        cost: 2,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "effect_chance", per_level: 0.02, description: "+2% effect chance per level" },
+            UpgradeEffect { target: "charge_chance", per_level: 0.01, description: "+1% charge chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "fortification_elixir",
+        cost: 2,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "block_chance", per_level: 0.01, description: "+1% block chance per level" },
+            UpgradeEffect { target: "block_regen", per_level: 0.1, description: "+10% regen on empowered ticks after a block per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "a_pirates_life_for_knox",
+        cost: 3,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "damage_reduction", per_level: 0.009, description: "+0.9% damage reduction per level" },
+            UpgradeEffect { target: "block_chance", per_level: 0.008, description: "+0.8% block chance per level" },
+            UpgradeEffect { target: "effect_chance", per_level: 0.007, description: "+0.7% effect chance per level" },
+            UpgradeEffect { target: "charge_chance", per_level: 0.006, description: "+0.6% charge chance per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "dead_men_tell_no_tales",
+        cost: 2,
+        max: Some(10),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "passive_charge_tank",
+        cost: 4,
+        max: Some(10),
+        effects: &[],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "shield_of_poseidon",
+        cost: 1,
+        max: Some(10),
+        effects: &[
+            UpgradeEffect { target: "charge_gained", per_level: 0.1, description: "+0.1 flat charge per charge proc, per level" },
+        ],
+    },
+    Upgrade {
+        hunter: HunterType::Knox,
+        pool: Attribute,
+        key: "timeless_mastery",
+        cost: 3,
+        max: Some(5),
+        effects: &[
+            UpgradeEffect { target: "loot", per_level: 0.14, description: "+14% loot per level" },
+        ],
+    },
+];
+
+/// Registry row of `key` in `hunter`'s `pool`, if the hunter has it
+pub fn upgrade(hunter: HunterType, pool: PointPool, key: &str) -> Option<&'static Upgrade> {
+    UPGRADES.iter().find(|r| r.hunter == hunter && r.pool == pool && r.key == key)
+}
+
+/// Per-level coefficient of `key`'s effect on `target` for `hunter`, if the
+/// registry declares one
+pub fn coefficient(hunter: HunterType, key: &str, target: &str) -> Option<f64> {
+    UPGRADES
+        .iter()
+        .filter(|r| r.hunter == hunter && r.key == key)
+        .flat_map(|r| r.effects)
+        .find(|e| e.target == target)
+        .map(|e| e.per_level)
+}
+
+/// Level cap of an input outside the talent and attribute registry
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct InputCap {
+    pub section: &'static str,
+    pub key: &'static str,
+    pub max: i32,
+    pub clamped: bool,  // The formula stops at `max`; otherwise only the game does
+}
+
+/// Caps the loot and XP formulas clamp to, and gem caps from the GUI's inputs
+pub const INPUT_CAPS: &[InputCap] = &[
+    InputCap { section: "bonuses", key: "scavenger", max: 25, clamped: true },
+    InputCap { section: "bonuses", key: "scavenger2", max: 25, clamped: true },
+    InputCap { section: "gems", key: "attraction_loot_borge", max: 50, clamped: true },
+    InputCap { section: "gems", key: "attraction_loot_ozzy", max: 50, clamped: true },
+    InputCap { section: "gems", key: "attraction_loot_knox", max: 50, clamped: true },
+    InputCap { section: "gems", key: "attraction_gem", max: 3, clamped: false },
+    InputCap { section: "gems", key: "attraction_catch-up", max: 5, clamped: false },
+    InputCap { section: "relics", key: "book_of_mephisto", max: 8, clamped: true },
+    InputCap { section: "inscryptions", key: "i33", max: 8, clamped: true },
+];
+
+/// Cap of `section.key` from `INPUT_CAPS`, if it has one
+pub fn input_cap(section: &str, key: &str) -> Option<&'static InputCap> {
+    INPUT_CAPS.iter().find(|c| c.section == section && c.key == key)
+}
+
+/// `value` limited to the cap its formula clamps to
+pub fn clamp_input(section: &str, key: &str, value: i32) -> i32 {
+    input_cap(section, key).filter(|c| c.clamped).map_or(value, |c| value.min(c.max))
+}
+
+/// What one relic or gadget does for one hunter
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ItemEffect {
+    pub hunter: HunterType,
+    pub description: &'static str,
+}
+
+const fn item(hunter: HunterType, description: &'static str) -> ItemEffect {
+    ItemEffect { hunter, description }
+}
+
+/// One row of the relic and gadget catalog
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CatalogItem {
+    pub section: &'static str,
+    pub key: &'static str,                  // Canonical key; see `config::KEY_ALIASES`
+    pub modeled: &'static [ItemEffect],     // Effects the simulator applies
+    pub unmodeled: &'static [ItemEffect],   // Effects the game has that it skips
+}
+
+/// How much of an item's effect on a hunter the simulator applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Coverage {
+    Modeled,
+    Partial,
+    Ignored,   // It does something in game, none of it simulated
+    NoEffect,  // It does nothing for this hunter
+}
+
+impl CatalogItem {
+    pub fn coverage(&self, hunter: HunterType) -> Coverage {
+        let modeled = self.modeled.iter().any(|e| e.hunter == hunter);
+        let unmodeled = self.unmodeled.iter().any(|e| e.hunter == hunter);
+        match (modeled, unmodeled) {
+            (true, false) => Coverage::Modeled,
+            (true, true) => Coverage::Partial,
+            (false, true) => Coverage::Ignored,
+            (false, false) => Coverage::NoEffect,
+        }
+    }
+}
+
+const GADGET_STATS: &str = "HP, power and regen x(1 + 0.3% per level) x 1.002^(level / 10)";
+const GADGET_LOOT: &str = "loot x1.005^level x 1.02^(level / 10)";
+
+/// Every relic and gadget a build can list, with what the simulator makes of
+/// each. Relics are listed with their game ids in the comments
+pub const CATALOG: &[CatalogItem] = &[
+    // r4
+    CatalogItem {
+        section: "relics",
+        key: "disk_of_dawn",
+        modeled: &[
+            item(HunterType::Borge, "+3% HP per level"),
+            item(HunterType::Ozzy, "+3% HP per level"),
+            item(HunterType::Knox, "+3% HP per level"),
+        ],
+        unmodeled: &[],
+    },
+    // r7
+    CatalogItem {
+        section: "relics",
+        key: "manifestation_core_titan",
+        modeled: &[
+            item(HunterType::Borge, "loot x1.05^level"),
+            item(HunterType::Ozzy, "loot x1.05^level"),
+            item(HunterType::Knox, "loot x1.05^level"),
+        ],
+        unmodeled: &[],
+    },
+    // r16
+    CatalogItem {
+        section: "relics",
+        key: "long_range_artillery_crawler",
+        modeled: &[item(HunterType::Borge, "+3% power per level")],
+        unmodeled: &[],
+    },
+    // r17
+    CatalogItem {
+        section: "relics",
+        key: "bee_gone_companion_drone",
+        modeled: &[item(HunterType::Ozzy, "+3% power per level")],
+        unmodeled: &[],
+    },
+    // r19
+    CatalogItem {
+        section: "relics",
+        key: "book_of_mephisto",
+        modeled: &[item(HunterType::Borge, "XP x2^level, up to level 8")],
+        unmodeled: &[],
+    },
+    // The GUI stores this relic's level with the bonuses; its rate is unknown
+    CatalogItem {
+        section: "bonuses",
+        key: "wastarian_relic_loot_bonus",
+        modeled: &[],
+        unmodeled: &[
+            item(HunterType::Borge, "loot bonus per level"),
+            item(HunterType::Ozzy, "loot bonus per level"),
+            item(HunterType::Knox, "loot bonus per level"),
+        ],
+    },
+    CatalogItem {
+        section: "gadgets",
+        key: "wrench_of_gore",
+        modeled: &[
+            item(HunterType::Borge, GADGET_STATS),
+            item(HunterType::Borge, GADGET_LOOT),
+            item(HunterType::Ozzy, GADGET_STATS),
+            item(HunterType::Knox, GADGET_STATS),
+        ],
+        unmodeled: &[],
+    },
+    CatalogItem {
+        section: "gadgets",
+        key: "zaptron_533",
+        modeled: &[
+            item(HunterType::Borge, GADGET_STATS),
+            item(HunterType::Ozzy, GADGET_STATS),
+            item(HunterType::Ozzy, GADGET_LOOT),
+            item(HunterType::Knox, GADGET_STATS),
+        ],
+        unmodeled: &[],
+    },
+    CatalogItem {
+        section: "gadgets",
+        key: "anchor_of_ages",
+        modeled: &[
+            item(HunterType::Borge, GADGET_STATS),
+            item(HunterType::Borge, GADGET_LOOT),
+            item(HunterType::Ozzy, GADGET_STATS),
+            item(HunterType::Ozzy, GADGET_LOOT),
+            item(HunterType::Knox, GADGET_LOOT),
+            item(HunterType::Knox, GADGET_STATS),
+        ],
+        unmodeled: &[],
+    },
+    CatalogItem {
+        section: "gadgets",
+        key: "trident_of_tides",
+        modeled: &[item(HunterType::Knox, GADGET_LOOT)],
+        unmodeled: &[item(HunterType::Knox, GADGET_STATS)],
+    },
+];
+
+/// Catalog row of `section.key`, if it's a known relic or gadget
+pub fn catalog_item(section: &str, key: &str) -> Option<&'static CatalogItem> {
+    CATALOG.iter().find(|i| i.section == section && i.key == key)
+}
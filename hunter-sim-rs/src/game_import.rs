@@ -0,0 +1,234 @@
+//! Importer for the game's hunter export (clipboard JSON or base64 string)
+//!
+//! The export uses the game's own field names (camelCase, spaces, or the WASM
+//! identifiers), nested however the client happened to group them. Every leaf
+//! value is matched against the keys of the hunter's empty template build by
+//! comparing "squashed" names (lowercase, alphanumerics only), so `soulOfAres`,
+//! `Soul of Ares` and `soul_of_ares` all land on `attributes.soul_of_ares`.
+//! Anything that doesn't match a known key is reported instead of guessed at.
+
+use crate::config::{BuildConfig, HunterType, Meta, KEY_ALIASES};
+use crate::error::ConfigError;
+use crate::examples;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Field names that identify the hunter and level rather than a build value
+const HUNTER_FIELDS: [&str; 5] = ["hunter", "hunter_name", "hunter_type", "class", "name"];
+const LEVEL_FIELDS: [&str; 2] = ["level", "hunter_level"];
+
+/// A game field that was mapped onto a config key
+#[derive(Debug, Clone, Serialize)]
+pub struct MappedField {
+    pub source: String,  // Path in the export, e.g. "hunter.attributes.soulOfAres"
+    pub target: String,  // Config key, e.g. "attributes.soul_of_ares"
+}
+
+/// Result of importing a game export
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub config: BuildConfig,
+    pub mapped: Vec<MappedField>,
+    pub unmapped: Vec<String>,  // Export paths with no matching config key
+}
+
+/// Parse a game export and map it onto a build config
+pub fn import_game_export(text: &str) -> Result<ImportReport, ConfigError> {
+    let root = parse_export(text)?;
+
+    let mut leaves = Vec::new();
+    flatten(&root, String::new(), &mut leaves);
+
+    // Hunter and level first, since the key catalog depends on the hunter
+    let mut hunter = None;
+    let mut level = None;
+    let mut data_leaves = Vec::new();
+    for (path, value) in leaves {
+        let name = squash(last_segment(&path));
+        if hunter.is_none() && HUNTER_FIELDS.iter().any(|f| squash(f) == name) {
+            if let Some(h) = value.as_str().and_then(parse_hunter) {
+                hunter = Some(h);
+                continue;
+            }
+        }
+        if level.is_none() && LEVEL_FIELDS.iter().any(|f| squash(f) == name) {
+            if let Some(l) = value.as_f64() {
+                level = Some(l.round() as i32);
+                continue;
+            }
+        }
+        data_leaves.push((path, value));
+    }
+    let hunter = hunter.ok_or_else(|| ConfigError::Invalid {
+        field: "hunter".to_string(),
+        message: "export does not say which hunter it is for".to_string(),
+    })?;
+
+    let mut config = examples::template(hunter)?;
+    config.meta = Some(Meta { hunter, level: level.unwrap_or(0) });
+
+    let catalog = key_catalog(&config);
+    let mut mapped = Vec::new();
+    let mut unmapped = Vec::new();
+    for (path, value) in data_leaves {
+        match resolve(&catalog, &path) {
+            Some((section, key)) if assign(&mut config, section, &key, &value) => {
+                mapped.push(MappedField { source: path, target: format!("{}.{}", section, key) });
+            }
+            _ => unmapped.push(path),
+        }
+    }
+
+    Ok(ImportReport { config, mapped, unmapped })
+}
+
+/// Accept raw JSON, or JSON wrapped in (URL-safe or standard) base64
+fn parse_export(text: &str) -> Result<Value, ConfigError> {
+    let text = text.trim();
+    if let Ok(v) = serde_json::from_str::<Value>(text) {
+        return Ok(v);
+    }
+    let compact: String = text.split_whitespace().collect();
+    let decoded = STANDARD.decode(&compact).or_else(|_| URL_SAFE_NO_PAD.decode(&compact));
+    decoded
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+        .ok_or_else(|| ConfigError::Parse {
+            field: "<root>".to_string(),
+            message: "export is neither JSON nor base64-encoded JSON".to_string(),
+        })
+}
+
+/// Collect every scalar leaf as (dotted path, value)
+fn flatten(value: &Value, path: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                let child = if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) };
+                flatten(v, child, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                flatten(v, format!("{}[{}]", path, i), out);
+            }
+        }
+        _ => out.push((path, value.clone())),
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// Lowercase alphanumerics only, so naming styles compare equal
+fn squash(name: &str) -> String {
+    name.chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+fn parse_hunter(name: &str) -> Option<HunterType> {
+    match squash(name).as_str() {
+        "borge" => Some(HunterType::Borge),
+        "ozzy" => Some(HunterType::Ozzy),
+        "knox" => Some(HunterType::Knox),
+        _ => None,
+    }
+}
+
+/// Squashed key -> (section, canonical key), per section
+type Catalog = Vec<(&'static str, HashMap<String, String>)>;
+
+fn key_catalog(config: &BuildConfig) -> Catalog {
+    let index = |keys: Vec<&String>| -> HashMap<String, String> {
+        keys.into_iter().map(|k| (squash(k), k.clone())).collect()
+    };
+    let mut catalog: Catalog = vec![
+        ("stats", index(config.stats.keys().collect())),
+        ("talents", index(config.talents.keys().collect())),
+        ("attributes", index(config.attributes.keys().collect())),
+        ("inscryptions", index(config.inscryptions.keys().collect())),
+        ("mods", index(config.mods.keys().collect())),
+        ("relics", index(config.relics.keys().collect())),
+        ("gems", index(config.gems.keys().collect())),
+        ("gadgets", index(config.gadgets.keys().collect())),
+        ("bonuses", index(config.bonuses.keys().collect())),
+    ];
+    // Aliases (and their canonical keys, which templates may not list) map
+    // straight to the canonical key, so imports never store an alias
+    for alias in KEY_ALIASES {
+        if let Some((_, keys)) = catalog.iter_mut().find(|(s, _)| *s == alias.canonical_section) {
+            keys.insert(squash(alias.alias), alias.canonical.to_string());
+            keys.insert(squash(alias.canonical), alias.canonical.to_string());
+        }
+    }
+    catalog
+}
+
+/// Find the config key for an export path. A parent segment naming a section
+/// (e.g. `talents.fooBar`) restricts the search to that section; otherwise the
+/// name must match exactly one section. Bare `iNN`/`rNN` ids are always accepted.
+fn resolve(catalog: &Catalog, path: &str) -> Option<(&'static str, String)> {
+    let name = squash(last_segment(path));
+    let hint = path.rsplit('.').nth(1).map(squash);
+
+    let hinted = hint.as_deref().and_then(|h| {
+        catalog.iter().find(|(section, _)| squash(section) == h || squash(section).trim_end_matches('s') == h)
+    });
+    if let Some((section, keys)) = hinted {
+        return keys.get(&name).map(|k| (*section, k.clone())).or_else(|| numbered_id(section, &name));
+    }
+
+    let mut hits = catalog.iter().filter_map(|(section, keys)| keys.get(&name).map(|k| (*section, k.clone())));
+    match (hits.next(), hits.next()) {
+        (Some(hit), None) => Some(hit),
+        _ => numbered_id("inscryptions", &name).or_else(|| numbered_id("relics", &name)),
+    }
+}
+
+/// `i42` is always an inscryption and `r7` always a relic, even if the template lacks it
+fn numbered_id(section: &'static str, name: &str) -> Option<(&'static str, String)> {
+    let prefix = match section {
+        "inscryptions" => 'i',
+        "relics" => 'r',
+        _ => return None,
+    };
+    let digits = name.strip_prefix(prefix)?;
+    (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())).then(|| (section, name.to_string()))
+}
+
+/// Store a value under `section.key`; returns false if the value has the wrong type
+fn assign(config: &mut BuildConfig, section: &str, key: &str, value: &Value) -> bool {
+    let key = key.to_string();
+    let as_int = || match value {
+        Value::Bool(b) => Some(*b as i32),
+        Value::Number(n) => n.as_f64().map(|f| f.round() as i32),
+        Value::String(s) => s.trim().parse::<f64>().ok().map(|f| f.round() as i32),
+        _ => None,
+    };
+    let ints = match section {
+        "stats" => &mut config.stats,
+        "talents" => &mut config.talents,
+        "attributes" => &mut config.attributes,
+        "inscryptions" => &mut config.inscryptions,
+        "relics" => &mut config.relics,
+        "gems" => &mut config.gems,
+        "gadgets" => &mut config.gadgets,
+        "mods" => {
+            let flag = match value {
+                Value::Bool(b) => Some(*b),
+                Value::Number(n) => n.as_f64().map(|f| f != 0.0),
+                _ => None,
+            };
+            return flag.map(|f| config.mods.insert(key, f)).is_some();
+        }
+        "bonuses" => {
+            config.bonuses.insert(key, value.clone());
+            return true;
+        }
+        _ => return false,
+    };
+    as_int().map(|v| ints.insert(key, v)).is_some()
+}
@@ -0,0 +1,129 @@
+//! Experimental wgpu backend for the screening engine (`gpu` feature)
+//!
+//! Uploads the hunter and per-stage foe tables, runs screen.wgsl with one
+//! invocation per run and reads back the final stages. Any failure (no
+//! adapter, device lost, batch too large) is returned as a message so the
+//! caller can fall back to the CPU.
+
+use wgpu::util::DeviceExt;
+
+use crate::screening::{ScreenInput, MAX_FIGHT_EVENTS};
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Pack the `Params` uniform in screen.wgsl field order
+fn params_bytes(input: &ScreenInput, runs: u32) -> Vec<u8> {
+    let h = &input.hunter;
+    let floats = [h.max_hp, h.power, h.speed, h.regen, h.mitigation, h.evade_chance, h.crit_chance, h.crit_mult, h.lifesteal];
+    let words = [h.max_revives, h.max_stage, runs, MAX_FIGHT_EVENTS, 0, 0, 0];
+    floats.iter().flat_map(|f| f.to_le_bytes()).chain(words.iter().flat_map(|w| w.to_le_bytes())).collect()
+}
+
+/// Pack the stage table as `array<Foe>`
+fn stages_bytes(input: &ScreenInput) -> Vec<u8> {
+    input
+        .stages
+        .iter()
+        .flat_map(|f| [f.hp, f.power, f.speed, f.regen, f.damage_reduction, f.special_chance, f.special_damage, 0.0])
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
+
+/// Run `count` screening runs (seeds 0..count) and return the adapter name
+/// with each run's final stage
+pub fn run(input: &ScreenInput, count: usize) -> Result<(String, Vec<u32>), String> {
+    let runs = u32::try_from(count).map_err(|_| format!("{} runs exceed one dispatch", count))?;
+    if runs == 0 {
+        return Ok((String::new(), Vec::new()));
+    }
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        ..Default::default()
+    }))
+    .map_err(|e| format!("no GPU adapter: {}", e))?;
+    let adapter_name = adapter.get_info().name;
+    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default()))
+        .map_err(|e| format!("failed to open {}: {}", adapter_name, e))?;
+
+    let workgroups = runs.div_ceil(WORKGROUP_SIZE);
+    if workgroups > device.limits().max_compute_workgroups_per_dimension {
+        return Err(format!("{} runs exceed one dispatch on {}", runs, adapter_name));
+    }
+
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("screen"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("screen.wgsl").into()),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("screen"),
+        layout: None,
+        module: &shader,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: None,
+    });
+
+    let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("params"),
+        contents: &params_bytes(input, runs),
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+    let stages = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("stages"),
+        contents: &stages_bytes(input),
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+    let size = runs as u64 * 4;
+    let results = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("results"),
+        size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("readback"),
+        size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("screen"),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: stages.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 2, resource: results.as_entire_binding() },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("screen") });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("screen"), timestamp_writes: None });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(workgroups, 1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&results, 0, &readback, 0, size);
+    queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |r| {
+        let _ = tx.send(r);
+    });
+    device.poll(wgpu::PollType::Wait).map_err(|e| format!("GPU poll failed: {}", e))?;
+    rx.recv()
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("failed to read results: {}", e))?;
+
+    let stages = slice
+        .get_mapped_range()
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    readback.unmap();
+    Ok((adapter_name, stages))
+}
@@ -1,10 +1,19 @@
 //! Hunter implementation with stat calculations for all three hunters
 
-use crate::config::{BuildConfig, HunterType};
-use crate::stats::SimResult;
+use crate::config::{BuildConfig, HunterType, MultiplierBreakdown, StackingRules};
+use crate::engine_flags::EngineFlag;
+use crate::error::SimError;
+use crate::events::LootEvent;
+use crate::formulas;
+use crate::game_data;
+use crate::logging::TraceFilter;
+use crate::mitigation::{HitAudit, Mitigation};
+use crate::stats::{FightPhase, SimResult};
+use crate::wall::StageFit;
+use serde::{Deserialize, Serialize};
 
 /// Computed hunter stats ready for combat simulation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hunter {
     pub hunter_type: HunterType,
     pub level: i32,
@@ -94,6 +103,12 @@ pub struct Hunter {
     // Mod flags
     pub has_trample: bool,
     pub has_decay: bool,
+    pub hold_burst_during_harden: bool,  // Ozzy policy: hold multistrikes while the boss is hardened
+    pub held_multistrikes: i32,  // Multistrikes held for release after harden
+    
+    // Enemy ability debuffs
+    pub regen_suppressed_ticks: i32,  // Regen ticks left under an enemy's regen suppression
+    pub regen_suppression: f64,  // Fraction of regen removed while suppressed
     
     // Catch-up gem values (for power/speed bonuses in early stages)
     pub attraction_catchup: i32,
@@ -103,6 +118,12 @@ pub struct Hunter {
     // Loot and XP multipliers
     pub loot_mult: f64,
     pub xp_mult: f64,
+    pub loot_events: Vec<LootEvent>,          // From the build's `events` section
+    pub event_stages: Vec<(i32, f64, f64)>,   // Stages cleared during events: (stage, loot, xp multiplier)
+    pub stage_fit: StageFit,                  // Recent stages, for the wall estimate at max_stage
+    pub fight_phase: FightPhase,              // Kind of fight underway, for the sustain split
+    pub hit_audit: Option<Vec<HitAudit>>,     // Every incoming hit's mitigation, when auditing
+    pub damage_log: Option<Vec<(f64, f64, i32)>>, // Every attack's (time, damage, stage), when logging damage
     
     // Combat tracking
     pub result: SimResult,
@@ -112,47 +133,268 @@ pub struct Hunter {
     pub max_stage: i32,
     pub hundred_souls_stacks: i32,  // Knox
     pub decay_stacks: i32,  // Ozzy crippling shots
+    pub mitigation: Mitigation,  // Incoming-damage layers, built from the DR fields above
+    pub trace_filter: TraceFilter,  // From the build's `logging` section
+    pub on_kill_policy: OnKillPolicy,
+    pub stacking: StackingRules,  // From the build's `stacking` section and engine flags
+    pub stun_affects_specials: bool,  // engine_flags.stun_affects_specials
+    pub enemy_abilities: bool,  // engine_flags.enemy_abilities
+    pub attack_procs: AttackProcs,  // Chance-based attack talents with points, built in from_config
+    pub coefficients: CombatCoefficients,  // Registry coefficients of the mid-fight effects, built in from_config
+}
+
+/// What killed an enemy, for the on-kill policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillCause {
+    Attack,   // The hunter's attack
+    Trample,  // Borge: Trample overflow onto the next enemies
+    Reflect,  // Borge: Helltouch Barrier during an enemy attack
+}
+
+/// How many times on-kill effects (Unfair Advantage, Lucky Loot, ...) run per kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnKillPolicy {
+    Single,         // Once per kill (this engine, all hunters)
+    AttackAndDeath, // Python sim Ozzy: once from attack() when the hit kills, once from on_death()
+}
+
+impl OnKillPolicy {
+    /// `mods.double_on_kill` (any hunter) or `engine_flags.ozzy_double_on_kill`
+    /// opts into the Python sim's double proc (default off)
+    pub fn for_config(config: &BuildConfig) -> Self {
+        let flagged = config.get_hunter_type() == HunterType::Ozzy && config.get_engine_flag(EngineFlag::OzzyDoubleOnKill);
+        if config.get_mod("double_on_kill") || flagged {
+            OnKillPolicy::AttackAndDeath
+        } else {
+            OnKillPolicy::Single
+        }
+    }
+    
+    /// On-kill runs for one kill
+    pub fn calls(self, cause: KillCause) -> u32 {
+        match (self, cause) {
+            (OnKillPolicy::AttackAndDeath, KillCause::Attack) => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A chance-based talent proc rolled on each attack (not per strike)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttackProc {
+    #[default]
+    LifeOfTheHunt,      // Borge: heal for a share of the hit
+    ImpeccableImpacts,  // Borge: stun
+    FiresOfWar,         // Borge: attack speed buff
+    ThousandNeedles,    // Ozzy: stun
+    EchoBullets,        // Ozzy: extra echo hit, at half effect chance
+    OmenOfDecay,        // Ozzy: main hit multiplier, at half effect chance
+}
+
+/// The attack procs a hunter has talent points in, in roll order. Filtering
+/// out level-0 talents once at construction keeps the per-attack cascade to
+/// the procs the build can actually trigger.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttackProcs {
+    procs: [AttackProc; 3],
+    len: u8,
+}
+
+impl AttackProcs {
+    /// Procs of `hunter` with at least one talent point (Knox has none)
+    pub fn for_hunter(hunter: &Hunter) -> Self {
+        let candidates = match hunter.hunter_type {
+            HunterType::Borge => [
+                (AttackProc::LifeOfTheHunt, hunter.life_of_the_hunt),
+                (AttackProc::ImpeccableImpacts, hunter.impeccable_impacts),
+                (AttackProc::FiresOfWar, hunter.fires_of_war),
+            ],
+            HunterType::Ozzy => [
+                (AttackProc::ThousandNeedles, hunter.thousand_needles),
+                (AttackProc::EchoBullets, hunter.echo_bullets),
+                (AttackProc::OmenOfDecay, hunter.omen_of_decay),
+            ],
+            HunterType::Knox => return Self::default(),
+        };
+        let mut active = Self::default();
+        for (proc, level) in candidates {
+            if level > 0 {
+                active.procs[active.len as usize] = proc;
+                active.len += 1;
+            }
+        }
+        active
+    }
+
+    /// Active procs in roll order
+    pub fn active(self) -> impl Iterator<Item = AttackProc> {
+        self.procs.into_iter().take(self.len as usize)
+    }
+}
+
+/// A per-level effect the engine applies mid-fight, read from the registry
+/// row of its upgrade (see `source`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatEffect {
+    PresenceOfGod,       // Share of the enemy's HP removed on spawn
+    OmenOfDefeat,        // Share of the enemy's regen removed on spawn
+    SoulOfSnekRegen,     // Ozzy: share of the enemy's regen removed on spawn
+    SoulOfSnekEmpower,   // Ozzy: regen and lifesteal bonus while empowered
+    GiftOfMedusa,        // Ozzy: enemy regen removed, as a share of the hunter's regen
+    LifeOfTheHunt,       // Borge: heal on proc, as a share of the hit
+    UnfairAdvantage,     // Heal on kill proc, as a share of max HP
+    ImpeccableImpacts,   // Borge: stun seconds
+    FiresOfWar,          // Borge: attack time removed from the next attack
+    ThousandNeedles,     // Ozzy: stun seconds
+    OmenOfDecay,         // Ozzy: main hit bonus
+    EchoBullets,         // Ozzy: echo hit, as a share of power
+    GhostBullets,        // Knox: chance of an extra projectile
+    HelltouchBarrier,    // Borge: reflected share of damage taken
+    DanceOfDashes,       // Ozzy: evade charge chance when an enemy crit lands
+    WeakspotAnalysis,    // Borge: enemy crit damage removed
+    AtlasEffectChance,   // Borge: effect chance against bosses
+    AtlasSpecialChance,  // Borge: crit chance against bosses
+    AtlasSpeed,          // Borge: share of attack time removed against bosses
+    BornForBattle,       // Borge: power per 1% of HP missing
+    LifedrainInhalers,   // Borge: regen per tick, as a share of missing HP
+    FortificationElixir, // Knox: regen bonus on empowered ticks after a block
+    SoulAmplification,   // Knox: extra Hundred Souls stacks
+}
+
+impl CombatEffect {
+    pub const ALL: [CombatEffect; 23] = [
+        Self::PresenceOfGod, Self::OmenOfDefeat, Self::SoulOfSnekRegen, Self::SoulOfSnekEmpower,
+        Self::GiftOfMedusa, Self::LifeOfTheHunt, Self::UnfairAdvantage, Self::ImpeccableImpacts,
+        Self::FiresOfWar, Self::ThousandNeedles, Self::OmenOfDecay, Self::EchoBullets,
+        Self::GhostBullets, Self::HelltouchBarrier, Self::DanceOfDashes, Self::WeakspotAnalysis,
+        Self::AtlasEffectChance, Self::AtlasSpecialChance, Self::AtlasSpeed, Self::BornForBattle,
+        Self::LifedrainInhalers, Self::FortificationElixir, Self::SoulAmplification,
+    ];
+
+    /// Upgrade key and effect target of the registry row this reads
+    pub fn source(self) -> (&'static str, &'static str) {
+        match self {
+            Self::PresenceOfGod => ("presence_of_god", "enemy_starting_hp"),
+            Self::OmenOfDefeat => ("omen_of_defeat", "enemy_regen"),
+            Self::SoulOfSnekRegen => ("soul_of_snek", "enemy_regen"),
+            Self::SoulOfSnekEmpower => ("soul_of_snek", "empowered_lifesteal"),
+            Self::GiftOfMedusa => ("gift_of_medusa", "enemy_regen"),
+            Self::LifeOfTheHunt => ("life_of_the_hunt", "heal_on_proc"),
+            Self::UnfairAdvantage => ("unfair_advantage", "heal_on_proc"),
+            Self::ImpeccableImpacts => ("impeccable_impacts", "stun"),
+            Self::FiresOfWar => ("fires_of_war", "attack_speed_buff"),
+            Self::ThousandNeedles => ("thousand_needles", "stun"),
+            Self::OmenOfDecay => ("omen_of_decay", "damage_on_proc"),
+            Self::EchoBullets => ("echo_bullets", "echo_damage"),
+            Self::GhostBullets => ("ghost_bullets", "ghost_chance"),
+            Self::HelltouchBarrier => ("helltouch_barrier", "reflect"),
+            Self::DanceOfDashes => ("dance_of_dashes", "evade_charge_on_enemy_crit"),
+            Self::WeakspotAnalysis => ("weakspot_analysis", "enemy_special_damage"),
+            Self::AtlasEffectChance => ("atlas_protocol", "boss_effect_chance"),
+            Self::AtlasSpecialChance => ("atlas_protocol", "boss_special_chance"),
+            Self::AtlasSpeed => ("atlas_protocol", "boss_speed"),
+            Self::BornForBattle => ("born_for_battle", "missing_hp_power"),
+            Self::LifedrainInhalers => ("lifedrain_inhalers", "missing_hp_regen"),
+            Self::FortificationElixir => ("fortification_elixir", "block_regen"),
+            Self::SoulAmplification => ("soul_amplification", "hundred_souls_cap"),
+        }
+    }
+}
+
+/// A hunter's coefficient for every `CombatEffect`, read from the registry
+/// once at construction so the fight never searches it; 0 for effects of
+/// upgrades the hunter doesn't have
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CombatCoefficients([f64; CombatEffect::ALL.len()]);
+
+impl CombatCoefficients {
+    pub fn for_hunter(hunter: HunterType) -> Self {
+        let mut coefficients = Self::default();
+        for effect in CombatEffect::ALL {
+            let (key, target) = effect.source();
+            coefficients.set(effect, game_data::coefficient(hunter, key, target).unwrap_or(0.0));
+        }
+        coefficients
+    }
+
+    pub fn get(&self, effect: CombatEffect) -> f64 {
+        self.0[effect as usize]
+    }
+
+    pub fn set(&mut self, effect: CombatEffect, per_level: f64) {
+        self.0[effect as usize] = per_level;
+    }
+}
+
+/// Per-level coefficient a hunter's stat formula reads from the registry.
+/// Each formula reads only its own hunter's upgrades, so a missing row is a
+/// gap in `game_data::UPGRADES`: debug builds, and with them every test that
+/// builds the hunter, fail on it; release builds leave the effect out.
+fn stat_coefficient(hunter: HunterType, key: &str, target: &str) -> f64 {
+    let per_level = game_data::coefficient(hunter, key, target);
+    debug_assert!(per_level.is_some(), "no `{}` effect of {:?} `{}` in game_data::UPGRADES", target, hunter, key);
+    per_level.unwrap_or(0.0)
+}
+
+/// Where a heal came from, for effective-healing and overheal accounting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealSource {
+    Regen,
+    Lifesteal,
+    LifeOfTheHunt,    // Borge heal proc
+    UnfairAdvantage,  // Ozzy heal proc (on kill)
 }
 
 impl Hunter {
     /// Create a hunter from a build configuration
     pub fn from_config(config: &BuildConfig) -> Self {
-        match config.get_hunter_type() {
+        let mut hunter = match config.get_hunter_type() {
             HunterType::Borge => Self::create_borge(config),
             HunterType::Ozzy => Self::create_ozzy(config),
             HunterType::Knox => Self::create_knox(config),
-        }
+        };
+        hunter.coefficients = CombatCoefficients::for_hunter(hunter.hunter_type);
+        hunter.mitigation = Mitigation {
+            minotaur: hunter.minotaur_dr,
+            scarab: hunter.scarab_dr,
+            weakspot: hunter.weakspot_analysis as f64 * hunter.per_level(CombatEffect::WeakspotAnalysis),
+            damage_reduction: hunter.damage_reduction,
+            rules: config.mitigation_rules(),
+        };
+        hunter.trace_filter = TraceFilter::new(&config.logging);
+        hunter.loot_events = config.events.clone();
+        hunter.max_stage = config.get_max_stage();
+        hunter.on_kill_policy = OnKillPolicy::for_config(config);
+        hunter.stacking = config.stacking_rules();
+        hunter.stun_affects_specials = config.get_engine_flag(EngineFlag::StunAffectsSpecials);
+        hunter.enemy_abilities = config.get_engine_flag(EngineFlag::EnemyAbilities);
+        hunter.attack_procs = AttackProcs::for_hunter(&hunter);
+        hunter
     }
     
     fn create_borge(c: &BuildConfig) -> Self {
         let level = c.get_level();
+        let k = |key, target| stat_coefficient(HunterType::Borge, key, target);
         
         // Get attribute values for calculations
         let soul_of_hermes = c.get_attr("soul_of_hermes");
         let soul_of_the_minotaur = c.get_attr("soul_of_the_minotaur");
         
         // Gadget multipliers (WASM-verified: ~0.3% per level + 0.2% bonus per 10 levels)
-        // WASM formula: (1 + level * 0.003) * (1.002 ** (level // 10))
-        fn gadget_mult(level: i32) -> f64 {
-            (1.0 + level as f64 * 0.003) * 1.002_f64.powi(level / 10)
-        }
-        let wrench_level = c.get_gadget("wrench").max(c.get_gadget("wrench_of_gore"));
-        let zaptron_level = c.get_gadget("zaptron").max(c.get_gadget("zaptron_533"));
-        let anchor_level = c.get_gadget("anchor").max(c.get_gadget("anchor_of_ages"));
-        let gadget_hp_mult = gadget_mult(wrench_level) * gadget_mult(zaptron_level) * gadget_mult(anchor_level);
+        let gadget_hp_mult = formulas::gadget_stat_multiplier(c);
         let gadget_power_mult = gadget_hp_mult;
         let gadget_regen_mult = gadget_hp_mult;
         
         // Legacy of Ultima: +1% HP/Power/Regen per point
-        let talent_dump_mult = 1.0 + c.get_talent("legacy_of_ultima") as f64 * 0.01;
+        let talent_dump_mult = 1.0 + c.get_talent("legacy_of_ultima") as f64 * k("legacy_of_ultima", "max_hp");
         
         // HP calculation - WASM: base * multipliers + flat inscryptions (i27/i3 added AFTER multipliers)
         let hp_stat = c.get_stat("hp") as f64;
-        let hp_base = 43.0 + hp_stat * (2.50 + 0.01 * (hp_stat / 5.0).floor());
+        let hp_base = formulas::base_hp(HunterType::Borge, hp_stat);
         let hp_multiplied = hp_base
-            * (1.0 + c.get_attr("soul_of_ares") as f64 * 0.01)
+            * (1.0 + c.get_attr("soul_of_ares") as f64 * k("soul_of_ares", "max_hp"))
             * (1.0 + c.get_relic("disk_of_dawn") as f64 * 0.03)
-            * (1.0 + (0.015 * (level - 39) as f64) * c.get_gem("creation_node_#3") as f64)
+            * formulas::creation_node_scaler(0.015, level, c.get_gem("creation_node_#3"))
             * (1.0 + 0.02 * c.get_gem("creation_node_#2") as f64)
             * (1.0 + 0.2 * c.get_gem("creation_node_#1") as f64)
             * gadget_hp_mult
@@ -162,15 +404,14 @@ impl Hunter {
         
         // Power calculation - includes soul_of_the_minotaur (+1% power per level)
         let pwr_stat = c.get_stat("power") as f64;
-        let power = (3.0 
-            + pwr_stat * (0.5 + 0.01 * (pwr_stat / 10.0).floor())
+        let power = (formulas::base_power(HunterType::Borge, pwr_stat)
             + c.get_inscr("i13") as f64 * 1.0
-            + c.get_talent("impeccable_impacts") as f64 * 2.0)
-            * (1.0 + c.get_attr("soul_of_ares") as f64 * 0.002)
-            * (1.0 + soul_of_the_minotaur as f64 * 0.01)  // +1% power per level
+            + c.get_talent("impeccable_impacts") as f64 * k("impeccable_impacts", "power"))
+            * (1.0 + c.get_attr("soul_of_ares") as f64 * k("soul_of_ares", "power"))
+            * (1.0 + soul_of_the_minotaur as f64 * k("soul_of_the_minotaur", "power"))  // +1% power per level
             * (1.0 + c.get_inscr("i60") as f64 * 0.03)
             * (1.0 + c.get_relic("long_range_artillery_crawler") as f64 * 0.03)
-            * (1.0 + (0.01 * (level - 39) as f64) * c.get_gem("creation_node_#3") as f64)
+            * formulas::creation_node_scaler(0.01, level, c.get_gem("creation_node_#3"))
             * (1.0 + 0.02 * c.get_gem("creation_node_#2") as f64)
             * (1.0 + 0.03 * c.get_gem("innovation_node_#3") as f64)
             * gadget_power_mult
@@ -178,31 +419,30 @@ impl Hunter {
         
         // Regen calculation
         let reg_stat = c.get_stat("regen") as f64;
-        let regen = (0.02 
-            + reg_stat * (0.03 + 0.01 * (reg_stat / 30.0).floor())
-            + c.get_attr("essence_of_ylith") as f64 * 0.04)
-            * (1.0 + c.get_attr("essence_of_ylith") as f64 * 0.009)
-            * (1.0 + (0.005 * (level - 39) as f64) * c.get_gem("creation_node_#3") as f64)
+        let regen = (formulas::base_regen(HunterType::Borge, reg_stat)
+            + c.get_attr("essence_of_ylith") as f64 * k("essence_of_ylith", "regen_flat"))
+            * (1.0 + c.get_attr("essence_of_ylith") as f64 * k("essence_of_ylith", "regen"))
+            * formulas::creation_node_scaler(0.005, level, c.get_gem("creation_node_#3"))
             * (1.0 + 0.02 * c.get_gem("creation_node_#2") as f64)
             * gadget_regen_mult
             * talent_dump_mult;
         
         // Damage reduction - includes soul_of_hermes (+0.2% DR per level) per WASM
         let damage_reduction = (c.get_stat("damage_reduction") as f64 * 0.0144
-            + c.get_attr("spartan_lineage") as f64 * 0.015
+            + c.get_attr("spartan_lineage") as f64 * k("spartan_lineage", "damage_reduction")
             + c.get_inscr("i24") as f64 * 0.004
-            + soul_of_hermes as f64 * 0.002)  // WASM: +0.2% DR per level
+            + soul_of_hermes as f64 * k("soul_of_hermes", "damage_reduction"))  // WASM: +0.2% DR per level
             * (1.0 + 0.02 * c.get_gem("creation_node_#2") as f64);
         
         // Evade chance
         let evade_chance = 0.01 
             + c.get_stat("evade_chance") as f64 * 0.0034
-            + c.get_attr("superior_sensors") as f64 * 0.016;
+            + c.get_attr("superior_sensors") as f64 * k("superior_sensors", "evade_chance");
         
         // Effect chance - includes soul_of_hermes (+0.4% per level)
         let effect_chance = (0.04 
             + c.get_stat("effect_chance") as f64 * 0.005
-            + c.get_attr("superior_sensors") as f64 * 0.012
+            + c.get_attr("superior_sensors") as f64 * k("superior_sensors", "effect_chance")
             // NOTE: Python does NOT add soul_of_hermes to effect_chance (though WASM does)
             + c.get_inscr("i11") as f64 * 0.02
             + 0.03 * c.get_gem("innovation_node_#3") as f64)
@@ -211,15 +451,15 @@ impl Hunter {
         // Special (crit) chance - Python uses +0.4% per level (not 0.5%)
         let special_chance = (0.05 
             + c.get_stat("special_chance") as f64 * 0.0018
-            + c.get_attr("explosive_punches") as f64 * 0.044
-            + soul_of_hermes as f64 * 0.004  // Match Python: +0.4% crit chance per level
+            + c.get_attr("explosive_punches") as f64 * k("explosive_punches", "special_chance")
+            + soul_of_hermes as f64 * k("soul_of_hermes", "special_chance")  // Match Python: +0.4% crit chance per level
             + c.get_inscr("i4") as f64 * 0.0065)
             * (1.0 + 0.02 * c.get_gem("creation_node_#2") as f64);
         
         // Special (crit) damage - Python does NOT add soul_of_hermes (though WASM does)
         let special_damage = 1.30 
             + c.get_stat("special_damage") as f64 * 0.01
-            + c.get_attr("explosive_punches") as f64 * 0.08;
+            + c.get_attr("explosive_punches") as f64 * k("explosive_punches", "special_damage");
         
         // Speed
         let speed = 5.0 
@@ -227,7 +467,7 @@ impl Hunter {
             - c.get_inscr("i23") as f64 * 0.04;
         
         // Lifesteal
-        let lifesteal = c.get_attr("book_of_baal") as f64 * 0.0111;
+        let lifesteal = c.get_attr("book_of_baal") as f64 * k("book_of_baal", "lifesteal");
         
         // Loot and XP multipliers - use comprehensive calculation from config
         let base_loot_mult = c.calculate_loot_multiplier(HunterType::Borge, effect_chance);
@@ -289,7 +529,7 @@ impl Hunter {
             soul_of_athena: c.get_attr("soul_of_athena"),
             soul_of_hermes,
             soul_of_the_minotaur,
-            minotaur_dr: soul_of_the_minotaur as f64 * 0.01,  // +1% multiplicative DR per level
+            minotaur_dr: soul_of_the_minotaur as f64 * k("soul_of_the_minotaur", "minotaur_dr"),  // +1% multiplicative DR per level
             soul_of_snek: 0,
             cycle_of_death: 0,
             gift_of_medusa: 0,
@@ -299,25 +539,44 @@ impl Hunter {
             soul_amplification: 0,
             fortification_elixir: 0,
             empowered_block_regen: 0,
-            has_trample: *c.mods.get("trample").unwrap_or(&false),
+            regen_suppressed_ticks: 0,
+            regen_suppression: 0.0,
+            has_trample: c.get_mod("trample"),
             has_decay: false,
-            attraction_catchup: c.get_gem("attraction_catch-up").max(c.get_gem("attraction_catch_up")),
+            hold_burst_during_harden: false,
+            held_multistrikes: 0,
+            attraction_catchup: c.get_gem("attraction_catch-up"),
             attraction_gem: c.get_gem("attraction_gem"),
             catching_up: true,  // Python starts with catching_up=True
             loot_mult,
             xp_mult,
+            loot_events: Vec::new(),
+            event_stages: Vec::new(),
+            stage_fit: StageFit::default(),
+            fight_phase: FightPhase::Trash,
+            hit_audit: None,
+            damage_log: None,
             result: SimResult::default(),
             current_stage: 0,  // Python starts at stage 0
             revive_count: 0,
             max_revives,
-            max_stage: 300,
+            max_stage: game_data::max_stage(HunterType::Borge),
             hundred_souls_stacks: 0,
             decay_stacks: 0,
+            mitigation: Mitigation::default(),
+            trace_filter: TraceFilter::default(),
+            on_kill_policy: OnKillPolicy::Single,
+            stacking: StackingRules::default(),
+            stun_affects_specials: false,
+            enemy_abilities: false,
+            attack_procs: AttackProcs::default(),
+            coefficients: CombatCoefficients::default(),
         }
     }
     
     fn create_ozzy(c: &BuildConfig) -> Self {
         let level = c.get_level();
+        let k = |key, target| stat_coefficient(HunterType::Ozzy, key, target);
         
         // Get attribute values for calculations
         let blessings_of_the_cat = c.get_attr("blessings_of_the_cat");
@@ -328,26 +587,19 @@ impl Hunter {
         let deal_with_death = c.get_attr("deal_with_death");
         
         // Gadget multipliers (WASM verified: ~0.3% per level + 0.2% bonus per 10 levels)
-        // WASM formula: (1 + level * 0.003) * (1.002 ** (level // 10))
-        fn gadget_mult(level: f64) -> f64 {
-            (1.0 + level * 0.003) * 1.002_f64.powf((level / 10.0).floor())
-        }
-        let wrench_level = c.get_gadget("wrench").max(c.get_gadget("wrench_of_gore")) as f64;
-        let zaptron_level = c.get_gadget("zaptron").max(c.get_gadget("zaptron_533")) as f64;
-        let anchor_level = c.get_gadget("anchor").max(c.get_gadget("anchor_of_ages")) as f64;
-        let gadget_mult_hp = gadget_mult(wrench_level) * gadget_mult(zaptron_level) * gadget_mult(anchor_level);
+        let gadget_mult_hp = formulas::gadget_stat_multiplier(c);
         
         // Level multiplier for Power (Python: (1.001 ** level) * (1.02 ** (level // 10)))
-        let level_mult = 1.001_f64.powi(level) * 1.02_f64.powi(level / 10);
+        let level_mult = formulas::ozzy_level_multiplier(level);
         
         // Attribute multipliers (WASM-verified)
-        let lotl_mult = 1.0 + c.get_attr("living_off_the_land") as f64 * 0.02;  // +2% HP/Regen per level
-        let exo_power_mult = 1.0 + c.get_attr("exo_piercers") as f64 * 0.012;   // +1.2% Power per level
-        let cat_power_mult = 1.0 + blessings_of_the_cat as f64 * 0.02;          // +2% Power per level
-        let cat_speed_mult = 1.0 - blessings_of_the_cat as f64 * 0.004;         // -0.4% speed per level (multiplicative!)
+        let lotl_mult = 1.0 + c.get_attr("living_off_the_land") as f64 * k("living_off_the_land", "max_hp");  // +2% HP/Regen per level
+        let exo_power_mult = 1.0 + c.get_attr("exo_piercers") as f64 * k("exo_piercers", "power");   // +1.2% Power per level
+        let cat_power_mult = 1.0 + blessings_of_the_cat as f64 * k("blessings_of_the_cat", "power");          // +2% Power per level
+        let cat_speed_mult = 1.0 - blessings_of_the_cat as f64 * k("blessings_of_the_cat", "speed");         // -0.4% speed per level (multiplicative!)
         
         // Legacy of Ultima: +1% HP/Power/Regen per point (WASM verified)
-        let talent_dump_mult = 1.0 + c.get_talent("legacy_of_ultima") as f64 * 0.01;
+        let talent_dump_mult = 1.0 + c.get_talent("legacy_of_ultima") as f64 * k("legacy_of_ultima", "max_hp");
         
         // Iridian Card: +3% HP, +3% Power, +3% Regen (WASM verified)
         let iridian_mult = if c.get_bonus_bool("iridian_card") { 1.03 } else { 1.0 };
@@ -355,22 +607,22 @@ impl Hunter {
         // HP calculation (WASM verified: HP * lotl_mult * talent_dump_mult * gadget_mult)
         // Note: HP does NOT use level_mult per Python/WASM
         let hp_stat = c.get_stat("hp") as f64;
-        let max_hp = (16.0 + hp_stat * (2.0 + 0.03 * (hp_stat / 5.0).floor()))
+        let max_hp = formulas::base_hp(HunterType::Ozzy, hp_stat)
             * lotl_mult
             * talent_dump_mult
-            * (1.0 + c.get_relic("disk_of_dawn").max(c.get_relic("r4")) as f64 * 0.03)
+            * (1.0 + c.get_relic("disk_of_dawn") as f64 * 0.03)
             * (1.0 + 0.03 * c.get_gem("innovation_node_#3") as f64)  // +3% HP from gem
             * gadget_mult_hp
             * iridian_mult;  // Iridian Card: +3% HP
         
         // Power calculation (WASM verified: Power * level_mult * exo_mult * cat_mult * talent_dump_mult * gadget_mult)
         let pwr_stat = c.get_stat("power") as f64;
-        let power = (2.0 + pwr_stat * (0.3 + 0.01 * (pwr_stat / 10.0).floor()))
+        let power = formulas::base_power(HunterType::Ozzy, pwr_stat)
             * level_mult
             * exo_power_mult
             * cat_power_mult
             * talent_dump_mult
-            * (1.0 + c.get_relic("bee_gone_companion_drone").max(c.get_relic("r17")) as f64 * 0.03)
+            * (1.0 + c.get_relic("bee_gone_companion_drone") as f64 * 0.03)
             * (1.0 + 0.03 * c.get_gem("innovation_node_#3") as f64)
             * gadget_mult_hp
             * iridian_mult;  // Iridian Card: +3% Power
@@ -378,7 +630,7 @@ impl Hunter {
         // Regen (WASM verified: Regen * lotl_mult * talent_dump_mult * gadget_mult)
         // Note: Regen does NOT use level_mult per Python/WASM
         let reg_stat = c.get_stat("regen") as f64;
-        let regen = (0.1 + reg_stat * (0.05 + 0.01 * (reg_stat / 30.0).floor()))
+        let regen = formulas::base_regen(HunterType::Ozzy, reg_stat)
             * lotl_mult
             * talent_dump_mult
             * (1.0 + 0.25 * c.get_gem("innovation_node_#3") as f64)  // +25% Regen from gem
@@ -388,19 +640,19 @@ impl Hunter {
         // Damage reduction - DOES NOT include scarab (scarab is multiplicative, applied in combat)
         // WASM: dr_stat * 0.0035 + wings_of_ibu * 0.026 + i37 * 0.0111 + i86 * 0.002
         let damage_reduction = c.get_stat("damage_reduction") as f64 * 0.0035
-            + c.get_attr("wings_of_ibu") as f64 * 0.026
+            + c.get_attr("wings_of_ibu") as f64 * k("wings_of_ibu", "damage_reduction")
             + c.get_inscr("i37") as f64 * 0.0111
             + c.get_inscr("i86") as f64 * 0.002;  // WASM verified: ab * 0.002
         
         // Evade chance - WASM: 0.05 + evade_stat * 0.0062 + wings_of_ibu * 0.005 (NO cat bonus!)
         let evade_chance = 0.05 
             + c.get_stat("evade_chance") as f64 * 0.0062
-            + c.get_attr("wings_of_ibu") as f64 * 0.005;
+            + c.get_attr("wings_of_ibu") as f64 * k("wings_of_ibu", "evade_chance");
         
         // Effect chance - WASM: 0.04 + effect_stat * 0.0035 + extermination_protocol * 0.028 + i31 * 0.006 + i92 * 0.002
         let effect_chance = 0.04 
             + c.get_stat("effect_chance") as f64 * 0.0035
-            + c.get_attr("extermination_protocol") as f64 * 0.028
+            + c.get_attr("extermination_protocol") as f64 * k("extermination_protocol", "effect_chance")
             + c.get_inscr("i31") as f64 * 0.006
             + c.get_inscr("i92") as f64 * 0.002;  // WASM verified: bb * 0.002
         
@@ -421,11 +673,11 @@ impl Hunter {
         let speed = (4.0 
             - c.get_stat("speed") as f64 * 0.0418
             - c.get_inscr("i36") as f64 * 0.03
-            - thousand_needles_lvl as f64 * 0.06)
+            - thousand_needles_lvl as f64 * k("thousand_needles", "speed"))
             * cat_speed_mult;  // WASM: multiplicative, not additive
         
         // Lifesteal - Python: shimmering_scorpion * 0.033
-        let lifesteal = c.get_attr("shimmering_scorpion") as f64 * 0.033;
+        let lifesteal = c.get_attr("shimmering_scorpion") as f64 * k("shimmering_scorpion", "lifesteal");
         
         // Loot multiplier - use comprehensive calculation from config
         let base_loot_mult = c.calculate_loot_multiplier(HunterType::Ozzy, effect_chance);
@@ -495,80 +747,101 @@ impl Hunter {
             cycle_of_death,
             gift_of_medusa,
             deal_with_death,
-            scarab_dr: blessings_of_the_scarab as f64 * 0.01,  // +1% multiplicative DR per level
+            scarab_dr: blessings_of_the_scarab as f64 * k("blessings_of_the_scarab", "scarab_dr"),  // +1% multiplicative DR per level
             space_pirate_armory: 0,
             soul_amplification: 0,
             fortification_elixir: 0,
             empowered_block_regen: 0,
+            regen_suppressed_ticks: 0,
+            regen_suppression: 0.0,
             has_trample: false,
-            has_decay: *c.mods.get("decay").unwrap_or(&false),
-            attraction_catchup: c.get_gem("attraction_catch-up").max(c.get_gem("attraction_catch_up")),
+            has_decay: c.get_mod("decay"),
+            hold_burst_during_harden: c.get_mod("hold_burst_during_harden"),
+            held_multistrikes: 0,
+            attraction_catchup: c.get_gem("attraction_catch-up"),
             attraction_gem: c.get_gem("attraction_gem"),
             catching_up: true,  // Python starts with catching_up=True
             loot_mult,
             xp_mult,
+            loot_events: Vec::new(),
+            event_stages: Vec::new(),
+            stage_fit: StageFit::default(),
+            fight_phase: FightPhase::Trash,
+            hit_audit: None,
+            damage_log: None,
             result: SimResult::default(),
             current_stage: 0,  // Python starts at stage 0
             revive_count: 0,
             max_revives,
-            max_stage: 210,
+            max_stage: game_data::max_stage(HunterType::Ozzy),
             hundred_souls_stacks: 0,
             decay_stacks: 0,
+            mitigation: Mitigation::default(),
+            trace_filter: TraceFilter::default(),
+            on_kill_policy: OnKillPolicy::Single,
+            stacking: StackingRules::default(),
+            stun_affects_specials: false,
+            enemy_abilities: false,
+            attack_procs: AttackProcs::default(),
+            coefficients: CombatCoefficients::default(),
         }
     }
     
     fn create_knox(c: &BuildConfig) -> Self {
         let level = c.get_level();
+        let k = |key, target| stat_coefficient(HunterType::Knox, key, target);
+        
+        // Gadgets scale Knox's HP, power and regen like the other hunters'. The
+        // Python port leaves them out (its notes read the WASM that way), so
+        // builds with gadget levels won't match it
+        let gadget_mult = formulas::gadget_stat_multiplier(c);
         
         // HP calculation
-        // Python: 20 + (hp * (2.0 + hp / 50))
         let hp_stat = c.get_stat("hp") as f64;
-        let max_hp = (20.0 + hp_stat * (2.0 + hp_stat / 50.0))
-            * (1.0 + c.get_attr("release_the_kraken") as f64 * 0.005)
-            * (1.0 + c.get_relic("disk_of_dawn") as f64 * 0.03);
+        let max_hp = formulas::base_hp(HunterType::Knox, hp_stat)
+            * (1.0 + c.get_attr("release_the_kraken") as f64 * k("release_the_kraken", "max_hp"))
+            * (1.0 + c.get_relic("disk_of_dawn") as f64 * 0.03)
+            * gadget_mult;
         
         // Power calculation
-        // Python: 1.2 + (power * (0.06 + power / 1000))
         let pwr_stat = c.get_stat("power") as f64;
-        let power = (1.2 + pwr_stat * (0.06 + pwr_stat / 1000.0))
-            * (1.0 + c.get_attr("release_the_kraken") as f64 * 0.005);
+        let power = formulas::base_power(HunterType::Knox, pwr_stat)
+            * (1.0 + c.get_attr("release_the_kraken") as f64 * k("release_the_kraken", "power"))
+            * gadget_mult;
         
         // Regen
-        // Python: 0.05 + (regen * (0.01 + regen * 0.00075))
         let reg_stat = c.get_stat("regen") as f64;
-        let regen = 0.05 + reg_stat * (0.01 + reg_stat * 0.00075);
+        let regen = formulas::base_regen(HunterType::Knox, reg_stat) * gadget_mult;
         
         // Damage reduction
         let damage_reduction = c.get_stat("damage_reduction") as f64 * 0.01
-            + c.get_attr("a_pirates_life_for_knox") as f64 * 0.009;
+            + c.get_attr("a_pirates_life_for_knox") as f64 * k("a_pirates_life_for_knox", "damage_reduction");
         
         // Block chance (Knox's unique defense)
         let block_chance = 0.05 
             + c.get_stat("block_chance") as f64 * 0.005
-            + c.get_attr("fortification_elixir") as f64 * 0.01
-            + c.get_attr("a_pirates_life_for_knox") as f64 * 0.008;
+            + c.get_attr("fortification_elixir") as f64 * k("fortification_elixir", "block_chance")
+            + c.get_attr("a_pirates_life_for_knox") as f64 * k("a_pirates_life_for_knox", "block_chance");
         
         // Effect chance
         let effect_chance = 0.04 
             + c.get_stat("effect_chance") as f64 * 0.004
-            + c.get_attr("serious_efficiency") as f64 * 0.02
-            + c.get_attr("a_pirates_life_for_knox") as f64 * 0.007;
+            + c.get_attr("serious_efficiency") as f64 * k("serious_efficiency", "effect_chance")
+            + c.get_attr("a_pirates_life_for_knox") as f64 * k("a_pirates_life_for_knox", "effect_chance");
         
         // Charge chance
         let charge_chance = 0.05 
             + c.get_stat("charge_chance") as f64 * 0.003
-            + c.get_attr("serious_efficiency") as f64 * 0.01
-            + c.get_attr("a_pirates_life_for_knox") as f64 * 0.006;
+            + c.get_attr("serious_efficiency") as f64 * k("serious_efficiency", "charge_chance")
+            + c.get_attr("a_pirates_life_for_knox") as f64 * k("a_pirates_life_for_knox", "charge_chance");
         
         // Charge gained (shield of poseidon is FLAT charge)
         let charge_gained = 1.0 
             + c.get_stat("charge_gained") as f64 * 0.01
-            + c.get_attr("shield_of_poseidon") as f64 * 0.1;
+            + c.get_attr("shield_of_poseidon") as f64 * k("shield_of_poseidon", "charge_gained");
         
         // Speed (reload time)
-        // IRL CALIBRATION: Base adjusted from 4.0 to 8.0, coeff from 0.02 to 0.08
-        // to match 6.40 sec in-game with reload_time_stat=20
-        let speed = 8.0 - c.get_stat("reload_time") as f64 * 0.08;
+        let speed = formulas::knox_reload_interval(c.get_stat("reload_time"));
         
         // Projectiles per salvo (base 3 + upgrades)
         // Python: self.salvo_projectiles = 3 + self.base_stats.get("projectiles_per_salvo", 0)
@@ -576,7 +849,7 @@ impl Hunter {
         
         // Special chance/damage (for finishing move)
         let special_chance = 0.10;
-        let special_damage = 1.0 + c.get_talent("finishing_move") as f64 * 0.2;
+        let special_damage = 1.0 + c.get_talent("finishing_move") as f64 * k("finishing_move", "special_damage");
         
         // Loot and XP multipliers - use comprehensive calculation from config
         let base_loot_mult = c.calculate_loot_multiplier(HunterType::Knox, effect_chance);
@@ -648,21 +921,67 @@ impl Hunter {
             soul_amplification: c.get_attr("soul_amplification"),
             fortification_elixir: c.get_attr("fortification_elixir"),
             empowered_block_regen: 0,
+            regen_suppressed_ticks: 0,
+            regen_suppression: 0.0,
             has_trample: false,
             has_decay: false,
-            attraction_catchup: c.get_gem("attraction_catch-up").max(c.get_gem("attraction_catch_up")),
+            hold_burst_during_harden: false,
+            held_multistrikes: 0,
+            attraction_catchup: c.get_gem("attraction_catch-up"),
             attraction_gem: c.get_gem("attraction_gem"),
             catching_up: true,  // Python starts with catching_up=True
             loot_mult,
             xp_mult,
+            loot_events: Vec::new(),
+            event_stages: Vec::new(),
+            stage_fit: StageFit::default(),
+            fight_phase: FightPhase::Trash,
+            hit_audit: None,
+            damage_log: None,
             result: SimResult::default(),
             current_stage: 0,  // Python starts at stage 0
             revive_count: 0,
             max_revives,
-            max_stage: 100,
+            max_stage: game_data::max_stage(HunterType::Knox),
             hundred_souls_stacks: 0,
             decay_stacks: 0,
+            mitigation: Mitigation::default(),
+            trace_filter: TraceFilter::default(),
+            on_kill_policy: OnKillPolicy::Single,
+            stacking: StackingRules::default(),
+            stun_affects_specials: false,
+            enemy_abilities: false,
+            attack_procs: AttackProcs::default(),
+            coefficients: CombatCoefficients::default(),
+        }
+    }
+    
+    /// Fail fast on derived stats the engine can't use: NaN, infinite, or
+    /// negative values (reachable with extreme inscryption/gem levels, since
+    /// most multipliers are geometric in the level)
+    pub fn validate(&self) -> Result<(), SimError> {
+        let checks: [(&'static str, f64, &'static str); 14] = [
+            ("max_hp", self.max_hp, "base HP x gadget/attribute/relic/gem multipliers + flat inscryptions"),
+            ("power", self.power, "base power x level/gadget/attribute/gem multipliers"),
+            ("regen", self.regen, "base regen x gadget/attribute/gem multipliers"),
+            ("damage_reduction", self.damage_reduction, "stat points x DR per point + talent/inscryption DR"),
+            ("evade_chance", self.evade_chance, "base evade + stat/attribute evade"),
+            ("effect_chance", self.effect_chance, "base effect chance + stat/inscryption/attribute bonuses"),
+            ("special_chance", self.special_chance, "base special chance + stat/inscryption bonuses"),
+            ("special_damage", self.special_damage, "base special damage + stat/inscryption bonuses"),
+            ("speed", self.speed, "base attack time - speed stat/inscryption reductions, floored at 0.1s"),
+            ("lifesteal", self.lifesteal, "stat/talent/attribute lifesteal"),
+            ("minotaur_dr", self.minotaur_dr, "Soul of the Minotaur level x DR per level"),
+            ("scarab_dr", self.scarab_dr, "Blessings of the Scarab level x DR per level"),
+            ("loot_mult", self.loot_mult, "product of every loot source (see `explain loot`)"),
+            ("xp_mult", self.xp_mult, "product of every XP source (see `explain xp`)"),
+        ];
+        for (stat, value, formula) in checks {
+            if !value.is_finite() || value < 0.0 {
+                return Err(SimError::InvalidStat { stat, value, formula });
+            }
         }
+        Ok(())
     }
     
     /// Reset hunter for a new simulation
@@ -676,6 +995,9 @@ impl Hunter {
         self.trickster_charges = 0;
         self.empowered_regen = 0;
         self.empowered_block_regen = 0;
+        self.regen_suppressed_ticks = 0;
+        self.held_multistrikes = 0;
+        self.regen_suppression = 0.0;
         self.fires_of_war_buff = 0.0;
         self.decay_stacks = 0;
         self.result = SimResult::default();
@@ -686,11 +1008,22 @@ impl Hunter {
         self.hp <= 0.0
     }
     
+    /// Per-level coefficient of a mid-fight effect, from the registry
+    #[inline(always)]
+    pub fn per_level(&self, effect: CombatEffect) -> f64 {
+        self.coefficients.get(effect)
+    }
+    
+    /// Most Hundred Souls stacks Knox can hold (Soul Amplification raises it)
+    pub fn hundred_souls_cap(&self) -> i32 {
+        100 + (self.soul_amplification as f64 * self.per_level(CombatEffect::SoulAmplification)) as i32
+    }
+    
     /// Get effective effect chance, accounting for Atlas Protocol (bosses)
     /// Python: (self._effect_chance + self.attributes["atlas_protocol"] * 0.014) on bosses
     pub fn get_effective_effect_chance(&self, is_boss: bool) -> f64 {
         if is_boss && self.atlas_protocol > 0 {
-            self.effect_chance + self.atlas_protocol as f64 * 0.014
+            self.effect_chance + self.atlas_protocol as f64 * self.per_level(CombatEffect::AtlasEffectChance)
         } else {
             self.effect_chance
         }
@@ -700,7 +1033,7 @@ impl Hunter {
     /// Python: (self._special_chance + self.attributes["atlas_protocol"] * 0.025) on bosses
     pub fn get_effective_special_chance(&self, is_boss: bool) -> f64 {
         if is_boss && self.atlas_protocol > 0 {
-            self.special_chance + self.atlas_protocol as f64 * 0.025
+            self.special_chance + self.atlas_protocol as f64 * self.per_level(CombatEffect::AtlasSpecialChance)
         } else {
             self.special_chance
         }
@@ -727,7 +1060,7 @@ impl Hunter {
         };
         
         self.power 
-            * (1.0 + missing_hp_pct * self.born_for_battle as f64 * 0.001)
+            * (1.0 + missing_hp_pct * self.born_for_battle as f64 * self.per_level(CombatEffect::BornForBattle))
             * self.get_catchup_mult()
     }
     
@@ -743,7 +1076,7 @@ impl Hunter {
         
         // Atlas Protocol: -4% attack time per level on bosses
         let mut current_speed = if is_boss && self.atlas_protocol > 0 {
-            self.speed * (1.0 - self.atlas_protocol as f64 * 0.04)
+            self.speed * (1.0 - self.atlas_protocol as f64 * self.per_level(CombatEffect::AtlasSpeed))
         } else {
             self.speed
         };
@@ -769,7 +1102,7 @@ impl Hunter {
         
         // Atlas Protocol: -4% attack time per level on bosses
         if is_boss && self.atlas_protocol > 0 {
-            effective_speed *= 1.0 - self.atlas_protocol as f64 * 0.04;
+            effective_speed *= 1.0 - self.atlas_protocol as f64 * self.per_level(CombatEffect::AtlasSpeed);
         }
         
         // Fires of War: temporary attack speed reduction
@@ -781,47 +1114,116 @@ impl Hunter {
         effective_speed.max(0.1)  // Minimum attack time
     }
     
-    /// Apply regeneration
+    /// Apply one second of regeneration
     pub fn regen_hp(&mut self) {
-        if self.hp < self.max_hp {
-            // Vectid Elixir + Soul of Snek - empowered regen for 5 ticks after Unfair Advantage
-            // WASM: Vectid just activates the buff, Soul of Snek determines the strength!
-            let mut regen_value = if self.empowered_regen > 0 {
-                self.empowered_regen -= 1;
-                self.regen * (1.0 + self.soul_of_snek as f64 * 0.15)  // Soul of Snek, not Vectid!
-            } else {
-                self.regen
-            };
-            
-            // Fortification Elixir (Knox) - +10% regen for 5 ticks after block
-            if self.empowered_block_regen > 0 {
-                self.empowered_block_regen -= 1;
-                regen_value *= 1.0 + self.fortification_elixir as f64 * 0.10;
+        self.regen_hp_over(1.0, 1);
+    }
+    
+    /// Apply `seconds` of regeneration. Timed buffs and suppression last whole
+    /// seconds, so they count down by `whole_seconds`, the number of the
+    /// clock's whole seconds this tick passed
+    pub fn regen_hp_over(&mut self, seconds: f64, whole_seconds: i32) {
+        // Vectid Elixir uptime: the buff also empowers lifesteal, so it counts
+        // while banked at full HP too
+        if self.empowered_regen > 0 {
+            self.result.empowered_regen_seconds += seconds;
+        }
+        // Enemy regen suppression (high-stage enemy ability) runs out even at full HP
+        let suppression_mult = if self.regen_suppressed_ticks > 0 {
+            self.regen_suppressed_ticks = (self.regen_suppressed_ticks - whole_seconds).max(0);
+            1.0 - self.regen_suppression
+        } else {
+            1.0
+        };
+        
+        // Vectid Elixir + Soul of Snek - empowered regen for 5 ticks after Unfair Advantage
+        // WASM: Vectid just activates the buff, Soul of Snek determines the strength!
+        // At full HP the whole tick is overheal, and the buffs only tick down while healing
+        let healing = self.hp < self.max_hp;
+        let mut regen_value = if self.empowered_regen > 0 {
+            if healing {
+                self.empowered_regen = (self.empowered_regen - whole_seconds).max(0);
+            }
+            self.regen * (1.0 + self.soul_of_snek as f64 * self.per_level(CombatEffect::SoulOfSnekEmpower))  // Soul of Snek, not Vectid!
+        } else {
+            self.regen
+        };
+
+        // Fortification Elixir (Knox) - +10% regen for 5 ticks after block
+        if self.empowered_block_regen > 0 {
+            if healing {
+                self.empowered_block_regen = (self.empowered_block_regen - whole_seconds).max(0);
+            }
+            regen_value *= 1.0 + self.fortification_elixir as f64 * self.per_level(CombatEffect::FortificationElixir);
+        }
+
+        // Lifedrain Inhalers (Borge) - +0.08% missing HP regen per level
+        let missing_hp = (self.max_hp - self.hp).max(0.0);
+        let lifedrain_bonus = if self.lifedrain_inhalers > 0 {
+            missing_hp * self.per_level(CombatEffect::LifedrainInhalers) * self.lifedrain_inhalers as f64
+        } else {
+            0.0
+        };
+
+        let total_regen = (regen_value + lifedrain_bonus) * seconds * suppression_mult;
+        self.heal(total_regen, HealSource::Regen);
+    }
+    
+    /// Heal up to max HP, recording the effective amount and the overheal
+    /// (the part that would have gone above max HP) under `source`. Heal
+    /// procs also keep their attempted total, which is what they always reported
+    pub fn heal(&mut self, amount: f64, source: HealSource) -> f64 {
+        let effective = amount.min(self.max_hp - self.hp).max(0.0);
+        let overheal = amount - effective;
+        self.hp = (self.hp + amount).min(self.max_hp);
+        let phase = self.fight_phase;
+        let r = &mut self.result;
+        let sustain = r.sustain_mut(phase);
+        sustain.overheal += overheal;
+        match source {
+            HealSource::Regen => sustain.regen += effective,
+            HealSource::Lifesteal => sustain.lifesteal += effective,
+            HealSource::LifeOfTheHunt | HealSource::UnfairAdvantage => sustain.procs += effective,
+        }
+        match source {
+            HealSource::Regen => {
+                r.regenerated_hp += effective;
+                r.overheal_regen += overheal;
+                let stage = self.current_stage;
+                let band = r.band_mut(stage);
+                band.regen += effective;
+                if stage % 100 == 0 && stage > 0 {
+                    band.boss_regen += effective;
+                }
+            }
+            HealSource::Lifesteal => {
+                r.lifesteal += effective;
+                r.overheal_lifesteal += overheal;
+            }
+            HealSource::LifeOfTheHunt => {
+                r.life_of_the_hunt_healing += amount;
+                r.life_of_the_hunt_healing_effective += effective;
+                r.overheal_procs += overheal;
+            }
+            HealSource::UnfairAdvantage => {
+                r.unfair_advantage_healing += amount;
+                r.unfair_advantage_healing_effective += effective;
+                r.overheal_procs += overheal;
             }
-            
-            // Lifedrain Inhalers (Borge) - +0.08% missing HP regen per level
-            let missing_hp = self.max_hp - self.hp;
-            let lifedrain_bonus = if self.lifedrain_inhalers > 0 {
-                missing_hp * 0.0008 * self.lifedrain_inhalers as f64
-            } else {
-                0.0
-            };
-            
-            let total_regen = regen_value + lifedrain_bonus;
-            let healed = total_regen.min(self.max_hp - self.hp);
-            self.hp += healed;
-            self.result.regenerated_hp += healed;
         }
+        effective
     }
     
     /// Try to revive if possible
     pub fn try_revive(&mut self) -> bool {
         if self.revive_count < self.max_revives {
             self.revive_count += 1;
+            self.result.revives += 1;
             // Python: self.hp = self.max_hp * 0.8
             // Death is my Companion revives at 80% HP
             let revive_hp = self.max_hp * 0.8;
             self.hp = revive_hp;
+            tracing::debug!(revive = self.revive_count, max_revives = self.max_revives, "hunter revived");
             true
         } else {
             false
@@ -851,7 +1253,6 @@ impl Hunter {
         };
         
         // Loot: Each stage has 10 enemies
-        let enemies_per_stage = 10.0_f64;
         let total_enemy_factor = geom_sum;
         
         // Final loot = BASE × GeomSum × LootMultiplier
@@ -865,4 +1266,124 @@ impl Hunter {
         
         (mat1, mat2, mat3, xp)
     }
-}
\ No newline at end of file
+}
+/// Serializable snapshot of every derived hunter stat for a build.
+///
+/// This is what the `stats` command and the Python `get_hunter_stats` binding
+/// report, so both show exactly the values the engine will fight with.
+#[derive(Debug, Clone, Serialize)]
+pub struct HunterStats {
+    pub hunter_type: HunterType,
+    pub level: i32,
+    
+    // Core stats
+    pub max_hp: f64,
+    pub power: f64,
+    pub regen: f64,
+    pub damage_reduction: f64,
+    pub evade_chance: f64,
+    pub effect_chance: f64,
+    pub special_chance: f64,
+    pub special_damage: f64,
+    pub speed: f64,
+    pub lifesteal: f64,
+    
+    // Multiplicative DR applied separately in combat
+    pub minotaur_dr: f64,  // Borge
+    pub scarab_dr: f64,    // Ozzy
+    pub effective_mitigation: f64,  // All layers combined against a non-crit hit, after caps
+    
+    // Borge combat-relevant levels
+    pub soul_of_hermes: i32,
+    pub atlas_protocol: i32,
+    pub impeccable_impacts: i32,
+    
+    // Knox-specific
+    pub block_chance: f64,
+    pub charge_chance: f64,
+    pub charge_gained: f64,
+    pub salvo_projectiles: i32,
+    
+    pub max_revives: i32,
+    pub max_stage: i32,
+    
+    // Loot and XP multipliers with their per-source contributions
+    pub loot_mult: f64,
+    pub xp_mult: f64,
+    pub loot_breakdown: MultiplierBreakdown,
+    pub xp_breakdown: MultiplierBreakdown,
+}
+
+impl HunterStats {
+    /// Build the hunter for a config and snapshot its derived stats
+    pub fn from_config(config: &BuildConfig) -> Self {
+        let hunter = Hunter::from_config(config);
+        Self {
+            hunter_type: hunter.hunter_type,
+            level: hunter.level,
+            max_hp: hunter.max_hp,
+            power: hunter.power,
+            regen: hunter.regen,
+            damage_reduction: hunter.damage_reduction,
+            evade_chance: hunter.evade_chance,
+            effect_chance: hunter.effect_chance,
+            special_chance: hunter.special_chance,
+            special_damage: hunter.special_damage,
+            speed: hunter.speed,
+            lifesteal: hunter.lifesteal,
+            minotaur_dr: hunter.minotaur_dr,
+            scarab_dr: hunter.scarab_dr,
+            effective_mitigation: hunter.mitigation.effective(false),
+            soul_of_hermes: hunter.soul_of_hermes,
+            atlas_protocol: hunter.atlas_protocol,
+            impeccable_impacts: hunter.impeccable_impacts,
+            block_chance: hunter.block_chance,
+            charge_chance: hunter.charge_chance,
+            charge_gained: hunter.charge_gained,
+            salvo_projectiles: hunter.salvo_projectiles,
+            max_revives: hunter.max_revives,
+            max_stage: hunter.max_stage,
+            loot_mult: hunter.loot_mult,
+            xp_mult: hunter.xp_mult,
+            loot_breakdown: config.loot_multiplier_breakdown(hunter.hunter_type, hunter.effect_chance),
+            xp_breakdown: config.xp_multiplier_breakdown(hunter.hunter_type),
+        }
+    }
+}
+
+/// Change in one derived stat between two builds
+#[derive(Debug, Clone, Serialize)]
+pub struct StatDelta {
+    pub stat: &'static str,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+}
+
+impl HunterStats {
+    /// Headline stats side by side with another snapshot (HP, power, DR, speed, loot, ...)
+    pub fn compare(&self, other: &HunterStats) -> Vec<StatDelta> {
+        let pairs = [
+            ("max_hp", self.max_hp, other.max_hp),
+            ("power", self.power, other.power),
+            ("regen", self.regen, other.regen),
+            ("damage_reduction", self.damage_reduction, other.damage_reduction),
+            ("evade_chance", self.evade_chance, other.evade_chance),
+            ("effect_chance", self.effect_chance, other.effect_chance),
+            ("special_chance", self.special_chance, other.special_chance),
+            ("special_damage", self.special_damage, other.special_damage),
+            ("speed", self.speed, other.speed),
+            ("lifesteal", self.lifesteal, other.lifesteal),
+            ("block_chance", self.block_chance, other.block_chance),
+            ("minotaur_dr", self.minotaur_dr, other.minotaur_dr),
+            ("scarab_dr", self.scarab_dr, other.scarab_dr),
+            ("effective_mitigation", self.effective_mitigation, other.effective_mitigation),
+            ("max_revives", self.max_revives as f64, other.max_revives as f64),
+            ("loot_mult", self.loot_mult, other.loot_mult),
+            ("xp_mult", self.xp_mult, other.xp_mult),
+        ];
+        pairs.into_iter()
+            .map(|(stat, before, after)| StatDelta { stat, before, after, delta: after - before })
+            .collect()
+    }
+}
@@ -0,0 +1,96 @@
+//! Daily loot for part-time players: active runs plus offline gains (`idle` subcommand)
+//!
+//! While the game is closed it keeps paying a fraction of the active loot rate,
+//! but only for a capped number of hours between collections. The rate, cap
+//! and play pattern are the player's inputs, since the game doesn't show them;
+//! the active rates come from simulating the build.
+//!
+//! Offline hours are split evenly between collections, and each collection is
+//! credited at most `offline_cap_hours`, so collecting more often recovers the
+//! hours a long gap would lose to the cap.
+
+use crate::error::ConfigError;
+use crate::stats::AggregatedStats;
+use serde::Serialize;
+
+const HOURS_PER_DAY: f64 = 24.0;
+
+/// The player's play pattern and the game's offline rules, as they see them
+#[derive(Debug, Clone, Serialize)]
+pub struct IdleModel {
+    pub active_hours: f64,              // Hours of active play per day
+    pub offline_rate: f64,              // Fraction of the active loot rate earned offline
+    pub offline_xp_rate: f64,           // Same for XP
+    pub offline_cap_hours: Option<f64>, // Most offline hours credited per collection; None is uncapped
+    pub collections: u32,               // Times per day offline gains are collected
+}
+
+impl IdleModel {
+    /// Reject inputs that don't describe a day
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |field: &str, message: String| Err(ConfigError::Invalid { field: field.to_string(), message });
+        if !(0.0..=HOURS_PER_DAY).contains(&self.active_hours) {
+            return invalid("active_hours", format!("{} is outside 0..=24", self.active_hours));
+        }
+        for (field, rate) in [("offline_rate", self.offline_rate), ("offline_xp_rate", self.offline_xp_rate)] {
+            if !rate.is_finite() || rate < 0.0 {
+                return invalid(field, format!("{} must be finite and at least 0", rate));
+            }
+        }
+        if let Some(cap) = self.offline_cap_hours.filter(|c| !c.is_finite() || *c < 0.0) {
+            return invalid("offline_cap_hours", format!("{} must be finite and at least 0", cap));
+        }
+        if self.collections == 0 {
+            return invalid("collections", "must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Offline hours per day the game pays for, after the per-collection cap
+    pub fn credited_offline_hours(&self) -> f64 {
+        let offline = HOURS_PER_DAY - self.active_hours;
+        let per_collection = offline / self.collections as f64;
+        let credited = self.offline_cap_hours.map_or(per_collection, |cap| per_collection.min(cap));
+        credited * self.collections as f64
+    }
+
+    /// Daily totals for a build's simulated active rates
+    pub fn report(&self, stats: &AggregatedStats) -> IdleReport {
+        let credited = self.credited_offline_hours();
+        let daily = |per_hour: f64, rate: f64| per_hour * (self.active_hours + credited * rate);
+        let daily_loot = daily(stats.avg_loot_per_hour, self.offline_rate);
+        IdleReport {
+            model: self.clone(),
+            offline_hours: HOURS_PER_DAY - self.active_hours,
+            credited_offline_hours: credited,
+            loot_per_hour: stats.avg_loot_per_hour,
+            xp_per_hour: stats.avg_xp_per_hour,
+            daily_active_loot: stats.avg_loot_per_hour * self.active_hours,
+            daily_offline_loot: stats.avg_loot_per_hour * credited * self.offline_rate,
+            daily_loot,
+            daily_loot_common: daily(stats.avg_loot_common_per_hour, self.offline_rate),
+            daily_loot_uncommon: daily(stats.avg_loot_uncommon_per_hour, self.offline_rate),
+            daily_loot_rare: daily(stats.avg_loot_rare_per_hour, self.offline_rate),
+            daily_xp: daily(stats.avg_xp_per_hour, self.offline_xp_rate),
+            effective_loot_per_hour: daily_loot / HOURS_PER_DAY,
+        }
+    }
+}
+
+/// One day of farming under an `IdleModel`
+#[derive(Debug, Clone, Serialize)]
+pub struct IdleReport {
+    pub model: IdleModel,
+    pub offline_hours: f64,
+    pub credited_offline_hours: f64,
+    pub loot_per_hour: f64,            // Active, from the simulation
+    pub xp_per_hour: f64,
+    pub daily_active_loot: f64,
+    pub daily_offline_loot: f64,
+    pub daily_loot: f64,
+    pub daily_loot_common: f64,
+    pub daily_loot_uncommon: f64,
+    pub daily_loot_rare: f64,
+    pub daily_xp: f64,
+    pub effective_loot_per_hour: f64,  // daily_loot over all 24 hours
+}
@@ -0,0 +1,159 @@
+//! What the binary understands, for GUIs to build their editors from (`introspect`)
+//!
+//! Nothing here is a second list to keep in step with the engine. A hunter's
+//! keys are the ones the engine reads while building and playing that
+//! hunter's bundled builds (see `config::record_key_reads`). Each key's effect
+//! is measured by moving it one step on the hunter's highest-level example and
+//! diffing the derived stats; keys whose effect only shows in combat (procs,
+//! on-kill effects) measure no change. Talents and attributes also carry
+//! their row of the `game_data` registry: cost, level cap, and the per-level
+//! effects the constructors and combat code apply, combat-only ones included.
+
+use crate::config::{self, BuildConfig, HunterType, KeyRead, ValueKind};
+use crate::engine_flags::{self, FlagInfo};
+use crate::examples::EXAMPLES;
+use crate::game_data::{self, CatalogItem, PointPool, UpgradeEffect};
+use crate::hunter::{HunterStats, StatDelta};
+use crate::simulation::run_simulation_with_seed;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// One key a hunter's builds can set
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyInfo {
+    pub section: &'static str,
+    pub key: String,
+    pub kind: ValueKind,
+    pub cost: Option<i32>,                  // Points per level, for talents and attributes
+    pub max: Option<i32>,                   // Level cap; None is uncapped or unknown
+    pub effects: &'static [UpgradeEffect],  // Per level, from the registry
+    pub effect_at: String,                  // The example the effect was measured on
+    pub measured: Vec<StatDelta>,           // Derived stats moved by one more step there
+}
+
+/// A hunter and every key the engine reads for it
+#[derive(Debug, Clone, Serialize)]
+pub struct HunterInfo {
+    pub hunter: HunterType,
+    pub max_stage: i32,
+    pub keys: Vec<KeyInfo>,
+}
+
+/// Everything `introspect` reports
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    pub version: &'static str,
+    pub hunters: Vec<HunterInfo>,
+    pub items: &'static [CatalogItem],  // Relics and gadgets, and what's simulated of each
+    pub engine_flags: &'static [FlagInfo],  // Switches for disputed mechanics
+    pub output_formats: Vec<String>,
+    pub number_formats: Vec<String>,
+}
+
+impl Capabilities {
+    /// The engine's hunters and keys, with the CLI's format names passed in
+    pub fn new(output_formats: Vec<String>, number_formats: Vec<String>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            hunters: [HunterType::Borge, HunterType::Ozzy, HunterType::Knox].into_iter().map(hunter_info).collect(),
+            items: game_data::CATALOG,
+            engine_flags: engine_flags::FLAGS,
+            output_formats,
+            number_formats,
+        }
+    }
+}
+
+fn hunter_info(hunter: HunterType) -> HunterInfo {
+    let builds: Vec<(&str, BuildConfig)> = EXAMPLES
+        .iter()
+        .filter(|e| e.hunter == hunter)
+        .filter_map(|e| e.config().ok().map(|c| (e.name, c)))
+        .collect();
+    let reads: BTreeSet<KeyRead> = builds
+        .iter()
+        .flat_map(|(_, config)| config::record_key_reads(|| {
+            run_simulation_with_seed(config, 0);
+        }))
+        .collect();
+    // Every hunter has at least its bundled blank template
+    let (example, base) = builds.iter().max_by_key(|(_, c)| c.get_level()).expect("bundled example present");
+    let keys = reads
+        .into_iter()
+        .map(|read| {
+            let pool = match read.section {
+                "talents" => Some(PointPool::Talent),
+                "attributes" => Some(PointPool::Attribute),
+                _ => None,
+            };
+            let upgrade = pool.and_then(|pool| game_data::upgrade(hunter, pool, &read.key));
+            let max = upgrade.and_then(|u| u.max);
+            let measured = measure(base, &read, max);
+            KeyInfo {
+                section: read.section,
+                key: read.key,
+                kind: read.kind,
+                cost: upgrade.map(|u| u.cost),
+                max,
+                effects: upgrade.map_or(&[], |u| u.effects),
+                effect_at: example.to_string(),
+                measured,
+            }
+        })
+        .collect();
+    HunterInfo { hunter, max_stage: game_data::max_stage(hunter), keys }
+}
+
+/// Derived stats that move when `read` goes one step up from its value in
+/// `base` (or up to it, when it's already at `max`)
+fn measure(base: &BuildConfig, read: &KeyRead, max: Option<i32>) -> Vec<StatDelta> {
+    let (mut low, mut high) = (base.clone(), base.clone());
+    match read.kind {
+        ValueKind::Int if read.section == "bonuses" => {
+            let value = base.get_bonus_int(&read.key);
+            high.bonuses.insert(read.key.clone(), (value + 1).into());
+        }
+        ValueKind::Int => {
+            let Some(values) = int_section(&mut high, read.section) else {
+                return Vec::new();
+            };
+            let value = values.entry(read.key.clone()).or_insert(0);
+            if max.is_some_and(|m| *value >= m) && *value > 0 {
+                *value -= 1;
+                std::mem::swap(&mut low, &mut high);
+            } else {
+                *value += 1;
+            }
+        }
+        ValueKind::Float => {
+            let value = base.get_bonus_float(&read.key);
+            high.bonuses.insert(read.key.clone(), (value + 0.1).into());
+        }
+        ValueKind::Bool if read.section == "mods" => {
+            low.mods.insert(read.key.clone(), false);
+            high.mods.insert(read.key.clone(), true);
+        }
+        ValueKind::Bool => {
+            low.bonuses.insert(read.key.clone(), false.into());
+            high.bonuses.insert(read.key.clone(), true.into());
+        }
+    }
+    HunterStats::from_config(&low)
+        .compare(&HunterStats::from_config(&high))
+        .into_iter()
+        .filter(|d| d.delta != 0.0)
+        .collect()
+}
+
+fn int_section<'a>(config: &'a mut BuildConfig, section: &str) -> Option<&'a mut HashMap<String, i32>> {
+    match section {
+        "stats" => Some(&mut config.stats),
+        "talents" => Some(&mut config.talents),
+        "attributes" => Some(&mut config.attributes),
+        "inscryptions" => Some(&mut config.inscryptions),
+        "relics" => Some(&mut config.relics),
+        "gems" => Some(&mut config.gems),
+        "gadgets" => Some(&mut config.gadgets),
+        _ => None,
+    }
+}
@@ -0,0 +1,137 @@
+//! Opt-in engine invariant checks for the `--validate` run mode
+//!
+//! When enabled, the event loop checks after every event that the hunter's HP
+//! stays within max HP, event time never moves backwards, cumulative damage dealt
+//! and taken never decrease (no negative damage), revives never exceed the
+//! maximum, and the queue holds no enemy events from an enemy that already died.
+//! Checks only read engine state, so results are identical with validation on
+//! or off. Violations are rare, so they go straight into a global list.
+
+use serde::Serialize;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::hunter::Hunter;
+
+/// Violations kept for the report; later ones are only counted
+const MAX_RECORDED: usize = 100;
+
+/// Slack for floating-point rounding in the HP and damage checks
+const EPSILON: f64 = 1e-9;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+static VIOLATIONS: Mutex<Vec<Violation>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static SEED: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Turn invariant checking on or off for all subsequent simulations
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether invariant checking is currently enabled
+#[inline(always)]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Clear all recorded violations
+pub fn reset() {
+    TOTAL.store(0, Ordering::Relaxed);
+    VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Seed of the simulation running on this thread (None when driven by a shared RNG)
+pub(crate) fn set_seed(seed: Option<u64>) {
+    SEED.with(|s| s.set(seed));
+}
+
+/// One broken invariant
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub seed: Option<u64>,  // Re-run with this seed to reproduce
+    pub stage: i32,
+    pub time: f64,
+    pub invariant: &'static str,
+    pub detail: String,
+}
+
+/// Snapshot of all violations recorded so far
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub total: u64,
+    pub violations: Vec<Violation>,  // First MAX_RECORDED violations
+}
+
+/// Build a report from the violations recorded so far
+pub fn report() -> ValidationReport {
+    ValidationReport {
+        total: TOTAL.load(Ordering::Relaxed),
+        violations: VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
+fn record(stage: i32, time: f64, invariant: &'static str, detail: String) {
+    TOTAL.fetch_add(1, Ordering::Relaxed);
+    let mut list = VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    if list.len() < MAX_RECORDED {
+        list.push(Violation { seed: SEED.with(|s| s.get()), stage, time, invariant, detail });
+    }
+}
+
+/// Per-simulation state carried between event checks
+pub(crate) struct Checker {
+    last_time: f64,
+    damage: f64,
+    damage_taken: f64,
+}
+
+impl Checker {
+    pub(crate) fn new() -> Self {
+        Self { last_time: 0.0, damage: 0.0, damage_taken: 0.0 }
+    }
+
+    /// Check the hunter after one event. `time` is None for events that are
+    /// dispatched out of time order by design (stuns are queued at time 0).
+    /// `enemy_events`/`special_events` count the current enemy's queued attacks.
+    pub(crate) fn after_event(&mut self, hunter: &Hunter, time: Option<f64>, enemy_events: usize, special_events: usize) {
+        let stage = hunter.current_stage;
+        let now = time.unwrap_or(self.last_time);
+
+        if let Some(t) = time {
+            if t + EPSILON < self.last_time {
+                record(stage, t, "time_monotonic", format!("event at {:.3}s after one at {:.3}s", t, self.last_time));
+            }
+            self.last_time = self.last_time.max(t);
+        }
+        if hunter.hp.is_nan() || hunter.hp > hunter.max_hp + EPSILON {
+            record(stage, now, "hp_within_max", format!("hp {} exceeds max_hp {}", hunter.hp, hunter.max_hp));
+        }
+        let r = &hunter.result;
+        if r.damage.is_nan() || r.damage + EPSILON < self.damage {
+            record(stage, now, "non_negative_damage", format!("damage dealt fell from {} to {}", self.damage, r.damage));
+        }
+        if r.damage_taken.is_nan() || r.damage_taken + EPSILON < self.damage_taken {
+            record(stage, now, "non_negative_damage", format!("damage taken fell from {} to {}", self.damage_taken, r.damage_taken));
+        }
+        self.damage = r.damage;
+        self.damage_taken = r.damage_taken;
+        if hunter.revive_count > hunter.max_revives {
+            record(stage, now, "revives_within_max", format!("{} revives used of {}", hunter.revive_count, hunter.max_revives));
+        }
+        if enemy_events > 1 || special_events > 1 {
+            record(stage, now, "no_stale_enemy_events", format!("{} enemy and {} special events queued", enemy_events, special_events));
+        }
+    }
+
+    /// Check that no events from a previous enemy survive into a new fight
+    pub(crate) fn fight_start(&self, stage: i32, stale_events: usize) {
+        if stale_events > 0 {
+            record(stage, self.last_time, "no_stale_enemy_events", format!("{} events left from the previous enemy", stale_events));
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Level-up estimates from an XP curve table
+//!
+//! The simulator doesn't know the game's XP curve, so players supply one: a
+//! YAML or JSON map from each level to the XP needed to reach the next.
+//!
+//! ```yaml
+//! 45: 2.1e15
+//! 46: 2.4e15
+//! 47: 2.8e15
+//! ```
+//!
+//! Combined with a build's simulated XP/hour this gives the farming time to
+//! the next level or to any level the table covers.
+
+use crate::error::ConfigError;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// XP needed to advance from each level to the next
+#[derive(Debug, Clone, Default)]
+pub struct XpCurve {
+    pub xp_to_next: BTreeMap<i32, f64>,
+}
+
+/// Farming time from one level to another at a fixed XP/hour
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelEta {
+    pub from_level: i32,
+    pub target_level: i32,
+    pub xp_needed: f64,
+    pub hours: f64,  // Infinite if the build earns no XP
+}
+
+impl XpCurve {
+    /// Load an XP curve file (YAML or JSON)
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Parse an XP curve from YAML or JSON text
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let xp_to_next: BTreeMap<i32, f64> = serde_yaml::from_str(text).map_err(|e| ConfigError::Parse {
+            field: "<root>".to_string(),
+            message: format!("expected a map from level to XP: {}", e),
+        })?;
+        if let Some((level, xp)) = xp_to_next.iter().find(|(_, xp)| !xp.is_finite() || **xp < 0.0) {
+            return Err(ConfigError::Invalid {
+                field: level.to_string(),
+                message: format!("XP to next level must be a non-negative number, got {}", xp),
+            });
+        }
+        Ok(Self { xp_to_next })
+    }
+
+    /// XP needed to go from `from` to `to`; errors on the first level the
+    /// table is missing
+    pub fn xp_between(&self, from: i32, to: i32) -> Result<f64, ConfigError> {
+        (from..to)
+            .map(|level| {
+                self.xp_to_next.get(&level).copied().ok_or_else(|| ConfigError::Invalid {
+                    field: level.to_string(),
+                    message: format!("XP curve has no entry for level {}", level),
+                })
+            })
+            .sum()
+    }
+
+    /// Time to farm from `from` to `to` at `xp_per_hour`
+    pub fn eta(&self, from: i32, to: i32, xp_per_hour: f64) -> Result<LevelEta, ConfigError> {
+        let xp_needed = self.xp_between(from, to)?;
+        Ok(LevelEta {
+            from_level: from,
+            target_level: to,
+            xp_needed,
+            hours: if xp_per_hour > 0.0 { xp_needed / xp_per_hour } else { f64::INFINITY },
+        })
+    }
+}
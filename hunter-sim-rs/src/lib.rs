@@ -1,20 +1,103 @@
 //! Hunter Sim - A fast combat simulation engine for CIFI idle game
 //! 
 //! This is a Rust rewrite of the Python simulation for 50-100x performance improvement.
+//!
+//! The supported API is what is re-exported at the crate root: load a
+//! [`BuildConfig`], run it through a [`Simulator`], and read the resulting
+//! [`AggregatedStats`] or per-run [`SimResult`]s. The engine modules stay public
+//! for the bundled CLI and Python bindings, but their contents change freely
+//! between releases and are not part of the stable surface.
 
+#[doc(hidden)]
+pub mod account;
+#[doc(hidden)]
+pub mod boss_preview;
+#[doc(hidden)]
+pub mod calibration;
+#[doc(hidden)]
+pub mod checkpoint;
+#[doc(hidden)]
 pub mod config;
+#[doc(hidden)]
+pub mod error;
+#[doc(hidden)]
+pub mod events;
+#[doc(hidden)]
+pub mod examples;
+#[doc(hidden)]
+pub mod fidelity;
+#[doc(hidden)]
+pub mod filter;
+#[doc(hidden)]
+pub mod formulas;
+#[doc(hidden)]
+pub mod format;
+#[doc(hidden)]
+pub mod game_data;
+#[doc(hidden)]
+pub mod game_import;
+#[doc(hidden)]
 pub mod hunter;
+#[doc(hidden)]
+pub mod idle;
+#[doc(hidden)]
+pub mod introspect;
+#[doc(hidden)]
+pub mod invariants;
+#[doc(hidden)]
+pub mod leveling;
+#[doc(hidden)]
+pub mod lint;
+#[doc(hidden)]
+pub mod logging;
+#[doc(hidden)]
+pub mod mitigation;
+#[doc(hidden)]
 pub mod enemy;
+#[doc(hidden)]
+pub mod engine_flags;
+#[doc(hidden)]
+pub mod plan;
+#[doc(hidden)]
+pub mod scenario;
+#[doc(hidden)]
+pub mod screening;
+#[doc(hidden)]
+pub mod server;
+#[doc(hidden)]
+pub mod share_code;
+#[doc(hidden)]
 pub mod simulation;
+#[doc(hidden)]
+pub mod simulator;
+#[doc(hidden)]
+pub mod stability;
+#[doc(hidden)]
 pub mod stats;
+#[doc(hidden)]
+pub mod wall;
+#[doc(hidden)]
+pub mod boss_script;
+#[doc(hidden)]
 pub mod build_generator;
+#[doc(hidden)]
+pub mod library;
+#[doc(hidden)]
+pub mod profiling;
 
+#[cfg(feature = "ffi")]
+#[doc(hidden)]
+pub mod ffi;
+#[cfg(feature = "gpu")]
+mod gpu;
 #[cfg(feature = "python")]
 mod python;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-pub use config::*;
-pub use hunter::*;
-pub use enemy::*;
-pub use simulation::*;
-pub use stats::*;
-pub use build_generator::*;
+pub use config::{BuildConfig, ConfigChange, HunterType, Meta, MultiplierBreakdown, MultiplierSource};
+pub use error::{ConfigError, OptimizeError, SimError};
+pub use format::NumberFormat;
+pub use hunter::{HunterStats, StatDelta};
+pub use simulator::{Observer, Simulator, SimulatorBuilder};
+pub use stats::{AggregatedStats, SimResult};
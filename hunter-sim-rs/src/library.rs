@@ -0,0 +1,154 @@
+//! Named build library stored under the user's data directory
+//!
+//! Each saved build is one JSON file `<data dir>/builds/<name>.json` holding the
+//! config together with its tags and notes. The data directory is
+//! `$HUNTER_SIM_DATA_DIR` if set, otherwise the platform data location
+//! (`%APPDATA%\hunter-sim` on Windows, `$XDG_DATA_HOME/hunter-sim` or
+//! `~/.local/share/hunter-sim` elsewhere).
+
+use crate::config::{BuildConfig, HunterType};
+use crate::error::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A build saved in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBuild {
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub saved_at: u64,  // Unix seconds
+    pub config: BuildConfig,
+}
+
+impl SavedBuild {
+    pub fn hunter_type(&self) -> HunterType {
+        self.config.get_hunter_type()
+    }
+}
+
+/// Handle to the build library directory
+#[derive(Debug, Clone)]
+pub struct BuildLibrary {
+    root: PathBuf,
+}
+
+impl BuildLibrary {
+    /// Open the library in the default data directory
+    pub fn open_default() -> Result<Self, ConfigError> {
+        default_data_dir().map(Self::open).ok_or_else(|| ConfigError::Invalid {
+            field: "HUNTER_SIM_DATA_DIR".to_string(),
+            message: "could not determine a data directory; set HUNTER_SIM_DATA_DIR".to_string(),
+        })
+    }
+
+    /// Open a library rooted at an explicit directory (created on first save)
+    pub fn open<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { root: dir.into().join("builds") }
+    }
+
+    /// Directory holding the build files
+    pub fn dir(&self) -> &Path {
+        &self.root
+    }
+
+    /// Save (or overwrite) a build under `name`
+    pub fn save(&self, name: &str, config: BuildConfig, tags: Vec<String>, notes: String) -> Result<SavedBuild, ConfigError> {
+        validate_name(name)?;
+        fs::create_dir_all(&self.root).map_err(|source| ConfigError::Io { path: self.root.clone(), source })?;
+
+        let saved = SavedBuild {
+            name: name.to_string(),
+            tags,
+            notes,
+            saved_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            config,
+        };
+        let path = self.path_for(name);
+        let json = serde_json::to_string_pretty(&saved).map_err(|e| ConfigError::Invalid {
+            field: name.to_string(),
+            message: e.to_string(),
+        })?;
+        fs::write(&path, json).map_err(|source| ConfigError::Io { path, source })?;
+        Ok(saved)
+    }
+
+    /// Load a saved build by name
+    pub fn load(&self, name: &str) -> Result<SavedBuild, ConfigError> {
+        validate_name(name)?;
+        let path = self.path_for(name);
+        let text = fs::read_to_string(&path).map_err(|source| ConfigError::Io { path, source })?;
+        let mut de = serde_json::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(&mut de).map_err(ConfigError::from_json)
+    }
+
+    /// All saved builds sorted by name; unreadable files are skipped
+    pub fn list(&self) -> Result<Vec<SavedBuild>, ConfigError> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(e) => e,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => return Err(ConfigError::Io { path: self.root.clone(), source }),
+        };
+        let mut builds: Vec<SavedBuild> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let path = e.path();
+                let name = path.file_stem()?.to_str()?.to_string();
+                (path.extension()? == "json").then_some(name)
+            })
+            .filter_map(|name| self.load(&name).ok())
+            .collect();
+        builds.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(builds)
+    }
+
+    /// Resolve a command-line reference: an existing file path wins, otherwise
+    /// the reference is looked up by name in the library
+    pub fn resolve(&self, reference: &Path) -> Result<BuildConfig, ConfigError> {
+        if reference.exists() {
+            return BuildConfig::from_file(reference);
+        }
+        match reference.to_str() {
+            Some(name) if validate_name(name).is_ok() => self.load(name).map(|b| b.config),
+            _ => BuildConfig::from_file(reference),
+        }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        self.root.join(format!("{}.json", name))
+    }
+}
+
+/// Names become file names, so keep them to a portable character set
+fn validate_name(name: &str) -> Result<(), ConfigError> {
+    let ok = !name.is_empty()
+        && name.len() <= 64
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if ok {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid {
+            field: "name".to_string(),
+            message: format!("`{}` is not a valid build name (use letters, digits, '-', '_', '.')", name),
+        })
+    }
+}
+
+fn default_data_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("HUNTER_SIM_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if cfg!(windows) {
+        return std::env::var_os("APPDATA").map(|d| PathBuf::from(d).join("hunter-sim"));
+    }
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        return Some(PathBuf::from(dir).join("hunter-sim"));
+    }
+    std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share/hunter-sim"))
+}
@@ -0,0 +1,240 @@
+//! Allocations in a build that can't do anything (`lint`)
+//!
+//! A key is wasted on a hunter when building that hunter never reads it (an
+//! Ozzy talent on a Borge build, an evade stat on Knox, who blocks instead),
+//! as seen by `config::record_key_reads`. Levels above a cap are wasted when
+//! the formula clamps to it and unrealistic when only the game enforces it.
+//! Derived chances past 100% and attack speed at its floor mean further
+//! points in what raises them are wasted too. None of this stops a build from
+//! simulating; a warning is a build that isn't spending its points.
+//!
+//! Relics and gadgets are checked against the `game_data` catalog instead,
+//! which also knows the effects the game has and the simulator skips: an item
+//! it skips entirely is inert, one it skips some of is partly simulated.
+//!
+//! The other way round, a build spending more talent or attribute points than
+//! its level gives (by the build generator's rules, at the registry's costs)
+//! simulates a hunter nobody can have. Stats are bought with resources, not
+//! points, so they have no level budget.
+
+use crate::build_generator;
+use crate::config::{self, BuildConfig, KeyRead};
+use crate::game_data::{self, Coverage, ItemEffect, PointPool};
+use crate::hunter::HunterStats;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Fastest attack interval the hunter constructors allow, in seconds
+const SPEED_FLOOR: f64 = 0.1;
+
+/// What is wrong with an allocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    NoEffect,    // The engine never reads the key for this hunter
+    Clamped,     // Above the cap its formula stops at
+    AboveCap,    // Above the game's cap, which the engine doesn't enforce
+    Saturated,   // A derived stat is pinned at its limit
+    OverBudget,  // More points spent than the level gives
+    Inert,       // A relic or gadget whose effects the simulator skips
+    Partial,     // A relic or gadget the simulator applies only some of
+}
+
+/// One warning, at the key it's about
+#[derive(Debug, Clone, Serialize)]
+pub struct Lint {
+    pub path: String,  // `section.key`, or the section of an overspent pool
+    pub kind: LintKind,
+    pub message: String,
+}
+
+/// Points a build has to spend in one pool and what it spends
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PointBudget {
+    pub pool: PointPool,
+    pub available: i32,
+    pub spent: i32,
+}
+
+/// Talent and attribute budgets of `config` at its level. Keys missing from
+/// the registry count a point per level
+pub fn point_budgets(config: &BuildConfig) -> [PointBudget; 2] {
+    let hunter = config.get_hunter_type();
+    let level = config.get_level();
+    let spent = |pool: PointPool, levels: &HashMap<String, i32>| -> i32 {
+        levels
+            .iter()
+            .map(|(key, n)| n * game_data::upgrade(hunter, pool, key).map_or(1, |u| u.cost))
+            .sum()
+    };
+    [
+        PointBudget {
+            pool: PointPool::Talent,
+            available: build_generator::talent_points(level),
+            spent: spent(PointPool::Talent, &config.talents),
+        },
+        PointBudget {
+            pool: PointPool::Attribute,
+            available: build_generator::attribute_points(level),
+            spent: spent(PointPool::Attribute, &config.attributes),
+        },
+    ]
+}
+
+/// A warning for each pool `config` overspends
+pub fn over_budget(config: &BuildConfig) -> Vec<Lint> {
+    point_budgets(config)
+        .into_iter()
+        .filter(|b| b.spent > b.available)
+        .map(|b| {
+            let (section, noun) = match b.pool {
+                PointPool::Talent => ("talents", "talent"),
+                PointPool::Attribute => ("attributes", "attribute"),
+            };
+            Lint {
+                path: section.to_string(),
+                kind: LintKind::OverBudget,
+                message: format!(
+                    "spends {} {} points; a level {} hunter has {}",
+                    b.spent, noun, config.get_level(), b.available
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Every warning for `config`, in key order
+pub fn lint(config: &BuildConfig) -> Vec<Lint> {
+    let hunter = config.get_hunter_type();
+    let mut stats = None;
+    let reads: BTreeSet<(&'static str, String)> = config::record_key_reads(|| {
+        stats = Some(HunterStats::from_config(config));
+    })
+    .into_iter()
+    .map(|KeyRead { section, key, .. }| (section, key))
+    .collect();
+    let stats = stats.expect("stats built");
+
+    let mut lints = Vec::new();
+    for (section, key, value) in allocations(config) {
+        let path = format!("{}.{}", section, key);
+        if let Some(lint) = item_lint(config, section, &key) {
+            let inert = lint.kind != LintKind::Partial;
+            lints.push(lint);
+            if inert {
+                continue;
+            }
+        }
+        if !reads.contains(&(section, key.clone())) {
+            lints.push(Lint { path, kind: LintKind::NoEffect, message: format!("{:?} doesn't use this", hunter) });
+            continue;
+        }
+        let Some(level) = value else { continue };
+        let pool = match section {
+            "talents" => Some(PointPool::Talent),
+            "attributes" => Some(PointPool::Attribute),
+            _ => None,
+        };
+        if let Some(max) = pool.and_then(|p| game_data::upgrade(hunter, p, &key)).and_then(|u| u.max) {
+            if level > max {
+                lints.push(Lint { path, kind: LintKind::AboveCap, message: format!("level {} is above the cap of {}", level, max) });
+            }
+        } else if let Some(cap) = game_data::input_cap(section, &key).filter(|c| level > c.max) {
+            let (kind, message) = if cap.clamped {
+                (LintKind::Clamped, format!("level {} counts as {}; the levels past it are wasted", level, cap.max))
+            } else {
+                (LintKind::AboveCap, format!("level {} is above the cap of {}", level, cap.max))
+            };
+            lints.push(Lint { path, kind, message });
+        }
+    }
+    lints.extend(saturation(config, &stats));
+    lints.extend(over_budget(config));
+    lints.sort_by(|a, b| a.path.cmp(&b.path));
+    lints
+}
+
+/// Relics and gadgets in `config` that the simulator ignores all or part of
+pub fn ignored_items(config: &BuildConfig) -> Vec<Lint> {
+    allocations(config)
+        .into_iter()
+        .filter_map(|(section, key, _)| item_lint(config, section, &key))
+        .collect()
+}
+
+/// The catalog's verdict on a catalogued item, when it's anything short of
+/// fully simulated
+fn item_lint(config: &BuildConfig, section: &'static str, key: &str) -> Option<Lint> {
+    let hunter = config.get_hunter_type();
+    let item = game_data::catalog_item(section, key)?;
+    let skipped = |effects: &[ItemEffect]| {
+        effects.iter().filter(|e| e.hunter == hunter).map(|e| e.description).collect::<Vec<_>>().join("; ")
+    };
+    let (kind, message) = match item.coverage(hunter) {
+        Coverage::Modeled => return None,
+        Coverage::Partial => (LintKind::Partial, format!("not simulated: {}", skipped(item.unmodeled))),
+        Coverage::Ignored => (LintKind::Inert, format!("not simulated for {:?}: {}", hunter, skipped(item.unmodeled))),
+        Coverage::NoEffect => (LintKind::NoEffect, format!("{:?} doesn't use this", hunter)),
+    };
+    Some(Lint { path: format!("{}.{}", section, key), kind, message })
+}
+
+/// Every key the build sets to something other than zero or false, with its
+/// level when it has one
+fn allocations(config: &BuildConfig) -> Vec<(&'static str, String, Option<i32>)> {
+    let ints = [
+        ("stats", &config.stats),
+        ("talents", &config.talents),
+        ("attributes", &config.attributes),
+        ("inscryptions", &config.inscryptions),
+        ("relics", &config.relics),
+        ("gems", &config.gems),
+        ("gadgets", &config.gadgets),
+    ];
+    let mut keys: Vec<_> = ints
+        .into_iter()
+        .flat_map(|(section, map)| map.iter().filter(|(_, v)| **v != 0).map(move |(k, v)| (section, k.clone(), Some(*v))))
+        .collect();
+    keys.extend(config.mods.iter().filter(|(_, on)| **on).map(|(k, _)| ("mods", k.clone(), None)));
+    keys.extend(config.bonuses.iter().filter(|(_, v)| is_set(v)).map(|(k, v)| ("bonuses", k.clone(), v.as_i64().map(|n| n as i32))));
+    keys
+}
+
+fn is_set(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        _ => false,
+    }
+}
+
+/// Derived chances past 100% and attack speed at its floor, reported at the
+/// stat that raises them
+fn saturation(config: &BuildConfig, stats: &HunterStats) -> Vec<Lint> {
+    let chances = [
+        ("damage_reduction", stats.damage_reduction),
+        ("evade_chance", stats.evade_chance),
+        ("effect_chance", stats.effect_chance),
+        ("special_chance", stats.special_chance),
+        ("block_chance", stats.block_chance),
+        ("charge_chance", stats.charge_chance),
+    ];
+    let mut lints: Vec<Lint> = chances
+        .into_iter()
+        .filter(|&(_, value)| value > 1.0)
+        .map(|(stat, value)| Lint {
+            path: format!("stats.{}", stat),
+            kind: LintKind::Saturated,
+            message: format!("{} is {:.1}%; points raising it past 100% are wasted", stat, value * 100.0),
+        })
+        .collect();
+    let speed_stat = ["speed", "reload_time"].into_iter().find(|k| config.stats.get(*k).is_some_and(|v| *v > 0));
+    if let Some(key) = speed_stat.filter(|_| stats.speed <= SPEED_FLOOR) {
+        lints.push(Lint {
+            path: format!("stats.{}", key),
+            kind: LintKind::Saturated,
+            message: format!("attack interval is at its {}s floor; points past it are wasted", SPEED_FLOOR),
+        });
+    }
+    lints
+}
@@ -0,0 +1,91 @@
+//! Which engine events a trace records (the build's `logging` section)
+//!
+//! `--log-level trace` on its own records every event of every stage, which
+//! for an hour-long run is millions of lines. The `logging` section narrows
+//! trace-level events down by subsystem and stage range:
+//!
+//! ```yaml
+//! logging:
+//!   subsystems: [attacks, procs]
+//!   min_stage: 195
+//! ```
+//!
+//! Leaving the section out traces everything, as before. It only filters: the
+//! log level still has to let trace events through, and debug-level events
+//! (boss fights, deaths, revives) are always kept.
+
+use serde::{Deserialize, Serialize};
+
+/// A group of trace events that can be switched on or off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Events,   // Every event popped from the queue
+    Attacks,  // Hunter and enemy attacks, boss enrage
+    Procs,    // Talent and enemy-ability procs
+    Regen,    // Regen ticks
+    Loot,     // Kills and the end-of-run loot and XP
+}
+
+/// The build's `logging` section; unset fields don't filter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsystems: Option<Vec<Subsystem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_stage: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stage: Option<i32>,
+}
+
+impl LoggingRules {
+    /// True when nothing is filtered (the section is then left out of saved builds)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// [`LoggingRules`] compiled for the engine's hot path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceFilter {
+    subsystems: u8,  // Bit per Subsystem
+    min_stage: i32,
+    max_stage: i32,
+}
+
+impl Default for TraceFilter {
+    fn default() -> Self {
+        Self { subsystems: u8::MAX, min_stage: i32::MIN, max_stage: i32::MAX }
+    }
+}
+
+impl TraceFilter {
+    pub fn new(rules: &LoggingRules) -> Self {
+        Self {
+            subsystems: rules.subsystems.as_ref().map_or(u8::MAX, |list| list.iter().fold(0, |mask, s| mask | 1 << *s as u8)),
+            min_stage: rules.min_stage.unwrap_or(i32::MIN),
+            max_stage: rules.max_stage.unwrap_or(i32::MAX),
+        }
+    }
+
+    /// Whether `subsystem` events at `stage` are traced
+    #[inline(always)]
+    pub fn allows(&self, subsystem: Subsystem, stage: i32) -> bool {
+        self.subsystems & (1 << subsystem as u8) != 0 && (self.min_stage..=self.max_stage).contains(&stage)
+    }
+}
+
+/// `trace!` for one subsystem, kept only if the hunter's `logging` rules
+/// allow it at the current stage. The rules are only consulted when trace
+/// events are enabled at all, so this costs nothing in normal runs.
+macro_rules! trace_at {
+    ($hunter:expr, $subsystem:ident, $($arg:tt)+) => {
+        if tracing::enabled!(tracing::Level::TRACE)
+            && $hunter.trace_filter.allows($crate::logging::Subsystem::$subsystem, $hunter.current_stage)
+        {
+            tracing::trace!($($arg)+);
+        }
+    };
+}
+pub(crate) use trace_at;
@@ -1,47 +1,86 @@
 //! CLI entry point for Hunter Simulator
-#![recursion_limit = "256"]
+#![recursion_limit = "512"]
 
-use clap::{Parser, ValueEnum};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use clap::{Parser, Subcommand, ValueEnum};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator};
 use rust_sim::{
-    config::BuildConfig,
-    hunter::Hunter,
-    enemy::Enemy,
-    simulation::run_and_aggregate,
-    stats::AggregatedStats,
+    account::{simulate_account, Account, AccountReport},
+    boss_script,
+    boss_preview::{self, BossPreview},
+    calibration::{self, CalibrationReport, ObservationSet},
+    checkpoint::{self, Checkpoint},
+    config::{BuildConfig, HunterType, Meta, MultiplierBreakdown},
+    fidelity::Fidelity,
+    format::NumberFormat,
+    error::{ErrorReport, SimError},
+    filter::{Objective, RunFilter},
+    hunter::HunterStats,
+    idle::{IdleModel, IdleReport},
+    introspect::Capabilities,
+    enemy::{Enemy, EnemyAbility, StageStats},
+    examples,
+    game_import,
+    invariants,
+    leveling::{LevelEta, XpCurve},
+    lint::{self, Lint},
+    library::BuildLibrary,
+    mitigation::HitAudit,
+    plan::{self, Calibration, RunPlan, SeedPlan},
+    scenario::{self, ScenarioFile, ScenarioReport},
+    screening::{self, Backend, ScreenReport},
+    stability::{self, StabilityReport},
+    server,
+    profiling,
+    share_code,
+    simulation::{self, run_seeds_parallel, run_simulation_with_seed, run_simulations_with_stream, try_run_and_aggregate, validate_config, FastRng, Snapshot},
+    stats::{AggregatedStats, DamageWindow, SimResult, STAGE_BAND},
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::time::Instant;
 
 #[derive(Debug, Clone, ValueEnum)]
 enum OutputFormat {
     Text,
     Json,
+    /// Comma-separated table; only `enemies` has one
+    Csv,
+}
+
+/// Multiplier that `explain` can itemize
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Explained {
+    Loot,
+    Xp,
 }
 
 #[derive(Parser, Debug)]
 #[command(name = "hunter-sim")]
 #[command(version = "1.0")]
 #[command(about = "High-performance Hunter Simulator for CIFI idle game", long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    
     /// Path to the build configuration file (YAML or JSON) or JSON array of configs
-    #[arg(short, long)]
-    configs: PathBuf,
+    #[arg(short, long, required = true)]
+    configs: Option<PathBuf>,
 
     /// Number of simulations to run
-    #[arg(short, long, default_value = "100")]
+    #[arg(short, long, default_value = "100", global = true)]
     num_sims: usize,
 
     /// Use parallel processing
-    #[arg(short, long, default_value = "false")]
+    #[arg(short, long, default_value = "false", global = true)]
     parallel: bool,
 
     /// Output format
-    #[arg(short, long, value_enum, default_value = "text")]
+    #[arg(short, long, value_enum, default_value = "text", global = true)]
     output: OutputFormat,
 
     /// Show timing information
-    #[arg(short, long, default_value = "false")]
+    #[arg(short, long, default_value = "false", global = true)]
     timing: bool,
     
     /// Debug: print computed hunter stats before simulation
@@ -52,72 +91,1592 @@ struct Args {
     #[arg(long)]
     debug_enemy_stage: Option<i32>,
     
-    /// Debug: enable detailed combat trace
+    /// Debug: enable detailed combat trace (same as --log-level trace). Boss fights
+    /// log a "boss enrage" sample with the stack count after every boss attack.
     #[arg(long, default_value = "false")]
     debug_trace: bool,
+    
+    /// Log filter for engine diagnostics on stderr (off, error, warn, info, debug, trace,
+    /// or per-module directives like "rust_sim::simulation=trace"). Overrides RUST_LOG.
+    #[arg(long)]
+    log_level: Option<String>,
+    
+    /// Emit log lines as JSON objects instead of human-readable text
+    #[arg(long, default_value = "false")]
+    log_json: bool,
+    
+    /// How to render large numbers (loot, XP, damage) in text output
+    #[arg(long, value_enum, default_value = "raw", global = true)]
+    number_format: NumberFormat,
+    
+    /// Report time spent in each engine phase (event dispatch, attacks, queue, loot)
+    #[arg(long, default_value = "false", global = true)]
+    profile: bool,
+    
+    /// Check engine invariants after every event and report violations with their seed
+    /// (exits 1 if any are found)
+    #[arg(long, default_value = "false", global = true)]
+    validate: bool,
+    
+    /// Speed/accuracy preset for the engine switches: standard, or exact (Python
+    /// parity: heapq ties, no early termination). The individual switches below
+    /// add to it.
+    #[arg(long, value_enum, default_value = "standard", global = true)]
+    fidelity: Fidelity,
+    
+    /// Break event ties (equal time and priority) like Python's heapq: by action
+    /// name, then push order
+    #[arg(long, default_value = "false", global = true)]
+    python_ties: bool,
+    
+    /// Log every event tie resolved differently than Python's heapq would and
+    /// report the count
+    #[arg(long, default_value = "false", global = true)]
+    audit_ties: bool,
+    
+    /// End any run that reaches this many simulated seconds, marking it timed out,
+    /// so a build that can neither kill nor be killed can't stall the batch
+    #[arg(long, value_name = "SECONDS", global = true)]
+    max_sim_time: Option<u64>,
+    
+    /// Abort any run that takes longer than this many milliseconds of real time,
+    /// marking it aborted, so a pathological build can't hang the batch
+    #[arg(long, value_name = "MS", global = true)]
+    watchdog: Option<u64>,
+    
+    /// Tick regen every this many simulated seconds instead of every second (0.001
+    /// to 10, in whole milliseconds), healing that much time's worth per tick, to
+    /// test how results depend on the tick or model a change to it
+    #[arg(long, value_name = "SECONDS", global = true)]
+    regen_tick: Option<f64>,
+    
+    /// Boss scripts file (YAML or JSON list of secondary attacks, see
+    /// `boss-scripts`) added on top of the built-in bosses, replacing any for
+    /// the same hunter and first stage; data/provisional_boss_scripts.yaml
+    /// opts in to the unverified post-300 bosses
+    #[arg(long, value_name = "FILE", global = true)]
+    boss_scripts: Option<PathBuf>,
+    
+    /// XP curve file (YAML or JSON map from level to XP needed for the next) for
+    /// level-up time estimates
+    #[arg(long)]
+    xp_curve: Option<PathBuf>,
+    
+    /// Level to estimate the farming time to with --xp-curve (default: the next level)
+    #[arg(long, requires = "xp_curve")]
+    target_level: Option<i32>,
+    
+    /// Save completed results to this file during the batch (simulation i runs with
+    /// seed i), so an interrupted batch can be continued with --resume
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+    
+    /// Simulations per config between checkpoint writes
+    #[arg(long, default_value = "1000")]
+    checkpoint_every: usize,
+    
+    /// Continue the batch saved in this checkpoint file (and keep checkpointing to it)
+    #[arg(long, conflicts_with = "checkpoint")]
+    resume: Option<PathBuf>,
+    
+    /// Print the resolved config, derived stats, stage plan, termination policy,
+    /// seeds and estimated runtime of the batch, then exit without simulating it
+    #[arg(long, default_value = "false", global = true)]
+    dry_run: bool,
+    
+    /// Ask before running a batch whose calibration runs estimate it at more than
+    /// this many seconds
+    #[arg(long, value_name = "SECONDS", default_value = "600", global = true)]
+    confirm_above: f64,
+    
+    /// Don't ask before long batches
+    #[arg(short, long, default_value = "false", global = true)]
+    yes: bool,
+    
+    /// Start every run mid-way, as the build's `start` section (repeatable):
+    /// stage=180, hp_fraction=0.6, trickster_charges=3, hundred_souls_stacks=40
+    #[arg(long = "start", value_name = "KEY=VALUE", global = true)]
+    start: Vec<String>,
+    
+    /// Switch a disputed mechanic for every build, as its `engine_flags`
+    /// section (repeatable): ozzy_double_on_kill=true, dr_cap_95=true,
+    /// stun_affects_specials=true, empowered_regen_refresh=true,
+    /// enemy_abilities=true
+    #[arg(long = "engine-flag", value_name = "NAME=BOOL", global = true)]
+    engine_flags: Vec<String>,
+    
+    /// Aggregate only the runs matching this expression over run fields, e.g.
+    /// "final_stage < 200" or "boss_kills >= 2 && damage_taken > 1e6"
+    #[arg(long)]
+    filter: Option<String>,
+    
+    /// Score and rank the configs by this expression over stats fields, highest
+    /// first, e.g. "0.7 * p10_loot_per_hour / 1e6 + 0.3 * survival_rate"
+    #[arg(long)]
+    objective: Option<String>,
 }
 
-fn main() {
-    let args = Args::parse();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print every derived hunter stat for a build without simulating
+    Stats {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+    },
+    
+    /// Compare two builds' inputs and derived stats without simulating
+    Diff {
+        /// Current build
+        before: PathBuf,
+        /// Proposed build (e.g. after a respec)
+        after: PathBuf,
+    },
+    
+    /// Print a compact share code for a build
+    ExportCode {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+    },
+    
+    /// Decode a share code into a build config
+    ImportCode {
+        /// Share code (as printed by export-code)
+        code: String,
+        
+        /// Write the build here instead of stdout (.json for JSON, YAML otherwise)
+        #[arg(short, long)]
+        write: Option<PathBuf>,
+    },
+    
+    /// Save a build to the local library under a name
+    SaveBuild {
+        /// Name to save under (letters, digits, '-', '_', '.')
+        name: String,
+        
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Tag to attach (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        
+        /// Free-form notes
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+    
+    /// List builds saved in the local library
+    ListBuilds {
+        /// Only show builds with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    
+    /// Simulate a saved build by name (accepts -n/-p/-o/-t like the default mode)
+    Run {
+        /// Saved build name (or a config file path)
+        name: PathBuf,
+    },
+    
+    /// Play a seeded run up to the start of a stage and save the full engine state
+    Snapshot {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Stage to pause at, before its enemies spawn (e.g. 200 for the boss fight)
+        #[arg(long)]
+        stage: i32,
+        
+        /// Seed of the run up to the snapshot
+        #[arg(long, default_value = "0")]
+        seed: u64,
+        
+        /// Write the snapshot here instead of stdout
+        #[arg(short, long)]
+        write: Option<PathBuf>,
+    },
+    
+    /// Finish a saved snapshot's run -n times with seeds 0..n and report the results
+    /// (accepts -n/-p/-o/-t like the default mode)
+    Replay {
+        /// Snapshot file written by `snapshot`
+        snapshot: PathBuf,
+        
+        /// Instead of replaying, finish the snapshot's own run once and print
+        /// the first N incoming hits layer by layer, from the raw damage to
+        /// the HP taken, to check the mitigation order against the game
+        #[arg(long, value_name = "N")]
+        audit_hits: Option<usize>,
+        
+        /// Instead of replaying, finish the snapshot's own run once and print the
+        /// hunter's damage per SECONDS-long window (default 10), to see ramp-ups and
+        /// boss dips; works with --output csv
+        #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "10", conflicts_with = "audit_hits")]
+        dps_curve: Option<f64>,
+    },
+    
+    /// Interactively create a starter build config
+    Init {
+        /// Where to write the config (defaults to <hunter>_build.yaml)
+        path: Option<PathBuf>,
+        
+        /// Overwrite the file if it already exists
+        #[arg(long, default_value = "false")]
+        force: bool,
+    },
+    
+    /// List the bundled example builds, or print / save one by name
+    Examples {
+        /// Example to print (omit to list them all)
+        name: Option<String>,
+        
+        /// Write the example here instead of stdout
+        #[arg(short, long)]
+        write: Option<PathBuf>,
+    },
+    
+    /// Simulate every hunter in an account file and suggest where to upgrade next
+    SimulateAccount {
+        /// Account file mapping borge/ozzy/knox to build paths or inline builds
+        account: PathBuf,
+        
+        /// Base seed shared by every batch (common random numbers for the +1 comparisons)
+        #[arg(long, default_value = "0")]
+        seed: u64,
+    },
+    
+    /// Estimate daily loot from an hour or so of active play plus offline gains
+    /// (accepts -n/-p/-o; the offline rules are your inputs)
+    Idle {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Hours of active play per day
+        #[arg(long, default_value = "1")]
+        active_hours: f64,
+        
+        /// Fraction of the active loot rate earned while offline (e.g. 0.25)
+        #[arg(long)]
+        offline_rate: f64,
+        
+        /// Fraction of the active XP rate earned while offline (default: --offline-rate)
+        #[arg(long)]
+        offline_xp_rate: Option<f64>,
+        
+        /// Most offline hours the game credits per collection (default: uncapped)
+        #[arg(long)]
+        offline_cap: Option<f64>,
+        
+        /// Times per day offline gains are collected
+        #[arg(long, default_value = "1")]
+        collections: u32,
+    },
+    
+    /// Itemize every source of a build's loot or XP multiplier, to check against the game
+    Explain {
+        /// Multiplier to break down
+        #[arg(value_enum)]
+        what: Explained,
+        
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+    },
+    
+    /// Warn about allocations that do nothing for the build's hunter (keys it never
+    /// reads, levels above their caps, stats pushed past their limits, relics and
+    /// gadgets the simulator skips) and about talent or attribute points beyond
+    /// what its level gives
+    Lint {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+    },
+    
+    /// Compare derived stats with values observed in game (exits 1 if any is out of tolerance)
+    Calibrate {
+        /// Base build the observations were taken on
+        config: PathBuf,
+        
+        /// Observation file (YAML or JSON) listing stat, observed value and inputs
+        observations: PathBuf,
+        
+        /// Also fit correction coefficients per stat
+        #[arg(long)]
+        fit: bool,
+    },
+    
+    /// Play the scripted combat cases of a scenario file and check their expected
+    /// outcomes (exits 1 if any case fails)
+    Scenario {
+        /// Scenario file (YAML or JSON)
+        file: PathBuf,
+    },
+    
+    /// Time a seeded batch with single vs batched RNG draws (accepts -n/-p/-o)
+    Bench {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+    },
+    
+    /// Repeat a seeded batch of -n runs with different seeds and report how far each
+    /// headline metric moves between batches, and the -n that would steady it
+    /// (accepts -n/-p/-o)
+    Stability {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Batches to compare (at least 2)
+        #[arg(long, default_value_t = stability::DEFAULT_BATCHES)]
+        batches: usize,
+        
+        /// Relative spread between batches to size -n for (0.01 is 1%)
+        #[arg(long, default_value_t = stability::DEFAULT_TARGET_SPREAD)]
+        target: f64,
+    },
+    
+    /// Screen a build with the simplified batch engine, on the GPU when built with the
+    /// `gpu` feature and an adapter is available (accepts -n/-o)
+    Screen {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Run on the CPU even if a GPU is available
+        #[arg(long, default_value = "false")]
+        cpu: bool,
+    },
+    
+    /// Serve a JSON API (POST /simulate, /compare, /optimize; GET /jobs/{id}, /jobs/{id}/events)
+    /// until stopped
+    Serve {
+        /// Address to listen on; use 0.0.0.0 to accept other machines
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+        
+        /// Origin whose web pages may call the API (repeatable; `*` for any).
+        /// None by default
+        #[arg(long = "allow-origin", value_name = "ORIGIN")]
+        allow_origins: Vec<String>,
+    },
+    
+    /// Fight one stage's boss -n times with the real engine, starting from the build's
+    /// full HP and revives, and report how often and how it's won (accepts -n/-p/-o)
+    BossPreview {
+        /// Build configuration file (YAML or JSON)
+        config: PathBuf,
+        
+        /// Boss stage to fight (a multiple of 100)
+        #[arg(long)]
+        stage: i32,
+    },
+    
+    /// Print regular enemy and boss stats stage by stage, as the raw scaling table
+    Enemies {
+        /// Hunter whose path to tabulate (borge, ozzy or knox)
+        #[arg(long, value_parser = parse_hunter)]
+        hunter: HunterType,
+        
+        /// First stage
+        #[arg(long, default_value = "1")]
+        from: i32,
+        
+        /// Last stage (default: the hunter's cap)
+        #[arg(long)]
+        to: Option<i32>,
+        
+        /// Stages between rows
+        #[arg(long, default_value = "1")]
+        step: usize,
+    },
+    
+    /// Print, as JSON, the hunters, every build key the engine reads for each
+    /// (with its cost, level cap and measured effect) and the output formats
+    Introspect,
+    
+    /// Print the boss scripts in effect (the built-in ones and any from
+    /// --boss-scripts), as a scripts file to start editing from
+    BossScripts,
+    
+    /// Convert the game's hunter export into a build config, listing unmapped fields
+    ImportGame {
+        /// File holding the export (JSON or base64 text), or `-` for stdin
+        export: PathBuf,
+        
+        /// Write the build here instead of stdout (.json for JSON, YAML otherwise)
+        #[arg(short, long)]
+        write: Option<PathBuf>,
+    },
+}
 
-    // Load configs
-    let configs: Vec<BuildConfig> = {
-        let content = match std::fs::read_to_string(&args.configs) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Error reading config file: {}", e);
-                std::process::exit(1);
+/// A hunter name, in any case
+fn parse_hunter(name: &str) -> Result<HunterType, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).map_err(|e| e.to_string())
+}
+
+/// Command-line names of a value enum's variants
+fn value_names<T: ValueEnum>() -> Vec<String> {
+    T::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect()
+}
+
+/// Load a single build config from a file path or saved build name,
+/// exiting with an error message on failure
+fn load_config_or_exit(path: &Path) -> BuildConfig {
+    let loaded = match BuildLibrary::open_default() {
+        Ok(library) => library.resolve(path),
+        Err(_) => BuildConfig::from_file(path),
+    };
+    match loaded {
+        Ok(c) => c,
+        Err(e) => fail_in("loading config", e),
+    }
+}
+
+/// `config` with the `--start` and `--engine-flag` overrides on top of its
+/// own `start` and `engine_flags` sections
+fn with_start(args: &Args, mut config: BuildConfig) -> BuildConfig {
+    for arg in &args.start {
+        let Some((key, value)) = arg.split_once('=') else {
+            fail(ErrorReport::new("config_invalid", format!("--start {}: expected KEY=VALUE", arg)));
+        };
+        let value: serde_json::Value = serde_json::from_str(value.trim())
+            .unwrap_or_else(|_| fail(ErrorReport::new("config_invalid", format!("--start {}: {} is not a number", arg, value))));
+        if let Err(e) = config.set_value(&format!("start.{}", key.trim()), &value).and_then(|_| config.normalize()) {
+            fail_in("applying --start", e);
+        }
+    }
+    for arg in &args.engine_flags {
+        let parsed = arg.split_once('=').and_then(|(name, on)| Some((name.trim(), on.trim().parse::<bool>().ok()?)));
+        let Some((name, on)) = parsed else {
+            fail(ErrorReport::new("config_invalid", format!("--engine-flag {}: expected NAME=true or NAME=false", arg)));
+        };
+        if let Err(e) = config.set_value(&format!("engine_flags.{}", name), &on.into()).and_then(|_| config.normalize()) {
+            fail_in("applying --engine-flag", e);
+        }
+    }
+    config
+}
+
+/// A build as a JSON value. Going through one makes keys come out sorted, and
+/// unset fields are dropped. Exits if the build can't be serialized.
+fn config_value(config: &BuildConfig) -> serde_json::Value {
+    let mut value = serde_json::to_value(config).unwrap_or_else(|e| fail_in("serializing config", SimError::from(e)));
+    if let serde_json::Value::Object(ref mut map) = value {
+        map.retain(|_, v| !v.is_null());
+    }
+    value
+}
+
+/// Write a build to a file (.json for JSON, YAML otherwise) or print it to stdout
+fn emit_config(config: &BuildConfig, write: Option<&Path>, output: &OutputFormat) {
+    let as_json = matches!(output, OutputFormat::Json)
+        || write.is_some_and(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")));
+    let value = config_value(config);
+    let text = if as_json {
+        serde_json::to_string_pretty(&value).map_err(|e| ErrorReport::from(SimError::from(e)))
+    } else {
+        serde_yaml::to_string(&value).map_err(|e| ErrorReport::new("serialize", e.to_string()))
+    };
+    let text = text.unwrap_or_else(|e| fail(e));
+    match write {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, text) {
+                fail_in(&format!("writing {}", path.display()), e);
             }
+            eprintln!("Wrote {}", path.display());
+        }
+        None => print!("{}", text),
+    }
+}
+
+/// Ask a question on stdin, returning the default on empty input or EOF
+fn prompt(question: &str, default: &str) -> String {
+    use std::io::{BufRead, Write};
+    
+    eprint!("{} [{}]: ", question, default);
+    let _ = std::io::stderr().flush();
+    let mut line = String::new();
+    match std::io::stdin().lock().read_line(&mut line) {
+        Ok(n) if n > 0 && !line.trim().is_empty() => line.trim().to_string(),
+        _ => default.to_string(),
+    }
+}
+
+/// Ask for an integer, re-asking until the answer parses
+fn prompt_int(question: &str, default: i32) -> i32 {
+    loop {
+        let answer = prompt(question, &default.to_string());
+        match answer.parse::<i32>() {
+            Ok(v) if v >= 0 => return v,
+            _ => eprintln!("  please enter a whole number >= 0"),
+        }
+    }
+}
+
+/// Walk the user through hunter, level and stat points and write a starter config
+fn init_build(path: Option<&Path>, force: bool) {
+    let hunter = loop {
+        match prompt("Hunter (borge/ozzy/knox)", "borge").to_lowercase().as_str() {
+            "borge" => break HunterType::Borge,
+            "ozzy" => break HunterType::Ozzy,
+            "knox" => break HunterType::Knox,
+            _ => eprintln!("  please enter borge, ozzy or knox"),
+        }
+    };
+    let mut config = examples::template(hunter).unwrap_or_else(|e| fail(e));
+    let level = prompt_int("Hunter level", 1);
+    config.meta = Some(Meta { hunter, level });
+    
+    // Ask for stats in the template's order rather than HashMap order
+    eprintln!("Stat points per stat (press Enter to leave at 0):");
+    let example = examples::find(&format!("empty_{:?}", hunter).to_lowercase());
+    let order: Vec<String> = example
+        .and_then(|e| serde_yaml::from_str::<serde_yaml::Value>(e.yaml).ok())
+        .and_then(|v| v.get("stats").and_then(|s| s.as_mapping().cloned()))
+        .map(|m| m.keys().filter_map(|k| k.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    for stat in order {
+        let points = prompt_int(&format!("  {}", stat), 0);
+        config.stats.insert(stat, points);
+    }
+    
+    let default_path = PathBuf::from(format!("{:?}_build.yaml", hunter).to_lowercase());
+    let path = path.unwrap_or(&default_path);
+    if path.exists() && !force {
+        fail(ErrorReport::new("exists", format!("{} already exists (use --force to overwrite)", path.display())));
+    }
+    emit_config(&config, Some(path), &OutputFormat::Text);
+    eprintln!("Next: hunter-sim -c {} -n 1000 -p", path.display());
+}
+
+/// Open the default build library, exiting with an error message on failure
+fn open_library_or_exit() -> BuildLibrary {
+    BuildLibrary::open_default().unwrap_or_else(|e| fail(e))
+}
+
+/// Print a JSON value, exiting with an error message if serialization fails
+fn print_json<T: serde::Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(s) => println!("{}", s),
+        Err(e) => fail(SimError::from(e)),
+    }
+}
+
+/// Print changed inputs and the resulting stat deltas side by side
+fn print_diff(changes: &[rust_sim::config::ConfigChange], deltas: &[rust_sim::hunter::StatDelta], nf: NumberFormat) {
+    let show = |v: &Option<serde_json::Value>| match v {
+        None => "-".to_string(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+    };
+    
+    println!("--- Changed Inputs ({}) ---", changes.len());
+    println!("{:<36} {:>12} {:>12}", "Input", "Before", "After");
+    for c in changes {
+        println!("{:<36} {:>12} {:>12}", c.key, show(&c.before), show(&c.after));
+    }
+    println!();
+    println!("--- Stat Deltas ---");
+    println!("{:<18} {:>14} {:>14} {:>14} {:>9}", "Stat", "Before", "After", "Delta", "Change");
+    for d in deltas {
+        let pct = if d.before != 0.0 {
+            format!("{:+.2}%", d.delta / d.before.abs() * 100.0)
+        } else {
+            "-".to_string()
         };
-        if content.trim_start().starts_with('[') {
-            match serde_json::from_str(&content) {
-                Ok(c) => c,
-                Err(e) => {
-                    eprintln!("Error parsing config array: {}", e);
+        let marker = if d.delta == 0.0 { " " } else { "*" };
+        println!("{:<18} {:>14} {:>14} {:>14} {:>9}{}", d.stat, nf.format(d.before, 4), nf.format(d.after, 4), nf.format(d.delta, 4), pct, marker);
+    }
+}
+
+/// Print per-hunter and combined account results with the upgrade suggestion
+fn print_account_report(report: &AccountReport, nf: NumberFormat) {
+    println!("=== Account Simulation ===");
+    println!("{:<6} {:>5} {:>9} {:>14} {:>14}  Best +1 stat", "Hunter", "Level", "Avg Stage", "Loot/hr", "XP/hr");
+    for h in &report.hunters {
+        let best = h.best_upgrade.as_ref().map_or("-".to_string(), |u| {
+            format!("{} ({:+.2}%)", u.stat, u.loot_per_hour_gain * 100.0)
+        });
+        println!("{:<6} {:>5} {:>9.2} {:>14} {:>14}  {}", format!("{:?}", h.hunter), h.level, h.avg_stage,
+            nf.format(h.loot_per_hour, 0), nf.format(h.xp_per_hour, 0), best);
+    }
+    println!();
+    println!("Combined Loot/hr: {}", nf.format(report.total_loot_per_hour, 0));
+    println!("Combined XP/hr:   {}", nf.format(report.total_xp_per_hour, 0));
+    match report.suggested_hunter {
+        Some(hunter) => println!("Suggested next upgrade: {:?} (largest relative loot/hr gain per stat point)", hunter),
+        None => println!("Suggested next upgrade: none (no single stat point improves loot/hr)"),
+    }
+}
+
+/// Print one day of active plus offline farming
+fn print_idle_report(report: &IdleReport, nf: NumberFormat) {
+    let model = &report.model;
+    let cap = model.offline_cap_hours.map_or("uncapped".to_string(), |c| format!("capped at {}h per collection", c));
+    println!("=== Daily Farming (active + offline) ===");
+    println!("Active: {:.1}h at {} loot/hr", model.active_hours, nf.format(report.loot_per_hour, 0));
+    println!(
+        "Offline: {:.1}h, {} collection(s), {}: {:.1}h credited at {:.0}% loot / {:.0}% XP",
+        report.offline_hours,
+        model.collections,
+        cap,
+        report.credited_offline_hours,
+        model.offline_rate * 100.0,
+        model.offline_xp_rate * 100.0,
+    );
+    println!();
+    let share = |part: f64| if report.daily_loot > 0.0 { part / report.daily_loot * 100.0 } else { 0.0 };
+    println!("Daily Loot: {}", nf.format(report.daily_loot, 0));
+    println!("  Active:  {} ({:.1}%)", nf.format(report.daily_active_loot, 0), share(report.daily_active_loot));
+    println!("  Offline: {} ({:.1}%)", nf.format(report.daily_offline_loot, 0), share(report.daily_offline_loot));
+    println!(
+        "  Common {} / Uncommon {} / Rare {}",
+        nf.format(report.daily_loot_common, 0),
+        nf.format(report.daily_loot_uncommon, 0),
+        nf.format(report.daily_loot_rare, 0),
+    );
+    println!("Daily XP: {}", nf.format(report.daily_xp, 0));
+    println!("Effective Loot/hr over 24h: {}", nf.format(report.effective_loot_per_hour, 0));
+}
+
+/// Print incoming hits step by step through the mitigation layers
+fn print_hit_audit(snapshot: &Snapshot, result: &SimResult, hits: &[HitAudit], total: usize, nf: NumberFormat) {
+    println!(
+        "=== Mitigation Audit (seed {} from stage {}, {} of {} hits; run ended at stage {} by {}) ===",
+        snapshot.seed,
+        snapshot.stage,
+        hits.len(),
+        total,
+        result.final_stage,
+        result.end.label(),
+    );
+    for hit in hits {
+        let mut line = format!(
+            "Stage {} {}{}: raw {}",
+            hit.stage,
+            if hit.boss { "boss" } else { "enemy" },
+            if hit.crit { " crit" } else { "" },
+            nf.format(hit.raw, 2),
+        );
+        if hit.blocked > 0.0 {
+            line += &format!(" -> block -{} = {}", nf.format(hit.blocked, 2), nf.format(hit.raw - hit.blocked, 2));
+        }
+        for step in &hit.steps {
+            line += &format!(" -> {} {:.2}% = {}", step.layer.label(), step.reduction * 100.0, nf.format(step.remaining, 2));
+        }
+        if hit.total_capped {
+            line += " -> total cap";
+        }
+        line += &format!(" => taken {} (HP {} -> {})", nf.format(hit.taken, 2), nf.format(hit.hp_before, 2), nf.format(hit.hp_before - hit.taken, 2));
+        println!("{}", line);
+    }
+}
+
+fn print_damage_curve(snapshot: &Snapshot, result: &SimResult, curve: &[DamageWindow], window: f64, nf: NumberFormat) {
+    println!(
+        "=== DPS Curve (seed {} from stage {}, {}s windows; run ended at stage {} by {}) ===",
+        snapshot.seed,
+        snapshot.stage,
+        window,
+        result.final_stage,
+        result.end.label(),
+    );
+    let peak = curve.iter().map(|w| w.dps).fold(0.0, f64::max);
+    println!("{:>9} {:>6} {:>8} {:>14}", "Time", "Stage", "Attacks", "DPS");
+    for w in curve {
+        // Bars are scaled to the peak window
+        let bar = if peak > 0.0 { "#".repeat((w.dps / peak * 40.0).round() as usize) } else { String::new() };
+        println!(
+            "{:>8.0}s {:>5}{} {:>8} {:>14}  {}",
+            w.start,
+            w.stage,
+            if w.boss { "B" } else { " " },
+            w.attacks,
+            nf.format(w.dps, 0),
+            bar
+        );
+    }
+}
+
+/// Print the derived hunter stats table, with only the lines that apply to this hunter
+fn print_hunter_stats(stats: &HunterStats, nf: NumberFormat) {
+    let pct = |v: f64| format!("{:.4} ({:.2}%)", v, v * 100.0);
+    let (special_chance, special_damage) = match stats.hunter_type {
+        HunterType::Borge => ("Crit Chance:", "Crit Damage:"),
+        HunterType::Ozzy => ("Multistrike:", "MS Damage:"),
+        HunterType::Knox => ("Finisher:", "Finish Dmg:"),
+    };
+    
+    println!("============================================================");
+    println!("RUST {:?} STATS", stats.hunter_type);
+    println!("============================================================");
+    println!("Max HP:        {}", nf.format(stats.max_hp, 2));
+    println!("Power:         {}", nf.format(stats.power, 4));
+    println!("Regen:         {:.4}", stats.regen);
+    println!("DR:            {}", pct(stats.damage_reduction));
+    println!("Mitigation:    {}", pct(stats.effective_mitigation));
+    if stats.hunter_type != HunterType::Knox {
+        println!("Evade:         {}", pct(stats.evade_chance));
+    }
+    println!("Effect:        {}", pct(stats.effect_chance));
+    println!("{:<15}{}", special_chance, pct(stats.special_chance));
+    println!("{:<15}{:.4}", special_damage, stats.special_damage);
+    println!("Speed:         {:.4}", stats.speed);
+    println!("Lifesteal:     {}", pct(stats.lifesteal));
+    println!("Loot Mult:     {}", nf.format(stats.loot_mult, 4));
+    println!("XP Mult:       {}", nf.format(stats.xp_mult, 4));
+    println!("Max Revives:   {}", stats.max_revives);
+    println!();
+    match stats.hunter_type {
+        HunterType::Borge => {
+            println!("BORGE-SPECIFIC:");
+            println!("Minotaur DR:   {}", pct(stats.minotaur_dr));
+            println!("Soul of Hermes:{}", stats.soul_of_hermes);
+            println!("Atlas Protocol:{}", stats.atlas_protocol);
+            println!("Impeccable Impacts: {}", stats.impeccable_impacts);
+        }
+        HunterType::Ozzy => {
+            println!("OZZY-SPECIFIC:");
+            println!("Scarab DR:     {}", pct(stats.scarab_dr));
+        }
+        HunterType::Knox => {
+            println!("KNOX-SPECIFIC:");
+            println!("Block Chance:  {}", pct(stats.block_chance));
+            println!("Charge Chance: {}", pct(stats.charge_chance));
+            println!("Charge Gained: {:.4}", stats.charge_gained);
+            println!("Salvo:         {}", stats.salvo_projectiles);
+        }
+    }
+    println!();
+    println!("LOOT SOURCES:");
+    for s in &stats.loot_breakdown.sources {
+        println!("  {:<24} lvl {:>6}  x{:.4}", s.source, s.level, s.factor);
+    }
+    println!("XP SOURCES:");
+    for s in &stats.xp_breakdown.sources {
+        println!("  {:<24} lvl {:>6}  x{:.4}", s.source, s.level, s.factor);
+    }
+    println!();
+}
+
+/// Print a multiplier breakdown with the running product after each source
+fn print_breakdown(title: &str, stats: &HunterStats, breakdown: &MultiplierBreakdown, nf: NumberFormat) {
+    println!("{} MULTIPLIER: {:?} lvl {}", title, stats.hunter_type, stats.level);
+    println!("{:<28} {:>8} {:>12} {:>14}", "Source", "Level", "Factor", "Running");
+    let mut running = 1.0;
+    for s in &breakdown.sources {
+        running *= s.factor;
+        println!("{:<28} {:>8} {:>12} {:>14}", s.source, s.level, format!("x{:.4}", s.factor), format!("x{}", nf.format(running, 4)));
+    }
+    println!("{:<28} {:>8} {:>12} {:>14}", "Total", "", "", format!("x{}", nf.format(breakdown.total, 4)));
+}
+
+/// Print observed vs derived values, then any fitted corrections
+fn print_boss_preview(preview: &BossPreview, nf: NumberFormat) {
+    let boss = &preview.boss;
+    println!("=== Boss {} ({} fights) ===", preview.stage, preview.fights);
+    println!(
+        "Boss: {} HP, {} power, {:.2} regen, {:.2}% DR, {:.3}s speed",
+        nf.format(boss.hp, 2),
+        nf.format(boss.power, 2),
+        boss.regen,
+        boss.damage_reduction * 100.0,
+        boss.speed
+    );
+    println!("Won: {}/{} ({:.1}%)", preview.wins, preview.fights, preview.win_rate * 100.0);
+    let times = |label: &str, t: &boss_preview::FightTimes| {
+        println!("{}: avg {:.1}s, p10 {:.1}s, median {:.1}s, p90 {:.1}s", label, t.avg, t.p10, t.median, t.p90);
+    };
+    if preview.wins > 0 {
+        times("Win time", &preview.win_seconds);
+        println!("HP left on a win: {:.1}%", preview.avg_hp_left * 100.0);
+    }
+    if preview.wins < preview.fights {
+        times("Loss time", &preview.loss_seconds);
+        let losses: Vec<String> = preview.losses.iter().map(|(end, n)| format!("{} {}", end.label(), n)).collect();
+        println!("Lost to: {}", losses.join(", "));
+    }
+    println!("Revives used: {:.2} per fight", preview.avg_revives_used);
+    println!("Max enrage reached: {:.1}% of fights", preview.max_enrage_rate * 100.0);
+}
+
+fn print_stage_table(rows: &[StageStats], nf: NumberFormat) {
+    println!(
+        "{:>6} {:>14} {:>12} {:>10} {:>7} {:>16} {:>12} {:>10} {:>6} {:>6} {:>7} {:>7}",
+        "Stage", "Enemy HP", "Power", "Regen", "Speed", "Boss HP", "Power", "Regen", "DR", "Crit", "CritDmg", "Speed"
+    );
+    for row in rows {
+        let (e, b) = (&row.enemy, &row.boss);
+        println!(
+            "{:>6} {:>14} {:>12} {:>10.2} {:>7.3} {:>16} {:>12} {:>10.2} {:>6.3} {:>6.3} {:>7.3} {:>7.3}",
+            row.stage,
+            nf.format(e.hp, 2),
+            nf.format(e.power, 2),
+            e.regen,
+            e.speed,
+            nf.format(b.hp, 2),
+            nf.format(b.power, 2),
+            b.regen,
+            b.damage_reduction,
+            b.special_chance,
+            b.special_damage,
+            b.speed
+        );
+    }
+}
+
+fn print_lints(lints: &[Lint]) {
+    for l in lints {
+        println!("warning: {}: {}", l.path, l.message);
+    }
+    println!("{} warning{}", lints.len(), if lints.len() == 1 { "" } else { "s" });
+}
+
+fn print_calibration(report: &CalibrationReport) {
+    println!("{:<16} {:<28} {:>12} {:>12} {:>9}", "Stat", "Inputs", "Observed", "Simulated", "Error");
+    for d in &report.deviations {
+        let inputs: Vec<String> = d.inputs.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        println!(
+            "{:<16} {:<28} {:>12.4} {:>12.4} {:>+8.2}%  {}",
+            d.stat,
+            inputs.join(","),
+            d.observed,
+            d.simulated,
+            d.relative_error * 100.0,
+            if d.within_tolerance { "ok" } else { "OFF" },
+        );
+    }
+    println!("{} of {} observations out of tolerance", report.failures(), report.deviations.len());
+    
+    if !report.corrections.is_empty() {
+        println!();
+        println!("CORRECTIONS:");
+        for c in &report.corrections {
+            println!("  {:<16} n={:<3} scale x{:.4}", c.stat, c.samples, c.scale);
+            if let Some(ref fit) = c.linear {
+                println!("    observed: {:.4} {:+.4} * {}", fit.observed_intercept, fit.observed_slope, fit.input);
+                println!("    engine:   {:.4} {:+.4} * {}", fit.simulated_intercept, fit.simulated_slope, fit.input);
+            }
+        }
+    }
+}
+
+fn print_scenario_report(report: &ScenarioReport) {
+    for case in &report.cases {
+        println!("{} {}", if case.passed() { "PASS" } else { "FAIL" }, case.name);
+        if case.unused_rolls > 0 {
+            println!("  ({} scripted rolls were never drawn)", case.unused_rolls);
+        }
+        for c in case.checks.iter().filter(|c| !c.passed) {
+            match c.actual {
+                Some(actual) => println!("  {:<20} expected {:.6}, got {:.6}", c.key, c.expected, actual),
+                None => println!("  {:<20} expected {:.6}, but no such value", c.key, c.expected),
+            }
+        }
+    }
+    println!("{} of {} cases failed", report.failures(), report.cases.len());
+}
+
+/// Print invariant violations found by `--validate`
+fn print_validation_report(report: &invariants::ValidationReport) {
+    println!();
+    println!("--- Validation ---");
+    if report.total == 0 {
+        println!("All invariants held");
+        return;
+    }
+    println!("{} invariant violations (showing {})", report.total, report.violations.len());
+    println!("{:<22} {:>8} {:>6} {:>10}  Detail", "Invariant", "Seed", "Stage", "Time");
+    for v in &report.violations {
+        let seed = v.seed.map_or("-".to_string(), |s| s.to_string());
+        println!("{:<22} {:>8} {:>6} {:>10.3}  {}", v.invariant, seed, v.stage, v.time, v.detail);
+    }
+}
+
+/// Whether errors are reported as JSON (`--output json`)
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Print an error the way the output format asks: "Error: ..." on stderr, or
+/// under `--output json` an `{"error": {code, message, field}}` object on
+/// stdout, where a script reading the results will find it
+fn report_error(report: &ErrorReport) {
+    if JSON_ERRORS.load(AtomicOrdering::Relaxed) {
+        let value = serde_json::json!({ "error": report });
+        println!("{}", serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()));
+    } else {
+        eprintln!("Error: {}", report.message);
+    }
+}
+
+/// Report an error and exit 1
+fn fail(error: impl Into<ErrorReport>) -> ! {
+    report_error(&error.into());
+    std::process::exit(1);
+}
+
+/// `fail` with what was being done, printed "Error loading config: ..." as text
+fn fail_in(context: &str, error: impl Into<ErrorReport>) -> ! {
+    let report = error.into();
+    if !JSON_ERRORS.load(AtomicOrdering::Relaxed) {
+        eprintln!("Error {}: {}", context, report.message);
+        std::process::exit(1);
+    }
+    fail(report.context(context))
+}
+
+/// Install the tracing subscriber used for engine diagnostics
+fn init_logging(args: &Args) {
+    use tracing_subscriber::EnvFilter;
+    
+    let filter = if args.debug_trace {
+        EnvFilter::new("trace")
+    } else if let Some(ref level) = args.log_level {
+        EnvFilter::new(level)
+    } else {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"))
+    };
+    
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    if args.log_json {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+}
+
+/// Print single vs batched RNG throughput
+fn print_rng_bench(runs: &[simulation::RngBench]) {
+    println!("{:<8} {:>8} {:>10} {:>12} {:>10}", "RNG", "Sims", "Time (s)", "Sims/sec", "Avg Stage");
+    for r in runs {
+        let mode = if r.batched { "batched" } else { "single" };
+        println!("{:<8} {:>8} {:>10.3} {:>12.1} {:>10.2}", mode, r.sims, r.seconds, r.sims_per_sec, r.avg_stage);
+    }
+    if let [single, batched] = runs {
+        println!("Speedup: {:.3}x", batched.sims_per_sec / single.sims_per_sec);
+        if single.avg_stage != batched.avg_stage || single.avg_loot != batched.avg_loot {
+            println!("WARNING: batched results differ from single-draw results");
+        }
+    }
+}
+
+fn print_stability_report(report: &StabilityReport) {
+    println!("--- Batch Stability ({} batches of {} sims) ---", report.batches, report.sims);
+    println!("{:<20} {:>18} {:>16} {:>9} {:>18}", "Metric", "Mean", "Std Dev", "Spread", "Sims for target");
+    for m in &report.metrics {
+        let needed = m.sims_for_target.map_or("-".to_string(), |n| n.to_string());
+        println!("{:<20} {:>18.4} {:>16.4} {:>8.3}% {:>18}", m.metric, m.mean, m.std_dev, m.relative_spread * 100.0, needed);
+    }
+    println!(
+        "For a {:.2}% spread on every metric, use -n {} (now {})",
+        report.target_spread * 100.0,
+        report.recommended_sims,
+        report.sims
+    );
+}
+
+/// Print a screening batch summary
+fn print_screen_report(report: &ScreenReport) {
+    let backend = match &report.backend {
+        Backend::Cpu => "CPU".to_string(),
+        Backend::Gpu { adapter } => format!("GPU ({})", adapter),
+    };
+    println!("--- Screening ({} runs, simplified engine) ---", report.runs);
+    println!("Backend:    {}", backend);
+    if let Some(ref reason) = report.fallback_reason {
+        println!("Fallback:   {}", reason);
+    }
+    println!("Avg Stage:  {:.2}", report.avg_stage);
+    println!("Range:      {} - {}", report.min_stage, report.max_stage);
+    println!("Time:       {:.3}s ({:.0} runs/sec)", report.seconds, report.runs as f64 / report.seconds.max(f64::EPSILON));
+}
+
+/// Print the per-phase profiling table
+fn print_profile_report(report: &profiling::ProfileReport) {
+    println!();
+    println!("--- Profile ({} sims) ---", report.simulations);
+    println!("{:<20} {:>12} {:>12} {:>10} {:>7}", "Phase", "Calls", "Total (ms)", "Avg (ns)", "Share");
+    for p in &report.phases {
+        println!("{:<20} {:>12} {:>12.2} {:>10.1} {:>6.1}%", p.phase, p.calls, p.total_ms, p.avg_ns, p.share * 100.0);
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    JSON_ERRORS.store(matches!(args.output, OutputFormat::Json), AtomicOrdering::Relaxed);
+    init_logging(&args);
+    if matches!(args.output, OutputFormat::Csv)
+        && !matches!(args.command, Some(Command::Enemies { .. } | Command::Replay { dps_curve: Some(_), .. }))
+    {
+        fail(ErrorReport::new("unsupported_output", "--output csv is only supported by `enemies` and `replay --dps-curve`"));
+    }
+    
+    // The preset first, then the individual switches on top
+    args.fidelity.apply();
+    if args.python_ties {
+        simulation::set_python_ties(true);
+    }
+    simulation::set_max_sim_time(args.max_sim_time);
+    simulation::set_watchdog(args.watchdog);
+    if let Some(seconds) = args.regen_tick {
+        let ms = (seconds * 1000.0).round();
+        if !(1.0..=10_000.0).contains(&ms) || (seconds * 1000.0 - ms).abs() > 1e-6 {
+            fail(ErrorReport::new("config_invalid", format!("--regen-tick {} must be 0.001 to 10 seconds in whole milliseconds", seconds)));
+        }
+        simulation::set_regen_tick(Some(ms as u64));
+    }
+    if let Some(ref path) = args.boss_scripts {
+        if let Err(e) = boss_script::from_file(path).and_then(boss_script::install) {
+            fail_in("loading boss scripts", e);
+        }
+    }
+    
+    if let Some(ref command) = args.command {
+        match command {
+            Command::Stats { config } => {
+                let stats = HunterStats::from_config(&load_config_or_exit(config));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_hunter_stats(&stats, args.number_format),
+                    OutputFormat::Json => print_json(&stats),
+                }
+            }
+            Command::ExportCode { config } => {
+                match share_code::encode(&load_config_or_exit(config)) {
+                    Ok(code) => println!("{}", code),
+                    Err(e) => fail(e),
+                }
+            }
+            Command::ImportCode { code, write } => {
+                let config = match share_code::decode(code) {
+                    Ok(c) => c,
+                    Err(e) => fail(e),
+                };
+                emit_config(&config, write.as_deref(), &args.output);
+            }
+            Command::Serve { addr, allow_origins } => {
+                let api = server::bind(addr)
+                    .unwrap_or_else(|e| fail(ErrorReport::from(e).context(&format!("failed to listen on {}", addr))))
+                    .allow_origins(allow_origins.clone());
+                match api.local_addr() {
+                    Some(local) => eprintln!("Serving on http://{}", local),
+                    None => eprintln!("Serving on {}", addr),
+                }
+                api.run();
+            }
+            Command::ImportGame { export, write } => {
+                let text = if export.as_os_str() == "-" {
+                    let mut buf = String::new();
+                    std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf).map(|_| buf)
+                } else {
+                    std::fs::read_to_string(export)
+                };
+                let text = text.unwrap_or_else(|e| fail_in(&format!("reading {}", export.display()), e));
+                let report = match game_import::import_game_export(&text) {
+                    Ok(r) => r,
+                    Err(e) => fail(e),
+                };
+                eprintln!("Mapped {} fields, {} unmapped", report.mapped.len(), report.unmapped.len());
+                for field in &report.unmapped {
+                    eprintln!("  unmapped: {}", field);
+                }
+                emit_config(&report.config, write.as_deref(), &args.output);
+            }
+            Command::SaveBuild { name, config, tags, notes } => {
+                let library = open_library_or_exit();
+                let config = load_config_or_exit(config);
+                match library.save(name, config, tags.clone(), notes.clone()) {
+                    Ok(saved) => eprintln!("Saved {:?} build `{}` to {}", saved.hunter_type(), saved.name, library.dir().display()),
+                    Err(e) => fail(e),
+                }
+            }
+            Command::ListBuilds { tag } => {
+                let library = open_library_or_exit();
+                let builds = library.list().unwrap_or_else(|e| fail(e));
+                let builds: Vec<_> = builds.into_iter()
+                    .filter(|b| tag.as_ref().is_none_or(|t| b.tags.contains(t)))
+                    .collect();
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => {
+                        println!("{:<24} {:<6} {:>5}  {:<24} Notes", "Name", "Hunter", "Level", "Tags");
+                        for b in &builds {
+                            println!("{:<24} {:<6} {:>5}  {:<24} {}", b.name, format!("{:?}", b.hunter_type()), b.config.get_level(), b.tags.join(","), b.notes);
+                        }
+                    }
+                    OutputFormat::Json => print_json(&builds),
+                }
+            }
+            Command::Init { path, force } => init_build(path.as_deref(), *force),
+            Command::Examples { name: None, .. } => {
+                println!("{:<16} {:<6} Description", "Name", "Hunter");
+                for e in examples::EXAMPLES {
+                    println!("{:<16} {:<6} {}", e.name, format!("{:?}", e.hunter), e.description);
+                }
+            }
+            Command::Examples { name: Some(name), write } => {
+                let Some(example) = examples::find(name) else {
+                    fail(ErrorReport::new("unknown_example", format!("no example named `{}` (run `hunter-sim examples` to list them)", name)));
+                };
+                match write {
+                    // Keep the original file's comments and key order
+                    Some(path) => {
+                        if let Err(e) = std::fs::write(path, example.yaml) {
+                            fail_in(&format!("writing {}", path.display()), e);
+                        }
+                        eprintln!("Wrote {}", path.display());
+                    }
+                    None => print!("{}", example.yaml),
+                }
+            }
+            Command::SimulateAccount { account, seed } => {
+                let account = Account::from_file(account).unwrap_or_else(|e| fail_in("loading account", e));
+                let report = simulate_account(&account, args.num_sims, *seed).unwrap_or_else(|e| fail(e));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_account_report(&report, args.number_format),
+                    OutputFormat::Json => print_json(&report),
+                }
+            }
+            Command::Idle { config, active_hours, offline_rate, offline_xp_rate, offline_cap, collections } => {
+                let model = IdleModel {
+                    active_hours: *active_hours,
+                    offline_rate: *offline_rate,
+                    offline_xp_rate: offline_xp_rate.unwrap_or(*offline_rate),
+                    offline_cap_hours: *offline_cap,
+                    collections: *collections,
+                };
+                if let Err(e) = model.validate() {
+                    fail(e);
+                }
+                let config = load_config_or_exit(config);
+                let stats = try_run_and_aggregate(&config, args.num_sims, args.parallel).unwrap_or_else(|e| fail(e));
+                let report = model.report(&stats);
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_idle_report(&report, args.number_format),
+                    OutputFormat::Json => print_json(&report),
+                }
+            }
+            Command::Explain { what, config } => {
+                let stats = HunterStats::from_config(&load_config_or_exit(config));
+                let (title, breakdown) = match what {
+                    Explained::Loot => ("LOOT", &stats.loot_breakdown),
+                    Explained::Xp => ("XP", &stats.xp_breakdown),
+                };
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_breakdown(title, &stats, breakdown, args.number_format),
+                    OutputFormat::Json => print_json(breakdown),
+                }
+            }
+            Command::Lint { config } => {
+                let lints = lint::lint(&load_config_or_exit(config));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_lints(&lints),
+                    OutputFormat::Json => print_json(&lints),
+                }
+            }
+            Command::Calibrate { config, observations, fit } => {
+                let config = load_config_or_exit(config);
+                let report = ObservationSet::from_file(observations)
+                    .and_then(|set| calibration::calibrate(&config, &set, *fit))
+                    .unwrap_or_else(|e| fail(e));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_calibration(&report),
+                    OutputFormat::Json => print_json(&report),
+                }
+                if report.failures() > 0 {
                     std::process::exit(1);
                 }
             }
-        } else {
-            match BuildConfig::from_file(&args.configs) {
-                Ok(c) => vec![c],
-                Err(e) => {
-                    eprintln!("Error loading config: {}", e);
+            Command::Scenario { file } => {
+                let report = ScenarioFile::from_file(file).and_then(|f| scenario::run_file(&f)).unwrap_or_else(|e| fail(e));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_scenario_report(&report),
+                    OutputFormat::Json => print_json(&report),
+                }
+                if report.failures() > 0 {
                     std::process::exit(1);
                 }
             }
+            Command::Bench { config } => {
+                let config = load_config_or_exit(config);
+                if let Err(e) = validate_config(&config) {
+                    fail(e);
+                }
+                let runs = simulation::bench_rng(&config, args.num_sims.max(1), args.parallel);
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_rng_bench(&runs),
+                    OutputFormat::Json => print_json(&runs),
+                }
+            }
+            Command::Stability { config, batches, target } => {
+                let config = with_start(&args, load_config_or_exit(config));
+                if let Err(e) = validate_config(&config) {
+                    fail(e);
+                }
+                if *batches < 2 {
+                    fail(ErrorReport::new("config_invalid", "--batches must be at least 2 to compare batches"));
+                }
+                if target.is_nan() || *target <= 0.0 {
+                    fail(ErrorReport::new("config_invalid", "--target must be a positive spread, such as 0.01"));
+                }
+                let report = stability::measure(&config, args.num_sims.max(1), *batches, *target, args.parallel);
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_stability_report(&report),
+                    OutputFormat::Json => print_json(&report),
+                }
+            }
+            Command::Screen { config, cpu } => {
+                let config = load_config_or_exit(config);
+                if let Err(e) = validate_config(&config) {
+                    fail(e);
+                }
+                let report = screening::screen(&config, args.num_sims.max(1), !*cpu);
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_screen_report(&report),
+                    OutputFormat::Json => print_json(&report),
+                }
+            }
+            Command::Snapshot { config, stage, seed, write } => {
+                let config = with_start(&args, load_config_or_exit(config));
+                if let Err(e) = validate_config(&config) {
+                    fail(e);
+                }
+                let Some(snapshot) = Snapshot::take(&config, *seed, *stage) else {
+                    fail(ErrorReport::new("snapshot_stage", format!("seed {} ends before stage {}", seed, stage)));
+                };
+                let hunter = snapshot.hunter();
+                eprintln!(
+                    "Stage {} reached at {}s with {:.2}/{:.2} HP and {} revives used",
+                    stage, snapshot.elapsed_time(), hunter.hp, hunter.max_hp, hunter.revive_count
+                );
+                let json = snapshot.to_json().unwrap_or_else(|e| fail(e));
+                match write {
+                    Some(path) => {
+                        if let Err(e) = std::fs::write(path, json) {
+                            fail(ErrorReport::from(e).context(&format!("failed to write {}", path.display())));
+                        }
+                    }
+                    None => println!("{}", json),
+                }
+            }
+            Command::Replay { snapshot, audit_hits, dps_curve } => {
+                let snapshot = std::fs::read_to_string(snapshot)
+                    .map_err(|e| ErrorReport::from(e).context(&format!("failed to read {}", snapshot.display())))
+                    .and_then(|json| Snapshot::from_json(&json).map_err(ErrorReport::from))
+                    .unwrap_or_else(|e| fail(e));
+                if let Some(window) = *dps_curve {
+                    if window.is_nan() || window <= 0.0 {
+                        fail(ErrorReport::new("config_invalid", "--dps-curve must be a positive number of seconds"));
+                    }
+                    let (result, curve) = snapshot.damage_curve(window);
+                    match args.output {
+                        OutputFormat::Text => print_damage_curve(&snapshot, &result, &curve, window, args.number_format),
+                        OutputFormat::Csv => {
+                            println!("start,end,stage,boss,attacks,damage,dps");
+                            for w in &curve {
+                                println!("{},{},{},{},{},{},{}", w.start, w.end, w.stage, w.boss, w.attacks, w.damage, w.dps);
+                            }
+                        }
+                        OutputFormat::Json => print_json(&serde_json::json!({
+                            "seed": snapshot.seed,
+                            "stage": snapshot.stage,
+                            "window": window,
+                            "final_stage": result.final_stage,
+                            "end": result.end,
+                            "windows": curve,
+                        })),
+                    }
+                    return;
+                }
+                match audit_hits {
+                    Some(limit) => {
+                        let (result, mut hits) = snapshot.audit_hits();
+                        let total = hits.len();
+                        hits.truncate(*limit);
+                        match args.output {
+                            OutputFormat::Text | OutputFormat::Csv => print_hit_audit(&snapshot, &result, &hits, total, args.number_format),
+                            OutputFormat::Json => print_json(&serde_json::json!({
+                                "seed": snapshot.seed,
+                                "stage": snapshot.stage,
+                                "total_hits": total,
+                                "final_stage": result.final_stage,
+                                "end": result.end,
+                                "hits": hits,
+                            })),
+                        }
+                    }
+                    None => run_configs(&args, vec![snapshot.config.clone()], Some(&snapshot)),
+                }
+            }
+            Command::Run { name } => {
+                run_configs(&args, vec![with_start(&args, load_config_or_exit(name))], None);
+            }
+            Command::BossPreview { config, stage } => {
+                let config = with_start(&args, load_config_or_exit(config));
+                if let Err(e) = validate_config(&config) {
+                    fail(e);
+                }
+                if *stage <= 0 || stage % 100 != 0 {
+                    fail(ErrorReport::new("boss_stage", format!("stage {} has no boss; bosses are on every 100th stage", stage)));
+                }
+                let preview = boss_preview::preview(&config, *stage, args.num_sims.max(1), args.parallel);
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_boss_preview(&preview, args.number_format),
+                    OutputFormat::Json => print_json(&preview),
+                }
+            }
+            Command::Enemies { hunter, from, to, step } => {
+                let to = to.unwrap_or_else(|| rust_sim::game_data::max_stage(*hunter));
+                if *from < 1 || to < *from || *step == 0 {
+                    fail(ErrorReport::new("stage_range", format!("no stages from {} to {} in steps of {}", from, to, step)));
+                }
+                let rows = StageStats::range(*hunter, *from, to, *step);
+                match args.output {
+                    OutputFormat::Text => print_stage_table(&rows, args.number_format),
+                    OutputFormat::Json => print_json(&rows),
+                    OutputFormat::Csv => {
+                        println!("{}", StageStats::csv_header());
+                        for row in &rows {
+                            println!("{}", row.csv_row());
+                        }
+                    }
+                }
+            }
+            Command::Introspect => {
+                print_json(&Capabilities::new(value_names::<OutputFormat>(), value_names::<NumberFormat>()));
+            }
+            Command::BossScripts => {
+                let scripts = boss_script::scripts();
+                match args.output {
+                    OutputFormat::Json => print_json(&scripts),
+                    OutputFormat::Text | OutputFormat::Csv => match serde_yaml::to_string(&scripts) {
+                        Ok(text) => print!("{}", text),
+                        Err(e) => fail(ErrorReport::new("serialize", e.to_string())),
+                    },
+                }
+            }
+            Command::Diff { before, after } => {
+                let (old_cfg, new_cfg) = (load_config_or_exit(before), load_config_or_exit(after));
+                let changes = old_cfg.diff_inputs(&new_cfg);
+                let deltas = HunterStats::from_config(&old_cfg).compare(&HunterStats::from_config(&new_cfg));
+                match args.output {
+                    OutputFormat::Text | OutputFormat::Csv => print_diff(&changes, &deltas, args.number_format),
+                    OutputFormat::Json => print_json(&serde_json::json!({
+                        "changed_inputs": changes,
+                        "stat_deltas": deltas,
+                    })),
+                }
+            }
         }
+        return;
+    }
+    let Some(ref configs_path) = args.configs else {
+        report_error(&ErrorReport::new(SimError::MissingConfig.code(), "--configs is required"));
+        std::process::exit(2);
     };
 
-    // Debug: print computed hunter stats
-    if args.debug_stats {
-        let hunter = Hunter::from_config(&configs[0]);
-        println!("============================================================");
-        println!("RUST {:?} STATS", hunter.hunter_type);
-        println!("============================================================");
-        println!("Max HP:        {:.2}", hunter.max_hp);
-        println!("Power:         {:.4}", hunter.power);
-        println!("Regen:         {:.4}", hunter.regen);
-        println!("DR:            {:.4} ({:.2}%)", hunter.damage_reduction, hunter.damage_reduction * 100.0);
-        println!("Evade:         {:.4} ({:.2}%)", hunter.evade_chance, hunter.evade_chance * 100.0);
-        println!("Effect:        {:.4} ({:.2}%)", hunter.effect_chance, hunter.effect_chance * 100.0);
-        println!("Special Chance:{:.4} ({:.2}%)", hunter.special_chance, hunter.special_chance * 100.0);
-        println!("Special Damage:{:.4}", hunter.special_damage);
-        println!("Speed:         {:.4}", hunter.speed);
-        println!("Lifesteal:     {:.4} ({:.2}%)", hunter.lifesteal, hunter.lifesteal * 100.0);
-        println!("Loot Mult:     {:.4}", hunter.loot_mult);
-        println!("XP Mult:       {:.4}", hunter.xp_mult);
+    // Load configs
+    let configs: Vec<BuildConfig> = match BuildConfig::load_all(configs_path) {
+        Ok(c) if !c.is_empty() => c,
+        Ok(_) => fail_in("loading config", ErrorReport::new(SimError::MissingConfig.code(), "file contains no builds")),
+        Err(e) => fail_in("loading config", e),
+    };
+    let configs = configs.into_iter().map(|c| with_start(&args, c)).collect();
+    run_configs(&args, configs, None);
+}
+
+/// Simulations `range` of every config's batch, continuing each config's
+/// stream in `streams` without -p, so the batch can be run in pieces
+fn run_batch_range(configs: &[BuildConfig], range: std::ops::Range<usize>, parallel: bool, streams: &mut [FastRng]) -> Vec<Vec<SimResult>> {
+    configs
+        .par_iter()
+        .zip(streams.par_iter_mut())
+        .map(|(config, rng)| if parallel {
+            run_seeds_parallel(config, range.clone())
+        } else {
+            run_simulations_with_stream(config, range.len(), rng)
+        })
+        .collect()
+}
+
+/// Report a long batch's estimated runtime from its calibration runs, and
+/// unless --yes, ask whether to go on when it's over --confirm-above (only
+/// when someone is at the terminal to answer)
+fn preflight(args: &Args, calibration: &Calibration) {
+    use std::io::IsTerminal;
+    
+    let total = calibration.total_seconds();
+    if total < plan::PREFLIGHT_NOTICE_SECS {
+        return;
+    }
+    eprintln!(
+        "Estimated runtime: {} ({:.0} sims/sec over {} calibration runs, which count towards the results)",
+        format_duration(total),
+        calibration.sims_per_sec(),
+        calibration.sims,
+    );
+    if args.yes || total <= args.confirm_above || !std::io::stdin().is_terminal() {
+        return;
+    }
+    let question = format!(
+        "Run the remaining {} simulations (about {})?",
+        calibration.batch_sims - calibration.sims,
+        format_duration(calibration.remaining_seconds()),
+    );
+    let answer = prompt(&question, "y");
+    if !answer.eq_ignore_ascii_case("y") && !answer.eq_ignore_ascii_case("yes") {
+        eprintln!("Cancelled");
+        std::process::exit(0);
+    }
+}
+
+/// Seconds as "42.0s", "12m 30s" or "3h 05m"
+fn format_duration(seconds: f64) -> String {
+    let whole = seconds.round() as u64;
+    match whole {
+        0..60 => format!("{:.1}s", seconds),
+        60..3600 => format!("{}m {:02}s", whole / 60, whole % 60),
+        _ => format!("{}h {:02}m", whole / 3600, whole % 3600 / 60),
+    }
+}
+
+/// `--dry-run`: what the batch would do, with a short timed probe for its runtime
+fn print_dry_run(args: &Args, configs: &[BuildConfig], snapshot: Option<&Snapshot>) {
+    let pool = rayon::current_num_threads();
+    let threads = if args.parallel { pool } else { 1 };
+    let seeds = if args.parallel || snapshot.is_some() || args.checkpoint.is_some() || args.resume.is_some() {
+        SeedPlan::Indexed { count: args.num_sims }
+    } else {
+        SeedPlan::Random
+    };
+    let start_stage = |config: &BuildConfig| snapshot.map_or(config.start.stage.unwrap_or(1), |s| s.stage);
+    let plans: Vec<RunPlan> = configs
+        .iter()
+        .map(|config| RunPlan::new(config, start_stage(config), args.num_sims, seeds, threads, |n| match snapshot {
+            Some(snapshot) => snapshot.replay(n, false),
+            None => (0..n).map(|i| run_simulation_with_seed(config, i as u64)).collect(),
+        }))
+        .collect();
+    // Configs run side by side, so without -p they still share the pool
+    let cpu_seconds: f64 = plans.iter().map(|p| p.sims as f64 / p.estimate.sims_per_sec).sum();
+    let workers = if args.parallel { pool } else { pool.min(configs.len()) };
+    let total_seconds = cpu_seconds / workers.min(args.num_sims * configs.len()).max(1) as f64;
+
+    if matches!(args.output, OutputFormat::Json) {
+        let plans: Vec<serde_json::Value> = plans
+            .iter()
+            .map(|plan| {
+                let mut value = serde_json::to_value(plan).unwrap_or_else(|e| fail_in("serializing plan", SimError::from(e)));
+                value["config"] = config_value(&plan.config);
+                value
+            })
+            .collect();
+        print_json(&serde_json::json!({ "plans": plans, "estimated_seconds": total_seconds }));
+        return;
+    }
+
+    let nf = args.number_format;
+    for (i, plan) in plans.iter().enumerate() {
+        println!("=== Dry Run: config {} of {} ===", i + 1, plans.len());
+        println!("--- Resolved config ---");
+        match serde_yaml::to_string(&config_value(&plan.config)) {
+            Ok(text) => print!("{}", text),
+            Err(e) => println!("(unprintable: {})", e),
+        }
         println!();
-        println!("KNOX-SPECIFIC:");
-        println!("Charge Chance: {:.4} ({:.2}%)", hunter.charge_chance, hunter.charge_chance * 100.0);
-        println!("Charge Gained: {:.4}", hunter.charge_gained);
-        println!("Salvo:         {}", hunter.salvo_projectiles);
+        print_hunter_stats(&plan.stats, nf);
         println!();
-        println!("BORGE-SPECIFIC:");
-        println!("Minotaur DR:   {:.4} ({:.2}%)", hunter.minotaur_dr, hunter.minotaur_dr * 100.0);
-        println!("Soul of Hermes:{}", hunter.soul_of_hermes);
-        println!("Atlas Protocol:{}", hunter.atlas_protocol);
-        println!("Impeccable Impacts: {}", hunter.impeccable_impacts);
+        println!("--- Plan ---");
+        let stages = &plan.stages;
+        let cap_source = if stages.max_stage == stages.max_stage_default { "game data" } else { "build override" };
+        println!("Stages: {} - {} (max stage from {})", stages.start_stage, stages.max_stage, cap_source);
+        let bosses: Vec<String> = stages.boss_stages.iter().map(|s| s.to_string()).collect();
+        println!("Bosses: {}", if bosses.is_empty() { "none".to_string() } else { bosses.join(", ") });
+        if stages.events.is_empty() {
+            println!("Events: none");
+        }
+        for event in &stages.events {
+            let end = event.duration.map_or("the end".to_string(), |d| format!("{}s", event.start + d));
+            let name = if event.name.is_empty() { "event" } else { &event.name };
+            println!("Event: {} (loot x{}, XP x{}) from {}s to {}", name, event.loot, event.xp, event.start, end);
+        }
+        let carried = &plan.config.persistence;
+        if carried.any() {
+            let mechanics: Vec<&str> = [("hundred souls", carried.hundred_souls), ("trickster charges", carried.trickster_charges)]
+                .into_iter()
+                .filter_map(|(name, on)| on.then_some(name))
+                .collect();
+            println!("Carry-over: {} from {} warm-up runs per simulation", mechanics.join(", "), carried.warmup_runs());
+        }
+        let flags: Vec<String> = plan.config.engine_flags.iter().map(|(name, on)| format!("{}={}", name, on)).collect();
+        if !flags.is_empty() {
+            println!("Engine Flags: {}", flags.join(", "));
+        }
+        let stacking = &plan.config.stacking;
+        if !stacking.is_default() {
+            let cap = stacking.empowered_regen_cap.map_or("no cap".to_string(), |c| format!("cap {} ticks", c));
+            println!("Empowered Regen Stacking: {}, {}", format!("{:?}", stacking.empowered_regen.unwrap_or_default()).to_lowercase(), cap);
+        }
+        if plan.regen_tick != 1.0 {
+            println!("Regen Tick: every {}s", plan.regen_tick);
+        }
+        let t = &plan.termination;
+        println!(
+            "Termination: death, max stage, stage limit {}; hopeless runs end early: {}; max simulated time: {}; watchdog: {}",
+            t.stage_limit,
+            if t.early_termination { "yes" } else { "no" },
+            t.max_sim_time.map_or("none".to_string(), |s| format!("{}s", s)),
+            t.watchdog_ms.map_or("off".to_string(), |ms| format!("{}ms", ms)),
+        );
+        match plan.seeds {
+            SeedPlan::Indexed { count } => println!("Seeds: 0..{} (simulation i runs with seed i)", count),
+            SeedPlan::Random => println!("Seeds: one random stream shared by the batch (use -p for seeds 0..n)"),
+        }
+        println!("Simulations: {} on {} thread{}", plan.sims, plan.estimate.threads, if plan.estimate.threads == 1 { "" } else { "s" });
+        println!(
+            "Estimated Runtime: {} ({:.0} sims/sec per thread over {} probe runs)",
+            format_duration(plan.estimate.seconds),
+            plan.estimate.sims_per_sec,
+            plan.estimate.probe_sims,
+        );
         println!();
+    }
+    if plans.len() > 1 {
+        println!("Estimated Total Runtime: {}", format_duration(total_seconds));
+    }
+}
+
+/// Simulate every config and print the results (the default mode and `run`)
+/// Simulate and report `configs`, or replay `snapshot` (whose config is then
+/// the only one) when given
+fn run_configs(args: &Args, configs: Vec<BuildConfig>, snapshot: Option<&Snapshot>) {
+    if args.num_sims == 0 {
+        fail(SimError::NoSimulations);
+    }
+    for config in &configs {
+        if let Err(e) = validate_config(config) {
+            fail(e);
+        }
+        for l in lint::over_budget(config) {
+            eprintln!("Warning: {}: {}; results won't match a real hunter", l.path, l.message);
+        }
+        let ignored = lint::ignored_items(config);
+        if !ignored.is_empty() {
+            eprintln!("Warning: the simulator ignores some of this build's relics and gadgets:");
+            for l in ignored {
+                eprintln!("  {}: {}", l.path, l.message);
+            }
+        }
+    }
+
+    let xp_curve = match args.xp_curve.as_ref().map(XpCurve::from_file).transpose() {
+        Ok(curve) => curve,
+        Err(e) => fail_in("loading XP curve", e),
+    };
+    
+    // Debug: print computed hunter stats
+    if args.debug_stats {
+        print_hunter_stats(&HunterStats::from_config(&configs[0]), args.number_format);
         return;
     }
 
@@ -130,7 +1689,7 @@ fn main() {
         println!("============================================================");
         
         // Regular enemy
-        let enemy = Enemy::new(0, stage, hunter_type);
+        let enemy = Enemy::new(1, stage, hunter_type);
         println!("\nREGULAR ENEMY:");
         println!("  HP:      {:.2}", enemy.max_hp);
         println!("  Power:   {:.4}", enemy.power);
@@ -139,6 +1698,11 @@ fn main() {
         println!("  SpecC:   {:.4} ({:.2}%)", enemy.special_chance, enemy.special_chance * 100.0);
         println!("  SpecD:   {:.4}", enemy.special_damage);
         println!("  Speed:   {:.4}", enemy.speed);
+        if enemy.ability != EnemyAbility::None {
+            println!("  EffectC: {:.4} ({:.2}%)", enemy.effect_chance, enemy.effect_chance * 100.0);
+            let provisional = rust_sim::game_data::enemy_ability(hunter_type, stage).is_some_and(|r| !r.verified);
+            println!("  Ability: {:?}{} (engine_flags.enemy_abilities)", enemy.ability, if provisional { ", provisional" } else { "" });
+        }
         
         // Boss
         let boss = Enemy::new_boss(stage, hunter_type);
@@ -151,49 +1715,406 @@ fn main() {
         println!("  SpecD:   {:.4}", boss.special_damage);
         println!("  Speed:   {:.4}", boss.speed);
         println!("  Speed2:  {:.4}", boss.speed2);
-        println!("  Secondary: {:?}", boss.secondary_type);
+        match boss.secondary {
+            Some(script) => println!("  Secondary: {}{}", script.name, if script.verified { "" } else { " (provisional)" }),
+            None => println!("  Secondary: None"),
+        }
         println!();
         return;
     }
 
+    if args.dry_run {
+        print_dry_run(args, &configs, snapshot);
+        return;
+    }
+
+    if args.profile {
+        profiling::reset();
+        profiling::set_enabled(true);
+    }
+    if args.validate {
+        invariants::reset();
+        invariants::set_enabled(true);
+    }
+    simulation::set_tie_audit(args.audit_ties);
+    
+    let filter = args.filter.as_deref().map(|expr| RunFilter::parse(expr).unwrap_or_else(|e| fail(e)));
+    let objective = args.objective.as_deref().map(|expr| Objective::parse(expr).unwrap_or_else(|e| fail(e)));
+    
     // Run simulations
     let start = Instant::now();
-    let stats_vec: Vec<AggregatedStats> = configs.par_iter().map(|config| run_and_aggregate(config, args.num_sims, args.parallel)).collect();
+    let results: Vec<Vec<SimResult>> = match (snapshot, args.checkpoint.as_ref().or(args.resume.as_ref())) {
+        (Some(snapshot), _) => vec![snapshot.replay(args.num_sims, args.parallel)],
+        (None, Some(path)) => {
+            let resumed = args.resume.as_ref().map(|path| Checkpoint::load(path).unwrap_or_else(|e| fail(e)));
+            if let Some(ref checkpoint) = resumed {
+                eprintln!("Resuming {}: {} simulations already done", path.display(), checkpoint.completed());
+            }
+            checkpoint::run_checkpointed(&configs, args.num_sims, args.parallel, path, args.checkpoint_every, resumed).unwrap_or_else(|e| fail(e))
+        }
+        (None, None) => {
+            let mut streams: Vec<FastRng> = configs.iter().map(|_| FastRng::new(rand::random::<u64>())).collect();
+            let mut results = vec![Vec::new(); configs.len()];
+            let mut done = 0;
+            if args.num_sims > plan::CALIBRATION_SIMS {
+                done = plan::CALIBRATION_SIMS;
+                results = run_batch_range(&configs, 0..done, args.parallel, &mut streams);
+                let calibration = Calibration {
+                    sims: done * configs.len(),
+                    seconds: start.elapsed().as_secs_f64(),
+                    batch_sims: args.num_sims * configs.len(),
+                };
+                preflight(args, &calibration);
+            }
+            let rest = run_batch_range(&configs, done..args.num_sims, args.parallel, &mut streams);
+            for (runs, more) in results.iter_mut().zip(rest) {
+                runs.extend(more);
+            }
+            results
+        }
+    };
     let elapsed = start.elapsed();
+    
+    let stats_vec: Vec<AggregatedStats> = match &filter {
+        Some(filter) => results
+            .iter()
+            .map(|runs| {
+                let matching = filter.apply(runs);
+                if matching.is_empty() {
+                    let message = format!("matched none of the {} runs", runs.len());
+                    fail(SimError::Filter { expr: filter.expr().to_string(), message });
+                }
+                AggregatedStats::from_results(&matching)
+            })
+            .collect(),
+        None => results.iter().map(|runs| AggregatedStats::from_results(runs)).collect(),
+    };
+    
+    // Level-up estimates, one per config
+    let level_etas: Vec<Vec<LevelEta>> = match &xp_curve {
+        Some(curve) => match configs
+            .iter()
+            .zip(&stats_vec)
+            .map(|(config, stats)| {
+                let level = config.get_level();
+                let mut targets = vec![level + 1];
+                targets.extend(args.target_level.filter(|&t| t > level + 1));
+                targets.into_iter().map(|t| curve.eta(level, t, stats.avg_xp_per_hour)).collect()
+            })
+            .collect()
+        {
+            Ok(etas) => etas,
+            Err(e) => fail(e),
+        },
+        None => vec![Vec::new(); configs.len()],
+    };
 
+    let ranking = objective.as_ref().map(|o| o.rank(&stats_vec));
+    
     // Output results
     match args.output {
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Csv => {
             if configs.len() > 1 {
                 println!("=== Hunter Simulation Results ({} configs) ===", configs.len());
                 println!("Total Simulations: {}", args.num_sims * configs.len());
                 println!("Total Time: {:.3}s", elapsed.as_secs_f64());
                 println!("Simulations/sec: {:.0}", (args.num_sims * configs.len()) as f64 / elapsed.as_secs_f64());
+                if let (Some(objective), Some(ranking)) = (&objective, &ranking) {
+                    println!();
+                    println!("--- Ranking by {} ---", objective.expr());
+                    println!("{:>4} {:>6} {:<6} {:>5} {:>18} {:>10}", "Rank", "Config", "Hunter", "Level", "Score", "Avg Stage");
+                    for (rank, &(i, score)) in ranking.iter().enumerate() {
+                        let config = &configs[i];
+                        println!(
+                            "{:>4} {:>6} {:<6} {:>5} {:>18.4} {:>10.2}",
+                            rank + 1,
+                            i,
+                            format!("{:?}", config.get_hunter_type()),
+                            config.get_level(),
+                            score,
+                            stats_vec[i].avg_stage
+                        );
+                    }
+                }
             } else {
                 let stats = &stats_vec[0];
+                let nf = args.number_format;
+                let hunter_type = configs[0].get_hunter_type();
                 println!("=== Hunter Simulation Results ===");
                 println!("Simulations: {}", args.num_sims);
+                if let Some(ref filter) = filter {
+                    println!("Filter: {} ({} matching runs, {:.1}%)", filter.expr(), stats.runs, stats.runs as f64 / args.num_sims as f64 * 100.0);
+                }
+                if let (Some(objective), Some(ranking)) = (&objective, &ranking) {
+                    println!("Objective: {} = {:.4}", objective.expr(), ranking[0].1);
+                }
                 println!();
                 println!("Average Final Stage: {:.2} ± {:.2}", stats.avg_stage, stats.std_stage);
                 println!("Stage Range: {} - {}", stats.min_stage, stats.max_stage);
+                if stats.timed_out_runs > 0 {
+                    println!(
+                        "Warning: {} of {} runs timed out at {}s of simulated time; they count where they stopped",
+                        stats.timed_out_runs,
+                        stats.runs,
+                        args.max_sim_time.unwrap_or(0),
+                    );
+                }
+                if stats.aborted_runs > 0 {
+                    println!(
+                        "Warning: {} of {} runs were aborted after {}ms by the watchdog; they count where they stopped",
+                        stats.aborted_runs,
+                        stats.runs,
+                        args.watchdog.unwrap_or(0),
+                    );
+                }
+                let wall = &stats.wall;
+                if wall.capped_runs > 0 {
+                    let capped = wall.capped_runs as f64 / stats.runs as f64 * 100.0;
+                    let stage = |p: Option<i32>| p.map_or("beyond".to_string(), |s| s.to_string());
+                    if wall.extrapolated_runs + wall.unbounded_runs > 0 {
+                        println!(
+                            "Predicted Wall: stage {} (p10 {} - p90 {}), {:.1}% of runs hit max stage; stage time +{:.1}%/stage near the cap",
+                            stage(wall.median),
+                            stage(wall.p10),
+                            stage(wall.p90),
+                            capped,
+                            (wall.stage_time_growth - 1.0) * 100.0,
+                        );
+                        if wall.unbounded_runs > 0 {
+                            println!(
+                                "  {} capped runs found no wall within {} stages past the cap",
+                                wall.unbounded_runs,
+                                rust_sim::wall::MAX_EXTRAPOLATION,
+                            );
+                        }
+                    } else {
+                        println!("Predicted Wall: too few stages before the cap to extrapolate ({:.1}% of runs hit max stage)", capped);
+                    }
+                    if capped >= rust_sim::wall::CAP_WARNING_RATE * 100.0 {
+                        println!(
+                            "Warning: {:.1}% of runs stopped at max stage {}; if the game now allows more, raise `max_stage` in the build",
+                            capped,
+                            configs[0].get_max_stage(),
+                        );
+                    }
+                }
                 println!();
                 println!("Average Elapsed Time: {:.2}s", stats.avg_time);
-                println!("Average Total Loot: {:.0}", stats.avg_loot);
+                println!("Average Total Loot: {}", nf.format(stats.avg_loot, 0));
+                println!(
+                    "Loot per Hour: {} (common {} / uncommon {} / rare {})",
+                    nf.format(stats.avg_loot_per_hour, 0),
+                    nf.format(stats.avg_loot_common_per_hour, 0),
+                    nf.format(stats.avg_loot_uncommon_per_hour, 0),
+                    nf.format(stats.avg_loot_rare_per_hour, 0),
+                );
+                println!(
+                    "Loot per Hour Range: p10 {} (a bad session) - p90 {}",
+                    nf.format(stats.p10_loot_per_hour, 0),
+                    nf.format(stats.p90_loot_per_hour, 0),
+                );
+                println!(
+                    "Loot per Stage: {} | over all simulated time: {}/hr",
+                    nf.format(stats.loot_per_stage, 0),
+                    nf.format(stats.loot_per_sim_hour, 0),
+                );
+                println!("Average XP: {} ({}/hr)", nf.format(stats.avg_xp, 0), nf.format(stats.avg_xp_per_hour, 0));
+                if !configs[0].events.is_empty() {
+                    let base_loot = stats.avg_loot_per_hour - stats.avg_event_loot_per_hour;
+                    let base_xp = stats.avg_xp_per_hour - stats.avg_event_xp_per_hour;
+                    let pct = |gain: f64, base: f64| if base > 0.0 { gain / base * 100.0 } else { 0.0 };
+                    println!(
+                        "Event Value: +{} loot/hr (+{:.1}% over {} without events), +{} XP/hr (+{:.1}%)",
+                        nf.format(stats.avg_event_loot_per_hour, 0),
+                        pct(stats.avg_event_loot_per_hour, base_loot),
+                        nf.format(base_loot, 0),
+                        nf.format(stats.avg_event_xp_per_hour, 0),
+                        pct(stats.avg_event_xp_per_hour, base_xp),
+                    );
+                }
+                for eta in &level_etas[0] {
+                    println!("Time to Level {}: {:.1}h ({} XP)", eta.target_level, eta.hours, nf.format(eta.xp_needed, 0));
+                }
                 println!();
                 println!("--- Combat Stats ---");
-                println!("Avg Damage Dealt: {:.0}", stats.avg_damage);
-                println!("Avg Damage Taken: {:.0}", stats.avg_damage_taken);
-                println!("Avg Damage Mitigated: {:.0}", stats.avg_mitigated);
-                println!("Avg Lifesteal: {:.0}", stats.avg_lifesteal);
+                println!("Avg Damage Dealt: {}", nf.format(stats.avg_damage, 0));
+                println!("Avg Damage Taken: {}", nf.format(stats.avg_damage_taken, 0));
+                println!("Avg Damage Mitigated: {} by DR, {} by all layers ({:.2}% effective)", nf.format(stats.avg_mitigated, 0), nf.format(stats.avg_total_mitigated, 0), stats.effective_mitigation * 100.0);
+                println!("Effective HP: {} per fight", nf.format(stats.ehp, 0));
                 println!();
-                println!("Avg Attacks: {:.0}", stats.avg_attacks);
-                println!("Avg Crits: {:.0}", stats.avg_crits);
-                println!("Avg Kills: {:.0}", stats.avg_kills);
-                println!("Avg Evades: {:.0}", stats.avg_evades);
-                println!("Avg Trickster Evades: {:.0}", stats.avg_trickster_evades);
-                println!("Avg Enemy Attacks: {:.0}", stats.avg_enemy_attacks);
-                println!("Avg Effect Procs: {:.0}", stats.avg_effect_procs);
-                println!("Avg Stun Duration: {:.2}s", stats.avg_stun_duration);
+                println!("--- Healing (effective / overheal) ---");
+                println!("Regen: {} / {}", nf.format(stats.avg_regen, 0), nf.format(stats.avg_overheal_regen, 0));
+                println!("Lifesteal: {} / {}", nf.format(stats.avg_lifesteal, 0), nf.format(stats.avg_overheal_lifesteal, 0));
+                println!("Heal Procs: {} / {}", nf.format(stats.avg_loth_healing_effective + stats.avg_ua_healing_effective, 0), nf.format(stats.avg_overheal_procs, 0));
+                for phase in &stats.sustain {
+                    println!(
+                        "  In {} fights: heals {:.1}% of {}/s taken (regen {:.0}% / lifesteal {:.0}% / procs {:.0}%), {:.1}% overheal, {:.0}s per run",
+                        phase.phase.label(),
+                        phase.coverage * 100.0,
+                        nf.format(phase.damage_taken_per_second, 1),
+                        phase.regen_share * 100.0,
+                        phase.lifesteal_share * 100.0,
+                        phase.procs_share * 100.0,
+                        phase.overheal_rate * 100.0,
+                        phase.seconds,
+                    );
+                }
+                println!();
+                println!("Avg Attacks: {}", nf.format(stats.avg_attacks, 0));
+                if hunter_type == HunterType::Borge {
+                    println!("Avg Crits: {}", nf.format(stats.avg_crits, 0));
+                }
+                println!("Avg Kills: {}", nf.format(stats.avg_kills, 0));
+                println!("Attacks per Kill: {:.2} (avg overkill {})", stats.attacks_per_kill, nf.format(stats.avg_overkill, 0));
+                println!("Attack Interval: {:.3}s realized vs {:.3}s nominal ({:.2} attacks/s)", stats.avg_attack_interval, stats.nominal_speed, stats.effective_aps);
+                if stats.on_kill_calls_per_kill > 1.0 {
+                    println!("On-Kill Calls per Kill: {:.2}", stats.on_kill_calls_per_kill);
+                }
+                if hunter_type != HunterType::Knox {
+                    println!("Avg Evades: {}", nf.format(stats.avg_evades, 0));
+                }
+                println!("Avg Enemy Attacks: {}", nf.format(stats.avg_enemy_attacks, 0));
+                println!("Avg Effect Procs: {}", nf.format(stats.avg_effect_procs, 0));
+                if stats.max_stage >= 100 {
+                    println!();
+                    println!("Avg Enrage at Boss Kill: {:.1} stacks (max enrage above 200)", stats.avg_enrage_at_boss_kill);
+                    println!("Boss Fights Reaching Max Enrage: {:.1}%", stats.max_enrage_rate * 100.0);
+                    for boss in &stats.boss_survival {
+                        println!(
+                            "Boss {}: {:.1}% reached, {:.1}% passed ({:.1}% kill rate), EHP {}",
+                            boss.stage,
+                            boss.reached * 100.0,
+                            boss.passed * 100.0,
+                            boss.kill_rate * 100.0,
+                            nf.format(boss.ehp, 0),
+                        );
+                        if let Some(entry) = stats.boss_entry.iter().find(|e| e.stage == boss.stage) {
+                            let buffs = match hunter_type {
+                                HunterType::Borge => format!("Fires of War {:.0}%", entry.fires_of_war_rate * 100.0),
+                                HunterType::Ozzy => format!(
+                                    "{:.1} trickster charges, empowered regen {:.0}%",
+                                    entry.avg_trickster_charges,
+                                    entry.empowered_regen_rate * 100.0,
+                                ),
+                                HunterType::Knox => format!(
+                                    "{:.1} Hundred Souls stacks, {:.1} charge",
+                                    entry.avg_hundred_souls_stacks,
+                                    entry.avg_charge,
+                                ),
+                            };
+                            let below_half = entry.hp_buckets[0] + entry.hp_buckets[1];
+                            let split = if below_half > 0.0 {
+                                format!(
+                                    " (kill rate {:.1}% from below half vs {:.1}% from above)",
+                                    entry.kill_rate_below_half * 100.0,
+                                    entry.kill_rate_above_half * 100.0,
+                                )
+                            } else {
+                                String::new()
+                            };
+                            println!(
+                                "  Arriving: {:.1}% HP, {:.1}% below half{}, {:.2} revives left, {}",
+                                entry.avg_hp_fraction * 100.0,
+                                below_half * 100.0,
+                                split,
+                                entry.avg_revives_left,
+                                buffs,
+                            );
+                        }
+                    }
+                }
+                
+                println!();
+                println!("--- By Stage Band ---");
+                for band in &stats.bands {
+                    println!(
+                        "Stages {}-{}: {:.1}% of runs, {:.1}% of loot, {:.2} attacks/kill, avg overkill {}, {:.3}s/attack ({:.1}s stunned), EHP {}",
+                        band.start_stage,
+                        band.start_stage + STAGE_BAND - 1,
+                        band.runs as f64 / stats.runs as f64 * 100.0,
+                        band.loot_share * 100.0,
+                        band.attacks_per_kill,
+                        nf.format(band.avg_overkill, 0),
+                        band.avg_attack_interval,
+                        band.avg_stun_time,
+                        nf.format(band.ehp, 0),
+                    );
+                }
+                let tail: Vec<String> = stats
+                    .loot_tail
+                    .iter()
+                    .map(|t| format!("last {} stages {:.1}%", t.stages, t.share * 100.0))
+                    .collect();
+                println!("Loot Concentration: {}", tail.join(", "));
+                
+                println!();
+                match hunter_type {
+                    HunterType::Borge => {
+                        println!("--- Borge ---");
+                        println!("Avg Extra Damage from Crits: {}", nf.format(stats.avg_extra_from_crits, 0));
+                        println!("Avg Helltouch Damage: {}", nf.format(stats.avg_helltouch, 0));
+                        println!("Avg Trample Kills: {}", nf.format(stats.avg_trample_kills, 0));
+                        println!("Avg Stun Duration: {:.2}s", stats.avg_stun_duration);
+                    }
+                    HunterType::Ozzy => {
+                        println!("--- Ozzy ---");
+                        println!("Avg Multistrikes: {}", nf.format(stats.avg_multistrikes, 0));
+                        println!("Avg Multistrike Extra Damage: {}", nf.format(stats.avg_ms_extra_damage, 0));
+                        println!("Avg Echo Bullets: {}", nf.format(stats.avg_echo_bullets, 0));
+                        println!("Avg Trickster Evades: {}", nf.format(stats.avg_trickster_evades, 0));
+                        if stats.avg_empowered_regen_procs > 0.0 {
+                            println!(
+                                "Empowered Regen Uptime: {:.1}% ({} procs, peak {} ticks banked, {} ticks dropped)",
+                                stats.empowered_regen_uptime * 100.0,
+                                nf.format(stats.avg_empowered_regen_procs, 1),
+                                nf.format(stats.avg_empowered_regen_peak, 1),
+                                nf.format(stats.avg_empowered_regen_dropped, 1),
+                            );
+                        }
+                        if stats.avg_harden_windows > 0.0 {
+                            println!("Avg Harden Windows: {:.2} ({:.1}s hardened)", stats.avg_harden_windows, stats.avg_harden_duration);
+                            println!("Avg Damage Wasted into Harden: {}", nf.format(stats.avg_harden_wasted_damage, 0));
+                            if stats.avg_held_multistrikes > 0.0 {
+                                println!("Avg Multistrikes Held: {}", nf.format(stats.avg_held_multistrikes, 1));
+                            }
+                        }
+                    }
+                    HunterType::Knox => {
+                        println!("--- Knox ---");
+                        println!("Avg Charges: {}", nf.format(stats.avg_crits, 0));
+                        println!("Avg Ghost Bullets: {}", nf.format(stats.avg_ghost_bullets, 0));
+                        println!("Avg Extra Salvo Damage: {}", nf.format(stats.avg_extra_salvo_damage, 0));
+                    }
+                }
+                
+                if !stats.correlations.is_empty() {
+                    println!();
+                    println!("--- What Drives Variance (Pearson r, strongest first) ---");
+                    for c in stats.correlations.iter().take(10) {
+                        println!("{:<36} vs {:<16} {:>+7.3}  ({} runs)", c.metric, c.outcome, c.r, c.runs);
+                    }
+                }
+                
+                println!();
+                println!("--- Best and Worst Runs ---");
+                println!("{:<6} {:>10} {:>6} {:>9} {:>12} {:>7}  End", "", "Seed", "Stage", "Time", "Loot", "Revives");
+                for (label, runs) in [("Best", &stats.best_runs), ("Worst", &stats.worst_runs)] {
+                    for run in runs.iter() {
+                        println!(
+                            "{:<6} {:>10} {:>6} {:>8.0}s {:>12} {:>7}  {}",
+                            label,
+                            run.seed.map_or("-".to_string(), |s| s.to_string()),
+                            run.final_stage,
+                            run.elapsed_time,
+                            nf.format(run.total_loot, 0),
+                            run.revives,
+                            run.end.label(),
+                        );
+                    }
+                }
+                if stats.best_runs.iter().any(|r| r.seed.is_none()) {
+                    println!("(Sequential runs share one RNG stream; use -p or --checkpoint for replayable seeds)");
+                }
                 
                 if args.timing {
                     println!();
@@ -203,14 +2124,26 @@ fn main() {
                     println!("Simulations/sec: {:.0}", args.num_sims as f64 / elapsed.as_secs_f64());
                 }
             }
+            
+            if args.profile {
+                print_profile_report(&profiling::report());
+            }
+            if args.validate {
+                print_validation_report(&invariants::report());
+            }
+            if args.audit_ties {
+                println!();
+                println!("Event ties resolved differently than heapq: {}", simulation::tie_divergences());
+            }
         }
         OutputFormat::Json => {
-            let output = serde_json::json!({
+            let mut output = serde_json::json!({
                 "simulations": args.num_sims,
                 "parallel": args.parallel,
                 "elapsed_seconds": elapsed.as_secs_f64(),
-                "stats": stats_vec.into_iter().map(|stats| {
-                    serde_json::json!({
+                "filter": filter.as_ref().map(RunFilter::expr),
+                "stats": stats_vec.into_iter().zip(level_etas).map(|(stats, etas)| {
+                    let mut stats_json = serde_json::json!({
                         "avg_stage": stats.avg_stage,
                         "std_stage": stats.std_stage,
                         "min_stage": stats.min_stage,
@@ -218,6 +2151,15 @@ fn main() {
                         "avg_time": stats.avg_time,
                         "avg_loot": stats.avg_loot,
                         "avg_loot_per_hour": stats.avg_loot_per_hour,
+                        "loot_per_sim_hour": stats.loot_per_sim_hour,
+                        "loot_per_stage": stats.loot_per_stage,
+                        "p10_loot_per_hour": stats.p10_loot_per_hour,
+                        "p90_loot_per_hour": stats.p90_loot_per_hour,
+                        "avg_loot_common_per_hour": stats.avg_loot_common_per_hour,
+                        "avg_loot_uncommon_per_hour": stats.avg_loot_uncommon_per_hour,
+                        "avg_loot_rare_per_hour": stats.avg_loot_rare_per_hour,
+                        "avg_event_loot_per_hour": stats.avg_event_loot_per_hour,
+                        "avg_event_xp_per_hour": stats.avg_event_xp_per_hour,
                         "min_loot_common": stats.min_loot_common,
                         "max_loot_common": stats.max_loot_common,
                         "avg_loot_common": stats.avg_loot_common,
@@ -228,9 +2170,11 @@ fn main() {
                         "max_loot_rare": stats.max_loot_rare,
                         "avg_loot_rare": stats.avg_loot_rare,
                         "avg_xp": stats.avg_xp,
+                        "avg_xp_per_hour": stats.avg_xp_per_hour,
                         "avg_damage": stats.avg_damage,
                         "avg_damage_taken": stats.avg_damage_taken,
                         "avg_mitigated": stats.avg_mitigated,
+                        "avg_total_mitigated": stats.avg_total_mitigated,
                         "avg_lifesteal": stats.avg_lifesteal,
                         "avg_attacks": stats.avg_attacks,
                         "avg_crits": stats.avg_crits,
@@ -240,28 +2184,94 @@ fn main() {
                         "avg_effect_procs": stats.avg_effect_procs,
                         "avg_stun_duration": stats.avg_stun_duration,
                         "avg_regen": stats.avg_regen,
+                        "avg_overheal_regen": stats.avg_overheal_regen,
+                        "avg_overheal_lifesteal": stats.avg_overheal_lifesteal,
+                        "avg_overheal_procs": stats.avg_overheal_procs,
+                        "sustain": stats.sustain,
                         "avg_loth_healing": stats.avg_loth_healing,
                         "avg_ua_healing": stats.avg_ua_healing,
+                        "avg_loth_healing_effective": stats.avg_loth_healing_effective,
+                        "avg_ua_healing_effective": stats.avg_ua_healing_effective,
                         "avg_trample_kills": stats.avg_trample_kills,
                         // Hunter-specific stats
                         "avg_extra_from_crits": stats.avg_extra_from_crits,  // Borge
                         "avg_helltouch": stats.avg_helltouch,                // Borge
                         "avg_multistrikes": stats.avg_multistrikes,          // Ozzy
                         "avg_ms_extra_damage": stats.avg_ms_extra_damage,    // Ozzy
+                        "avg_echo_bullets": stats.avg_echo_bullets,          // Ozzy
+                        "avg_harden_windows": stats.avg_harden_windows,      // Ozzy
+                        "avg_harden_duration": stats.avg_harden_duration,    // Ozzy
+                        "avg_harden_wasted_damage": stats.avg_harden_wasted_damage, // Ozzy
+                        "avg_held_multistrikes": stats.avg_held_multistrikes, // Ozzy
+                        "avg_empowered_regen_procs": stats.avg_empowered_regen_procs, // Ozzy
+                        "avg_empowered_regen_dropped": stats.avg_empowered_regen_dropped, // Ozzy
+                        "avg_empowered_regen_peak": stats.avg_empowered_regen_peak, // Ozzy
+                        "empowered_regen_uptime": stats.empowered_regen_uptime, // Ozzy
                         "avg_ghost_bullets": stats.avg_ghost_bullets,        // Knox
                         "avg_extra_salvo_damage": stats.avg_extra_salvo_damage, // Knox
                         // Debug stats
                         "avg_on_kill_calls": stats.avg_on_kill_calls,
+                        "on_kill_calls_per_kill": stats.on_kill_calls_per_kill,
                         "survival_rate": stats.survival_rate,
                         "boss1_survival": stats.boss1_survival,
                         "boss2_survival": stats.boss2_survival,
                         "boss3_survival": stats.boss3_survival,
                         "boss4_survival": stats.boss4_survival,
                         "boss5_survival": stats.boss5_survival,
-                    })
+                        "boss_survival": stats.boss_survival,
+                        "boss_entry": stats.boss_entry,
+                        "best_runs": stats.best_runs,
+                        "worst_runs": stats.worst_runs,
+                        "correlations": stats.correlations,
+                        "attacks_per_kill": stats.attacks_per_kill,
+                        "avg_overkill": stats.avg_overkill,
+                        "nominal_speed": stats.nominal_speed,
+                        "avg_attack_interval": stats.avg_attack_interval,
+                        "effective_aps": stats.effective_aps,
+                        "ehp": stats.ehp,
+                        "bands": stats.bands,
+                        "loot_tail": stats.loot_tail,
+                        "wall": stats.wall,
+                        "effective_mitigation": stats.effective_mitigation,
+                        "avg_enrage_at_boss_kill": stats.avg_enrage_at_boss_kill,
+                        "max_enrage_rate": stats.max_enrage_rate,
+                    });
+                    if xp_curve.is_some() {
+                        stats_json["level_etas"] = serde_json::json!(etas);
+                    }
+                    if filter.is_some() {
+                        stats_json["matching_runs"] = serde_json::json!(stats.runs);
+                    }
+                    if args.max_sim_time.is_some() {
+                        stats_json["timed_out_runs"] = serde_json::json!(stats.timed_out_runs);
+                    }
+                    if args.watchdog.is_some() {
+                        stats_json["aborted_runs"] = serde_json::json!(stats.aborted_runs);
+                    }
+                    stats_json
                 }).collect::<Vec<_>>()
             });
-            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            if let (Some(objective), Some(ranking)) = (&objective, &ranking) {
+                output["objective"] = serde_json::json!(objective.expr());
+                for (rank, &(i, score)) in ranking.iter().enumerate() {
+                    output["stats"][i]["score"] = serde_json::json!(score);
+                    output["stats"][i]["rank"] = serde_json::json!(rank + 1);
+                }
+            }
+            if args.profile {
+                output["profile"] = serde_json::json!(profiling::report());
+            }
+            if args.validate {
+                output["validation"] = serde_json::json!(invariants::report());
+            }
+            if args.audit_ties {
+                output["tie_divergences"] = serde_json::json!(simulation::tie_divergences());
+            }
+            print_json(&output);
         }
     }
+    
+    if args.validate && invariants::report().total > 0 {
+        std::process::exit(1);
+    }
 }
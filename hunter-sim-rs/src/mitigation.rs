@@ -0,0 +1,159 @@
+//! Incoming-damage mitigation layers
+//!
+//! A hit that isn't evaded passes through the hunter's mitigation layers in
+//! [`LAYER_ORDER`], each multiplying the remaining damage by `1 - reduction`.
+//! Knox's block halves the raw hit before any layer and is handled with the
+//! block roll, not here. The old engine capped main DR at 95% while this one
+//! leaves it uncapped; a build's optional `mitigation` section sets the caps
+//! explicitly so either behavior can be simulated.
+//!
+//! [`HitAudit`] records one hit's trip through the layers, for checking the
+//! order and numbers against the game (`replay --audit-hits`).
+
+use serde::{Deserialize, Serialize};
+
+/// One multiplicative mitigation layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layer {
+    Minotaur,         // Borge: Soul of the Minotaur
+    Scarab,           // Ozzy: Blessings of the Scarab
+    Weakspot,         // Borge: Weakspot Analysis, crits only
+    DamageReduction,  // Main DR stat, always last
+}
+
+impl Layer {
+    pub fn label(self) -> &'static str {
+        match self {
+            Layer::Minotaur => "minotaur",
+            Layer::Scarab => "scarab",
+            Layer::Weakspot => "weakspot",
+            Layer::DamageReduction => "DR",
+        }
+    }
+}
+
+/// Order the layers are applied in (WASM: hunter-specific DR first, main DR last)
+pub const LAYER_ORDER: [Layer; 4] = [Layer::Minotaur, Layer::Scarab, Layer::Weakspot, Layer::DamageReduction];
+
+/// Caps from the build's `mitigation` section; unset means uncapped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MitigationRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dr_cap: Option<f64>,     // Cap on main DR (0.95 reproduces the old engine)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer_cap: Option<f64>,  // Cap on each of the other layers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_cap: Option<f64>,  // Cap on all layers combined
+}
+
+impl MitigationRules {
+    /// True when no cap is set (the section is then left out of saved builds)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Every set cap as (`mitigation.key`, value), for range checks
+    pub fn caps(&self) -> impl Iterator<Item = (&'static str, f64)> {
+        [
+            ("mitigation.dr_cap", self.dr_cap),
+            ("mitigation.layer_cap", self.layer_cap),
+            ("mitigation.total_cap", self.total_cap),
+        ]
+        .into_iter()
+        .filter_map(|(key, cap)| cap.map(|c| (key, c)))
+    }
+}
+
+/// One layer's part of a `HitAudit`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LayerStep {
+    pub layer: Layer,
+    pub reduction: f64,  // After its cap
+    pub remaining: f64,  // Damage left after this layer
+}
+
+/// One incoming hit, step by step from the raw damage to the HP it took
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HitAudit {
+    pub stage: i32,
+    pub boss: bool,
+    pub crit: bool,
+    pub raw: f64,
+    pub blocked: f64,              // Knox: taken off the raw hit before any layer
+    pub steps: Vec<LayerStep>,     // Layers with a reduction, in LAYER_ORDER
+    pub total_capped: bool,        // mitigation.total_cap raised the result back up
+    pub taken: f64,
+    pub hp_before: f64,
+}
+
+/// A hunter's mitigation layers with the build's caps applied
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mitigation {
+    pub minotaur: f64,
+    pub scarab: f64,
+    pub weakspot: f64,  // Applies to crits only
+    pub damage_reduction: f64,
+    pub rules: MitigationRules,
+}
+
+impl Mitigation {
+    /// Reduction of one layer against a hit, after its cap
+    pub fn reduction(&self, layer: Layer, is_crit: bool) -> f64 {
+        let capped = |r: f64, cap: Option<f64>| cap.map_or(r, |c| r.min(c));
+        match layer {
+            Layer::Minotaur => capped(self.minotaur, self.rules.layer_cap),
+            Layer::Scarab => capped(self.scarab, self.rules.layer_cap),
+            Layer::Weakspot if is_crit => capped(self.weakspot, self.rules.layer_cap),
+            Layer::Weakspot => 0.0,
+            Layer::DamageReduction => capped(self.damage_reduction, self.rules.dr_cap),
+        }
+    }
+
+    /// Damage left after every layer but main DR, before any total cap
+    pub fn before_dr(&self, damage: f64, is_crit: bool) -> f64 {
+        LAYER_ORDER
+            .iter()
+            .filter(|&&layer| layer != Layer::DamageReduction)
+            .fold(damage, |remaining, &layer| remaining * (1.0 - self.reduction(layer, is_crit)))
+    }
+
+    /// Damage left after every layer
+    #[inline(always)]
+    pub fn apply(&self, damage: f64, is_crit: bool) -> f64 {
+        let mut remaining = damage;
+        for layer in LAYER_ORDER {
+            let r = self.reduction(layer, is_crit);
+            if r != 0.0 {
+                remaining *= 1.0 - r;
+            }
+        }
+        match self.rules.total_cap {
+            Some(cap) => remaining.max(damage * (1.0 - cap)),
+            None => remaining,
+        }
+    }
+
+    /// The layers `apply` runs `damage` through, with what each leaves, and
+    /// whether the total cap then raised the result
+    pub fn steps(&self, damage: f64, is_crit: bool) -> (Vec<LayerStep>, bool) {
+        let mut remaining = damage;
+        let steps = LAYER_ORDER
+            .iter()
+            .filter_map(|&layer| {
+                let reduction = self.reduction(layer, is_crit);
+                (reduction != 0.0).then(|| {
+                    remaining *= 1.0 - reduction;
+                    LayerStep { layer, reduction, remaining }
+                })
+            })
+            .collect();
+        let capped = self.rules.total_cap.is_some_and(|cap| remaining < damage * (1.0 - cap));
+        (steps, capped)
+    }
+
+    /// Fraction of a hit removed by all layers combined
+    pub fn effective(&self, is_crit: bool) -> f64 {
+        1.0 - self.apply(1.0, is_crit)
+    }
+}
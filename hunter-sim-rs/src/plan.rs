@@ -0,0 +1,148 @@
+//! What a batch is about to do and how long it should take (`--dry-run`)
+//!
+//! A plan is the config as the engine will see it (aliases folded, defaults
+//! filled in), the stats the hunter will fight with, where runs start and
+//! stop, how they're seeded, and how long the batch should take. The time is
+//! measured, not modeled: a handful of seeded runs are timed and scaled up to
+//! the batch, so it's only as good as those runs are typical.
+//!
+//! A real batch large enough to take a while opens with a calibration batch
+//! of its own first runs instead, which then count towards its results.
+
+use crate::config::BuildConfig;
+use crate::events::LootEvent;
+use crate::game_data;
+use crate::hunter::HunterStats;
+use crate::simulation::{regen_tick, termination_policy, TerminationPolicy};
+use crate::stats::SimResult;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Runs timed to estimate a batch's runtime
+pub const PROBE_SIMS: usize = 10;
+
+/// Runs per config in the calibration batch that opens every larger batch
+pub const CALIBRATION_SIMS: usize = 20;
+
+/// Estimated batch time, in seconds, below which the calibration goes unreported
+pub const PREFLIGHT_NOTICE_SECS: f64 = 10.0;
+
+/// A batch's first runs and how long they took. They ran exactly as the rest
+/// will (same threads, same configs side by side), so scaling them up needs
+/// no thread count
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Calibration {
+    pub sims: usize,        // Over all configs
+    pub seconds: f64,
+    pub batch_sims: usize,  // The whole batch's, calibration included
+}
+
+impl Calibration {
+    pub fn sims_per_sec(&self) -> f64 {
+        self.sims as f64 / self.seconds.max(1e-9)
+    }
+
+    /// Estimated time of the whole batch
+    pub fn total_seconds(&self) -> f64 {
+        self.batch_sims as f64 / self.sims_per_sec()
+    }
+
+    /// Estimated time of the batch after the calibration
+    pub fn remaining_seconds(&self) -> f64 {
+        (self.total_seconds() - self.seconds).max(0.0)
+    }
+}
+
+/// Stages a run fights through
+#[derive(Debug, Clone, Serialize)]
+pub struct StagePlan {
+    pub start_stage: i32,         // 1, the build's `start.stage`, or a snapshot's stage
+    pub max_stage: i32,
+    pub max_stage_default: i32,   // The hunter's cap in game data
+    pub boss_stages: Vec<i32>,
+    pub events: Vec<LootEvent>,
+}
+
+impl StagePlan {
+    pub fn new(config: &BuildConfig, start_stage: i32) -> Self {
+        let max_stage = config.get_max_stage();
+        let first_boss = (start_stage.max(1) + 99) / 100 * 100;
+        Self {
+            start_stage,
+            max_stage,
+            max_stage_default: game_data::max_stage(config.get_hunter_type()),
+            boss_stages: (first_boss..=max_stage).step_by(100).collect(),
+            events: config.events.clone(),
+        }
+    }
+}
+
+/// How the batch's runs get their random draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SeedPlan {
+    /// Simulation i runs with seed i, so the batch is reproducible
+    Indexed { count: usize },
+    /// One randomly seeded stream shared by every run in turn
+    Random,
+}
+
+/// Measured throughput and the batch time it implies
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RuntimeEstimate {
+    pub probe_sims: usize,
+    pub sims_per_sec: f64,  // On one thread
+    pub seconds: f64,       // For the whole batch on `threads` threads
+    pub threads: usize,
+}
+
+impl RuntimeEstimate {
+    /// Time `run(PROBE_SIMS)` (or `sims`, if fewer) on one thread and scale it
+    /// to `sims` runs spread over `threads`
+    pub fn probe(sims: usize, threads: usize, run: impl FnOnce(usize) -> Vec<SimResult>) -> Self {
+        let probe_sims = sims.clamp(1, PROBE_SIMS);
+        let start = Instant::now();
+        run(probe_sims);
+        let seconds = start.elapsed().as_secs_f64().max(1e-9);
+        let sims_per_sec = probe_sims as f64 / seconds;
+        let threads = threads.max(1);
+        Self { probe_sims, sims_per_sec, seconds: sims as f64 / sims_per_sec / threads.min(sims.max(1)) as f64, threads }
+    }
+}
+
+/// Everything a batch of one config will do
+#[derive(Debug, Clone, Serialize)]
+pub struct RunPlan {
+    pub config: BuildConfig,
+    pub stats: HunterStats,
+    pub stages: StagePlan,
+    pub termination: TerminationPolicy,
+    pub regen_tick: f64,  // Seconds between regen ticks
+    pub seeds: SeedPlan,
+    pub sims: usize,
+    pub estimate: RuntimeEstimate,
+}
+
+impl RunPlan {
+    /// The plan for `sims` runs of `config` from `start_stage` under the
+    /// current engine switches; `run` plays n seeded runs for the estimate
+    pub fn new(
+        config: &BuildConfig,
+        start_stage: i32,
+        sims: usize,
+        seeds: SeedPlan,
+        threads: usize,
+        run: impl FnOnce(usize) -> Vec<SimResult>,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            stats: HunterStats::from_config(config),
+            stages: StagePlan::new(config, start_stage),
+            termination: termination_policy(),
+            regen_tick: regen_tick(),
+            seeds,
+            sims,
+            estimate: RuntimeEstimate::probe(sims, threads, run),
+        }
+    }
+}
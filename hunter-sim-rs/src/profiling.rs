@@ -0,0 +1,164 @@
+//! Opt-in per-phase timing counters for the simulation engine
+//!
+//! Timers only read the clock while profiling is enabled. Samples are accumulated
+//! in a thread-local buffer and flushed to global atomics once per simulation, so
+//! rayon workers never contend on a shared counter inside the event loop and the
+//! RNG stream (and therefore every result) is identical with profiling on or off.
+
+use serde::Serialize;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Engine phase being timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    EventDispatch,     // Popping the next event off the queue
+    AttackResolution,  // Hunter/enemy attack and damage math
+    QueueMaintenance,  // Stale enemy event purges and stun rescheduling
+    LootMath,          // Final geometric-series loot/XP calculation
+}
+
+const PHASE_COUNT: usize = 4;
+
+impl Phase {
+    pub const ALL: [Phase; PHASE_COUNT] = [
+        Phase::EventDispatch,
+        Phase::AttackResolution,
+        Phase::QueueMaintenance,
+        Phase::LootMath,
+    ];
+
+    /// Name used in the `--profile` report
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::EventDispatch => "event_dispatch",
+            Phase::AttackResolution => "attack_resolution",
+            Phase::QueueMaintenance => "queue_maintenance",
+            Phase::LootMath => "loot_math",
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SIMS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_NANOS: [AtomicU64; PHASE_COUNT] = [
+    AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0),
+];
+static CALLS: [AtomicU64; PHASE_COUNT] = [
+    AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0),
+];
+
+thread_local! {
+    static LOCAL_NANOS: Cell<[u64; PHASE_COUNT]> = const { Cell::new([0; PHASE_COUNT]) };
+    static LOCAL_CALLS: Cell<[u64; PHASE_COUNT]> = const { Cell::new([0; PHASE_COUNT]) };
+}
+
+/// Turn profiling on or off for all subsequent simulations
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether profiling is currently enabled
+#[inline(always)]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Clear all accumulated counters
+pub fn reset() {
+    SIMS.store(0, Ordering::Relaxed);
+    for i in 0..PHASE_COUNT {
+        TOTAL_NANOS[i].store(0, Ordering::Relaxed);
+        CALLS[i].store(0, Ordering::Relaxed);
+    }
+}
+
+/// Guard that records elapsed time for a phase when dropped
+pub struct PhaseTimer {
+    phase: Phase,
+    start: Option<Instant>,
+}
+
+/// Start timing a phase (no clock read when profiling is disabled)
+#[inline(always)]
+pub fn start(phase: Phase) -> PhaseTimer {
+    let start = if is_enabled() { Some(Instant::now()) } else { None };
+    PhaseTimer { phase, start }
+}
+
+impl Drop for PhaseTimer {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let nanos = start.elapsed().as_nanos() as u64;
+            let i = self.phase as usize;
+            LOCAL_NANOS.with(|c| {
+                let mut v = c.get();
+                v[i] += nanos;
+                c.set(v);
+            });
+            LOCAL_CALLS.with(|c| {
+                let mut v = c.get();
+                v[i] += 1;
+                c.set(v);
+            });
+        }
+    }
+}
+
+/// Flush this thread's counters into the global totals (called once per simulation)
+pub fn flush_sim() {
+    if !is_enabled() {
+        return;
+    }
+    let nanos = LOCAL_NANOS.with(|c| c.replace([0; PHASE_COUNT]));
+    let calls = LOCAL_CALLS.with(|c| c.replace([0; PHASE_COUNT]));
+    for i in 0..PHASE_COUNT {
+        TOTAL_NANOS[i].fetch_add(nanos[i], Ordering::Relaxed);
+        CALLS[i].fetch_add(calls[i], Ordering::Relaxed);
+    }
+    SIMS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Timing summary for a single phase
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseProfile {
+    pub phase: &'static str,
+    pub calls: u64,
+    pub total_ms: f64,
+    pub avg_ns: f64,
+    pub share: f64,  // Fraction of all timed phases
+}
+
+/// Snapshot of all profiling counters
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub simulations: u64,
+    pub phases: Vec<PhaseProfile>,
+}
+
+/// Build a report from the counters accumulated so far
+pub fn report() -> ProfileReport {
+    let totals: Vec<u64> = TOTAL_NANOS.iter().map(|a| a.load(Ordering::Relaxed)).collect();
+    let grand_total = totals.iter().sum::<u64>().max(1) as f64;
+    let phases = Phase::ALL
+        .iter()
+        .map(|&phase| {
+            let i = phase as usize;
+            let calls = CALLS[i].load(Ordering::Relaxed);
+            PhaseProfile {
+                phase: phase.name(),
+                calls,
+                total_ms: totals[i] as f64 / 1_000_000.0,
+                avg_ns: if calls > 0 { totals[i] as f64 / calls as f64 } else { 0.0 },
+                share: totals[i] as f64 / grand_total,
+            }
+        })
+        .collect();
+
+    ProfileReport {
+        simulations: SIMS.load(Ordering::Relaxed),
+        phases,
+    }
+}
@@ -4,11 +4,112 @@ use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyAny};
 use numpy::{PyReadonlyArray2, PyArray1};
 use crate::config::{BuildConfig, HunterType, Meta};
-use crate::simulation::{run_and_aggregate, FastRng};
+use crate::error::{ConfigError, OptimizeError, SimError};
+use crate::filter::Objective;
+use crate::simulation::{run_and_aggregate, validate_config, FastRng};
+use crate::simulator::{BatchProgress, CancelToken, Simulator};
+use crate::stats::{AggregatedStats, BandResult, BossEntry, FightPhase, PairedDeltas, PhaseSustain, RunEnd, SimResult};
 use crate::build_generator::{BuildGenerator, AttributeInfo, TalentInfo};
 use std::collections::HashMap;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::Duration;
 use rayon::prelude::*;
 
+/// Exception classes raised by the bindings. Both carry a `kind` attribute
+/// naming the Rust error variant; `ConfigError` also has `field`, the key path
+/// of the offending input (empty when the error isn't about one field).
+mod exceptions {
+    pyo3::create_exception!(
+        rust_sim,
+        ConfigError,
+        pyo3::exceptions::PyValueError,
+        "A build config (or talent/attribute definition) could not be read, parsed or used"
+    );
+    pyo3::create_exception!(
+        rust_sim,
+        SimulationError,
+        pyo3::exceptions::PyRuntimeError,
+        "A valid config failed while simulating (e.g. a derived stat came out NaN)"
+    );
+}
+
+/// Create a `ConfigError` with its `kind` and `field` attributes set
+fn config_error(kind: &str, field: &str, message: String) -> PyErr {
+    Python::with_gil(|py| {
+        let err = exceptions::ConfigError::new_err(message);
+        let value = err.value(py);
+        let _ = value.setattr("kind", kind);
+        let _ = value.setattr("field", field);
+        err
+    })
+}
+
+/// Create a `SimulationError` with its `kind` attribute set
+fn simulation_error(kind: &str, message: String) -> PyErr {
+    Python::with_gil(|py| {
+        let err = exceptions::SimulationError::new_err(message);
+        let _ = err.value(py).setattr("kind", kind);
+        err
+    })
+}
+
+impl From<ConfigError> for PyErr {
+    fn from(err: ConfigError) -> PyErr {
+        let kind = match err {
+            ConfigError::Io { .. } => "io",
+            ConfigError::Parse { .. } => "parse",
+            ConfigError::Invalid { .. } => "invalid",
+            ConfigError::ShareCode(_) => "share_code",
+        };
+        config_error(kind, err.field(), err.to_string())
+    }
+}
+
+impl From<SimError> for PyErr {
+    fn from(err: SimError) -> PyErr {
+        let kind = match err {
+            SimError::Config(e) => return e.into(),
+            SimError::NoSimulations => return PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+            SimError::MissingConfig => "missing_config",
+            SimError::InvalidStat { .. } => "invalid_stat",
+            SimError::ThreadPool(_) => "thread_pool",
+            SimError::Serialize(_) => "serialize",
+            SimError::Checkpoint { .. } => "checkpoint",
+            SimError::Filter { .. } => "filter",
+            SimError::Objective { .. } => return PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+        };
+        simulation_error(kind, err.to_string())
+    }
+}
+
+impl From<OptimizeError> for PyErr {
+    fn from(err: OptimizeError) -> PyErr {
+        match err {
+            OptimizeError::Sim(e) => e.into(),
+            OptimizeError::InvalidDefinition { ref name, .. } => config_error("invalid_definition", name, err.to_string()),
+            OptimizeError::UnknownAttribute { ref referenced_by, .. } => config_error("unknown_attribute", referenced_by, err.to_string()),
+        }
+    }
+}
+
+/// Helper to fetch a required key from a talent/attribute definition dict
+fn required_item<'py>(dict: &Bound<'py, PyDict>, name: &str, key: &str) -> PyResult<Bound<'py, PyAny>> {
+    dict.get_item(key)?.ok_or_else(|| {
+        OptimizeError::InvalidDefinition {
+            name: name.to_string(),
+            message: format!("missing `{}`", key),
+        }
+        .into()
+    })
+}
+
+/// Helper to parse a case-insensitive hunter name
+fn parse_hunter(hunter: &str) -> PyResult<HunterType> {
+    serde_json::from_value(serde_json::Value::String(hunter.to_string()))
+        .map_err(|e| config_error("invalid", "hunter", format!("Invalid hunter type: {}", e)))
+}
+
 /// Helper to convert PyDict to HashMap<String, i32>
 fn pydict_to_hashmap_i32_global(dict: &Bound<'_, PyDict>) -> PyResult<HashMap<String, i32>> {
     let mut map = HashMap::new();
@@ -52,6 +153,169 @@ fn py_to_json_value(py_value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value>
     Ok(serde_json::Value::Null)
 }
 
+/// Helper to convert a serde_json::Value to the matching Python object
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    use serde_json::Value;
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_pyobject(py)?.into_any().unbind(),
+            None => n.as_f64().unwrap_or(f64::NAN).into_pyobject(py)?.into_any().unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(items) => {
+            let list = pyo3::types::PyList::empty(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (k, v) in map {
+                dict.set_item(k, json_to_py(py, v)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// Dedicated rayon pool for a call's `threads=N` argument; `None` leaves the
+/// call on the global pool
+fn thread_pool(threads: Option<usize>) -> PyResult<Option<rayon::ThreadPool>> {
+    match threads {
+        None => Ok(None),
+        Some(0) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("threads must be at least 1")),
+        Some(n) => Ok(Some(rayon::ThreadPoolBuilder::new().num_threads(n).build().map_err(SimError::from)?)),
+    }
+}
+
+/// Run `f` inside `pool`, so its parallel iterators use only that pool's
+/// workers, or on the global pool without one
+fn install<T: Send>(pool: Option<&rayon::ThreadPool>, f: impl FnOnce() -> T + Send) -> T {
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+/// Run `f` with the GIL released, on a dedicated pool of `threads` workers
+/// if given (leaving the other cores to the rest of the process)
+fn run_in_pool<T: Send>(py: Python<'_>, threads: Option<usize>, f: impl FnOnce() -> T + Send) -> PyResult<T> {
+    let pool = thread_pool(threads)?;
+    Ok(py.allow_threads(|| install(pool.as_ref(), f)))
+}
+
+/// Base seed of a `simulate`-style batch: `seed` if given, else 0 for a
+/// parallel batch (seeds `0..num_sims`, as `run_and_aggregate` runs them).
+/// None leaves a sequential batch to draw a random base.
+fn batch_seed(seed: Option<u64>, parallel: bool) -> Option<u64> {
+    seed.or(parallel.then_some(0))
+}
+
+/// Run a batch with the GIL released, on the seeds `batch_seed` picks, so a
+/// progress callback never changes the results. With a `progress_callback`, the batch
+/// runs on a worker thread while this thread calls
+/// `progress_callback(completed, total, partial_stats)` every
+/// `progress_interval` seconds and once more at the end; `partial_stats` is a
+/// dict of the running aggregates. An exception from the callback cancels
+/// the batch and is raised once the runs in flight finish.
+#[allow(clippy::too_many_arguments)]
+fn run_with_progress(
+    py: Python<'_>,
+    config: &BuildConfig,
+    num_sims: usize,
+    parallel: bool,
+    progress_callback: Option<&Bound<'_, PyAny>>,
+    progress_interval: f64,
+    threads: Option<usize>,
+    seed: Option<u64>,
+) -> PyResult<AggregatedStats> {
+    let seed = batch_seed(seed, parallel);
+    let Some(callback) = progress_callback else {
+        let Some(seed) = seed else {
+            return run_in_pool(py, threads, || run_and_aggregate(config, num_sims, parallel));
+        };
+        let sim = Simulator::builder().config(config.clone()).sims(num_sims).seed(seed).parallel(parallel).build()?;
+        return run_in_pool(py, threads, || sim.run());
+    };
+    if !(progress_interval > 0.0 && progress_interval.is_finite()) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("progress_interval must be a positive number of seconds"));
+    }
+    let pool = thread_pool(threads)?;
+    
+    let progress = Arc::new(BatchProgress::default());
+    let cancel = CancelToken::default();
+    let mut builder = Simulator::builder()
+        .config(config.clone())
+        .sims(num_sims)
+        .parallel(parallel)
+        .observer(progress.clone())
+        .cancel_token(cancel.clone());
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    let sim = builder.build()?;
+    let interval = Duration::from_secs_f64(progress_interval);
+    let report = |completed: usize| -> PyResult<()> {
+        let partial = serde_json::to_value(progress.snapshot()).map_err(SimError::from)?;
+        callback.call1((completed, num_sims, json_to_py(py, &partial)?))?;
+        Ok(())
+    };
+    
+    std::thread::scope(|scope| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let rx = std::sync::Mutex::new(rx);  // Receiver isn't Sync; only this thread locks it
+        let (sim, pool) = (&sim, pool.as_ref());
+        scope.spawn(move || {
+            let _ = tx.send(install(pool, || sim.run()));
+        });
+        let mut callback_error = None;
+        let stats = loop {
+            match py.allow_threads(|| rx.lock().unwrap().recv_timeout(interval)) {
+                Ok(stats) => break stats,
+                Err(RecvTimeoutError::Timeout) if callback_error.is_none() => {
+                    callback_error = report(progress.completed()).err();
+                    if callback_error.is_some() {
+                        cancel.cancel();
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(simulation_error("worker", "simulation worker stopped unexpectedly".to_string()));
+                }
+            }
+        };
+        match callback_error {
+            Some(e) => Err(e),
+            None => report(num_sims).map(|_| stats),
+        }
+    })
+}
+
+/// Helper to convert a nested Python dict/list structure to serde_json::Value
+fn py_to_json_tree(py_value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    if let Ok(dict) = py_value.downcast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (key, value) in dict.iter() {
+            map.insert(key.extract()?, py_to_json_tree(&value)?);
+        }
+        return Ok(serde_json::Value::Object(map));
+    }
+    if !py_value.is_instance_of::<pyo3::types::PyString>() {
+        if let Ok(items) = py_value.extract::<Vec<Bound<'_, PyAny>>>() {
+            return items.iter().map(py_to_json_tree).collect::<PyResult<Vec<_>>>().map(serde_json::Value::Array);
+        }
+    }
+    py_to_json_value(py_value)
+}
+
+/// Helper to build a BuildConfig from a config dict (same layout as the JSON)
+fn config_from_pydict(config: &Bound<'_, PyDict>) -> PyResult<BuildConfig> {
+    Ok(BuildConfig::from_json(&py_to_json_tree(config.as_any())?.to_string())?)
+}
+
 /// Helper to convert PyDict to HashMap<String, serde_json::Value>
 fn pydict_to_hashmap_json_global(dict: &Bound<'_, PyDict>) -> PyResult<HashMap<String, serde_json::Value>> {
     let mut map = HashMap::new();
@@ -64,9 +328,11 @@ fn pydict_to_hashmap_json_global(dict: &Bound<'_, PyDict>) -> PyResult<HashMap<S
 }
 
 /// Python-callable simulation function - accepts individual keyword arguments
-/// Returns a dict with stats for GUI compatibility
+/// Returns the full aggregated stats (every `simulate_json` field) as a dict.
+/// Run `i` uses seed `seed + i`; see `simulate_json` for the default.
 #[pyfunction]
-#[pyo3(signature = (hunter, level, stats, talents, attributes, inscryptions=None, mods=None, relics=None, gems=None, gadgets=None, bonuses=None, num_sims=100, parallel=true))]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (hunter, level, stats, talents, attributes, inscryptions=None, mods=None, relics=None, gems=None, gadgets=None, bonuses=None, num_sims=100, parallel=true, progress_callback=None, progress_interval=0.25, threads=None, seed=None))]
 fn simulate(
     py: Python<'_>,
     hunter: &str,
@@ -82,17 +348,19 @@ fn simulate(
     bonuses: Option<&Bound<'_, PyDict>>,
     num_sims: usize,
     parallel: bool,
+    progress_callback: Option<&Bound<'_, PyAny>>,
+    progress_interval: f64,
+    threads: Option<usize>,
+    seed: Option<u64>,
 ) -> PyResult<PyObject> {
     let hunter_type = match hunter.to_lowercase().as_str() {
         "borge" => HunterType::Borge,
         "ozzy" => HunterType::Ozzy,
         "knox" => HunterType::Knox,
-        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-            format!("Invalid hunter type: {}", hunter)
-        )),
+        _ => return Err(config_error("invalid", "hunter", format!("Invalid hunter type: {}", hunter))),
     };
     
-    let config = BuildConfig {
+    let mut config = BuildConfig {
         meta: Some(Meta {
             hunter: hunter_type,
             level,
@@ -108,75 +376,455 @@ fn simulate(
         gems: gems.map(|d| pydict_to_hashmap_i32_global(d)).transpose()?.unwrap_or_default(),
         gadgets: gadgets.map(|d| pydict_to_hashmap_i32_global(d)).transpose()?.unwrap_or_default(),
         bonuses: bonuses.map(|d| pydict_to_hashmap_json_global(d)).transpose()?.unwrap_or_default(),
+        mitigation: Default::default(),
+        logging: Default::default(),
+        events: Vec::new(),
+        max_stage: None,
+        start: Default::default(),
+        persistence: Default::default(),
+        stacking: Default::default(),
+        engine_flags: Default::default(),
     };
+    config.normalize()?;
+    validate_config(&config)?;
     
     // Release GIL during computation to prevent GUI freezing
-    let sim_result = py.allow_threads(|| run_and_aggregate(&config, num_sims, parallel));
-    
-    // Convert to Python dict for GUI compatibility - flat structure expected by GUI
-    let result_dict = PyDict::new(py);
-    
-    result_dict.set_item("avg_stage", sim_result.avg_stage)?;
-    result_dict.set_item("max_stage", sim_result.max_stage)?;
-    result_dict.set_item("min_stage", sim_result.min_stage)?;
-    result_dict.set_item("avg_loot_per_hour", sim_result.avg_loot_per_hour)?;
-    result_dict.set_item("min_loot_common", sim_result.min_loot_common)?;
-    result_dict.set_item("max_loot_common", sim_result.max_loot_common)?;
-    result_dict.set_item("avg_loot_common", sim_result.avg_loot_common)?;
-    result_dict.set_item("min_loot_uncommon", sim_result.min_loot_uncommon)?;
-    result_dict.set_item("max_loot_uncommon", sim_result.max_loot_uncommon)?;
-    result_dict.set_item("avg_loot_uncommon", sim_result.avg_loot_uncommon)?;
-    result_dict.set_item("min_loot_rare", sim_result.min_loot_rare)?;
-    result_dict.set_item("max_loot_rare", sim_result.max_loot_rare)?;
-    result_dict.set_item("avg_loot_rare", sim_result.avg_loot_rare)?;
-    result_dict.set_item("avg_damage", sim_result.avg_damage)?;
-    result_dict.set_item("avg_kills", sim_result.avg_kills)?;
-    result_dict.set_item("avg_time", sim_result.avg_time)?;
-    result_dict.set_item("avg_damage_taken", sim_result.avg_damage_taken)?;
-    result_dict.set_item("survival_rate", sim_result.survival_rate)?;
-    result_dict.set_item("boss1_survival", sim_result.boss1_survival)?;
-    result_dict.set_item("boss2_survival", sim_result.boss2_survival)?;
-    result_dict.set_item("boss3_survival", sim_result.boss3_survival)?;
-    result_dict.set_item("boss4_survival", sim_result.boss4_survival)?;
-    result_dict.set_item("boss5_survival", sim_result.boss5_survival)?;
-    
-    Ok(result_dict.into())
-}
-
-/// Python-callable simulation function from JSON string
+    let sim_result = run_with_progress(py, &config, num_sims, parallel, progress_callback, progress_interval, threads, seed)?;
+    
+    // Same fields as simulate_json, as a flat dict
+    json_to_py(py, &serde_json::to_value(&sim_result).map_err(SimError::from)?)
+}
+
+/// Python-callable simulation function from JSON string. Run `i` uses seed
+/// `seed + i`; without a seed a parallel batch uses seeds `0..num_sims` and
+/// a sequential one a random base.
 #[pyfunction]
-#[pyo3(signature = (config_json, num_sims, parallel=false))]
-fn simulate_json(py: Python<'_>, config_json: &str, num_sims: usize, parallel: bool) -> PyResult<String> {
-    let config: BuildConfig = serde_json::from_str(config_json)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid config JSON: {}", e)))?;
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (config_json, num_sims, parallel=false, progress_callback=None, progress_interval=0.25, threads=None, seed=None))]
+fn simulate_json(
+    py: Python<'_>,
+    config_json: ConfigArg<'_>,
+    num_sims: usize,
+    parallel: bool,
+    progress_callback: Option<&Bound<'_, PyAny>>,
+    progress_interval: f64,
+    threads: Option<usize>,
+    seed: Option<u64>,
+) -> PyResult<String> {
+    let config = config_json.into_config()?;
+    validate_config(&config)?;
     
     // Release GIL during computation to prevent GUI freezing
-    let stats = py.allow_threads(|| run_and_aggregate(&config, num_sims, parallel));
+    let stats = run_with_progress(py, &config, num_sims, parallel, progress_callback, progress_interval, threads, seed)?;
     
-    let result = serde_json::to_string(&stats)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to serialize results: {}", e)))?;
+    let result = serde_json::to_string(&stats).map_err(SimError::from)?;
     
     Ok(result)
 }
 
+/// Handle to a batch started by `start_simulation`, running on a background
+/// thread. `cancel()` stops it between runs; `result()` then returns the
+/// aggregate of the runs that finished (its `runs` field says how many).
+#[pyclass]
+struct SimulationJob {
+    total: usize,
+    progress: Arc<BatchProgress>,
+    cancel: CancelToken,
+    handle: std::sync::Mutex<Option<std::thread::JoinHandle<AggregatedStats>>>,
+    stats: std::sync::Mutex<Option<AggregatedStats>>,
+}
+
+#[pymethods]
+impl SimulationJob {
+    /// Stop the batch; runs already in flight finish first
+    fn cancel(&self) {
+        self.cancel.cancel();
+    }
+    
+    /// Whether `cancel()` was called
+    fn cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+    
+    /// Whether the batch has stopped (finished or cancelled)
+    fn done(&self) -> bool {
+        self.stats.lock().unwrap().is_some() || self.handle.lock().unwrap().as_ref().is_none_or(|h| h.is_finished())
+    }
+    
+    /// (completed, total) runs
+    fn progress(&self) -> (usize, usize) {
+        (self.progress.completed(), self.total)
+    }
+    
+    /// Running aggregates over the runs finished so far, as a dict
+    fn partial_stats(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let partial = serde_json::to_value(self.progress.snapshot()).map_err(SimError::from)?;
+        json_to_py(py, &partial)
+    }
+    
+    /// Wait for the batch and return its stats as a JSON string (like
+    /// `simulate_json`). Raises TimeoutError if `timeout` seconds pass first.
+    #[pyo3(signature = (timeout=None))]
+    fn result(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<String> {
+        let deadline = timeout.map(|t| std::time::Instant::now() + Duration::from_secs_f64(t.max(0.0)));
+        loop {
+            if let Some(ref stats) = *self.stats.lock().unwrap() {
+                return Ok(serde_json::to_string(stats).map_err(SimError::from)?);
+            }
+            if self.done() {
+                let handle = self.handle.lock().unwrap().take();
+                if let Some(handle) = handle {
+                    let stats = py.allow_threads(|| handle.join()).map_err(|_| {
+                        simulation_error("worker", "simulation worker stopped unexpectedly".to_string())
+                    })?;
+                    *self.stats.lock().unwrap() = Some(stats);
+                }
+                continue;
+            }
+            if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                return Err(PyErr::new::<pyo3::exceptions::PyTimeoutError, _>("simulation still running"));
+            }
+            py.allow_threads(|| std::thread::sleep(Duration::from_millis(10)));
+            py.check_signals()?;
+        }
+    }
+}
+
+#[pymethods]
+impl SimResult {
+    /// Every field as a dict (e.g. for a pandas DataFrame row)
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_to_py(py, &serde_json::to_value(self).map_err(SimError::from)?)
+    }
+    
+    fn __repr__(&self) -> String {
+        format!(
+            "SimResult(final_stage={}, elapsed_time={:.1}, kills={}, total_loot={:.0})",
+            self.final_stage, self.elapsed_time, self.kills, self.total_loot
+        )
+    }
+}
+
+/// Run a batch from a config (JSON string or `BuildConfig`) and return one
+/// `SimResult` per run, in run order, for custom distributions and filters.
+/// Run `i` uses seed `seed + i`; without a seed a random base is drawn.
+#[pyfunction]
+#[pyo3(signature = (config_json, num_sims, parallel=true, seed=None, threads=None))]
+fn simulate_detailed(
+    py: Python<'_>,
+    config_json: ConfigArg<'_>,
+    num_sims: usize,
+    parallel: bool,
+    seed: Option<u64>,
+    threads: Option<usize>,
+) -> PyResult<Vec<SimResult>> {
+    let config = config_json.into_config()?;
+    let mut builder = Simulator::builder().config(config).sims(num_sims).parallel(parallel);
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    let sim = builder.build()?;
+    run_in_pool(py, threads, || sim.run_detailed())
+}
+
+/// One per-run field gathered for `simulate_arrays`
+enum Column {
+    Int(Vec<i64>),
+    Float(Vec<f64>),
+}
+
+/// Run a batch and return the chosen `SimResult` fields as a dict of NumPy
+/// arrays (int64 for counters, float64 otherwise), one element per run in run
+/// order. Only the arrays cross into Python, so million-run batches load
+/// straight into pandas. `fields=None` returns every field.
+#[pyfunction]
+#[pyo3(signature = (config_json, num_sims, fields=None, parallel=true, seed=None, threads=None))]
+fn simulate_arrays(
+    py: Python<'_>,
+    config_json: ConfigArg<'_>,
+    num_sims: usize,
+    fields: Option<Vec<String>>,
+    parallel: bool,
+    seed: Option<u64>,
+    threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let config = config_json.into_config()?;
+    let mut known = match serde_json::to_value(SimResult::default()).map_err(SimError::from)? {
+        serde_json::Value::Object(map) => map,
+        _ => unreachable!("SimResult serializes as a map"),
+    };
+    known.retain(|_, v| v.is_number());
+    let fields = fields.unwrap_or_else(|| known.keys().cloned().collect());
+    if let Some(unknown) = fields.iter().find(|f| !known.contains_key(f.as_str())) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown SimResult field `{}`; expected one of {}",
+            unknown,
+            known.keys().cloned().collect::<Vec<_>>().join(", ")
+        )));
+    }
+    
+    let mut builder = Simulator::builder().config(config).sims(num_sims).parallel(parallel);
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    let sim = builder.build()?;
+    let columns = run_in_pool(py, threads, || -> Result<Vec<Column>, SimError> {
+        let mut columns: Vec<Column> = fields
+            .iter()
+            .map(|f| if known[f.as_str()].is_i64() { Column::Int(Vec::with_capacity(num_sims)) } else { Column::Float(Vec::with_capacity(num_sims)) })
+            .collect();
+        for result in sim.run_detailed() {
+            let row = serde_json::to_value(&result)?;
+            for (field, column) in fields.iter().zip(columns.iter_mut()) {
+                match column {
+                    Column::Int(v) => v.push(row[field.as_str()].as_i64().unwrap_or(0)),
+                    Column::Float(v) => v.push(row[field.as_str()].as_f64().unwrap_or(f64::NAN)),
+                }
+            }
+        }
+        Ok(columns)
+    })??;
+    
+    let dict = PyDict::new(py);
+    for (field, column) in fields.iter().zip(columns) {
+        match column {
+            Column::Int(v) => dict.set_item(field, PyArray1::from_vec(py, v))?,
+            Column::Float(v) => dict.set_item(field, PyArray1::from_vec(py, v))?,
+        }
+    }
+    Ok(dict.into_any().unbind())
+}
+
+/// Base batches `simulate_delta` keeps for reuse
+const DELTA_CACHE_SIZE: usize = 8;
+
+/// Base batches of recent `simulate_delta` calls, keyed by canonical config
+/// JSON, run count and seed; least recently used first
+#[allow(clippy::type_complexity)]
+static DELTA_CACHE: std::sync::Mutex<Vec<(String, usize, u64, Arc<Vec<SimResult>>)>> = std::sync::Mutex::new(Vec::new());
+
+/// What-if for one build: apply `overrides` (as `BuildConfig.apply_overrides`)
+/// to `base_config` and return the paired change in stage, time, loot,
+/// loot/hour and XP as a dict of `{base, variant, delta, stderr}`. Both
+/// batches use seeds `seed..seed + num_sims`, so luck common to both cancels,
+/// and the base batch is cached so repeated probes of one build only
+/// simulate the variant.
+#[pyfunction]
+#[pyo3(signature = (base_config, overrides, num_sims, seed=42, parallel=true, threads=None))]
+fn simulate_delta(
+    py: Python<'_>,
+    base_config: ConfigArg<'_>,
+    overrides: &Bound<'_, PyDict>,
+    num_sims: usize,
+    seed: u64,
+    parallel: bool,
+    threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let base = base_config.into_config()?;
+    let serde_json::Value::Object(overrides) = py_to_json_tree(overrides.as_any())? else {
+        unreachable!("a dict converts to a JSON object")
+    };
+    let mut variant = base.clone();
+    variant.apply_overrides(&overrides)?;
+    variant.normalize()?;
+    
+    let run = |config: BuildConfig| -> PyResult<Vec<SimResult>> {
+        let sim = Simulator::builder().config(config).sims(num_sims).seed(seed).parallel(parallel).build()?;
+        run_in_pool(py, threads, || sim.run_detailed())
+    };
+    let key = serde_json::to_value(&base).map_err(SimError::from)?.to_string();
+    let cached = {
+        let mut cache = DELTA_CACHE.lock().unwrap();
+        let hit = cache.iter().position(|(k, n, s, _)| *k == key && *n == num_sims && *s == seed);
+        hit.map(|i| {
+            let entry = cache.remove(i);
+            let results = entry.3.clone();
+            cache.push(entry);
+            results
+        })
+    };
+    let base_results = match cached {
+        Some(results) => results,
+        None => {
+            let results = Arc::new(run(base)?);
+            let mut cache = DELTA_CACHE.lock().unwrap();
+            if cache.len() >= DELTA_CACHE_SIZE {
+                cache.remove(0);
+            }
+            cache.push((key, num_sims, seed, results.clone()));
+            results
+        }
+    };
+    let variant_results = run(variant)?;
+    
+    let deltas = PairedDeltas::from_results(&base_results, &variant_results);
+    json_to_py(py, &serde_json::to_value(deltas).map_err(SimError::from)?)
+}
+
+/// Run `config` once per entry of `values` with `key` (as in
+/// `BuildConfig.apply_overrides`) set to it, e.g. `sweep(cfg,
+/// "talents.impeccable_impacts", range(0, 11))`. Every point uses seeds
+/// `seed..seed + num_sims`, so the curve isn't bent by luck. Returns a list
+/// of `{value, stats}` with `stats` shaped like `simulate_json`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (config, key, values, num_sims=100, seed=42, parallel=true, threads=None))]
+fn sweep(
+    py: Python<'_>,
+    config: ConfigArg<'_>,
+    key: &str,
+    values: Vec<Bound<'_, PyAny>>,
+    num_sims: usize,
+    seed: u64,
+    parallel: bool,
+    threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let base = config.into_config()?;
+    let mut points = Vec::with_capacity(values.len());
+    for value in &values {
+        let value = py_to_json_value(value)?;
+        let mut config = base.clone();
+        config.set_value(key, &value)?;
+        config.normalize()?;
+        points.push((value, Simulator::builder().config(config).sims(num_sims).seed(seed).parallel(parallel).build()?));
+    }
+    
+    let results = run_in_pool(py, threads, || -> Result<Vec<serde_json::Value>, SimError> {
+        points
+            .iter()
+            .map(|(value, sim)| Ok(serde_json::json!({ "value": value, "stats": serde_json::to_value(sim.run())? })))
+            .collect()
+    })??;
+    json_to_py(py, &serde_json::Value::Array(results))
+}
+
+/// Simulate two builds on the same seeds and return `{a, b, delta, changes}`:
+/// each build's stats (shaped like `simulate_json`), the paired change from
+/// `a` to `b` (as `simulate_delta` reports it) and the inputs that differ
+#[pyfunction]
+#[pyo3(signature = (a, b, num_sims=100, seed=42, parallel=true, threads=None))]
+fn compare(
+    py: Python<'_>,
+    a: ConfigArg<'_>,
+    b: ConfigArg<'_>,
+    num_sims: usize,
+    seed: u64,
+    parallel: bool,
+    threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let (a, b) = (a.into_config()?, b.into_config()?);
+    let changes = a.diff_inputs(&b);
+    let sim = |config: BuildConfig| Simulator::builder().config(config).sims(num_sims).seed(seed).parallel(parallel).build();
+    let (sim_a, sim_b) = (sim(a)?, sim(b)?);
+    let (runs_a, runs_b) = run_in_pool(py, threads, || (sim_a.run_detailed(), sim_b.run_detailed()))?;
+    
+    let report = serde_json::json!({
+        "a": AggregatedStats::from_results(&runs_a),
+        "b": AggregatedStats::from_results(&runs_b),
+        "delta": PairedDeltas::from_results(&runs_a, &runs_b),
+        "changes": changes,
+    });
+    json_to_py(py, &report)
+}
+
+/// Drop the base batches cached by `simulate_delta`
+#[pyfunction]
+fn clear_delta_cache() {
+    DELTA_CACHE.lock().unwrap().clear();
+}
+
+/// Start a batch from a config (JSON string or `BuildConfig`) on a background
+/// thread and return a `SimulationJob` to poll, cancel, or wait on. Seeded
+/// like `simulate_json`, so a job reproduces its results.
+#[pyfunction]
+#[pyo3(signature = (config_json, num_sims, parallel=true, threads=None, seed=None))]
+fn start_simulation(config_json: ConfigArg<'_>, num_sims: usize, parallel: bool, threads: Option<usize>, seed: Option<u64>) -> PyResult<SimulationJob> {
+    let config = config_json.into_config()?;
+    let progress = Arc::new(BatchProgress::default());
+    let cancel = CancelToken::default();
+    let mut builder = Simulator::builder()
+        .config(config)
+        .sims(num_sims)
+        .parallel(parallel)
+        .observer(progress.clone())
+        .cancel_token(cancel.clone());
+    if let Some(seed) = batch_seed(seed, parallel) {
+        builder = builder.seed(seed);
+    }
+    let sim = builder.build()?;
+    let pool = thread_pool(threads)?;
+    let handle = std::thread::spawn(move || install(pool.as_ref(), || sim.run()));
+    Ok(SimulationJob {
+        total: num_sims,
+        progress,
+        cancel,
+        handle: std::sync::Mutex::new(Some(handle)),
+        stats: std::sync::Mutex::new(None),
+    })
+}
+
+/// Run a batch on the running event loop's default executor, with the GIL
+/// released, and return an asyncio future for its stats JSON string (like
+/// `simulate_json`), so `await rust_sim.simulate_async(...)` never blocks the
+/// loop. Cancelling the future cancels the batch. Seeded like `simulate_json`.
+#[pyfunction]
+#[pyo3(signature = (config_json, num_sims, parallel=true, threads=None, seed=None))]
+fn simulate_async<'py>(
+    py: Python<'py>,
+    config_json: ConfigArg<'_>,
+    num_sims: usize,
+    parallel: bool,
+    threads: Option<usize>,
+    seed: Option<u64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    use pyo3::types::PyCFunction;
+    
+    let config = config_json.into_config()?;
+    let cancel = CancelToken::default();
+    let mut builder = Simulator::builder()
+        .config(config)
+        .sims(num_sims)
+        .parallel(parallel)
+        .cancel_token(cancel.clone());
+    if let Some(seed) = batch_seed(seed, parallel) {
+        builder = builder.seed(seed);
+    }
+    let sim = builder.build()?;
+    let pool = thread_pool(threads)?;
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let run = PyCFunction::new_closure(py, None, None, move |args, _| -> PyResult<String> {
+        let stats = args.py().allow_threads(|| install(pool.as_ref(), || sim.run()));
+        Ok(serde_json::to_string(&stats).map_err(SimError::from)?)
+    })?;
+    let future = event_loop.call_method1("run_in_executor", (py.None(), run))?;
+    let on_done = PyCFunction::new_closure(py, None, None, move |args, _| -> PyResult<()> {
+        if args.get_item(0)?.call_method0("cancelled")?.is_truthy()? {
+            cancel.cancel();
+        }
+        Ok(())
+    })?;
+    future.call_method1("add_done_callback", (on_done,))?;
+    Ok(future)
+}
+
 /// Python-callable simulation function from YAML file
 #[pyfunction]
-#[pyo3(signature = (config_path, num_sims, parallel=false))]
-fn simulate_from_file(py: Python<'_>, config_path: &str, num_sims: usize, parallel: bool) -> PyResult<String> {
-    let config = BuildConfig::from_file(config_path)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load config: {}", e)))?;
+#[pyo3(signature = (config_path, num_sims, parallel=false, threads=None))]
+fn simulate_from_file(py: Python<'_>, config_path: &str, num_sims: usize, parallel: bool, threads: Option<usize>) -> PyResult<String> {
+    let config = BuildConfig::from_file(config_path)?;
+    validate_config(&config)?;
     
     // Release GIL during computation to prevent GUI freezing
-    let stats = py.allow_threads(|| run_and_aggregate(&config, num_sims, parallel));
+    let stats = run_in_pool(py, threads, || run_and_aggregate(&config, num_sims, parallel))?;
     
-    let result = serde_json::to_string(&stats)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to serialize results: {}", e)))?;
+    let result = serde_json::to_string(&stats).map_err(SimError::from)?;
     
     Ok(result)
 }
 
 /// Python-callable function to create a BuildConfig from Python dicts
 #[pyfunction]
+#[allow(clippy::too_many_arguments)]
 #[pyo3(signature = (hunter, level, stats, talents, attributes, inscryptions=None, mods=None, relics=None, gems=None))]
 fn create_config(
     hunter: &str,
@@ -193,12 +841,10 @@ fn create_config(
         "borge" => HunterType::Borge,
         "ozzy" => HunterType::Ozzy,
         "knox" => HunterType::Knox,
-        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-            format!("Invalid hunter type: {}", hunter)
-        )),
+        _ => return Err(config_error("invalid", "hunter", format!("Invalid hunter type: {}", hunter))),
     };
     
-    let config = BuildConfig {
+    let mut config = BuildConfig {
         meta: Some(Meta {
             hunter: hunter_type,
             level,
@@ -214,14 +860,305 @@ fn create_config(
         gems: gems.map(|d| pydict_to_hashmap_i32_global(d)).transpose()?.unwrap_or_default(),
         gadgets: HashMap::new(),
         bonuses: HashMap::new(),
+        mitigation: Default::default(),
+        logging: Default::default(),
+        events: Vec::new(),
+        max_stage: None,
+        start: Default::default(),
+        persistence: Default::default(),
+        stacking: Default::default(),
+        engine_flags: Default::default(),
     };
+    config.normalize()?;
     
-    let json = serde_json::to_string(&config)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to serialize config: {}", e)))?;
+    let json = serde_json::to_string(&config).map_err(SimError::from)?;
     
     Ok(json)
 }
 
+/// A build held natively, so the GUI can edit one object instead of
+/// reassembling dicts and JSON per call. Section getters return copies; edit
+/// a section by assigning a whole dict or through `apply_overrides`.
+/// `simulate_json`, `simulate_detailed`, `simulate_arrays` and
+/// `start_simulation` accept one in place of a config JSON string.
+#[pyclass(name = "BuildConfig", module = "rust_sim")]
+#[derive(Clone)]
+struct PyBuildConfig {
+    inner: BuildConfig,
+}
+
+/// A config argument: a `BuildConfig` object or a config JSON string
+#[derive(FromPyObject)]
+enum ConfigArg<'py> {
+    Object(PyRef<'py, PyBuildConfig>),
+    Json(String),
+}
+
+impl ConfigArg<'_> {
+    /// The parsed config; objects are re-normalized since setters skip range checks
+    fn into_config(self) -> PyResult<BuildConfig> {
+        match self {
+            ConfigArg::Object(object) => {
+                let mut config = object.inner.clone();
+                config.normalize()?;
+                Ok(config)
+            }
+            ConfigArg::Json(json) => Ok(BuildConfig::from_json(&json)?),
+        }
+    }
+}
+
+#[pymethods]
+impl PyBuildConfig {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (hunter="borge", level=1, stats=None, talents=None, attributes=None, inscryptions=None, mods=None, relics=None, gems=None, gadgets=None, bonuses=None))]
+    fn new(
+        hunter: &str,
+        level: i32,
+        stats: Option<HashMap<String, i32>>,
+        talents: Option<HashMap<String, i32>>,
+        attributes: Option<HashMap<String, i32>>,
+        inscryptions: Option<HashMap<String, i32>>,
+        mods: Option<HashMap<String, bool>>,
+        relics: Option<HashMap<String, i32>>,
+        gems: Option<HashMap<String, i32>>,
+        gadgets: Option<HashMap<String, i32>>,
+        bonuses: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let mut config = BuildConfig {
+            meta: Some(Meta {
+                hunter: parse_hunter(hunter)?,
+                level,
+            }),
+            hunter: None,
+            level: None,
+            stats: stats.unwrap_or_default(),
+            talents: talents.unwrap_or_default(),
+            attributes: attributes.unwrap_or_default(),
+            inscryptions: inscryptions.unwrap_or_default(),
+            mods: mods.unwrap_or_default(),
+            relics: relics.unwrap_or_default(),
+            gems: gems.unwrap_or_default(),
+            gadgets: gadgets.unwrap_or_default(),
+            bonuses: bonuses.map(pydict_to_hashmap_json_global).transpose()?.unwrap_or_default(),
+            mitigation: Default::default(),
+            logging: Default::default(),
+            events: Vec::new(),
+            max_stage: None,
+            start: Default::default(),
+            persistence: Default::default(),
+            stacking: Default::default(),
+            engine_flags: Default::default(),
+        };
+        config.normalize()?;
+        Ok(PyBuildConfig { inner: config })
+    }
+    
+    /// Parse a config JSON string (either the meta or the flat format)
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(PyBuildConfig { inner: BuildConfig::from_json(json)? })
+    }
+    
+    /// Build from a config dict with the same layout as the JSON
+    #[staticmethod]
+    fn from_dict(config: &Bound<'_, PyDict>) -> PyResult<Self> {
+        Ok(PyBuildConfig { inner: config_from_pydict(config)? })
+    }
+    
+    /// Load a YAML or JSON build file
+    #[staticmethod]
+    fn from_file(path: &str) -> PyResult<Self> {
+        Ok(PyBuildConfig { inner: BuildConfig::from_file(path)? })
+    }
+    
+    fn to_json(&self) -> PyResult<String> {
+        Ok(serde_json::to_string(&self.inner).map_err(SimError::from)?)
+    }
+    
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_to_py(py, &serde_json::to_value(&self.inner).map_err(SimError::from)?)
+    }
+    
+    /// Raise ValueError if the build is out of range or the hunter it
+    /// produces can't be simulated
+    fn validate(&self) -> PyResult<()> {
+        let mut config = self.inner.clone();
+        config.normalize()?;
+        validate_config(&config)?;
+        Ok(())
+    }
+    
+    /// Set inputs from a dict keyed `section.key`, `hunter` or `level`; a
+    /// section name mapped to a dict sets each of its keys. Nothing changes
+    /// if any override is rejected.
+    fn apply_overrides(&mut self, overrides: &Bound<'_, PyDict>) -> PyResult<()> {
+        let serde_json::Value::Object(overrides) = py_to_json_tree(overrides.as_any())? else {
+            unreachable!("a dict converts to a JSON object")
+        };
+        let mut config = self.inner.clone();
+        config.apply_overrides(&overrides)?;
+        self.inner = config;
+        Ok(())
+    }
+    
+    fn copy(&self) -> Self {
+        self.clone()
+    }
+    
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("BuildConfig(hunter={:?}, level={})", self.inner.get_hunter_type(), self.inner.get_level())
+    }
+    
+    #[getter]
+    fn hunter(&self) -> String {
+        format!("{:?}", self.inner.get_hunter_type())
+    }
+    
+    #[setter]
+    fn set_hunter(&mut self, hunter: &str) -> PyResult<()> {
+        let hunter = parse_hunter(hunter)?;
+        match self.inner.meta.as_mut() {
+            Some(meta) => meta.hunter = hunter,
+            None => self.inner.hunter = Some(hunter),
+        }
+        Ok(())
+    }
+    
+    #[getter]
+    fn level(&self) -> i32 {
+        self.inner.get_level()
+    }
+    
+    #[setter]
+    fn set_level(&mut self, level: i32) -> PyResult<()> {
+        Ok(self.inner.set_input("level", level)?)
+    }
+    
+    #[getter]
+    fn stats(&self) -> HashMap<String, i32> {
+        self.inner.stats.clone()
+    }
+    
+    #[setter]
+    fn set_stats(&mut self, stats: HashMap<String, i32>) {
+        self.inner.stats = stats;
+    }
+    
+    #[getter]
+    fn talents(&self) -> HashMap<String, i32> {
+        self.inner.talents.clone()
+    }
+    
+    #[setter]
+    fn set_talents(&mut self, talents: HashMap<String, i32>) {
+        self.inner.talents = talents;
+    }
+    
+    #[getter]
+    fn attributes(&self) -> HashMap<String, i32> {
+        self.inner.attributes.clone()
+    }
+    
+    #[setter]
+    fn set_attributes(&mut self, attributes: HashMap<String, i32>) {
+        self.inner.attributes = attributes;
+    }
+    
+    #[getter]
+    fn inscryptions(&self) -> HashMap<String, i32> {
+        self.inner.inscryptions.clone()
+    }
+    
+    #[setter]
+    fn set_inscryptions(&mut self, inscryptions: HashMap<String, i32>) {
+        self.inner.inscryptions = inscryptions;
+    }
+    
+    #[getter]
+    fn mods(&self) -> HashMap<String, bool> {
+        self.inner.mods.clone()
+    }
+    
+    #[setter]
+    fn set_mods(&mut self, mods: HashMap<String, bool>) {
+        self.inner.mods = mods;
+    }
+    
+    #[getter]
+    fn relics(&self) -> HashMap<String, i32> {
+        self.inner.relics.clone()
+    }
+    
+    #[setter]
+    fn set_relics(&mut self, relics: HashMap<String, i32>) {
+        self.inner.relics = relics;
+    }
+    
+    #[getter]
+    fn gems(&self) -> HashMap<String, i32> {
+        self.inner.gems.clone()
+    }
+    
+    #[setter]
+    fn set_gems(&mut self, gems: HashMap<String, i32>) {
+        self.inner.gems = gems;
+    }
+    
+    #[getter]
+    fn gadgets(&self) -> HashMap<String, i32> {
+        self.inner.gadgets.clone()
+    }
+    
+    #[setter]
+    fn set_gadgets(&mut self, gadgets: HashMap<String, i32>) {
+        self.inner.gadgets = gadgets;
+    }
+    
+    #[getter]
+    fn bonuses(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_to_py(py, &serde_json::to_value(&self.inner.bonuses).map_err(SimError::from)?)
+    }
+    
+    #[setter]
+    fn set_bonuses(&mut self, bonuses: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.bonuses = pydict_to_hashmap_json_global(bonuses)?;
+        Ok(())
+    }
+}
+
+/// Per-run limits for every simulation started afterwards: `max_sim_time`
+/// simulated seconds (runs end as `RunEnd.TimedOut`) and `watchdog_ms` of real
+/// time (`RunEnd.Aborted`). None turns a limit off.
+#[pyfunction]
+#[pyo3(signature = (max_sim_time=None, watchdog_ms=None))]
+fn set_run_limits(max_sim_time: Option<u64>, watchdog_ms: Option<u64>) {
+    crate::simulation::set_max_sim_time(max_sim_time);
+    crate::simulation::set_watchdog(watchdog_ms);
+}
+
+/// Install boss scripts (YAML or JSON text, see `hunter-sim boss-scripts`) on
+/// top of the built-in bosses for the rest of the process, plus the
+/// provisional post-300 ones when `provisional` is set; scripts in `text` win
+/// over provisional ones for the same hunter and stage. Call once, before the
+/// first simulation.
+#[pyfunction]
+#[pyo3(signature = (text="", provisional=false))]
+fn install_boss_scripts(text: &str, provisional: bool) -> PyResult<()> {
+    let mut scripts = if text.trim().is_empty() { Vec::new() } else { crate::boss_script::from_str(text)? };
+    if provisional {
+        let given = scripts.clone();
+        let extra = crate::boss_script::from_str(crate::boss_script::PROVISIONAL)?;
+        scripts.extend(extra.into_iter().filter(|p| !given.iter().any(|s| s.hunter == p.hunter && s.min_stage == p.min_stage)));
+    }
+    Ok(crate::boss_script::install(scripts)?)
+}
+
 /// Get number of threads being used for parallel simulation
 #[pyfunction]
 fn get_thread_count() -> PyResult<usize> {
@@ -239,82 +1176,111 @@ fn get_available_cores() -> PyResult<usize> {
 /// Get hunter stats from a config file for debugging
 #[pyfunction]
 fn get_hunter_stats(config_path: &str) -> PyResult<String> {
-    use crate::hunter::Hunter;
-    
-    let config = BuildConfig::from_file(config_path)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load config: {}", e)))?;
-    
-    let hunter = Hunter::from_config(&config);
-    
-    // Build a JSON object with all stats
-    let stats = serde_json::json!({
-        "hunter_type": format!("{:?}", hunter.hunter_type),
-        "level": hunter.level,
-        "max_hp": hunter.max_hp,
-        "power": hunter.power,
-        "regen": hunter.regen,
-        "damage_reduction": hunter.damage_reduction,
-        "evade_chance": hunter.evade_chance,
-        "effect_chance": hunter.effect_chance,
-        "special_chance": hunter.special_chance,
-        "special_damage": hunter.special_damage,
-        "speed": hunter.speed,
-        "lifesteal": hunter.lifesteal,
-        "loot_mult": hunter.loot_mult,
-        "xp_mult": hunter.xp_mult,
-        "max_revives": hunter.max_revives,
-    });
+    use crate::hunter::HunterStats;
+    
+    let config = BuildConfig::from_file(config_path)?;
+    
+    let stats = HunterStats::from_config(&config);
+    let json = serde_json::to_string(&stats).map_err(SimError::from)?;
+    
+    Ok(json)
+}
+
+/// Every derived hunter stat for a config dict, without simulating
+#[pyfunction]
+fn compute_hunter_stats(py: Python<'_>, config: &Bound<'_, PyDict>) -> PyResult<PyObject> {
+    use crate::hunter::HunterStats;
+    
+    let stats = HunterStats::from_config(&config_from_pydict(config)?);
+    json_to_py(py, &serde_json::to_value(&stats).map_err(SimError::from)?)
+}
+
+/// Regular enemy and boss stats of one stage for a hunter ("borge", "ozzy", "knox")
+#[pyfunction]
+fn compute_enemy_stats(py: Python<'_>, stage: i32, hunter: &str) -> PyResult<PyObject> {
+    use crate::enemy::StageStats;
+    
+    json_to_py(py, &serde_json::to_value(StageStats::new(parse_hunter(hunter)?, stage)).map_err(SimError::from)?)
+}
+
+/// Loot multiplier the engine applies for a config dict
+#[pyfunction]
+fn compute_loot_multiplier(config: &Bound<'_, PyDict>) -> PyResult<f64> {
+    use crate::hunter::HunterStats;
     
-    Ok(stats.to_string())
+    Ok(HunterStats::from_config(&config_from_pydict(config)?).loot_mult)
+}
+
+/// Itemized loot multiplier for a config JSON string (total plus every source)
+#[pyfunction]
+fn explain_loot(config_json: &str) -> PyResult<String> {
+    use crate::hunter::HunterStats;
+    
+    let config = BuildConfig::from_json(config_json)?;
+    let stats = HunterStats::from_config(&config);
+    let json = serde_json::to_string(&stats.loot_breakdown).map_err(SimError::from)?;
+    
+    Ok(json)
+}
+
+/// Encode a config JSON string as a compact share code
+#[pyfunction]
+fn export_share_code(config_json: &str) -> PyResult<String> {
+    let config = BuildConfig::from_json(config_json)?;
+    Ok(crate::share_code::encode(&config)?)
+}
+
+/// Decode a share code back into a config JSON string
+#[pyfunction]
+fn import_share_code(code: &str) -> PyResult<String> {
+    let config = crate::share_code::decode(code)?;
+    let json = serde_json::to_string(&config).map_err(SimError::from)?;
+    
+    Ok(json)
 }
 
 /// Python-callable batch simulation function - simulate multiple configs at once
 #[pyfunction]
-#[pyo3(signature = (config_jsons, num_sims, parallel=false))]
-fn simulate_batch(py: Python<'_>, config_jsons: Vec<String>, num_sims: usize, parallel: bool) -> PyResult<Vec<String>> {
+#[pyo3(signature = (config_jsons, num_sims, parallel=false, threads=None))]
+fn simulate_batch(py: Python<'_>, config_jsons: Vec<String>, num_sims: usize, parallel: bool, threads: Option<usize>) -> PyResult<Vec<String>> {
     // Parse all configs first (inside GIL)
-    let configs: Result<Vec<BuildConfig>, _> = config_jsons.iter()
-        .map(|json| serde_json::from_str(json))
-        .collect();
-    
-    let configs = configs.map_err(|e| 
-        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid config JSON: {}", e))
-    )?;
+    let configs = config_jsons.iter()
+        .map(|json| BuildConfig::from_json(json))
+        .collect::<Result<Vec<BuildConfig>, _>>()?;
+    for config in &configs {
+        validate_config(config)?;
+    }
     
     // Release GIL and run all simulations in parallel
-    let results = py.allow_threads(|| {
+    let results = run_in_pool(py, threads, || {
         configs.iter()
             .map(|config| run_and_aggregate(config, num_sims, parallel))
             .collect::<Vec<_>>()
-    });
+    })?;
     
     // Serialize results (inside GIL)
-    let json_results: Result<Vec<String>, _> = results.iter()
-        .map(|stats| serde_json::to_string(stats))
-        .collect();
-    
-    let json_results = json_results.map_err(|e| 
-        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to serialize results: {}", e))
-    )?;
+    let json_results = results.iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(SimError::from)?;
     
     Ok(json_results)
 }
 
 /// Python-callable batch evaluation function - evaluate multiple builds efficiently
 #[pyfunction]
-#[pyo3(signature = (config_jsons, sims_per_build, seed=42))]
-fn eval_builds(py: Python<'_>, config_jsons: Vec<String>, sims_per_build: usize, seed: u64) -> PyResult<Vec<f32>> {
+#[pyo3(signature = (config_jsons, sims_per_build, seed=42, threads=None))]
+fn eval_builds(py: Python<'_>, config_jsons: Vec<String>, sims_per_build: usize, seed: u64, threads: Option<usize>) -> PyResult<Vec<f32>> {
     // Parse all configs first (inside GIL)
-    let configs: Result<Vec<BuildConfig>, _> = config_jsons.iter()
-        .map(|json| serde_json::from_str(json))
-        .collect();
-    
-    let configs = configs.map_err(|e| 
-        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid config JSON: {}", e))
-    )?;
+    let configs = config_jsons.iter()
+        .map(|json| BuildConfig::from_json(json))
+        .collect::<Result<Vec<BuildConfig>, _>>()?;
+    for config in &configs {
+        validate_config(config)?;
+    }
     
     // Release GIL and run all simulations in parallel with better batching
-    let results = py.allow_threads(|| {
+    let results = run_in_pool(py, threads, || {
         configs.into_par_iter()
             .enumerate()
             .map(|(i, config)| {
@@ -328,14 +1294,15 @@ fn eval_builds(py: Python<'_>, config_jsons: Vec<String>, sims_per_build: usize,
                 total_score / sims_per_build as f32
             })
             .collect::<Vec<f32>>()
-    });
+    })?;
     
     Ok(results)
 }
 
 /// Python-callable batch evaluation function using NumPy arrays for zero-copy performance
 #[pyfunction]
-#[pyo3(signature = (hunter_type, level, base_stats, talent_names, talent_values, attribute_names, attribute_values, sims_per_build, seed=42))]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (hunter_type, level, base_stats, talent_names, talent_values, attribute_names, attribute_values, sims_per_build, seed=42, threads=None))]
 fn eval_builds_np(
     py: Python<'_>,
     hunter_type: u8,  // 0=Borge, 1=Ozzy, 2=Knox
@@ -346,13 +1313,14 @@ fn eval_builds_np(
     attribute_names: Vec<String>,
     attribute_values: PyReadonlyArray2<f64>,  // Shape: (num_builds, num_attributes)
     sims_per_build: usize,
-    seed: u64
+    seed: u64,
+    threads: Option<usize>,
 ) -> PyResult<Py<PyArray1<f32>>> {
     let ht = match hunter_type {
         0 => HunterType::Borge,
         1 => HunterType::Ozzy,
         2 => HunterType::Knox,
-        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid hunter type")),
+        _ => return Err(config_error("invalid", "hunter_type", "Invalid hunter type".to_string())),
     };
 
     let t_array = talent_values.as_array();
@@ -371,7 +1339,7 @@ fn eval_builds_np(
     }
 
     let num_builds = t_array.nrows();
-    let results = py.allow_threads(|| {
+    let results = run_in_pool(py, threads, || {
         (0..num_builds).into_par_iter().map(|i| {
             // Build config from arrays
             let mut talents = HashMap::new();
@@ -397,6 +1365,14 @@ fn eval_builds_np(
                 gems: HashMap::new(),
                 gadgets: HashMap::new(),
                 bonuses: HashMap::new(),
+                mitigation: Default::default(),
+                logging: Default::default(),
+                events: Vec::new(),
+                max_stage: None,
+                start: Default::default(),
+                persistence: Default::default(),
+                stacking: Default::default(),
+                engine_flags: Default::default(),
             };
             
             // Run simulations
@@ -408,31 +1384,31 @@ fn eval_builds_np(
             }
             total_score / sims_per_build as f32
         }).collect::<Vec<f32>>()
-    });
+    })?;
     
     Ok(PyArray1::from_vec(py, results).unbind())
 }
 
-/// Python-callable build generation function - generate multiple valid builds at once
-#[pyfunction]
-#[pyo3(signature = (level, talents, attributes, attribute_dependencies, attribute_point_gates, attribute_exclusions, count))]
-fn generate_builds(
-    py: Python<'_>,
+/// (talents, attributes) allocation pair returned by the build generator
+type GeneratedBuild = (HashMap<String, i32>, HashMap<String, i32>);
+
+/// Helper to build a generator from the talent/attribute definition dicts
+/// `generate_builds` and `optimize` take
+fn build_generator_from_py(
     level: i32,
     talents: &Bound<'_, PyDict>,
     attributes: &Bound<'_, PyDict>,
-    attribute_dependencies: &Bound<'_, PyDict>,
-    attribute_point_gates: &Bound<'_, PyDict>,
+    attribute_dependencies: Option<&Bound<'_, PyDict>>,
+    attribute_point_gates: Option<&Bound<'_, PyDict>>,
     attribute_exclusions: Vec<(String, String)>,
-    count: usize,
-) -> PyResult<Vec<(HashMap<String, i32>, HashMap<String, i32>)>> {
+) -> PyResult<BuildGenerator> {
     // Parse talents
     let mut talent_map = HashMap::new();
     for (key, value) in talents.iter() {
         let name: String = key.extract()?;
         let dict: &Bound<'_, PyDict> = value.downcast()?;
-        let cost: i32 = dict.get_item("cost")?.unwrap().extract()?;
-        let max: i32 = dict.get_item("max")?.unwrap().extract()?;
+        let cost: i32 = required_item(dict, &name, "cost")?.extract()?;
+        let max: i32 = required_item(dict, &name, "max")?.extract()?;
         talent_map.insert(name, TalentInfo { cost, max });
     }
     
@@ -441,15 +1417,13 @@ fn generate_builds(
     for (key, value) in attributes.iter() {
         let name: String = key.extract()?;
         let dict: &Bound<'_, PyDict> = value.downcast()?;
-        let cost: i32 = dict.get_item("cost")?.unwrap().extract()?;
-        let max_val = dict.get_item("max")?.unwrap();
+        let cost: i32 = required_item(dict, &name, "cost")?.extract()?;
+        let max_val = required_item(dict, &name, "max")?;
         
         let max: f64 = if let Ok(v) = max_val.extract::<i32>() {
             v as f64
-        } else if let Ok(v) = max_val.extract::<f64>() {
-            v
         } else {
-            f64::INFINITY
+            max_val.extract::<f64>().unwrap_or(f64::INFINITY)
         };
         
         attr_map.insert(name, AttributeInfo { cost, max });
@@ -457,7 +1431,7 @@ fn generate_builds(
     
     // Parse dependencies
     let mut deps_map = HashMap::new();
-    for (key, value) in attribute_dependencies.iter() {
+    for (key, value) in attribute_dependencies.into_iter().flat_map(|d| d.iter()) {
         let attr_name: String = key.extract()?;
         let deps_dict: &Bound<'_, PyDict> = value.downcast()?;
         
@@ -473,13 +1447,12 @@ fn generate_builds(
     
     // Parse point gates
     let mut gates_map = HashMap::new();
-    for (key, value) in attribute_point_gates.iter() {
+    for (key, value) in attribute_point_gates.into_iter().flat_map(|d| d.iter()) {
         let name: String = key.extract()?;
         let gate: i32 = value.extract()?;
         gates_map.insert(name, gate);
     }
     
-    // Create generator
     let generator = BuildGenerator::new(
         level,
         talent_map,
@@ -488,6 +1461,32 @@ fn generate_builds(
         gates_map,
         attribute_exclusions,
     );
+    generator.validate()?;
+    Ok(generator)
+}
+
+/// Python-callable build generation function - generate multiple valid builds at once
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (level, talents, attributes, attribute_dependencies, attribute_point_gates, attribute_exclusions, count))]
+fn generate_builds(
+    py: Python<'_>,
+    level: i32,
+    talents: &Bound<'_, PyDict>,
+    attributes: &Bound<'_, PyDict>,
+    attribute_dependencies: &Bound<'_, PyDict>,
+    attribute_point_gates: &Bound<'_, PyDict>,
+    attribute_exclusions: Vec<(String, String)>,
+    count: usize,
+) -> PyResult<Vec<GeneratedBuild>> {
+    let generator = build_generator_from_py(
+        level,
+        talents,
+        attributes,
+        Some(attribute_dependencies),
+        Some(attribute_point_gates),
+        attribute_exclusions,
+    )?;
     
     // Generate builds (release GIL)
     let builds = py.allow_threads(|| generator.generate_builds(count));
@@ -495,19 +1494,104 @@ fn generate_builds(
     Ok(builds)
 }
 
+/// Generate `budget` talent/attribute allocations for `config`'s level and
+/// rank them by `objective` (an expression over numeric `simulate_json`
+/// fields, such as `avg_stage` or `0.7 * p10_loot_per_hour + 0.3 *
+/// avg_loot_per_hour`, highest first). Every build runs on the same seeds, so rankings aren't decided by
+/// luck. Returns `{builds_evaluated, objective, builds: [{talents,
+/// attributes, score}]}`, keeping the best `top` if given.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (config, budget, objective="avg_stage", *, talents, attributes, attribute_dependencies=None, attribute_point_gates=None, attribute_exclusions=None, sims_per_build=100, seed=42, top=None, threads=None))]
+fn optimize(
+    py: Python<'_>,
+    config: ConfigArg<'_>,
+    budget: usize,
+    objective: &str,
+    talents: &Bound<'_, PyDict>,
+    attributes: &Bound<'_, PyDict>,
+    attribute_dependencies: Option<&Bound<'_, PyDict>>,
+    attribute_point_gates: Option<&Bound<'_, PyDict>>,
+    attribute_exclusions: Option<Vec<(String, String)>>,
+    sims_per_build: usize,
+    seed: u64,
+    top: Option<usize>,
+    threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let base = config.into_config()?;
+    let ranking = Objective::parse(objective)?;
+    let generator = build_generator_from_py(
+        base.get_level(),
+        talents,
+        attributes,
+        attribute_dependencies,
+        attribute_point_gates,
+        attribute_exclusions.unwrap_or_default(),
+    )?;
+    
+    let mut scored = run_in_pool(py, threads, || -> Result<Vec<serde_json::Value>, SimError> {
+        generator
+            .generate_builds(budget)
+            .into_par_iter()
+            .map(|(talents, attributes)| {
+                let mut config = base.clone();
+                config.talents = talents;
+                config.attributes = attributes;
+                let stats = Simulator::builder().config(config.clone()).sims(sims_per_build).seed(seed).parallel(false).build()?.run();
+                let score = ranking.score(&stats);
+                Ok(serde_json::json!({ "talents": config.talents, "attributes": config.attributes, "score": score }))
+            })
+            .collect()
+    })??;
+    let score = |build: &serde_json::Value| build["score"].as_f64().unwrap_or(f64::NAN);
+    scored.sort_by(|a, b| score(b).total_cmp(&score(a)));
+    let builds_evaluated = scored.len();
+    if let Some(top) = top {
+        scored.truncate(top);
+    }
+    json_to_py(py, &serde_json::json!({ "builds_evaluated": builds_evaluated, "objective": objective, "builds": scored }))
+}
+
 /// Python module definition
 #[pymodule]
 fn rust_sim(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ConfigError", m.py().get_type::<exceptions::ConfigError>())?;
+    m.add("SimulationError", m.py().get_type::<exceptions::SimulationError>())?;
     m.add_function(wrap_pyfunction!(simulate, m)?)?;
     m.add_function(wrap_pyfunction!(simulate_json, m)?)?;
     m.add_function(wrap_pyfunction!(simulate_from_file, m)?)?;
+    m.add_function(wrap_pyfunction!(start_simulation, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_async, m)?)?;
+    m.add_class::<SimulationJob>()?;
+    m.add_function(wrap_pyfunction!(simulate_detailed, m)?)?;
+    m.add_class::<SimResult>()?;
+    m.add_class::<BandResult>()?;
+    m.add_class::<BossEntry>()?;
+    m.add_class::<RunEnd>()?;
+    m.add_class::<FightPhase>()?;
+    m.add_class::<PhaseSustain>()?;
+    m.add_function(wrap_pyfunction!(simulate_arrays, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(clear_delta_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep, m)?)?;
+    m.add_function(wrap_pyfunction!(compare, m)?)?;
     m.add_function(wrap_pyfunction!(simulate_batch, m)?)?;
     m.add_function(wrap_pyfunction!(eval_builds, m)?)?;
     m.add_function(wrap_pyfunction!(eval_builds_np, m)?)?;
     m.add_function(wrap_pyfunction!(create_config, m)?)?;
+    m.add_class::<PyBuildConfig>()?;
+    m.add_function(wrap_pyfunction!(set_run_limits, m)?)?;
+    m.add_function(wrap_pyfunction!(install_boss_scripts, m)?)?;
     m.add_function(wrap_pyfunction!(get_thread_count, m)?)?;
     m.add_function(wrap_pyfunction!(get_available_cores, m)?)?;
     m.add_function(wrap_pyfunction!(get_hunter_stats, m)?)?;
     m.add_function(wrap_pyfunction!(generate_builds, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize, m)?)?;
+    m.add_function(wrap_pyfunction!(explain_loot, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_hunter_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_enemy_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_loot_multiplier, m)?)?;
+    m.add_function(wrap_pyfunction!(export_share_code, m)?)?;
+    m.add_function(wrap_pyfunction!(import_share_code, m)?)?;
     Ok(())
 }
@@ -0,0 +1,112 @@
+// Screening engine kernel: one invocation per run, mirrors screening::run_cpu
+
+struct Params {
+    max_hp: f32,
+    power: f32,
+    speed: f32,
+    regen: f32,
+    mitigation: f32,
+    evade_chance: f32,
+    crit_chance: f32,
+    crit_mult: f32,
+    lifesteal: f32,
+    max_revives: u32,
+    max_stage: u32,
+    runs: u32,
+    max_fight_events: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+struct Foe {
+    hp: f32,
+    power: f32,
+    speed: f32,
+    regen: f32,
+    damage_reduction: f32,
+    special_chance: f32,
+    special_damage: f32,
+    _pad: f32,
+}
+
+@group(0) @binding(0) var<storage, read> params: Params;
+@group(0) @binding(1) var<storage, read> stages: array<Foe>;
+@group(0) @binding(2) var<storage, read_write> results: array<u32>;
+
+var<private> rng_state: u32;
+
+fn rng_f32() -> f32 {
+    rng_state = rng_state * 747796405u + 2891336453u;
+    let word = ((rng_state >> ((rng_state >> 28u) + 4u)) ^ rng_state) * 277803737u;
+    let x = (word >> 22u) ^ word;
+    return f32(x >> 8u) * (1.0 / 16777216.0);
+}
+
+fn run(seed: u32) -> u32 {
+    rng_state = seed * 747796405u + 2891336453u;
+    var hp = params.max_hp;
+    var revives = 0u;
+    var stage = 1u;
+    var elapsed = 0.0;
+
+    while (stage <= params.max_stage) {
+        let foe = stages[stage - 1u];
+        var fights = 10u;
+        if (stage % 100u == 0u) {
+            fights = 1u;
+        }
+        for (var f = 0u; f < fights; f = f + 1u) {
+            var foe_hp = foe.hp;
+            var hunter_at = elapsed + params.speed;
+            var foe_at = elapsed + foe.speed;
+            var events = 0u;
+            while (foe_hp > 0.0) {
+                events = events + 1u;
+                if (events > params.max_fight_events) {
+                    return stage;
+                }
+                let regen_at = elapsed + 1.0;
+                if (hunter_at <= foe_at && hunter_at <= regen_at) {
+                    var dmg = params.power;
+                    if (rng_f32() < params.crit_chance) {
+                        dmg = dmg * params.crit_mult;
+                    }
+                    foe_hp = foe_hp - dmg * (1.0 - foe.damage_reduction);
+                    hp = min(hp + dmg * params.lifesteal, params.max_hp);
+                    hunter_at = hunter_at + params.speed;
+                } else if (foe_at <= regen_at) {
+                    var dmg = foe.power;
+                    if (rng_f32() < foe.special_chance) {
+                        dmg = dmg * foe.special_damage;
+                    }
+                    if (rng_f32() >= params.evade_chance) {
+                        hp = hp - dmg * (1.0 - params.mitigation);
+                    }
+                    foe_at = foe_at + foe.speed;
+                    if (hp <= 0.0) {
+                        if (revives >= params.max_revives) {
+                            return stage;
+                        }
+                        revives = revives + 1u;
+                        hp = params.max_hp * 0.8;
+                    }
+                } else {
+                    hp = min(hp + params.regen, params.max_hp);
+                    foe_hp = min(foe_hp + foe.regen, foe.hp);
+                    elapsed = regen_at;
+                }
+            }
+        }
+        stage = stage + 1u;
+    }
+    return params.max_stage;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.runs) {
+        return;
+    }
+    results[id.x] = run(id.x);
+}
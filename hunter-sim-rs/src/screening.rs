@@ -0,0 +1,242 @@
+//! Simplified batch engine for screening many builds quickly
+//!
+//! Screening runs a stripped-down duel loop: the hunter's and each enemy's
+//! primary attacks, crits, evades, mitigation, lifesteal, regen and revives,
+//! with no talent procs, boss secondaries, loot or tracing. It is meant for
+//! ranking candidate builds before verifying the finalists with the full
+//! engine, not for reporting stages. Everything is in f32 with a counter-based
+//! RNG so the same run gives (nearly) the same stage on the CPU and on the
+//! experimental wgpu backend (`gpu` feature), which is used when available.
+
+use serde::Serialize;
+use std::time::Instant;
+
+use crate::config::{BuildConfig, HunterType};
+use crate::enemy::Enemy;
+use crate::hunter::Hunter;
+use rayon::prelude::*;
+
+/// Events one fight may take before the run ends on that stage. Guards against
+/// stalemates (neither side can out-damage the other's regen) hanging a GPU
+/// dispatch, and stays under the 65535-iteration loop limit some software
+/// drivers (llvmpipe) silently enforce.
+pub const MAX_FIGHT_EVENTS: u32 = 50_000;
+
+/// Hunter inputs of the screening engine (matches `Params` in screen.wgsl)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScreenHunter {
+    pub max_hp: f32,
+    pub power: f32,          // Per attack (Knox: whole salvo)
+    pub speed: f32,          // Seconds between attacks
+    pub regen: f32,          // HP per regen tick
+    pub mitigation: f32,     // All non-crit layers combined
+    pub evade_chance: f32,
+    pub crit_chance: f32,    // Borge crit, Ozzy multistrike, Knox charge
+    pub crit_mult: f32,      // Damage multiplier of a crit (a multistrike adds an extra hit)
+    pub lifesteal: f32,
+    pub max_revives: u32,
+    pub max_stage: u32,
+}
+
+/// Enemy inputs of one stage (matches `Foe` in screen.wgsl)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct ScreenFoe {
+    pub hp: f32,
+    pub power: f32,
+    pub speed: f32,
+    pub regen: f32,
+    pub damage_reduction: f32,
+    pub special_chance: f32,
+    pub special_damage: f32,
+}
+
+impl ScreenFoe {
+    fn from_enemy(e: &Enemy) -> Self {
+        Self {
+            hp: e.max_hp as f32,
+            power: e.power as f32,
+            speed: e.speed as f32,
+            regen: e.regen as f32,
+            damage_reduction: e.damage_reduction as f32,
+            special_chance: e.special_chance as f32,
+            special_damage: e.special_damage as f32,
+        }
+    }
+}
+
+/// Everything a screening batch reads: the hunter plus each stage's foe
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenInput {
+    pub hunter: ScreenHunter,
+    pub stages: Vec<ScreenFoe>,  // Index stage - 1: the boss on boss stages, else a regular enemy
+}
+
+impl ScreenInput {
+    pub fn from_config(config: &BuildConfig) -> Self {
+        let h = Hunter::from_config(config);
+        let (power, crit_chance, crit_mult) = match h.hunter_type {
+            HunterType::Borge => (h.power, h.special_chance, h.special_damage),
+            HunterType::Ozzy => (h.power, h.special_chance, 1.0 + h.special_damage),
+            HunterType::Knox => (h.power * h.salvo_projectiles.max(1) as f64, h.charge_chance, 1.0 + h.charge_gained),
+        };
+        let hunter = ScreenHunter {
+            max_hp: h.max_hp as f32,
+            power: power as f32,
+            speed: h.speed as f32,
+            regen: h.regen as f32,
+            mitigation: h.mitigation.effective(false) as f32,
+            evade_chance: h.evade_chance as f32,
+            crit_chance: crit_chance as f32,
+            crit_mult: crit_mult as f32,
+            lifesteal: h.lifesteal as f32,
+            max_revives: h.max_revives.max(0) as u32,
+            max_stage: h.max_stage.max(1) as u32,
+        };
+        let stages = (1..=h.max_stage.max(1))
+            .map(|stage| {
+                let enemy = if stage % 100 == 0 { Enemy::new_boss(stage, h.hunter_type) } else { Enemy::new(1, stage, h.hunter_type) };
+                ScreenFoe::from_enemy(&enemy)
+            })
+            .collect();
+        Self { hunter, stages }
+    }
+}
+
+/// Where a screening batch ran
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    Cpu,
+    Gpu { adapter: String },
+}
+
+/// Result of a screening batch
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenReport {
+    pub backend: Backend,
+    pub fallback_reason: Option<String>,  // Why the GPU wasn't used, if it was requested
+    pub runs: usize,
+    pub seconds: f64,
+    pub avg_stage: f64,
+    pub min_stage: u32,
+    pub max_stage: u32,
+    pub stages: Vec<u32>,  // Final stage per run, in seed order
+}
+
+/// Counter-based RNG shared with screen.wgsl (PCG hash of a per-run state)
+struct ScreenRng(u32);
+
+impl ScreenRng {
+    fn new(seed: u32) -> Self {
+        Self(seed.wrapping_mul(747796405).wrapping_add(2891336453))
+    }
+
+    fn f32(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(747796405).wrapping_add(2891336453);
+        let word = ((self.0 >> ((self.0 >> 28) + 4)) ^ self.0).wrapping_mul(277803737);
+        let x = (word >> 22) ^ word;
+        (x >> 8) as f32 * (1.0 / 16_777_216.0)
+    }
+}
+
+/// Run one screening run on the CPU (reference for the shader)
+pub fn run_cpu(input: &ScreenInput, seed: u32) -> u32 {
+    let h = &input.hunter;
+    let mut rng = ScreenRng::new(seed);
+    let mut hp = h.max_hp;
+    let mut revives = 0;
+    let mut stage = 1u32;
+    let mut elapsed = 0.0f32;  // Whole seconds, advanced by regen ticks
+
+    while stage <= h.max_stage {
+        let foe = input.stages[stage as usize - 1];
+        let fights = if stage.is_multiple_of(100) { 1 } else { 10 };
+        for _ in 0..fights {
+            let mut foe_hp = foe.hp;
+            let mut hunter_at = elapsed + h.speed;
+            let mut foe_at = elapsed + foe.speed;
+            let mut events = 0u32;
+            while foe_hp > 0.0 {
+                events += 1;
+                if events > MAX_FIGHT_EVENTS {
+                    return stage;
+                }
+                let regen_at = elapsed + 1.0;
+                if hunter_at <= foe_at && hunter_at <= regen_at {
+                    let mut dmg = h.power;
+                    if rng.f32() < h.crit_chance {
+                        dmg *= h.crit_mult;
+                    }
+                    foe_hp -= dmg * (1.0 - foe.damage_reduction);
+                    hp = (hp + dmg * h.lifesteal).min(h.max_hp);
+                    hunter_at += h.speed;
+                } else if foe_at <= regen_at {
+                    let mut dmg = foe.power;
+                    if rng.f32() < foe.special_chance {
+                        dmg *= foe.special_damage;
+                    }
+                    if rng.f32() >= h.evade_chance {
+                        hp -= dmg * (1.0 - h.mitigation);
+                    }
+                    foe_at += foe.speed;
+                    if hp <= 0.0 {
+                        if revives >= h.max_revives {
+                            return stage;
+                        }
+                        revives += 1;
+                        hp = h.max_hp * 0.8;
+                    }
+                } else {
+                    hp = (hp + h.regen).min(h.max_hp);
+                    foe_hp = (foe_hp + foe.regen).min(foe.hp);
+                    elapsed = regen_at;
+                }
+            }
+        }
+        stage += 1;
+    }
+    h.max_stage
+}
+
+/// Screen `count` runs (seeds 0..count) on the GPU when the `gpu` feature is
+/// built and an adapter is found, otherwise on the CPU
+pub fn screen(config: &BuildConfig, count: usize, prefer_gpu: bool) -> ScreenReport {
+    let input = ScreenInput::from_config(config);
+    let start = Instant::now();
+    let mut fallback_reason = None;
+
+    let gpu = if prefer_gpu { gpu_stages(&input, count) } else { Err("CPU requested".to_string()) };
+    let (backend, stages) = match gpu {
+        Ok((adapter, stages)) => (Backend::Gpu { adapter }, stages),
+        Err(reason) => {
+            if prefer_gpu {
+                tracing::info!(%reason, "GPU screening unavailable, falling back to CPU");
+                fallback_reason = Some(reason);
+            }
+            let stages = (0..count as u32).into_par_iter().map(|seed| run_cpu(&input, seed)).collect();
+            (Backend::Cpu, stages)
+        }
+    };
+
+    let n = stages.len().max(1) as f64;
+    ScreenReport {
+        backend,
+        fallback_reason,
+        runs: stages.len(),
+        seconds: start.elapsed().as_secs_f64(),
+        avg_stage: stages.iter().map(|&s| s as f64).sum::<f64>() / n,
+        min_stage: stages.iter().copied().min().unwrap_or(0),
+        max_stage: stages.iter().copied().max().unwrap_or(0),
+        stages,
+    }
+}
+
+#[cfg(feature = "gpu")]
+fn gpu_stages(input: &ScreenInput, count: usize) -> Result<(String, Vec<u32>), String> {
+    crate::gpu::run(input, count)
+}
+
+#[cfg(not(feature = "gpu"))]
+fn gpu_stages(_input: &ScreenInput, _count: usize) -> Result<(String, Vec<u32>), String> {
+    Err("built without the `gpu` feature".to_string())
+}
@@ -0,0 +1,718 @@
+//! HTTP server mode (`hunter-sim serve`)
+//!
+//! A small JSON API so web frontends and spreadsheets can use the simulator
+//! without a local Python or CLI install. Request and response bodies mirror
+//! the Python bindings: `POST /simulate` takes one config like
+//! `simulate_json`, `POST /compare` a list like `simulate_batch`, and
+//! `POST /optimize` the `generate_builds` definitions plus a base config,
+//! scoring each generated build by average final stage like `eval_builds`.
+//! Both `compare` and `optimize` take an optional `objective` expression over
+//! the stats fields (see `filter::Objective`) to score and rank by instead.
+//!
+//! Every POST starts a background job and answers `202` with its id; poll
+//! `GET /jobs/{id}` for progress and the result, or send `"wait": true` to
+//! get the finished job in the response. `GET /jobs/{id}/events` streams
+//! progress and running aggregates as server-sent events for live charts.
+//! Finished jobs are kept in memory for an hour, the newest thousand of
+//! them, or until `DELETE /jobs/{id}`.
+//!
+//! Boss scripts are the ones installed at startup, with
+//! `hunter-sim --boss-scripts FILE serve`.
+//!
+//! No cross-origin headers are sent unless the server is started with
+//! allowed origins (`--allow-origin`), so by default a web page on another
+//! site can't drive a server bound to a local port.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::build_generator::{AttributeInfo, BuildGenerator, TalentInfo};
+use crate::config::{BuildConfig, MAX_CONFIG_BYTES};
+use crate::error::SimError;
+use crate::simulation::{run_simulation_with_rng, validate_config, FastRng};
+use crate::simulator::{BatchProgress, Simulator};
+use crate::filter::Objective;
+use crate::stats::{AggregatedStats, RunningSnapshot};
+
+/// Largest request body accepted (a `compare` may carry many configs)
+const MAX_BODY_BYTES: usize = 16 * MAX_CONFIG_BYTES;
+
+/// Most builds one `optimize` job may generate
+const MAX_OPTIMIZE_BUILDS: usize = 100_000;
+
+/// Most simulations per config (`num_sims`, `sims_per_build`)
+const MAX_SIMS: usize = 1_000_000;
+
+/// Most simulation runs one job may add up to
+const MAX_JOB_RUNS: usize = 100_000_000;
+
+/// How long a finished job is kept, and how many finished jobs at most
+const JOB_TTL: Duration = Duration::from_secs(60 * 60);
+const MAX_FINISHED_JOBS: usize = 1000;
+
+/// Default and allowed range of the event stream interval
+const EVENT_INTERVAL_MS: u64 = 250;
+const EVENT_INTERVAL_RANGE_MS: (u64, u64) = (50, 10_000);
+
+/// Which endpoint started a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Simulate,
+    Compare,
+    Optimize,
+}
+
+/// Lifecycle of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug)]
+struct JobState {
+    status: JobStatus,
+    result: Option<Value>,
+    error: Option<String>,
+    seconds: Option<f64>,
+}
+
+/// Live progress of a job: one [`BatchProgress`] per config for
+/// `simulate`/`compare`, or a bare run count for `optimize`
+#[derive(Debug, Default)]
+struct Progress {
+    completed: AtomicUsize,
+    builds: Vec<Arc<BatchProgress>>,
+}
+
+impl Progress {
+    fn completed(&self) -> usize {
+        self.completed.load(Ordering::Relaxed) + self.builds.iter().map(|b| b.completed()).sum::<usize>()
+    }
+}
+
+/// A background job and its progress in simulation runs
+#[derive(Debug)]
+struct Job {
+    id: u64,
+    kind: JobKind,
+    total: usize,
+    progress: Arc<Progress>,
+    started: Instant,
+    state: Mutex<JobState>,
+    finished: Condvar,
+}
+
+/// What `GET /jobs/{id}` returns
+#[derive(Debug, Clone, Serialize)]
+pub struct JobView {
+    pub id: u64,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub completed: usize,  // Simulation runs finished so far
+    pub total: usize,
+    pub seconds: f64,      // Wall time so far, or until it finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Job {
+    fn view(&self, with_result: bool) -> JobView {
+        let state = self.state.lock().unwrap();
+        JobView {
+            id: self.id,
+            kind: self.kind,
+            status: state.status,
+            completed: self.progress.completed(),
+            total: self.total,
+            seconds: state.seconds.unwrap_or_else(|| self.started.elapsed().as_secs_f64()),
+            result: if with_result { state.result.clone() } else { None },
+            error: state.error.clone(),
+        }
+    }
+
+    fn finish(&self, outcome: Result<Value, String>) {
+        let mut state = self.state.lock().unwrap();
+        match outcome {
+            Ok(result) => {
+                state.status = JobStatus::Done;
+                state.result = Some(result);
+            }
+            Err(error) => {
+                state.status = JobStatus::Failed;
+                state.error = Some(error);
+            }
+        }
+        state.seconds = Some(self.started.elapsed().as_secs_f64());
+        self.finished.notify_all();
+    }
+
+    /// When the job finished, if it has
+    fn finished_at(&self) -> Option<Instant> {
+        let seconds = self.state.lock().unwrap().seconds?;
+        Some(self.started + Duration::from_secs_f64(seconds))
+    }
+
+    fn wait(&self) {
+        let mut state = self.state.lock().unwrap();
+        while state.status == JobStatus::Running {
+            state = self.finished.wait(state).unwrap();
+        }
+    }
+
+    /// Wait up to `timeout` for the job to finish; true once it has
+    fn wait_timeout(&self, timeout: Duration) -> bool {
+        let state = self.state.lock().unwrap();
+        let (state, _) = self.finished.wait_timeout_while(state, timeout, |s| s.status == JobStatus::Running).unwrap();
+        state.status != JobStatus::Running
+    }
+
+    fn progress_event(&self) -> ProgressEvent {
+        ProgressEvent {
+            id: self.id,
+            completed: self.progress.completed(),
+            total: self.total,
+            seconds: self.started.elapsed().as_secs_f64(),
+            aggregates: self.progress.builds.iter().map(|b| b.snapshot()).collect(),
+        }
+    }
+}
+
+/// One `progress` event of `GET /jobs/{id}/events`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressEvent {
+    pub id: u64,
+    pub completed: usize,
+    pub total: usize,
+    pub seconds: f64,
+    pub aggregates: Vec<RunningSnapshot>,  // In config order
+}
+
+/// In-memory job table shared by the request threads
+#[derive(Debug, Default)]
+struct JobStore {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+}
+
+impl JobStore {
+    /// Register a job tracking `builds` batches and run `work` on its
+    /// own thread
+    fn spawn<F>(&self, kind: JobKind, total: usize, builds: usize, work: F) -> Arc<Job>
+    where
+        F: FnOnce(Arc<Progress>) -> Result<Value, String> + Send + 'static,
+    {
+        let job = Arc::new(Job {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+            kind,
+            total,
+            progress: Arc::new(Progress { completed: AtomicUsize::new(0), builds: (0..builds).map(|_| Arc::default()).collect() }),
+            started: Instant::now(),
+            state: Mutex::new(JobState { status: JobStatus::Running, result: None, error: None, seconds: None }),
+            finished: Condvar::new(),
+        });
+        self.evict();
+        self.jobs.lock().unwrap().insert(job.id, job.clone());
+
+        let worker = job.clone();
+        std::thread::spawn(move || {
+            tracing::info!(id = worker.id, kind = ?worker.kind, total = worker.total, "job started");
+            let outcome = work(worker.progress.clone());
+            if let Err(ref error) = outcome {
+                tracing::warn!(id = worker.id, %error, "job failed");
+            }
+            worker.finish(outcome);
+        });
+        job
+    }
+
+    fn get(&self, id: u64) -> Option<Arc<Job>> {
+        self.evict();
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    fn list(&self) -> Vec<JobView> {
+        self.evict();
+        let mut views: Vec<JobView> = self.jobs.lock().unwrap().values().map(|j| j.view(false)).collect();
+        views.sort_by_key(|v| v.id);
+        views
+    }
+
+    fn remove(&self, id: u64) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().remove(&id)
+    }
+
+    /// Drop finished jobs older than `JOB_TTL`, then the oldest past
+    /// `MAX_FINISHED_JOBS`; running jobs are always kept
+    fn evict(&self) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut finished: Vec<(Instant, u64)> = jobs.values().filter_map(|j| Some((j.finished_at()?, j.id))).collect();
+        finished.sort_unstable();
+        let expired = finished.iter().take_while(|(at, _)| at.elapsed() > JOB_TTL).count();
+        let excess = finished.len().saturating_sub(MAX_FINISHED_JOBS);
+        for (_, id) in &finished[..expired.max(excess)] {
+            jobs.remove(id);
+        }
+    }
+}
+
+/// A config given either inline as an object or as a JSON string (as the
+/// Python bindings take it)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ConfigBody {
+    Json(String),
+    Object(Value),
+}
+
+impl ConfigBody {
+    fn parse(&self) -> Result<BuildConfig, ApiError> {
+        let config = match self {
+            ConfigBody::Json(text) => BuildConfig::from_json(text),
+            ConfigBody::Object(value) => BuildConfig::from_json(&value.to_string()),
+        }
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        validate_config(&config).map_err(|e| ApiError::bad_request(e.to_string()))?;
+        Ok(config)
+    }
+}
+
+fn default_sims() -> usize {
+    100
+}
+
+fn default_seed() -> u64 {
+    42
+}
+
+/// Body of `POST /simulate` (mirrors `simulate_json`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SimulateRequest {
+    config: ConfigBody,
+    #[serde(default = "default_sims")]
+    num_sims: usize,
+    #[serde(default)]
+    parallel: bool,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    wait: bool,
+}
+
+/// Body of `POST /compare` (mirrors `simulate_batch`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CompareRequest {
+    configs: Vec<ConfigBody>,
+    #[serde(default = "default_sims")]
+    num_sims: usize,
+    #[serde(default)]
+    parallel: bool,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    objective: Option<String>,  // Adds `score` and `rank` (1 is best) to each config's stats
+    #[serde(default)]
+    wait: bool,
+}
+
+/// Attribute definition as `generate_builds` takes it; a missing or null
+/// `max` means unlimited
+#[derive(Debug, Deserialize)]
+struct AttributeDef {
+    cost: i32,
+    #[serde(default)]
+    max: Option<f64>,
+}
+
+/// Body of `POST /optimize` (the `generate_builds` definitions plus the
+/// base build whose talents and attributes are replaced, scored like
+/// `eval_builds`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OptimizeRequest {
+    config: ConfigBody,
+    talents: HashMap<String, TalentInfo>,
+    attributes: HashMap<String, AttributeDef>,
+    #[serde(default)]
+    attribute_dependencies: HashMap<String, HashMap<String, i32>>,
+    #[serde(default)]
+    attribute_point_gates: HashMap<String, i32>,
+    #[serde(default)]
+    attribute_exclusions: Vec<(String, String)>,
+    count: usize,
+    #[serde(default = "default_sims")]
+    sims_per_build: usize,
+    #[serde(default = "default_seed")]
+    seed: u64,
+    #[serde(default)]
+    top: Option<usize>,  // Keep only the best N builds (default: all)
+    #[serde(default)]
+    objective: Option<String>,  // What to rank by (default: avg_stage)
+    #[serde(default)]
+    wait: bool,
+}
+
+/// One generated build and its score
+#[derive(Debug, Clone, Serialize)]
+struct ScoredBuild {
+    talents: HashMap<String, i32>,
+    attributes: HashMap<String, i32>,
+    avg_stage: f64,
+    score: f64,  // The objective's value; avg_stage without one
+}
+
+/// An error answered as `{"error": ...}`
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self { status: 400, message: message.into() }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self { status: 404, message: message.into() }
+    }
+}
+
+/// A bound, not yet running API server
+pub struct ApiServer {
+    server: tiny_http::Server,
+    jobs: Arc<JobStore>,
+    origins: Arc<Vec<String>>,
+}
+
+/// Bind the API server to `addr` (e.g. `127.0.0.1:8080`)
+pub fn bind(addr: &str) -> io::Result<ApiServer> {
+    let server = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+    Ok(ApiServer { server, jobs: Arc::new(JobStore::default()), origins: Arc::default() })
+}
+
+impl ApiServer {
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Let web pages from `origins` (e.g. `https://example.com`, or `*` for
+    /// any) call the API; none may by default
+    pub fn allow_origins(mut self, origins: Vec<String>) -> Self {
+        self.origins = Arc::new(origins);
+        self
+    }
+
+    /// Serve requests until the process exits, one thread per request
+    pub fn run(self) {
+        for request in self.server.incoming_requests() {
+            let (jobs, origins) = (self.jobs.clone(), self.origins.clone());
+            std::thread::spawn(move || handle(request, &jobs, &origins));
+        }
+    }
+}
+
+/// The request's `Origin`, if it is one the server allows
+fn allowed_origin(request: &Request, origins: &[String]) -> Option<String> {
+    let origin = request.headers().iter().find(|h| h.field.equiv("Origin"))?.value.as_str();
+    origins.iter().any(|o| o == "*" || o == origin).then(|| origin.to_string())
+}
+
+fn handle(mut request: Request, jobs: &JobStore, origins: &[String]) {
+    let cors = allowed_origin(&request, origins);
+    let method = request.method().clone();
+    let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    let (path, query) = (path.trim_end_matches('/').to_string(), query.to_string());
+    tracing::debug!(%method, %path, "request");
+
+    if let (Method::Get, Some(id)) = (&method, path.strip_prefix("/jobs/").and_then(|p| p.strip_suffix("/events"))) {
+        match find_job(jobs, id).and_then(|job| Ok((job, event_interval(&query)?))) {
+            Ok((job, interval)) => stream_events(request, &job, interval, cors.as_deref()),
+            Err(e) => respond(request, e.status, &json!({ "error": e.message }), cors.as_deref()),
+        }
+        return;
+    }
+
+    let outcome = if method == Method::Options {
+        Ok((204, Value::Null))
+    } else {
+        read_body(&mut request).and_then(|body| route(&method, &path, &body, jobs))
+    };
+    let (status, body) = match outcome {
+        Ok(ok) => ok,
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+    respond(request, status, &body, cors.as_deref());
+}
+
+fn read_body(request: &mut Request) -> Result<String, ApiError> {
+    if request.body_length().unwrap_or(0) > MAX_BODY_BYTES {
+        return Err(ApiError { status: 413, message: format!("request body exceeds {} bytes", MAX_BODY_BYTES) });
+    }
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::bad_request(format!("failed to read body: {}", e)))?;
+    if body.len() > MAX_BODY_BYTES {
+        return Err(ApiError { status: 413, message: format!("request body exceeds {} bytes", MAX_BODY_BYTES) });
+    }
+    Ok(body)
+}
+
+fn route(method: &Method, path: &str, body: &str, jobs: &JobStore) -> Result<(u16, Value), ApiError> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match (method, segments.as_slice()) {
+        (Method::Get, []) | (Method::Get, ["health"]) => Ok((200, json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))),
+        (Method::Post, ["simulate"]) => simulate(parse_body(body)?, jobs),
+        (Method::Post, ["compare"]) => compare(parse_body(body)?, jobs),
+        (Method::Post, ["optimize"]) => optimize(parse_body(body)?, jobs),
+        (Method::Get, ["jobs"]) => Ok((200, json!({ "jobs": jobs.list() }))),
+        (Method::Get, ["jobs", id]) => Ok((200, json!(find_job(jobs, id)?.view(true)))),
+        (Method::Delete, ["jobs", id]) => {
+            let job = find_job(jobs, id)?;
+            if job.view(false).status == JobStatus::Running {
+                return Err(ApiError { status: 409, message: format!("job {} is still running", job.id) });
+            }
+            jobs.remove(job.id);
+            Ok((200, json!(job.view(false))))
+        }
+        (_, ["simulate"] | ["compare"] | ["optimize"] | ["jobs"] | ["jobs", _] | ["jobs", _, "events"] | ["health"]) => {
+            Err(ApiError { status: 405, message: format!("{} is not allowed on /{}", method, segments.join("/")) })
+        }
+        _ => Err(ApiError::not_found(format!("no endpoint at {}", path))),
+    }
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, ApiError> {
+    let mut de = serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(&mut de).map_err(|e| {
+        let path = e.path().to_string();
+        if path == "." {
+            ApiError::bad_request(e.into_inner().to_string())
+        } else {
+            ApiError::bad_request(format!("{}: {}", path, e.into_inner()))
+        }
+    })
+}
+
+fn find_job(jobs: &JobStore, id: &str) -> Result<Arc<Job>, ApiError> {
+    let parsed = id.parse::<u64>().map_err(|_| ApiError::bad_request(format!("invalid job id `{}`", id)))?;
+    jobs.get(parsed).ok_or_else(|| ApiError::not_found(format!("no job {}", parsed)))
+}
+
+fn parse_objective(expr: Option<&str>) -> Result<Option<Objective>, ApiError> {
+    expr.map(Objective::parse).transpose().map_err(|e| ApiError::bad_request(e.to_string()))
+}
+
+fn check_sims(num_sims: usize) -> Result<(), ApiError> {
+    if num_sims == 0 {
+        return Err(ApiError::bad_request(SimError::NoSimulations.to_string()));
+    }
+    if num_sims > MAX_SIMS {
+        return Err(ApiError::bad_request(format!("expected at most {} simulations per config, got {}", MAX_SIMS, num_sims)));
+    }
+    Ok(())
+}
+
+/// Simulation runs of `per` runs for each of `count` configs or builds
+fn job_runs(count: usize, per: usize) -> Result<usize, ApiError> {
+    count
+        .checked_mul(per)
+        .filter(|&runs| runs <= MAX_JOB_RUNS)
+        .ok_or_else(|| ApiError::bad_request(format!("{} x {} simulations exceeds the {} a job may run", count, per, MAX_JOB_RUNS)))
+}
+
+fn simulator(config: BuildConfig, num_sims: usize, parallel: bool, seed: Option<u64>, progress: &Progress, slot: usize) -> Result<Simulator, ApiError> {
+    let observer = progress.builds[slot].clone();
+    let mut builder = Simulator::builder().config(config).sims(num_sims).parallel(parallel).observer(observer);
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    builder.build().map_err(|e| ApiError::bad_request(e.to_string()))
+}
+
+/// Answer with the new job, or with the finished job when the client waits
+fn accepted(job: Arc<Job>, wait: bool) -> Result<(u16, Value), ApiError> {
+    if wait {
+        job.wait();
+        return Ok((200, json!(job.view(true))));
+    }
+    Ok((202, json!(job.view(false))))
+}
+
+fn simulate(req: SimulateRequest, jobs: &JobStore) -> Result<(u16, Value), ApiError> {
+    let config = req.config.parse()?;
+    check_sims(req.num_sims)?;
+    let (num_sims, parallel, seed) = (req.num_sims, req.parallel, req.seed);
+    let job = jobs.spawn(JobKind::Simulate, num_sims, 1, move |progress| {
+        let stats = simulator(config, num_sims, parallel, seed, &progress, 0).map_err(|e| e.message)?.run();
+        serde_json::to_value(stats).map_err(|e| e.to_string())
+    });
+    accepted(job, req.wait)
+}
+
+fn compare(req: CompareRequest, jobs: &JobStore) -> Result<(u16, Value), ApiError> {
+    if req.configs.is_empty() {
+        return Err(ApiError::bad_request("configs: expected at least one config"));
+    }
+    let configs = req
+        .configs
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c.parse().map_err(|e| ApiError::bad_request(format!("configs[{}]: {}", i, e.message))))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_sims(req.num_sims)?;
+    let objective = parse_objective(req.objective.as_deref())?;
+    let total = job_runs(configs.len(), req.num_sims)?;
+    let (num_sims, parallel, seed) = (req.num_sims, req.parallel, req.seed);
+    let job = jobs.spawn(JobKind::Compare, total, configs.len(), move |progress| {
+        let sims = configs
+            .into_iter()
+            .enumerate()
+            .map(|(slot, config)| simulator(config, num_sims, parallel, seed, &progress, slot).map_err(|e| e.message))
+            .collect::<Result<Vec<_>, _>>()?;
+        let stats: Vec<_> = sims.iter().map(Simulator::run).collect();
+        let mut body = serde_json::to_value(&stats).map_err(|e| e.to_string())?;
+        if let Some(objective) = objective {
+            for (rank, (i, score)) in objective.rank(&stats).into_iter().enumerate() {
+                body[i]["score"] = json!(score);
+                body[i]["rank"] = json!(rank + 1);
+            }
+        }
+        Ok(body)
+    });
+    accepted(job, req.wait)
+}
+
+fn optimize(req: OptimizeRequest, jobs: &JobStore) -> Result<(u16, Value), ApiError> {
+    let base = req.config.parse()?;
+    check_sims(req.sims_per_build)?;
+    if req.count == 0 || req.count > MAX_OPTIMIZE_BUILDS {
+        return Err(ApiError::bad_request(format!("count: expected 1 to {} builds", MAX_OPTIMIZE_BUILDS)));
+    }
+    let attributes = req
+        .attributes
+        .into_iter()
+        .map(|(name, def)| (name, AttributeInfo { cost: def.cost, max: def.max.unwrap_or(f64::INFINITY) }))
+        .collect();
+    let generator = BuildGenerator::new(
+        base.get_level(),
+        req.talents,
+        attributes,
+        req.attribute_dependencies,
+        req.attribute_point_gates,
+        req.attribute_exclusions,
+    );
+    generator.validate().map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let objective = parse_objective(req.objective.as_deref())?;
+
+    let total = job_runs(req.count, req.sims_per_build)?;
+    let (count, sims_per_build, seed, top) = (req.count, req.sims_per_build, req.seed, req.top);
+    let job = jobs.spawn(JobKind::Optimize, total, 0, move |progress| {
+        let builds = generator.generate_builds(count);
+        let mut scored: Vec<ScoredBuild> = builds
+            .into_par_iter()
+            .enumerate()
+            .map(|(i, (talents, attributes))| {
+                let mut config = base.clone();
+                config.talents = talents;
+                config.attributes = attributes;
+                let mut rng = FastRng::new(seed ^ (i as u64));
+                let results: Vec<_> = (0..sims_per_build)
+                    .map(|_| {
+                        let result = run_simulation_with_rng(&config, &mut rng);
+                        progress.completed.fetch_add(1, Ordering::Relaxed);
+                        result
+                    })
+                    .collect();
+                let stats = AggregatedStats::from_results(&results);
+                let score = objective.as_ref().map_or(stats.avg_stage, |o| o.score(&stats));
+                ScoredBuild { talents: config.talents, attributes: config.attributes, avg_stage: stats.avg_stage, score }
+            })
+            .collect();
+        let key = |score: f64| if score.is_nan() { f64::NEG_INFINITY } else { score };
+        scored.sort_by(|a, b| key(b.score).total_cmp(&key(a.score)));
+        if let Some(top) = top {
+            scored.truncate(top);
+        }
+        Ok(json!({ "builds_evaluated": count, "builds": scored }))
+    });
+    accepted(job, req.wait)
+}
+
+/// `interval_ms` query parameter of the event stream
+fn event_interval(query: &str) -> Result<Duration, ApiError> {
+    let (lo, hi) = EVENT_INTERVAL_RANGE_MS;
+    let ms = match query.split('&').find_map(|kv| kv.strip_prefix("interval_ms=")) {
+        Some(v) => v.parse::<u64>().ok().filter(|ms| (lo..=hi).contains(ms)).ok_or_else(|| {
+            ApiError::bad_request(format!("interval_ms: expected {} to {}", lo, hi))
+        })?,
+        None => EVENT_INTERVAL_MS,
+    };
+    Ok(Duration::from_millis(ms))
+}
+
+/// Stream `progress` events every `interval` until the job ends, then one
+/// `done` event with the full job (as `GET /jobs/{id}` returns it).
+/// Written by hand so each event is flushed as it happens rather than
+/// buffered into a chunk.
+fn stream_events(request: Request, job: &Job, interval: Duration, cors: Option<&str>) {
+    let mut out = request.into_writer();
+    let result = (|| -> io::Result<()> {
+        out.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n")?;
+        if let Some(origin) = cors {
+            write!(out, "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n", origin)?;
+        }
+        out.write_all(b"\r\n")?;
+        out.flush()?;
+        loop {
+            let finished = job.wait_timeout(Duration::ZERO);
+            write!(out, "event: progress\ndata: {}\n\n", json!(job.progress_event()))?;
+            out.flush()?;
+            if finished {
+                break;
+            }
+            job.wait_timeout(interval);
+        }
+        write!(out, "event: done\ndata: {}\n\n", json!(job.view(true)))?;
+        out.flush()
+    })();
+    if let Err(e) = result {
+        tracing::debug!(id = job.id, error = %e, "event stream closed");
+    }
+}
+
+fn respond(request: Request, status: u16, body: &Value, cors: Option<&str>) {
+    let header = |name: &str, value: &str| Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap();
+    let text = if body.is_null() { String::new() } else { body.to_string() };
+    let mut response = Response::from_string(text).with_status_code(status).with_header(header("Content-Type", "application/json"));
+    if let Some(origin) = cors {
+        response = response
+            .with_header(header("Access-Control-Allow-Origin", origin))
+            .with_header(header("Access-Control-Allow-Methods", "GET, POST, DELETE, OPTIONS"))
+            .with_header(header("Access-Control-Allow-Headers", "Content-Type"))
+            .with_header(header("Vary", "Origin"));
+    }
+    if let Err(e) = request.respond(response) {
+        tracing::debug!(error = %e, "failed to send response");
+    }
+}
@@ -0,0 +1,57 @@
+//! Compact share codes for passing builds around in chat
+//!
+//! A code is `HSC1.` followed by URL-safe base64 (no padding) of the
+//! deflate-compressed JSON config. Maps are written in sorted key order, so the
+//! same build always produces the same code, and decoding goes through the
+//! normal JSON loader so a bad code reports the same field-path errors as a file.
+
+use crate::config::BuildConfig;
+use crate::error::ConfigError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Prefix identifying the share-code format version
+const PREFIX: &str = "HSC1.";
+
+/// Upper bound on decompressed size, so a hostile code can't exhaust memory
+const MAX_DECODED_BYTES: u64 = 1 << 20;
+
+/// Encode a build as a share code
+pub fn encode(config: &BuildConfig) -> Result<String, ConfigError> {
+    let bad = |e: &dyn std::fmt::Display| ConfigError::ShareCode(e.to_string());
+    
+    // Round-trip through Value so HashMaps serialize in sorted order
+    let value = serde_json::to_value(config).map_err(|e| bad(&e))?;
+    let json = serde_json::to_vec(&value).map_err(|e| bad(&e))?;
+    
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&json).map_err(|e| bad(&e))?;
+    let compressed = encoder.finish().map_err(|e| bad(&e))?;
+    
+    Ok(format!("{}{}", PREFIX, URL_SAFE_NO_PAD.encode(compressed)))
+}
+
+/// Decode a share code back into a build (surrounding whitespace is ignored)
+pub fn decode(code: &str) -> Result<BuildConfig, ConfigError> {
+    let bad = |msg: String| ConfigError::ShareCode(msg);
+    
+    let code: String = code.split_whitespace().collect();
+    let payload = code.strip_prefix(PREFIX).ok_or_else(|| {
+        bad(format!("expected a code starting with `{}`", PREFIX))
+    })?;
+    let compressed = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| bad(format!("not valid base64: {}", e)))?;
+    
+    let mut json = String::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_DECODED_BYTES)
+        .read_to_string(&mut json)
+        .map_err(|e| bad(format!("corrupt payload: {}", e)))?;
+    
+    BuildConfig::from_json(&json)
+}
@@ -1,17 +1,143 @@
 //! Core simulation engine - IDENTICAL to Python's sim.py
 
-use crate::config::{BuildConfig, HunterType};
-use crate::enemy::{Enemy, SecondaryAttackType};
-use crate::hunter::Hunter;
-use crate::stats::{AggregatedStats, SimResult};
+use crate::boss_script::BossEffect;
+use crate::config::{BuildConfig, HunterType, EMPOWERED_REGEN_TICKS};
+use crate::enemy::{Enemy, EnemyAbility, EnemyPool};
+use crate::error::SimError;
+use crate::events;
+use crate::hunter::{AttackProc, CombatEffect, HealSource, Hunter, KillCause};
+use crate::invariants;
+use crate::mitigation::HitAudit;
+use crate::logging::trace_at;
+use crate::profiling::{self, Phase};
+use crate::stats::{AggregatedStats, BossEntry, DamageWindow, FightPhase, RunEnd, SimResult, LOOT_TAIL_STAGES, STAGE_BAND};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::BinaryHeap;
 use std::cmp::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::time::Instant;
+use tracing::{debug, debug_span, warn};
+
+/// f64 draws generated per refill when batch RNG is on
+const RNG_BATCH: usize = 16;
+
+/// Safety limit on the stage a run can reach, whatever its `max_stage`
+pub const STAGE_LIMIT: i32 = 1000;
+
+static BATCH_RNG: AtomicBool = AtomicBool::new(false);
+static PYTHON_TIES: AtomicBool = AtomicBool::new(false);
+static TIE_AUDIT: AtomicBool = AtomicBool::new(false);
+static EARLY_TERMINATION: AtomicBool = AtomicBool::new(true);
+static TIE_DIVERGENCES: AtomicU64 = AtomicU64::new(0);
+static MAX_SIM_TIME: AtomicU64 = AtomicU64::new(0);  // Simulated seconds; 0 is unlimited
+static WATCHDOG_MS: AtomicU64 = AtomicU64::new(0);   // Wall-clock milliseconds per play; 0 is off
+static REGEN_TICK_MS: AtomicU64 = AtomicU64::new(DEFAULT_REGEN_TICK_MS);
+
+/// Simulated milliseconds between regen ticks, as in the game and Python
+pub const DEFAULT_REGEN_TICK_MS: u64 = 1000;
+
+/// Events between wall-clock checks of the watchdog
+const WATCHDOG_EVENTS: u32 = 1024;
+
+/// Turn batched f64 generation on or off for RNGs created afterwards. Batches
+/// come from the same stream in the same order, so results are identical
+/// either way; only throughput changes (see the `bench` subcommand).
+pub fn set_batch_rng(enabled: bool) {
+    BATCH_RNG.store(enabled, AtomicOrdering::Relaxed);
+}
+
+/// Break ties between events with equal time and priority the way Python's
+/// heapq does (by action name, then by push order) for simulations started
+/// afterwards. Off by default: the heap then resolves ties by its internal
+/// layout, which is deterministic but not what the Python engine does.
+pub fn set_python_ties(enabled: bool) {
+    PYTHON_TIES.store(enabled, AtomicOrdering::Relaxed);
+}
+
+/// Count (and log at warn level) every event popped ahead of an event that
+/// Python's heapq would have run first, for simulations started afterwards.
+/// Clears the count.
+pub fn set_tie_audit(enabled: bool) {
+    TIE_AUDIT.store(enabled, AtomicOrdering::Relaxed);
+    TIE_DIVERGENCES.store(0, AtomicOrdering::Relaxed);
+}
+
+/// Turn the early end of hopeless runs (see `can_terminate`) on or off for
+/// simulations started afterwards. On by default; Python always plays a run
+/// out until the hunter dies.
+pub fn set_early_termination(enabled: bool) {
+    EARLY_TERMINATION.store(enabled, AtomicOrdering::Relaxed);
+}
+
+/// End every run started afterwards once it has played `seconds` of simulated
+/// time (as `RunEnd::TimedOut`), so a build that can't kill or be killed can't
+/// stall a batch. None, the default, plays runs out however long they take.
+pub fn set_max_sim_time(seconds: Option<u64>) {
+    MAX_SIM_TIME.store(seconds.unwrap_or(0), AtomicOrdering::Relaxed);
+}
+
+/// Abort every run started afterwards that spends more than `ms` milliseconds
+/// of real time in the engine (as `RunEnd::Aborted`), so one pathological
+/// build can't hang a worker thread. None, the default, never aborts.
+pub fn set_watchdog(ms: Option<u64>) {
+    WATCHDOG_MS.store(ms.unwrap_or(0), AtomicOrdering::Relaxed);
+}
+
+/// Tick regen every `ms` simulated milliseconds in runs started afterwards,
+/// healing `ms / 1000` seconds' worth each time, to test how much results
+/// depend on the 1-second tick. Buffs and effects that last a number of ticks
+/// keep lasting that many seconds. None restores the default.
+pub fn set_regen_tick(ms: Option<u64>) {
+    REGEN_TICK_MS.store(ms.unwrap_or(DEFAULT_REGEN_TICK_MS).max(1), AtomicOrdering::Relaxed);
+}
+
+/// The current `set_regen_tick`, in seconds
+pub fn regen_tick() -> f64 {
+    REGEN_TICK_MS.load(AtomicOrdering::Relaxed) as f64 / 1000.0
+}
+
+/// The conditions, besides the hunter dying, that end runs started now
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TerminationPolicy {
+    pub early_termination: bool,     // Hopeless runs end early (see `can_terminate`)
+    pub max_sim_time: Option<u64>,   // Simulated seconds
+    pub watchdog_ms: Option<u64>,    // Real milliseconds
+    pub stage_limit: i32,
+}
+
+/// The current `set_early_termination`, `set_max_sim_time` and `set_watchdog` switches
+pub fn termination_policy() -> TerminationPolicy {
+    let limit = |v: u64| (v > 0).then_some(v);
+    TerminationPolicy {
+        early_termination: EARLY_TERMINATION.load(AtomicOrdering::Relaxed),
+        max_sim_time: limit(MAX_SIM_TIME.load(AtomicOrdering::Relaxed)),
+        watchdog_ms: limit(WATCHDOG_MS.load(AtomicOrdering::Relaxed)),
+        stage_limit: STAGE_LIMIT,
+    }
+}
+
+/// Ties resolved differently than heapq since the audit was last turned on
+pub fn tie_divergences() -> u64 {
+    TIE_DIVERGENCES.load(AtomicOrdering::Relaxed)
+}
+
+/// Source of the engine's random draws. Every roll is `rng.f64() < chance`,
+/// so a draw below the chance passes it. [`FastRng`] drives real runs;
+/// [`ScriptedRng`] replays chosen outcomes to test one branch at a time.
+pub trait SimRng {
+    /// Uniform draw in [0, 1)
+    fn f64(&mut self) -> f64;
+}
 
 /// Fast RNG wrapper for better performance
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "FastRngState", into = "FastRngState")]
 pub struct FastRng {
     inner: fastrand::Rng,
+    batched: bool,
+    buf: [f64; RNG_BATCH],
+    pos: usize,  // Next unread draw in `buf`; RNG_BATCH when empty
 }
 
 impl FastRng {
@@ -19,14 +145,35 @@ impl FastRng {
     pub fn new(seed: u64) -> Self {
         Self {
             inner: fastrand::Rng::with_seed(seed),
+            batched: BATCH_RNG.load(AtomicOrdering::Relaxed),
+            buf: [0.0; RNG_BATCH],
+            pos: RNG_BATCH,
         }
     }
 
     #[inline(always)]
     pub fn f64(&mut self) -> f64 {
-        self.inner.f64()
+        if !self.batched {
+            return self.inner.f64();
+        }
+        if self.pos == RNG_BATCH {
+            self.refill();
+        }
+        let v = self.buf[self.pos];
+        self.pos += 1;
+        v
+    }
+
+    /// Draw the next batch of f64s in one tight loop
+    #[inline(never)]
+    fn refill(&mut self) {
+        for v in &mut self.buf {
+            *v = self.inner.f64();
+        }
+        self.pos = 0;
     }
 
+    // Integer draws bypass the batch buffer (the engine itself only draws f64s)
     #[inline(always)]
     pub fn u32(&mut self) -> u32 {
         self.inner.u32(..)
@@ -38,18 +185,157 @@ impl FastRng {
     }
 }
 
+/// Saved form of a [`FastRng`]: the generator state plus any batched draws
+/// not used yet, so a restored RNG continues the same stream
+#[derive(Serialize, Deserialize)]
+struct FastRngState {
+    state: u64,
+    batched: bool,
+    pending: Vec<f64>,
+}
+
+impl From<FastRng> for FastRngState {
+    fn from(rng: FastRng) -> Self {
+        Self { state: rng.inner.get_seed(), batched: rng.batched, pending: rng.buf[rng.pos..].to_vec() }
+    }
+}
+
+impl From<FastRngState> for FastRng {
+    fn from(saved: FastRngState) -> Self {
+        let pending = &saved.pending[..saved.pending.len().min(RNG_BATCH)];
+        let pos = RNG_BATCH - pending.len();
+        let mut buf = [0.0; RNG_BATCH];
+        buf[pos..].copy_from_slice(pending);
+        Self { inner: fastrand::Rng::with_seed(saved.state), batched: saved.batched, buf, pos }
+    }
+}
+
+impl SimRng for FastRng {
+    #[inline(always)]
+    fn f64(&mut self) -> f64 {
+        FastRng::f64(self)
+    }
+}
+
+/// One scripted outcome for [`ScriptedRng`]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "RawRoll")]
+pub enum Roll {
+    Pass,       // Draws 0.0: passes any nonzero chance
+    Fail,       // Draws just under 1.0: fails any chance below 100%
+    Draw(f64),  // This exact draw
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRoll {
+    Draw(f64),
+    Name(String),
+}
+
+impl TryFrom<RawRoll> for Roll {
+    type Error = String;
+
+    fn try_from(raw: RawRoll) -> Result<Self, String> {
+        match raw {
+            RawRoll::Draw(x) => Roll::draw(x),
+            RawRoll::Name(name) => name.parse(),
+        }
+    }
+}
+
+impl std::str::FromStr for Roll {
+    type Err = String;
+
+    /// `crit`, `proc`, `evade` or `pass`; `no-crit`, `no-proc`, `no-evade` or
+    /// `fail`; or a number in [0, 1)
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().replace('_', "-").as_str() {
+            "crit" | "proc" | "evade" | "pass" => Ok(Roll::Pass),
+            "no-crit" | "no-proc" | "no-evade" | "fail" => Ok(Roll::Fail),
+            other => match other.parse::<f64>() {
+                Ok(x) => Roll::draw(x),
+                Err(_) => Err(format!("unknown roll `{}` (expected crit, proc, evade, pass, no-crit, no-proc, no-evade, fail or a draw in [0, 1))", s.trim())),
+            },
+        }
+    }
+}
+
+impl Roll {
+    fn draw(x: f64) -> Result<Self, String> {
+        if (0.0..1.0).contains(&x) {
+            Ok(Roll::Draw(x))
+        } else {
+            Err(format!("draw {} is outside [0, 1)", x))
+        }
+    }
+
+    fn value(self) -> f64 {
+        match self {
+            Roll::Pass => 0.0,
+            Roll::Fail => 1.0 - f64::EPSILON,
+            Roll::Draw(x) => x,
+        }
+    }
+
+    /// Parse a comma-separated script like `"crit, no-proc, evade"`
+    pub fn parse_script(script: &str) -> Result<Vec<Roll>, String> {
+        script.split(',').filter(|s| !s.trim().is_empty()).map(str::parse).collect()
+    }
+}
+
+/// RNG that answers the engine's rolls from a script, in order, then falls
+/// back to a seeded [`FastRng`] so an under-scripted test stays deterministic
+#[derive(Clone)]
+pub struct ScriptedRng {
+    rolls: Vec<Roll>,
+    pos: usize,
+    fallback: FastRng,
+}
+
+impl ScriptedRng {
+    pub fn new(rolls: Vec<Roll>, fallback_seed: u64) -> Self {
+        Self { rolls, pos: 0, fallback: FastRng::new(fallback_seed) }
+    }
+
+    /// Scripted rolls drawn so far
+    pub fn used(&self) -> usize {
+        self.pos.min(self.rolls.len())
+    }
+
+    /// Scripted rolls not yet drawn
+    pub fn remaining(&self) -> usize {
+        self.rolls.len() - self.used()
+    }
+}
+
+impl SimRng for ScriptedRng {
+    fn f64(&mut self) -> f64 {
+        match self.rolls.get(self.pos) {
+            Some(roll) => {
+                self.pos += 1;
+                roll.value()
+            }
+            None => self.fallback.f64(),
+        }
+    }
+}
+
 /// Event in the simulation queue
 /// Python: (time, priority, action) tuple in heapq
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Event {
-    time: f64,
+    time: Tick,
     priority: i32,  // Lower = higher priority (Python uses 0, 1, 2, 3)
     action: Action,
+    #[serde(default)]
+    skew: i8,       // A stunned attack off its tick: -1 just before `time`, 1 just after (see EventQueue::delay)
+    tie: u64,       // Lower runs first among equal (time, priority); 0 for all unless Python ties are on
 }
 
 impl PartialEq for Event {
     fn eq(&self, other: &Self) -> bool {
-        self.time == other.time && self.priority == other.priority
+        self.time == other.time && self.skew == other.skew && self.priority == other.priority && self.tie == other.tie
     }
 }
 
@@ -65,13 +351,14 @@ impl Ord for Event {
     fn cmp(&self, other: &Self) -> Ordering {
         // Reverse ordering for min-heap behavior (BinaryHeap is max-heap by default)
         // Python heapq is min-heap, sorts by (time, priority)
-        other.time.partial_cmp(&self.time)
-            .unwrap_or(Ordering::Equal)
+        other.time.cmp(&self.time)
+            .then(other.skew.cmp(&self.skew))
             .then(other.priority.cmp(&self.priority))
+            .then(other.tie.cmp(&self.tie))
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum Action {
     Hunter,        // 'hunter' in Python
     Enemy,         // 'enemy' in Python  
@@ -80,30 +367,174 @@ enum Action {
     Stun,          // 'stun' in Python
 }
 
+impl Action {
+    /// Position of the Python action name in string order, which is how
+    /// heapq breaks (time, priority) ties
+    fn python_rank(self) -> u64 {
+        match self {
+            Action::Enemy => 0,
+            Action::EnemySpecial => 1,
+            Action::Hunter => 2,
+            Action::Regen => 3,
+            Action::Stun => 4,
+        }
+    }
+    
+    /// Slot of `EventQueue::stunned` for an enemy attack
+    fn stun_slot(self) -> usize {
+        (self == Action::EnemySpecial) as usize
+    }
+}
+
+/// The event heap, with the optional Python tie-breaking and tie audit
+#[derive(Clone, Serialize, Deserialize)]
+struct EventQueue {
+    heap: BinaryHeap<Event>,
+    next_seq: u64,
+    python_ties: bool,
+    audit: bool,
+    #[serde(default)]
+    stunned: [f64; 2],  // Exact seconds of the queued enemy attack and special while `skew` has them off their tick
+}
+
+impl EventQueue {
+    fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            next_seq: 0,
+            python_ties: PYTHON_TIES.load(AtomicOrdering::Relaxed),
+            audit: TIE_AUDIT.load(AtomicOrdering::Relaxed),
+            stunned: [0.0; 2],
+        }
+    }
+    
+    /// Queue a new event (Python: hpush)
+    #[inline(always)]
+    fn push(&mut self, time: Tick, priority: i32, action: Action) {
+        let tie = if self.python_ties {
+            self.next_seq += 1;
+            action.python_rank() << 56 | self.next_seq
+        } else {
+            0
+        };
+        self.heap.push(Event { time, priority, action, skew: 0, tie });
+    }
+    
+    /// Push `event` back by a `seconds` stun, keeping its place among ties.
+    /// Python adds the stun without rounding (qe[0] + duration), so a stunned
+    /// attack can sit a float error off its millisecond, before or after the
+    /// events rounded onto it. `skew` orders it that side of them and the
+    /// exact time is kept here: each enemy has one attack and one special
+    /// queued at a time, so one slot each suffices. (Were both on the same
+    /// side of the same millisecond, they would order by priority.)
+    fn delay(&mut self, event: Event, seconds: f64) {
+        let exact = self.seconds(&event) + seconds;
+        let time = ticks(exact);
+        let skew = exact.total_cmp(&tick_seconds(time)) as i8;
+        if skew != 0 {
+            self.stunned[event.action.stun_slot()] = exact;
+        }
+        self.heap.push(Event { time, skew, ..event });
+    }
+    
+    /// Time of `event` in seconds, exact for a stunned attack
+    #[inline(always)]
+    fn seconds(&self, event: &Event) -> f64 {
+        if event.skew == 0 {
+            tick_seconds(event.time)
+        } else {
+            self.stunned[event.action.stun_slot()]
+        }
+    }
+    
+    /// Tick `seconds` after `event` (Python: round(prev_time + seconds, 3))
+    #[inline(always)]
+    fn after(&self, event: &Event, seconds: f64) -> Tick {
+        if event.skew == 0 {
+            event.time + ticks(seconds)
+        } else {
+            ticks(self.seconds(event) + seconds)
+        }
+    }
+    
+    /// Put back an event taken out with `pop`, keeping its place among ties
+    #[inline(always)]
+    fn requeue(&mut self, event: Event) {
+        self.heap.push(event);
+    }
+    
+    #[inline(always)]
+    fn pop(&mut self) -> Option<Event> {
+        let event = self.heap.pop()?;
+        if self.audit {
+            if let Some(next) = self.heap.peek() {
+                if next.time == event.time && next.skew == event.skew && next.priority == event.priority && next.action.python_rank() < event.action.python_rank() {
+                    TIE_DIVERGENCES.fetch_add(1, AtomicOrdering::Relaxed);
+                    warn!(time = tick_seconds(event.time), priority = event.priority, popped = ?event.action, python_pops = ?next.action, "event tie resolved differently than heapq");
+                }
+            }
+        }
+        Some(event)
+    }
+    
+    fn iter(&self) -> impl Iterator<Item = &Event> {
+        self.heap.iter()
+    }
+}
+
 /// Run a single simulation - IDENTICAL to Python's Simulation.run()
 pub fn run_simulation(config: &BuildConfig) -> SimResult {
-    let mut rng = FastRng::new(rand::random::<u64>());
-    run_simulation_with_rng(config, &mut rng)
+    run_simulation_with_seed(config, rand::random::<u64>())
 }
 
 /// Run a single simulation with a specific seed
 pub fn run_simulation_with_seed(config: &BuildConfig, seed: u64) -> SimResult {
     let mut rng = FastRng::new(seed);
-    run_simulation_with_rng(config, &mut rng)
+    invariants::set_seed(Some(seed));
+    let mut result = run_simulation_with_rng(config, &mut rng);
+    invariants::set_seed(None);
+    result.seed = Some(seed);
+    result
+}
+
+/// Count the queued attacks of the current enemy (primary, special)
+fn enemy_event_counts(queue: &EventQueue) -> (usize, usize) {
+    queue.iter().fold((0, 0), |(e, s), ev| match ev.action {
+        Action::Enemy => (e + 1, s),
+        Action::EnemySpecial => (e, s + 1),
+        _ => (e, s),
+    })
+}
+
+/// Event time in whole milliseconds. Python rounds every queued time to 3
+/// decimals; integer ticks give the same times without float drift over a
+/// long run, and compare exactly in the heap. The one time Python leaves
+/// unrounded, a stunned enemy attack's, is kept exact (`EventQueue::delay`).
+type Tick = i64;
+
+const TICKS_PER_SECOND: Tick = 1000;
+
+/// Seconds to the nearest tick (Python: round(x, 3))
+#[inline(always)]
+fn ticks(seconds: f64) -> Tick {
+    (seconds * TICKS_PER_SECOND as f64).round() as Tick
 }
 
-/// Helper to round to 3 decimal places like Python's round(x, 3)
-fn round3(x: f64) -> f64 {
-    (x * 1000.0).round() / 1000.0
+#[inline(always)]
+fn tick_seconds(tick: Tick) -> f64 {
+    tick as f64 / TICKS_PER_SECOND as f64
 }
 
 /// Early termination check for obviously bad runs
 #[inline(always)]
-fn can_terminate(hunter: &Hunter, elapsed_time: f64) -> bool {
+fn can_terminate(hunter: &Hunter, elapsed_time: f64, early: bool) -> bool {
     // Terminate if dead
     if hunter.is_dead() {
         return true;
     }
+    if !early {
+        return false;
+    }
     
     // Terminate if out of revives and current stage is too low for time remaining
     // Rough estimate: need at least 10 stages per minute of remaining time
@@ -118,57 +549,238 @@ fn can_terminate(hunter: &Hunter, elapsed_time: f64) -> bool {
     false
 }
 
+/// Everything a run carries from one stage to the next
+#[derive(Clone, Serialize, Deserialize)]
+struct RunState {
+    hunter: Hunter,
+    queue: EventQueue,
+    elapsed_time: f64,  // Seconds, advanced by the regen ticks
+    last_attack_tick: Tick,
+    #[serde(default)]
+    start_stage: i32,  // Stage play began on; loot and XP count from it
+}
+
+impl RunState {
+    fn new(config: &BuildConfig) -> Self {
+        let mut hunter = Hunter::from_config(config);
+        
+        // Python: self.elapsed_time: int = 0
+        let elapsed_time = 0.0;
+        
+        // Python: self.queue = []
+        let mut queue = EventQueue::new();
+        
+        // Python: self.current_stage = 0
+        hunter.current_stage = 0;
+        
+        // The build's `start` section, over the fresh hunter
+        let start = config.start;
+        if let Some(stage) = start.stage {
+            hunter.current_stage = stage;
+        }
+        if let Some(fraction) = start.hp_fraction {
+            hunter.hp = hunter.max_hp * fraction;
+        }
+        if let Some(charges) = start.trickster_charges {
+            hunter.trickster_charges = charges;
+        }
+        if let Some(stacks) = start.hundred_souls_stacks {
+            hunter.hundred_souls_stacks = stacks.min(hunter.hundred_souls_cap());
+        }
+        
+        // Python: hpush(self.queue, (round(hunter.speed, 3), 1, 'hunter'))
+        let initial_speed = hunter.get_speed();  // Consumes fires_of_war like Python
+        queue.push(ticks(initial_speed), 1, Action::Hunter);
+        
+        // Python: hpush(self.queue, (self.elapsed_time, 3, 'regen'))
+        queue.push(ticks(elapsed_time), 3, Action::Regen);
+        
+        let start_stage = hunter.current_stage;
+        Self { hunter, queue, elapsed_time, last_attack_tick: 0, start_stage }
+    }
+    
+    /// A fresh run of `config` after the warm-up runs its `persistence` section
+    /// asks for, played with `rng`, carrying what it keeps from each to the next
+    fn warmed_up(config: &BuildConfig, rng: &mut impl SimRng) -> Self {
+        let rules = config.persistence;
+        let mut state = Self::new(config);
+        for _ in 0..rules.warmup_runs() {
+            let ended = match play(state, rng, None) {
+                Progress::Paused(state) | Progress::Finished(state) => state.hunter,
+            };
+            state = Self::new(config);
+            if rules.hundred_souls {
+                state.hunter.hundred_souls_stacks = ended.hundred_souls_stacks;
+            }
+            if rules.trickster_charges {
+                state.hunter.trickster_charges = ended.trickster_charges;
+            }
+        }
+        state
+    }
+}
+
+/// Where `play` stopped
+enum Progress {
+    Paused(RunState),    // At the start of the requested stage, before its enemies spawn
+    Finished(RunState),  // The run is over; loot still to be computed
+}
+
 /// Run a simulation with a specific RNG
 /// This mirrors Python's Simulation.simulate_combat() EXACTLY
-pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimResult {
-    let mut hunter = Hunter::from_config(config);
+pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut impl SimRng) -> SimResult {
+    match play(RunState::warmed_up(config, rng), rng, None) {
+        Progress::Paused(state) | Progress::Finished(state) => finish(state),
+    }
+}
+
+/// A run paused at the start of a stage, before its enemies spawn: the hunter
+/// with everything accumulated so far, the event queue, the clock and the RNG.
+/// Enemies aren't saved because every stage spawns fresh ones. Resuming with
+/// the saved RNG finishes the run exactly as if it had never paused; resuming
+/// with other RNGs replays the rest of the run, e.g. the boss 200 fight, from
+/// this exact state as often as needed.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub config: BuildConfig,
+    pub seed: u64,   // Seed of the run up to the snapshot
+    pub stage: i32,
+    state: RunState,
+    rng: FastRng,
+}
+
+impl Snapshot {
+    /// Play a seeded run up to the start of `stage`; None if the run ends first
+    pub fn take(config: &BuildConfig, seed: u64, stage: i32) -> Option<Self> {
+        let mut rng = FastRng::new(seed);
+        match play(RunState::warmed_up(config, &mut rng), &mut rng, Some(stage)) {
+            Progress::Paused(state) => Some(Self { config: config.clone(), seed, stage, state, rng }),
+            Progress::Finished(_) => None,
+        }
+    }
+    
+    pub fn hunter(&self) -> &Hunter {
+        &self.state.hunter
+    }
     
-    // Python: self.elapsed_time: int = 0
-    let mut elapsed_time: i32 = 0;
+    /// Simulated seconds played before the snapshot
+    pub fn elapsed_time(&self) -> f64 {
+        self.state.elapsed_time
+    }
+    
+    /// Finish the run with the saved RNG, exactly as the uninterrupted run
+    pub fn resume(&self) -> SimResult {
+        SimResult { seed: Some(self.seed), ..self.resume_with(&mut self.rng.clone()) }
+    }
+    
+    /// Finish the run from the snapshot with a different RNG
+    pub fn resume_with(&self, rng: &mut impl SimRng) -> SimResult {
+        match play(self.state.clone(), rng, None) {
+            Progress::Paused(state) | Progress::Finished(state) => finish(state),
+        }
+    }
     
-    // Python: self.queue = []
-    let mut queue: BinaryHeap<Event> = BinaryHeap::new();
+    /// Finish the run with the saved RNG, as `resume`, recording every
+    /// incoming hit's trip through the mitigation layers
+    pub fn audit_hits(&self) -> (SimResult, Vec<HitAudit>) {
+        let mut state = self.state.clone();
+        state.hunter.hit_audit = Some(Vec::new());
+        let mut state = match play(state, &mut self.rng.clone(), None) {
+            Progress::Paused(state) | Progress::Finished(state) => state,
+        };
+        let hits = state.hunter.hit_audit.take().unwrap_or_default();
+        (SimResult { seed: Some(self.seed), ..finish(state) }, hits)
+    }
     
-    // Python: self.current_stage = 0
-    hunter.current_stage = 0;
+    /// Finish the run with the saved RNG, as `resume`, totalling the hunter's
+    /// damage in `window`-second windows from the snapshot to the run's end
+    pub fn damage_curve(&self, window: f64) -> (SimResult, Vec<DamageWindow>) {
+        let mut state = self.state.clone();
+        state.hunter.damage_log = Some(Vec::new());
+        let mut state = match play(state, &mut self.rng.clone(), None) {
+            Progress::Paused(state) | Progress::Finished(state) => state,
+        };
+        let samples = state.hunter.damage_log.take().unwrap_or_default();
+        let result = SimResult { seed: Some(self.seed), ..finish(state) };
+        let curve = DamageWindow::bucket(&samples, self.elapsed_time(), result.elapsed_time, window, self.stage);
+        (result, curve)
+    }
     
-    // Python: hpush(self.queue, (round(hunter.speed, 3), 1, 'hunter'))
-    let initial_speed = hunter.get_speed();  // Consumes fires_of_war like Python
-    queue.push(Event { 
-        time: round3(initial_speed), 
-        priority: 1, 
-        action: Action::Hunter 
-    });
+    /// Finish the run `count` times from the snapshot, with seeds 0..count.
+    /// Each result includes what the run had accumulated before the snapshot.
+    pub fn replay(&self, count: usize, parallel: bool) -> Vec<SimResult> {
+        let one = |i: usize| SimResult { seed: Some(i as u64), ..self.resume_with(&mut FastRng::new(i as u64)) };
+        if parallel {
+            (0..count).into_par_iter().map(one).collect()
+        } else {
+            (0..count).map(one).collect()
+        }
+    }
     
-    // Python: hpush(self.queue, (self.elapsed_time, 3, 'regen'))
-    queue.push(Event { 
-        time: elapsed_time as f64, 
-        priority: 3, 
-        action: Action::Regen 
-    });
+    pub fn to_json(&self) -> Result<String, SimError> {
+        Ok(serde_json::to_string(self)?)
+    }
     
-    // Debug flag
-    let debug = std::env::var("DEBUG_SIM").is_ok();
+    pub fn from_json(json: &str) -> Result<Self, SimError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Play only the boss fight of `stage`, with the hunter straight from `config`:
+/// full HP, every revive, and none of what a run builds up on its way there,
+/// but for what the build's `start` section sets. Returns the result and the
+/// hunter as the fight left it
+pub fn play_boss_fight(config: &BuildConfig, stage: i32, rng: &mut impl SimRng) -> (SimResult, Hunter) {
+    let mut state = RunState::warmed_up(config, rng);
+    state.hunter.current_stage = stage;
+    state.start_stage = stage;
+    // Past the boss, so max_stage can't end the fight by clearing it
+    state.hunter.max_stage = stage + 2;
+    let state = match play(state, rng, Some(stage + 1)) {
+        Progress::Paused(state) | Progress::Finished(state) => state,
+    };
+    let hunter = state.hunter.clone();
+    (finish(state), hunter)
+}
+
+/// Play stages until the run ends, or until `pause_at` is about to start
+fn play(state: RunState, rng: &mut impl SimRng, pause_at: Option<i32>) -> Progress {
+    let RunState { mut hunter, mut queue, mut elapsed_time, mut last_attack_tick, start_stage } = state;
+    let mut checker = invariants::is_enabled().then(invariants::Checker::new);
+    
+    // Enemy slots reused by every stage
+    let mut pool = EnemyPool::new();
+    let early_termination = EARLY_TERMINATION.load(AtomicOrdering::Relaxed);
+    let max_sim_time = match MAX_SIM_TIME.load(AtomicOrdering::Relaxed) {
+        0 => f64::INFINITY,
+        seconds => seconds as f64,
+    };
+    let regen_tick = REGEN_TICK_MS.load(AtomicOrdering::Relaxed) as Tick * TICKS_PER_SECOND / 1000;
+    let deadline = match WATCHDOG_MS.load(AtomicOrdering::Relaxed) {
+        0 => None,
+        ms => Some(Instant::now() + std::time::Duration::from_millis(ms)),
+    };
+    let mut events_until_check = WATCHDOG_EVENTS;
     
     // Python: while not hunter.is_dead():
-    'main_loop: while !can_terminate(&hunter, elapsed_time as f64) {
+    'main_loop: while !can_terminate(&hunter, elapsed_time, early_termination) {
+        if pause_at == Some(hunter.current_stage) {
+            return Progress::Paused(RunState { hunter, queue, elapsed_time, last_attack_tick, start_stage });
+        }
         let stage = hunter.current_stage;
         let is_boss = stage % 100 == 0 && stage > 0;
-        
-        if debug {
-            eprintln!("\n=== STAGE {} ===", stage);
-        }
+        let _stage_span = debug_span!("stage", stage, boss = is_boss).entered();
         
         // Python: self.spawn_enemies(hunter)
         // Creates list of enemies: [Boss(...)] for boss stages, [Enemy(...) for i in range(10)] otherwise
-        let mut enemies: Vec<Enemy> = if is_boss {
-            vec![Enemy::new_boss(stage, hunter.hunter_type)]
-        } else {
-            (1..=10).map(|i| Enemy::new(i, stage, hunter.hunter_type)).collect()
-        };
+        let enemies = pool.spawn(stage, hunter.hunter_type, is_boss);
+        
+        // Held multistrikes don't carry over to the next stage
+        hunter.held_multistrikes = 0;
+        let stage_start = (elapsed_time, hunter.result.damage_taken, healing_capacity(&hunter.result));
         
         // Apply on-spawn effects for each enemy (POG, OOD, etc.)
-        for enemy in &mut enemies {
+        for enemy in enemies.iter_mut() {
             apply_spawn_effects(&mut hunter, enemy, rng);
         }
         
@@ -181,122 +793,190 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
                 continue;
             }
             
-            if debug && is_boss {
-                eprintln!("Fighting enemy {} - HP: {:.0}", enemy_idx, enemies[enemy_idx].hp);
+            let _fight_span = debug_span!("fight", enemy = %enemies[enemy_idx].id).entered();
+            hunter.fight_phase = if is_boss { FightPhase::Boss } else { FightPhase::Trash };
+            if is_boss {
+                debug!(hp = enemies[enemy_idx].hp, power = enemies[enemy_idx].power, "boss fight started");
+                hunter.result.boss_fights += 1;
+                hunter.result.boss_stages_fought.push(stage);
+                let entry = BossEntry {
+                    stage,
+                    hp: hunter.hp,
+                    max_hp: hunter.max_hp,
+                    revives_left: hunter.max_revives - hunter.revive_count,
+                    fires_of_war_buff: hunter.fires_of_war_buff,
+                    trickster_charges: hunter.trickster_charges,
+                    empowered_regen: hunter.empowered_regen,
+                    hundred_souls_stacks: hunter.hundred_souls_stacks,
+                    charge: hunter.charge,
+                    empowered_block_regen: hunter.empowered_block_regen,
+                };
+                hunter.result.boss_entries.push(entry);
+            }
+            hunter.result.band_mut(stage).fights += 1;
+            
+            if let Some(ref c) = checker {
+                let (e, s) = enemy_event_counts(&queue);
+                c.fight_start(stage, e + s);
             }
             
             // Python: enemy = self.enemies.pop(0)
             // Python: enemy.queue_initial_attack()
             // This is: hpush(self.sim.queue, (round(self.sim.elapsed_time + self.speed, 3), 2, 'enemy'))
-            queue.push(Event {
-                time: round3(elapsed_time as f64 + enemies[enemy_idx].speed),
-                priority: 2,
-                action: Action::Enemy,
-            });
+            queue.push(ticks(elapsed_time) + ticks(enemies[enemy_idx].speed), 2, Action::Enemy);
             
             // If boss has secondary attack:
             // hpush(self.sim.queue, (round(self.sim.elapsed_time + self.speed2, 3), 2, 'enemy_special'))
             if enemies[enemy_idx].has_secondary {
-                queue.push(Event {
-                    time: round3(elapsed_time as f64 + enemies[enemy_idx].speed2),
-                    priority: 2,
-                    action: Action::EnemySpecial,
-                });
+                queue.push(ticks(elapsed_time) + ticks(enemies[enemy_idx].speed2), 2, Action::EnemySpecial);
             }
             
             // Python: while not enemy.is_dead() and not hunter.is_dead():
             // Store trample kills to apply after combat loop ends
             let mut pending_trample_kills = 0;
+            let mut kill_cause = KillCause::Attack;
+            let mut last_hit = RunEnd::Unfinished;  // The enemy hit that kills the hunter, if one does
             
             while !enemies[enemy_idx].is_dead() && !hunter.is_dead() {
                 // Python: prev_time, _, action = hpop(self.queue)
-                let event = match queue.pop() {
-                    Some(e) => e,
-                    None => break,
+                let event = {
+                    let _timer = profiling::start(Phase::EventDispatch);
+                    match queue.pop() {
+                        Some(e) => e,
+                        None => break,
+                    }
                 };
-                let prev_time = event.time;
-                
-                if debug && is_boss {
-                    eprintln!("  [{:.2}] {:?}", prev_time, event.action);
+                if let Some(deadline) = deadline {
+                    events_until_check -= 1;
+                    if events_until_check == 0 {
+                        events_until_check = WATCHDOG_EVENTS;
+                        if Instant::now() >= deadline {
+                            warn!(elapsed = elapsed_time, stage, "run aborted by the watchdog");
+                            hunter.result.end = RunEnd::Aborted;
+                            break 'main_loop;
+                        }
+                    }
                 }
+                let prev_tick = event.time;
+                let prev_time = queue.seconds(&event);
+                trace_at!(hunter, Events, time = prev_time, action = ?event.action, "event");
                 
                 match event.action {
                     Action::Hunter => {
                         // Python: hunter.attack(enemy)
-                        let trample_kills = hunter_attack(&mut hunter, &mut enemies[enemy_idx], rng, elapsed_time as f64);
+                        let damage_before = hunter.result.damage;
+                        let trample_kills = {
+                            let _timer = profiling::start(Phase::AttackResolution);
+                            hunter_attack(&mut hunter, &mut enemies[enemy_idx], rng, elapsed_time)
+                        };
+                        if let Some(log) = hunter.damage_log.as_mut() {
+                            log.push((prev_time, hunter.result.damage - damage_before, stage));
+                        }
                         pending_trample_kills = trample_kills;
+                        trace_at!(hunter, Attacks, time = prev_time, enemy_hp = enemies[enemy_idx].hp, trample = trample_kills, "hunter attack");
+                        let band = hunter.result.band_mut(stage);
+                        band.attacks += 1;
+                        band.attack_time += tick_seconds(prev_tick - last_attack_tick);
+                        last_attack_tick = prev_tick;
+                        if enemies[enemy_idx].is_dead() {
+                            band.killing_blows += 1;
+                            band.overkill -= enemies[enemy_idx].hp;
+                        }
+                        if enemies[enemy_idx].harden_absorbed > 0.0 {
+                            hunter.result.harden_wasted_damage += std::mem::take(&mut enemies[enemy_idx].harden_absorbed);
+                        }
                         
                         // Python: hpush(self.queue, (round(prev_time + hunter.speed, 3), 1, 'hunter'))
                         // NOTE: hunter.speed is a @property that applies FoW and consumes it!
                         let next_speed = hunter.get_speed();  // This consumes fires_of_war
-                        queue.push(Event {
-                            time: round3(prev_time + next_speed),
-                            priority: 1,
-                            action: Action::Hunter,
-                        });
+                        queue.push(prev_tick + ticks(next_speed), 1, Action::Hunter);
                         
                         // If stun was triggered, queue it at priority 0
                         // Python: hpush(self.sim.queue, (0, 0, 'stun'))
                         if hunter.pending_stun_duration > 0.0 {
-                            queue.push(Event {
-                                time: 0.0,
-                                priority: 0,
-                                action: Action::Stun,
-                            });
+                            queue.push(0, 0, Action::Stun);
                         }
                     }
                     
                     Action::Stun => {
                         // Python: hunter.apply_stun(enemy, isinstance(enemy, Boss))
                         // This finds 'enemy' event in queue and adds duration to its time
+                        let _timer = profiling::start(Phase::QueueMaintenance);
                         apply_stun(&mut hunter, &mut queue, is_boss);
                     }
                     
                     Action::Enemy => {
                         // Python: enemy.attack(hunter)
-                        enemy_attack(&mut hunter, &mut enemies[enemy_idx], rng);
+                        {
+                            let _timer = profiling::start(Phase::AttackResolution);
+                            enemy_attack(&mut hunter, &mut enemies[enemy_idx], rng);
+                        }
+                        last_hit = if is_boss { RunEnd::BossAttack } else { RunEnd::EnemyAttack };
+                        if enemies[enemy_idx].is_dead() {
+                            kill_cause = KillCause::Reflect;  // Helltouch Barrier
+                        }
+                        if is_boss {
+                            trace_at!(hunter, Attacks, time = prev_time, stacks = enemies[enemy_idx].enrage_stacks, "boss enrage");
+                        }
                         
                         // Python: if not enemy.is_dead():
                         //     hpush(self.queue, (round(prev_time + enemy.speed, 3), 2, 'enemy'))
                         if !enemies[enemy_idx].is_dead() {
-                            queue.push(Event {
-                                time: round3(prev_time + enemies[enemy_idx].speed),
-                                priority: 2,
-                                action: Action::Enemy,
-                            });
+                            queue.push(queue.after(&event, enemies[enemy_idx].speed), 2, Action::Enemy);
                         }
                     }
                     
                     Action::EnemySpecial => {
                         // Python: enemy.attack_special(hunter)
-                        enemy_attack_special(&mut hunter, &mut enemies[enemy_idx], rng);
+                        {
+                            let _timer = profiling::start(Phase::AttackResolution);
+                            enemy_attack_special(&mut hunter, &mut enemies[enemy_idx], rng);
+                        }
+                        last_hit = if is_boss { RunEnd::BossSpecial } else { RunEnd::EnemySpecial };
+                        if enemies[enemy_idx].is_dead() {
+                            kill_cause = KillCause::Reflect;  // Helltouch Barrier on a special's hits
+                        }
+                        trace_at!(hunter, Attacks, time = prev_time, stacks = enemies[enemy_idx].enrage_stacks, "boss enrage");
                         
                         // Python: if not enemy.is_dead():
                         //     hpush(self.queue, (round(prev_time + enemy.speed2, 3), 2, 'enemy_special'))
                         if !enemies[enemy_idx].is_dead() {
-                            queue.push(Event {
-                                time: round3(prev_time + enemies[enemy_idx].speed2),
-                                priority: 2,
-                                action: Action::EnemySpecial,
-                            });
+                            queue.push(queue.after(&event, enemies[enemy_idx].speed2), 2, Action::EnemySpecial);
                         }
                     }
                     
                     Action::Regen => {
+                        let next_tick = ticks(elapsed_time) + regen_tick;
+                        let whole_seconds = (next_tick / TICKS_PER_SECOND - ticks(elapsed_time) / TICKS_PER_SECOND) as i32;
+                        hunter.result.sustain_mut(hunter.fight_phase).seconds += tick_seconds(regen_tick);
+                        if enemies[enemy_idx].harden_ticks_left > 0 {
+                            hunter.result.harden_ticks += whole_seconds;
+                        }
                         // Python: hunter.regen_hp()
-                        hunter.regen_hp();
+                        hunter.regen_hp_over(tick_seconds(regen_tick), whole_seconds);
                         // Python: enemy.regen_hp()
-                        enemies[enemy_idx].regen_hp();
+                        enemies[enemy_idx].regen_hp_over(tick_seconds(regen_tick), whole_seconds);
+                        trace_at!(hunter, Regen, time = elapsed_time, hp = hunter.hp, enemy_hp = enemies[enemy_idx].hp, "regen tick");
                         // Python: self.elapsed_time += 1
-                        elapsed_time += 1;
+                        elapsed_time = tick_seconds(next_tick);
                         // Python: hpush(self.queue, (self.elapsed_time, 3, 'regen'))
-                        queue.push(Event {
-                            time: elapsed_time as f64,
-                            priority: 3,
-                            action: Action::Regen,
-                        });
+                        queue.push(next_tick, 3, Action::Regen);
+                        if elapsed_time >= max_sim_time {
+                            debug!(elapsed = elapsed_time, stage, "run timed out");
+                            hunter.result.end = RunEnd::TimedOut;
+                            break 'main_loop;
+                        }
                     }
                 }
+                
+                if is_boss && hunter.fight_phase == FightPhase::Boss && enemies[enemy_idx].max_enrage {
+                    hunter.fight_phase = FightPhase::BossEnraged;
+                }
+                
+                if let Some(ref mut c) = checker {
+                    let (e, s) = enemy_event_counts(&queue);
+                    c.after_event(&hunter, (event.action != Action::Stun).then_some(prev_time), e, s);
+                }
             }
             
             // Apply pending trample kills (mark additional enemies as dead)
@@ -305,19 +985,32 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
             for i in 1..=pending_trample_kills {
                 if enemy_idx + i < enemies.len() {
                     enemies[enemy_idx + i].hp = 0.0;
-                    hunter.result.kills += 1;
                     // Call on_kill for each trampled enemy (generates loot)
-                    on_kill(&mut hunter, rng, false);  // Trample only works on non-boss enemies
+                    resolve_kill(&mut hunter, rng, false, KillCause::Trample);  // Trample only works on non-boss enemies
                 }
             }
             
+            if is_boss && enemies[enemy_idx].max_enrage {
+                hunter.result.max_enrage_fights += 1;
+            }
+            
             // Python: if hunter.is_dead(): return
             if hunter.is_dead() {
+                debug!(elapsed = elapsed_time, revives = hunter.revive_count, "hunter died");
+                hunter.result.end = last_hit;
                 break 'main_loop;
             }
+            if is_boss {
+                let stacks = enemies[enemy_idx].enrage_stacks;
+                debug!(elapsed = elapsed_time, hp = hunter.hp, enrage = stacks, "boss defeated");
+                hunter.result.boss_kills += 1;
+                hunter.result.enrage_at_boss_kill += stacks;
+                hunter.result.boss_stages_cleared.push(stage);
+            }
             
             // Enemy dead - remove enemy events from queue (Python: on_death removes 'enemy' and 'enemy_special')
             // Python: self.sim.queue = [(p1, p2, u) for p1, p2, u in self.sim.queue if u not in ['enemy', 'enemy_special']]
+            let purge_timer = profiling::start(Phase::QueueMaintenance);
             let mut temp_events: Vec<Event> = Vec::new();
             while let Some(e) = queue.pop() {
                 match e.action {
@@ -330,12 +1023,12 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
                 }
             }
             for e in temp_events {
-                queue.push(e);
+                queue.requeue(e);
             }
+            drop(purge_timer);
             
             // Python: self.sim.hunter.on_kill() - called from enemy.on_death()
-            on_kill(&mut hunter, rng, is_boss);
-            hunter.result.kills += 1;
+            resolve_kill(&mut hunter, rng, is_boss, kill_cause);
             
             // Skip enemies that were killed by trample
             enemy_idx += 1 + pending_trample_kills;
@@ -344,21 +1037,63 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
         // Python: self.complete_stage()
         // Stage completion effects (Knox Calypso's Advantage, etc.)
         on_stage_complete(&mut hunter, rng, is_boss);
+        if !hunter.loot_events.is_empty() {
+            let (loot, xp) = events::multipliers(&hunter.loot_events, elapsed_time);
+            if loot != 1.0 || xp != 1.0 {
+                hunter.event_stages.push((stage, loot, xp));
+            }
+        }
+        if !is_boss {
+            let (start_time, start_damage, start_healing) = stage_start;
+            hunter.stage_fit.push(
+                stage,
+                elapsed_time - start_time,
+                hunter.result.damage_taken - start_damage,
+                healing_capacity(&hunter.result) - start_healing,
+            );
+        }
         hunter.current_stage += 1;
         
         if hunter.current_stage >= hunter.max_stage {
+            let revives_left = hunter.max_revives - hunter.revive_count;
+            let wall = hunter.stage_fit.predict_wall(hunter.current_stage, hunter.hp, hunter.max_hp, revives_left);
+            hunter.result.wall_stage = wall.flatten();
+            hunter.result.wall_unbounded = wall == Some(None);
+            hunter.result.stage_time_growth = hunter.stage_fit.stage_time_growth().unwrap_or(0.0);
             hunter.hp = 0.0;
             hunter.revive_count = hunter.max_revives;  // Prevent revive at max_stage
+            hunter.result.end = RunEnd::MaxStage;
         }
         
         // Safety limit
-        if hunter.current_stage > 1000 {
+        if hunter.current_stage > STAGE_LIMIT {
+            hunter.result.end = RunEnd::StageLimit;
             break;
         }
     }
+    if hunter.result.end == RunEnd::Unfinished {
+        hunter.result.end = RunEnd::Abandoned;
+    }
+    if hunter.result.end.is_death() {
+        hunter.result.wall_stage = Some(hunter.current_stage);
+    }
+    
+    Progress::Finished(RunState { hunter, queue, elapsed_time, last_attack_tick, start_stage })
+}
+
+/// Healing the hunter has been offered so far, overheal included
+fn healing_capacity(r: &SimResult) -> f64 {
+    r.regenerated_hp + r.overheal_regen + r.lifesteal + r.overheal_lifesteal
+        + r.life_of_the_hunt_healing + r.unfair_advantage_healing
+}
+
+/// Compute the end-of-run loot and XP and close out the result
+fn finish(state: RunState) -> SimResult {
+    let RunState { mut hunter, elapsed_time, start_stage, .. } = state;
     
     // === CALCULATE FINAL LOOT USING GEOMETRIC SERIES FORMULA (after all stages complete) ===
     // Loot: BASE × GeomSum × EnemiesPerStage × LootMultiplier
+    let loot_timer = profiling::start(Phase::LootMath);
     let final_stage = hunter.current_stage as f64;
     let enemies_per_stage = 10.0;
     
@@ -371,11 +1106,16 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
     
     // Geometric series: sum of (mult^0 + mult^1 + ... + mult^(stage-1))
     // Formula: (mult^stage - 1) / (mult - 1)
-    let geom_sum = if stage_loot_mult > 1.0 {
-        (stage_loot_mult.powf(final_stage) - 1.0) / (stage_loot_mult - 1.0)
-    } else {
-        final_stage
+    let geom_to = |stage: f64| {
+        if stage_loot_mult > 1.0 {
+            (stage_loot_mult.powf(stage) - 1.0) / (stage_loot_mult - 1.0)
+        } else {
+            stage
+        }
     };
+    // Stages before a configured start weren't played, so they earn nothing
+    let first_stage = start_stage as f64;
+    let geom_sum = geom_to(final_stage) - geom_to(first_stage);
     
     // Total enemy factor: geometric sum × enemies per stage
     let total_enemy_factor = geom_sum * enemies_per_stage;
@@ -396,13 +1136,62 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
     hunter.result.loot_rare = base_rare * total_enemy_factor * loot_mult;
     hunter.result.total_loot = hunter.result.loot_common + hunter.result.loot_uncommon + hunter.result.loot_rare;
     
+    // Each band's cleared stages are its part of the series
+    let (common, uncommon, rare) = (hunter.result.loot_common, hunter.result.loot_uncommon, hunter.result.loot_rare);
+    for band in hunter.result.bands.iter_mut() {
+        let start = band.start_stage.max(start_stage) as f64;
+        let end = (band.start_stage + STAGE_BAND).min(hunter.current_stage) as f64;
+        let share = if end > start && geom_sum > 0.0 { (geom_to(end) - geom_to(start)) / geom_sum } else { 0.0 };
+        band.loot_common = common * share;
+        band.loot_uncommon = uncommon * share;
+        band.loot_rare = rare * share;
+    }
+    
+    // Events: each stage cleared under a loot event adds (multiplier - 1) of its own term of the series
+    let mut event_xp_stages = 0.0;
+    for &(stage, loot, xp) in &hunter.event_stages {
+        let extra = stage_loot_mult.powi(stage) * enemies_per_stage * loot_mult * (loot - 1.0);
+        let band = hunter.result.band_mut(stage);
+        band.loot_common += base_common * extra;
+        band.loot_uncommon += base_uncommon * extra;
+        band.loot_rare += base_rare * extra;
+        hunter.result.loot_common += base_common * extra;
+        hunter.result.loot_uncommon += base_uncommon * extra;
+        hunter.result.loot_rare += base_rare * extra;
+        hunter.result.event_loot += (base_common + base_uncommon + base_rare) * extra;
+        event_xp_stages += xp - 1.0;
+    }
+    let reached = hunter.current_stage;
+    hunter.result.loot_tail = LOOT_TAIL_STAGES
+        .iter()
+        .map(|&n| if geom_sum > 0.0 { (geom_to(final_stage) - geom_to((reached - n).max(start_stage) as f64)) / geom_sum } else { 0.0 })
+        .collect();
+    
     // XP: BASE × Stages × XP_Multiplier (no enemies_per_stage multiplier)
-    hunter.result.total_xp = base_xp * final_stage * hunter.xp_mult;
+    hunter.result.total_xp = base_xp * (final_stage - first_stage) * hunter.xp_mult;
+    if event_xp_stages != 0.0 {
+        hunter.result.event_xp = base_xp * event_xp_stages * hunter.xp_mult;
+        hunter.result.total_xp += hunter.result.event_xp;
+    }
+    trace_at!(
+        hunter,
+        Loot,
+        common = hunter.result.loot_common,
+        uncommon = hunter.result.loot_uncommon,
+        rare = hunter.result.loot_rare,
+        xp = hunter.result.total_xp,
+        "run loot"
+    );
+    drop(loot_timer);
+    profiling::flush_sim();
     
     // Finalize
+    hunter.result.start_stage = start_stage;
     hunter.result.final_stage = hunter.current_stage;
-    hunter.result.elapsed_time = elapsed_time as f64;
+    hunter.result.elapsed_time = elapsed_time;
     hunter.result.total_loot = hunter.result.loot_common + hunter.result.loot_uncommon + hunter.result.loot_rare;
+    hunter.result.nominal_speed = hunter.speed;
+    hunter.result.max_hp = hunter.max_hp;
     
     hunter.result
 }
@@ -417,7 +1206,7 @@ pub fn run_simulation_with_rng(config: &BuildConfig, rng: &mut FastRng) -> SimRe
 ///   qe = [(p1, p2, u) for p1, p2, u in self.sim.queue if u == 'enemy'][0]
 ///   self.sim.queue.remove(qe)
 ///   hpush(self.sim.queue, (qe[0] + duration, qe[1], qe[2]))
-fn apply_stun(hunter: &mut Hunter, queue: &mut BinaryHeap<Event>, _is_boss: bool) {
+fn apply_stun(hunter: &mut Hunter, queue: &mut EventQueue, _is_boss: bool) {
     if hunter.pending_stun_duration <= 0.0 {
         return;
     }
@@ -425,14 +1214,19 @@ fn apply_stun(hunter: &mut Hunter, queue: &mut BinaryHeap<Event>, _is_boss: bool
     let stun_duration = hunter.pending_stun_duration;
     hunter.pending_stun_duration = 0.0;
     hunter.result.stun_duration_inflicted += stun_duration;
+    hunter.result.band_mut(hunter.current_stage).stun_time += stun_duration;
     
-    // Find the 'enemy' event and delay it
+    // Find the 'enemy' event (and the 'enemy_special' one, under
+    // engine_flags.stun_affects_specials) and delay it
     let mut temp_events: Vec<Event> = Vec::new();
     let mut found_enemy: Option<Event> = None;
+    let mut found_special: Option<Event> = None;
     
     while let Some(e) = queue.pop() {
         if found_enemy.is_none() && e.action == Action::Enemy {
             found_enemy = Some(e);
+        } else if hunter.stun_affects_specials && found_special.is_none() && e.action == Action::EnemySpecial {
+            found_special = Some(e);
         } else {
             temp_events.push(e);
         }
@@ -440,28 +1234,24 @@ fn apply_stun(hunter: &mut Hunter, queue: &mut BinaryHeap<Event>, _is_boss: bool
     
     // Put everything back
     for e in temp_events {
-        queue.push(e);
+        queue.requeue(e);
     }
     
-    // Add enemy event back with delayed time
-    if let Some(e) = found_enemy {
-        queue.push(Event {
-            time: e.time + stun_duration,
-            priority: e.priority,
-            action: e.action,
-        });
+    // Add the delayed events back
+    for e in found_enemy.into_iter().chain(found_special) {
+        queue.delay(e, stun_duration);
     }
 }
 
 /// Apply spawn effects - IDENTICAL to Python's hunter.apply_pog(), apply_ood(), etc.
-fn apply_spawn_effects(hunter: &mut Hunter, enemy: &mut Enemy, _rng: &mut FastRng) {
+pub(crate) fn apply_spawn_effects(hunter: &mut Hunter, enemy: &mut Enemy, _rng: &mut impl SimRng) {
     let is_boss = enemy.is_boss;
     let stage_effect = if is_boss { 0.5 } else { 1.0 };
     
     // Presence of God (Borge) - Python: enemy.hp = enemy.max_hp * (1 - pog_effect)
     // NOTE: Python does NOT track POG damage in total_damage!
     if hunter.presence_of_god > 0 {
-        let pog_effect = hunter.presence_of_god as f64 * 0.04 * stage_effect;
+        let pog_effect = hunter.presence_of_god as f64 * hunter.per_level(CombatEffect::PresenceOfGod) * stage_effect;
         let new_hp = enemy.max_hp * (1.0 - pog_effect);
         enemy.hp = new_hp;
         // Python does NOT add this to damage stats
@@ -469,19 +1259,19 @@ fn apply_spawn_effects(hunter: &mut Hunter, enemy: &mut Enemy, _rng: &mut FastRn
     
     // Omen of Defeat (Borge) - Python: enemy.regen = enemy.regen * (1 - ood_effect)
     if hunter.omen_of_defeat > 0 {
-        let ood_effect = hunter.omen_of_defeat as f64 * 0.08 * stage_effect;
+        let ood_effect = hunter.omen_of_defeat as f64 * hunter.per_level(CombatEffect::OmenOfDefeat) * stage_effect;
         enemy.regen *= 1.0 - ood_effect;
     }
     
     // Soul of Snek (Ozzy) - Python: regen_reduction = 1 - 0.088 * level
     if hunter.soul_of_snek > 0 {
-        let regen_reduction = 1.0 - (0.088 * hunter.soul_of_snek as f64);
+        let regen_reduction = 1.0 - (hunter.per_level(CombatEffect::SoulOfSnekRegen) * hunter.soul_of_snek as f64);
         enemy.regen *= regen_reduction.max(0.0);
     }
     
     // Gift of Medusa (Ozzy) - Python: enemy.regen -= hunter_regen * medusa_level * 0.06
     if hunter.gift_of_medusa > 0 {
-        let anti_regen = hunter.regen * hunter.gift_of_medusa as f64 * 0.06;
+        let anti_regen = hunter.regen * hunter.gift_of_medusa as f64 * hunter.per_level(CombatEffect::GiftOfMedusa);
         enemy.regen = (enemy.regen - anti_regen).max(0.0);
     }
 }
@@ -489,10 +1279,10 @@ fn apply_spawn_effects(hunter: &mut Hunter, enemy: &mut Enemy, _rng: &mut FastRn
 /// Hunter attack - mirrors Python's Borge.attack() / Ozzy.attack() / Knox.attack()
 /// Returns number of additional enemies killed by trample (caller handles marking them dead)
 #[inline(always)]
-fn hunter_attack(
+pub(crate) fn hunter_attack(
     hunter: &mut Hunter, 
     enemy: &mut Enemy, 
-    rng: &mut FastRng, 
+    rng: &mut impl SimRng, 
     _elapsed_time: f64,
 ) -> usize {
     let is_boss = enemy.is_boss;
@@ -519,44 +1309,86 @@ fn hunter_attack(
     if hunter.hunter_type == HunterType::Borge {
         // Lifesteal
         if hunter.lifesteal > 0.0 {
-            let heal = damage * hunter.lifesteal;
-            let effective = heal.min(hunter.max_hp - hunter.hp);
-            hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-            hunter.result.lifesteal += effective;
+            hunter.heal(damage * hunter.lifesteal, HealSource::Lifesteal);
         }
         
-        // Life of the Hunt
-        if hunter.life_of_the_hunt > 0 && rng.f64() < effective_effect_chance {
-            let loth_heal = damage * hunter.life_of_the_hunt as f64 * 0.06;
-            hunter.hp = (hunter.hp + loth_heal).min(hunter.max_hp);
-            hunter.result.life_of_the_hunt_healing += loth_heal;
-            hunter.result.effect_procs += 1;
-        }
-        
-        // Impeccable Impacts (stun)
-        if hunter.impeccable_impacts > 0 && rng.f64() < effective_effect_chance {
-            let stun_effect = if is_boss { 0.5 } else { 1.0 };
-            let stun_duration = hunter.impeccable_impacts as f64 * 0.1 * stun_effect;
-            hunter.pending_stun_duration = stun_duration;
-            hunter.result.effect_procs += 1;
-        }
-        
-        // Fires of War
-        if hunter.fires_of_war > 0 && rng.f64() < effective_effect_chance {
-            hunter.fires_of_war_buff = hunter.fires_of_war as f64 * 0.1;
-            hunter.result.effect_procs += 1;
-        }
+        // Life of the Hunt, Impeccable Impacts, Fires of War
+        roll_attack_procs(hunter, rng, damage, effective_effect_chance, is_boss);
     }
     
     trample_kills  // Return trample kills for Borge, 0 for others
 }
 
+/// Effects of attack procs that feed back into the attack itself
+struct ProcEffects {
+    echo: bool,            // Ozzy: Echo Bullets fires an extra hit
+    omen_multiplier: f64,  // Ozzy: Omen of Decay main-hit multiplier
+}
+
+/// Roll the hunter's active attack procs in order, one RNG draw each
+fn roll_attack_procs(hunter: &mut Hunter, rng: &mut impl SimRng, damage: f64, effect_chance: f64, is_boss: bool) -> ProcEffects {
+    let mut effects = ProcEffects { echo: false, omen_multiplier: 1.0 };
+    let stun_effect = if is_boss { 0.5 } else { 1.0 };
+    for proc in hunter.attack_procs.active() {
+        match proc {
+            AttackProc::LifeOfTheHunt => {
+                if rng.f64() < effect_chance {
+                    let loth_heal = damage * hunter.life_of_the_hunt as f64 * hunter.per_level(CombatEffect::LifeOfTheHunt);
+                    trace_at!(hunter, Procs, proc = "life_of_the_hunt", heal = loth_heal);
+                    hunter.heal(loth_heal, HealSource::LifeOfTheHunt);
+                    hunter.result.effect_procs += 1;
+                }
+            }
+            AttackProc::ImpeccableImpacts => {
+                if rng.f64() < effect_chance {
+                    let stun_duration = hunter.impeccable_impacts as f64 * hunter.per_level(CombatEffect::ImpeccableImpacts) * stun_effect;
+                    trace_at!(hunter, Procs, proc = "impeccable_impacts", stun = stun_duration);
+                    hunter.pending_stun_duration = stun_duration;
+                    hunter.result.effect_procs += 1;
+                }
+            }
+            AttackProc::FiresOfWar => {
+                if rng.f64() < effect_chance {
+                    hunter.fires_of_war_buff = hunter.fires_of_war as f64 * hunter.per_level(CombatEffect::FiresOfWar);
+                    trace_at!(hunter, Procs, proc = "fires_of_war", buff = hunter.fires_of_war_buff);
+                    hunter.result.effect_procs += 1;
+                }
+            }
+            AttackProc::ThousandNeedles => {
+                // Python: only on the main attack
+                if rng.f64() < effect_chance {
+                    let stun_duration = hunter.thousand_needles as f64 * hunter.per_level(CombatEffect::ThousandNeedles) * stun_effect;
+                    trace_at!(hunter, Procs, proc = "thousand_needles", stun = stun_duration);
+                    hunter.pending_stun_duration = stun_duration;
+                    hunter.result.effect_procs += 1;
+                }
+            }
+            AttackProc::EchoBullets => {
+                // Python: Echo Bullets at half effect chance
+                if rng.f64() < effect_chance / 2.0 {
+                    trace_at!(hunter, Procs, proc = "echo_bullets");
+                    effects.echo = true;
+                    hunter.result.effect_procs += 1;
+                }
+            }
+            AttackProc::OmenOfDecay => {
+                // Python: if self.talents["omen_of_decay"] and random.random() < (self.effect_chance / 2):
+                if rng.f64() < effect_chance / 2.0 {
+                    hunter.result.effect_procs += 1;
+                    effects.omen_multiplier = 1.0 + hunter.omen_of_decay as f64 * hunter.per_level(CombatEffect::OmenOfDecay);
+                }
+            }
+        }
+    }
+    effects
+}
+
 /// Borge attack - mirrors Python's Borge.attack()
 /// Returns (damage, trample_kills) where trample_kills is the number of ADDITIONAL enemies killed
 fn borge_attack(
     hunter: &mut Hunter, 
     enemy: &mut Enemy, 
-    rng: &mut FastRng, 
+    rng: &mut impl SimRng, 
     effective_power: f64, 
     _effective_effect_chance: f64,
     is_boss: bool,
@@ -582,7 +1414,8 @@ fn borge_attack(
     if hunter.has_trample && !is_boss && damage > enemy.max_hp {
         let trample_power = ((damage / enemy.max_hp) as usize).min(10);
         if trample_power > 1 {
-            enemy.hp = 0.0;
+            // Whatever doesn't fill another enemy's max HP is overkill
+            enemy.hp = (trample_power as f64 * enemy.max_hp - damage).min(0.0);
             // Python counts current_target + extras, but we return only extras to skip
             // trample_power - 1 because current enemy is already being processed
             trample_kills = trample_power - 1;
@@ -603,7 +1436,7 @@ fn borge_attack(
 fn ozzy_attack(
     hunter: &mut Hunter, 
     enemy: &mut Enemy, 
-    rng: &mut FastRng, 
+    rng: &mut impl SimRng, 
     effective_power: f64, 
     effective_effect_chance: f64,
     is_boss: bool,
@@ -614,32 +1447,34 @@ fn ozzy_attack(
     
     // Python: Trickster's Boon at half effect_chance gives evade charge
     if hunter.tricksters_boon > 0 && rng.f64() < effective_effect_chance / 2.0 {
+        trace_at!(hunter, Procs, proc = "tricksters_boon", charges = hunter.trickster_charges + 1);
         hunter.trickster_charges += 1;
         hunter.result.effect_procs += 1;
     }
     
     // Track which extra attacks were triggered (Python: attack_queue)
     let mut multistrike_triggered = false;
-    let mut echo_triggered = false;
     
     // Python: if random.random() < self.special_chance: trigger multistrike
     if rng.f64() < hunter.special_chance {
         multistrike_triggered = true;
     }
     
-    // Python: Thousand Needles stun (only on main attack)
-    if hunter.thousand_needles > 0 && rng.f64() < effective_effect_chance {
-        let stun_effect = if is_boss { 0.5 } else { 1.0 };
-        let stun_duration = hunter.thousand_needles as f64 * 0.05 * stun_effect;
-        hunter.pending_stun_duration = stun_duration;
-        hunter.result.effect_procs += 1;
+    // Hold-burst policy: bank multistrikes while the boss is hardened, then
+    // release them all on the first attack after the harden ends
+    let mut multistrikes = multistrike_triggered as i32;
+    if hunter.hold_burst_during_harden {
+        if enemy.harden_ticks_left > 0 {
+            hunter.held_multistrikes += multistrikes;
+            hunter.result.held_multistrikes += multistrikes;
+            multistrikes = 0;
+        } else {
+            multistrikes += std::mem::take(&mut hunter.held_multistrikes);
+        }
     }
     
-    // Python: Echo Bullets at half effect chance
-    if hunter.echo_bullets > 0 && rng.f64() < effective_effect_chance / 2.0 {
-        echo_triggered = true;
-        hunter.result.effect_procs += 1;
-    }
+    // Thousand Needles, Echo Bullets, Omen of Decay (main attack only)
+    let procs = roll_attack_procs(hunter, rng, base_damage, effective_effect_chance, is_boss);
     
     // === CRIPPLING SHOTS DAMAGE ===
     // Python: cripple_damage = target.hp * (self.crippling_on_target * 0.008) * cripple_boss_reduction
@@ -647,17 +1482,8 @@ fn ozzy_attack(
     let cripple_damage = enemy.hp * (hunter.decay_stacks as f64 * 0.008) * cripple_boss_reduction;
     hunter.decay_stacks = 0;  // Reset stacks after attack
     
-    // === OMEN OF DECAY MULTIPLIER ===
-    // Python: if self.talents["omen_of_decay"] and random.random() < (self.effect_chance / 2):
-    let omen_multiplier = if hunter.omen_of_decay > 0 && rng.f64() < effective_effect_chance / 2.0 {
-        hunter.result.effect_procs += 1;
-        1.0 + (hunter.omen_of_decay as f64 * 0.03)
-    } else {
-        1.0
-    };
-    
     // Final main attack damage
-    let main_damage = (base_damage + cripple_damage) * omen_multiplier;
+    let main_damage = (base_damage + cripple_damage) * procs.omen_multiplier;
     enemy.take_damage(main_damage);
     
     // Track damage
@@ -669,11 +1495,9 @@ fn ozzy_attack(
     if hunter.lifesteal > 0.0 {
         let mut heal = base_damage * hunter.lifesteal;
         if hunter.empowered_regen > 0 {
-            heal *= 1.0 + hunter.soul_of_snek as f64 * 0.15;
+            heal *= 1.0 + hunter.soul_of_snek as f64 * hunter.per_level(CombatEffect::SoulOfSnekEmpower);
         }
-        let effective = heal.min(hunter.max_hp - hunter.hp);
-        hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-        hunter.result.lifesteal += effective;
+        hunter.heal(heal, HealSource::Lifesteal);
     }
     
     // Crippling Shots proc for NEXT attack (main attack can proc)
@@ -686,7 +1510,7 @@ fn ozzy_attack(
     let mut total_extra_damage = 0.0;
     
     // Multistrike: deals special_damage multiplier of power
-    if multistrike_triggered {
+    for _ in 0..multistrikes {
         let ms_dmg = effective_power * hunter.special_damage;
         enemy.take_damage(ms_dmg);
         hunter.result.multistrikes += 1;
@@ -697,10 +1521,9 @@ fn ozzy_attack(
         if hunter.lifesteal > 0.0 {
             let mut heal = ms_dmg * hunter.lifesteal;
             if hunter.empowered_regen > 0 {
-                heal *= 1.0 + hunter.soul_of_snek as f64 * 0.15;
+                heal *= 1.0 + hunter.soul_of_snek as f64 * hunter.per_level(CombatEffect::SoulOfSnekEmpower);
             }
-            hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-            hunter.result.lifesteal += heal.min(hunter.max_hp - hunter.hp);
+            hunter.heal(heal, HealSource::Lifesteal);
         }
         
         // Crippling Shots proc (multistrike can proc)
@@ -711,8 +1534,8 @@ fn ozzy_attack(
     }
     
     // Echo Bullets: deals 5% per level of power (WASM: cannot trigger multistrike)
-    if echo_triggered {
-        let echo_dmg = effective_power * (hunter.echo_bullets as f64 * 0.05);
+    if procs.echo {
+        let echo_dmg = effective_power * (hunter.echo_bullets as f64 * hunter.per_level(CombatEffect::EchoBullets));
         enemy.take_damage(echo_dmg);
         hunter.result.echo_bullets += 1;
         total_extra_damage += echo_dmg;
@@ -721,10 +1544,9 @@ fn ozzy_attack(
         if hunter.lifesteal > 0.0 {
             let mut heal = echo_dmg * hunter.lifesteal;
             if hunter.empowered_regen > 0 {
-                heal *= 1.0 + hunter.soul_of_snek as f64 * 0.15;
+                heal *= 1.0 + hunter.soul_of_snek as f64 * hunter.per_level(CombatEffect::SoulOfSnekEmpower);
             }
-            hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-            hunter.result.lifesteal += heal.min(hunter.max_hp - hunter.hp);
+            hunter.heal(heal, HealSource::Lifesteal);
         }
         
         // Crippling Shots proc (echo can proc)
@@ -742,7 +1564,7 @@ fn ozzy_attack(
 fn knox_attack(
     hunter: &mut Hunter, 
     enemy: &mut Enemy, 
-    rng: &mut FastRng, 
+    rng: &mut impl SimRng, 
     effective_power: f64, 
     effective_effect_chance: f64,
     _is_boss: bool,
@@ -754,14 +1576,14 @@ fn knox_attack(
     // Ghost Bullets - chance for extra projectile
     // Python: ghost_chance = self.talents["ghost_bullets"] * 0.0667
     if hunter.ghost_bullets > 0 {
-        let ghost_chance = hunter.ghost_bullets as f64 * 0.0667;
+        let ghost_chance = hunter.ghost_bullets as f64 * hunter.per_level(CombatEffect::GhostBullets);
         if rng.f64() < ghost_chance {
+            trace_at!(hunter, Procs, proc = "ghost_bullets");
             num_projectiles += 1;
             hunter.result.ghost_bullets += 1;  // Track ghost bullet procs
         }
     }
     
-    let base_salvo = hunter.salvo_projectiles.max(1) as f64;
     let mut total_damage = 0.0;
     
     for i in 0..num_projectiles {
@@ -780,11 +1602,9 @@ fn knox_attack(
         // Finishing Move on last bullet
         // Python: if i == num_projectiles - 1 and self.talents["finishing_move"] > 0:
         //     if random.random() < (self.effect_chance * 2): bullet_damage *= self.special_damage
-        if i == num_projectiles - 1 && hunter.finishing_move > 0 {
-            if rng.f64() < effective_effect_chance * 2.0 {
-                bullet_damage *= hunter.special_damage;
-                hunter.result.effect_procs += 1;
-            }
+        if i == num_projectiles - 1 && hunter.finishing_move > 0 && rng.f64() < effective_effect_chance * 2.0 {
+            bullet_damage *= hunter.special_damage;
+            hunter.result.effect_procs += 1;
         }
         
         total_damage += bullet_damage;
@@ -807,10 +1627,7 @@ fn knox_attack(
     
     // Lifesteal (if Knox has any)
     if hunter.lifesteal > 0.0 {
-        let heal = total_damage * hunter.lifesteal;
-        let effective = heal.min(hunter.max_hp - hunter.hp);
-        hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-        hunter.result.lifesteal += effective;
+        hunter.heal(total_damage * hunter.lifesteal, HealSource::Lifesteal);
     }
     
     total_damage
@@ -818,7 +1635,7 @@ fn knox_attack(
 
 /// Enemy attack - mirrors Python's Enemy.attack()
 #[inline(always)]
-fn enemy_attack(hunter: &mut Hunter, enemy: &mut Enemy, rng: &mut FastRng) {
+pub(crate) fn enemy_attack(hunter: &mut Hunter, enemy: &mut Enemy, rng: &mut impl SimRng) {
     // Python: if random.random() < self.special_chance: damage = self.power * self.special_damage
     let (damage, is_crit) = if rng.f64() < enemy.special_chance {
         (enemy.power * enemy.special_damage, true)
@@ -827,67 +1644,122 @@ fn enemy_attack(hunter: &mut Hunter, enemy: &mut Enemy, rng: &mut FastRng) {
     };
     
     // Python: hunter.receive_damage(self, damage, is_crit)
+    let landed_before = hunter.result.enemy_attacks;
     hunter_receive_damage(hunter, enemy, damage, is_crit, rng);
+    
+    // High-stage enemy ability (engine_flags.enemy_abilities): procs on a hit
+    // that wasn't evaded; no roll at all with the flag off
+    if hunter.enemy_abilities
+        && enemy.ability != EnemyAbility::None
+        && hunter.result.enemy_attacks > landed_before
+        && rng.f64() < enemy.effect_chance.max(enemy.ability_params.min_effect_chance)
+    {
+        apply_enemy_ability(hunter, enemy);
+    }
 }
 
-/// Enemy special attack - mirrors Python's Boss.attack_special()
-fn enemy_attack_special(hunter: &mut Hunter, enemy: &mut Enemy, rng: &mut FastRng) {
-    match enemy.secondary_type {
-        SecondaryAttackType::Gothmorgor => {
-            // Gothmorgor: attack + enrage
-            enemy_attack(hunter, enemy, rng);
-            enemy.add_enrage();
+/// Apply a regular enemy's ability to the hunter
+fn apply_enemy_ability(hunter: &mut Hunter, enemy: &Enemy) {
+    let params = &enemy.ability_params;
+    match enemy.ability {
+        EnemyAbility::RegenSuppression => {
+            // Re-applying refreshes the duration rather than stacking
+            hunter.regen_suppressed_ticks = hunter.regen_suppressed_ticks.max(params.ticks);
+            hunter.regen_suppression = params.strength;
         }
-        SecondaryAttackType::Exoscarab => {
-            // Exoscarab: harden (95% DR for 5 ticks)
-            enemy.start_harden();
+        EnemyAbility::None => {}
+    }
+    trace_at!(hunter, Procs, ability = ?enemy.ability, ticks = hunter.regen_suppressed_ticks, "enemy ability applied");
+}
+
+/// Enemy special attack - mirrors Python's Boss.attack_special(), playing
+/// the boss's script effect by effect
+pub(crate) fn enemy_attack_special(hunter: &mut Hunter, enemy: &mut Enemy, rng: &mut impl SimRng) {
+    let Some(script) = enemy.secondary else { return };
+    for effect in script.effects.iter() {
+        match *effect {
+            BossEffect::Attack => enemy_attack(hunter, enemy, rng),
+            BossEffect::Hit { damage_mult } => {
+                let damage = enemy.power * damage_mult;
+                hunter_receive_damage(hunter, enemy, damage, false, rng);
+            }
+            BossEffect::Enrage { stacks } => enemy.add_enrage_stacks(stacks),
+            BossEffect::Harden(harden) => {
+                enemy.start_harden(harden);
+                hunter.result.harden_windows += 1;
+            }
+            BossEffect::Heal { fraction } => enemy.siphon(fraction),
         }
-        SecondaryAttackType::None => {}
     }
 }
 
 /// Hunter receives damage - mirrors Python's Borge/Ozzy/Knox.receive_damage()
-fn hunter_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut FastRng) {
+fn hunter_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut impl SimRng) {
+    let taken_before = hunter.result.damage_taken;
     match hunter.hunter_type {
         HunterType::Borge => borge_receive_damage(hunter, attacker, damage, is_crit, rng),
         HunterType::Ozzy => ozzy_receive_damage(hunter, attacker, damage, is_crit, rng),
         HunterType::Knox => knox_receive_damage(hunter, attacker, damage, is_crit, rng),
     }
+    
+    // Raw vs taken damage for effective HP
+    let taken = hunter.result.damage_taken - taken_before;
+    trace_at!(hunter, Attacks, damage, taken, hp = hunter.hp, "enemy attack");
+    hunter.result.sustain_mut(hunter.fight_phase).damage_taken += taken;
+    let band = hunter.result.band_mut(hunter.current_stage);
+    band.incoming_damage += damage;
+    band.damage_taken += taken;
+    if attacker.is_boss {
+        band.boss_incoming_damage += damage;
+        band.boss_damage_taken += taken;
+    }
+}
+
+/// Damage `raw - blocked` leaves after the hunter's mitigation layers,
+/// counting what main DR and what all layers took off it, and recording each
+/// step when the hit audit is on
+fn mitigate(hunter: &mut Hunter, attacker: &Enemy, raw: f64, blocked: f64, is_crit: bool) -> f64 {
+    let taken = hunter.mitigation.apply(raw - blocked, is_crit);
+    hunter.result.mitigated_damage += (hunter.mitigation.before_dr(raw - blocked, is_crit) - taken).max(0.0);
+    hunter.result.total_mitigated_damage += raw - blocked - taken;
+    if hunter.hit_audit.is_some() {
+        let (steps, total_capped) = hunter.mitigation.steps(raw - blocked, is_crit);
+        let audit = HitAudit {
+            stage: hunter.current_stage,
+            boss: attacker.is_boss,
+            crit: is_crit,
+            raw,
+            blocked,
+            steps,
+            total_capped,
+            taken,
+            hp_before: hunter.hp,
+        };
+        hunter.hit_audit.get_or_insert_with(Vec::new).push(audit);
+    }
+    taken
 }
 
 /// Borge receive damage - mirrors Python's Borge.receive_damage()
-fn borge_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut FastRng) {
+fn borge_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut impl SimRng) {
     // Python: if random.random() < self.evade_chance: return
     if rng.f64() < hunter.evade_chance {
         hunter.result.evades += 1;
         return;
     }
     
-    let mut final_damage = damage;
-    
-    // Borge: Minotaur DR first (separate layer)
-    if hunter.minotaur_dr > 0.0 {
-        final_damage *= 1.0 - hunter.minotaur_dr;
-    }
-    
-    // Borge: Crit reduction from Weakspot Analysis
-    if is_crit && hunter.weakspot_analysis > 0 {
-        final_damage *= 1.0 - hunter.weakspot_analysis as f64 * 0.11;
-    }
-    
-    // Apply main DR
-    let mitigated_damage = final_damage * (1.0 - hunter.damage_reduction);
+    // Borge: Minotaur DR, Weakspot Analysis on crits, then main DR
+    let mitigated_damage = mitigate(hunter, attacker, damage, 0.0, is_crit);
     hunter.hp -= mitigated_damage;
     
     // Track stats
     hunter.result.damage_taken += mitigated_damage;
     hunter.result.enemy_attacks += 1;
-    hunter.result.mitigated_damage += final_damage - mitigated_damage;
     
     // Helltouch Barrier reflection (Borge)
     if hunter.helltouch_barrier_level > 0 && mitigated_damage > 0.0 {
         let helltouch_effect = if attacker.is_boss { 0.1 } else { 1.0 };
-        let reflected = mitigated_damage * hunter.helltouch_barrier_level as f64 * 0.08 * helltouch_effect;
+        let reflected = mitigated_damage * hunter.helltouch_barrier_level as f64 * hunter.per_level(CombatEffect::HelltouchBarrier) * helltouch_effect;
         attacker.hp -= reflected;
         hunter.result.helltouch_barrier += reflected;
         if attacker.is_dead() {
@@ -902,7 +1774,7 @@ fn borge_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64,
 }
 
 /// Ozzy receive damage - mirrors Python's Ozzy.receive_damage()
-fn ozzy_receive_damage(hunter: &mut Hunter, _attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut FastRng) {
+fn ozzy_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut impl SimRng) {
     // Python Step 1: Check trickster charges FIRST
     if hunter.trickster_charges > 0 {
         hunter.trickster_charges -= 1;
@@ -917,22 +1789,18 @@ fn ozzy_receive_damage(hunter: &mut Hunter, _attacker: &mut Enemy, damage: f64,
     }
     
     // Python Step 3: Failed to evade - take damage
-    // Apply scarab DR (separate multiplicative layer)
-    let scarab_reduced = damage * (1.0 - hunter.scarab_dr);
-    let mitigated_damage = scarab_reduced * (1.0 - hunter.damage_reduction);
+    // Scarab DR (separate multiplicative layer), then main DR
+    let mitigated_damage = mitigate(hunter, attacker, damage, 0.0, is_crit);
     hunter.hp -= mitigated_damage;
     
     // Track stats
     hunter.result.damage_taken += mitigated_damage;
     hunter.result.enemy_attacks += 1;
-    hunter.result.mitigated_damage += scarab_reduced - mitigated_damage;
     
     // Python Step 4: Dance of Dashes - on crit, chance to gain trickster charge
-    if is_crit && hunter.dance_of_dashes > 0 {
-        if rng.f64() < hunter.dance_of_dashes as f64 * 0.05 {
-            hunter.trickster_charges += 1;
-            hunter.result.effect_procs += 1;
-        }
+    if is_crit && hunter.dance_of_dashes > 0 && rng.f64() < hunter.dance_of_dashes as f64 * hunter.per_level(CombatEffect::DanceOfDashes) {
+        hunter.trickster_charges += 1;
+        hunter.result.effect_procs += 1;
     }
     
     // Check death and revive
@@ -942,26 +1810,26 @@ fn ozzy_receive_damage(hunter: &mut Hunter, _attacker: &mut Enemy, damage: f64,
 }
 
 /// Knox receive damage - mirrors Python's Knox.receive_damage()
-fn knox_receive_damage(hunter: &mut Hunter, _attacker: &mut Enemy, damage: f64, _is_crit: bool, rng: &mut FastRng) {
+fn knox_receive_damage(hunter: &mut Hunter, attacker: &mut Enemy, damage: f64, is_crit: bool, rng: &mut impl SimRng) {
     let mut final_damage = damage;
+    let mut blocked = 0.0;
     
     // Check for block first
     // Python: if random.random() < self.block_chance: blocked_amount = damage * 0.5
     if rng.f64() < hunter.block_chance {
-        let blocked = damage * 0.5;
+        blocked = damage * 0.5;
         final_damage -= blocked;
         // Track blocked damage (we could add a field for this)
     }
     
     // Apply remaining damage through DR
     if final_damage > 0.0 {
-        let mitigated_damage = final_damage * (1.0 - hunter.damage_reduction);
+        let mitigated_damage = mitigate(hunter, attacker, damage, blocked, is_crit);
         hunter.hp -= mitigated_damage;
         
         // Track stats
         hunter.result.damage_taken += mitigated_damage;
         hunter.result.enemy_attacks += 1;
-        hunter.result.mitigated_damage += final_damage - mitigated_damage;
         
         // Check death and revive
         if hunter.is_dead() {
@@ -970,32 +1838,45 @@ fn knox_receive_damage(hunter: &mut Hunter, _attacker: &mut Enemy, damage: f64,
     }
 }
 
+/// Count one kill and run on-kill effects as many times as the hunter's
+/// `OnKillPolicy` calls for (Python's Ozzy runs them twice for attack kills)
+pub fn resolve_kill(hunter: &mut Hunter, rng: &mut impl SimRng, is_boss: bool, cause: KillCause) {
+    for _ in 0..hunter.on_kill_policy.calls(cause) {
+        on_kill(hunter, rng, is_boss);
+    }
+    hunter.result.kills += 1;
+    hunter.result.band_mut(hunter.current_stage).kills += 1;
+    trace_at!(hunter, Loot, ?cause, boss = is_boss, kills = hunter.result.kills, "kill");
+}
+
 /// On kill effects - mirrors Python's Hunter.on_kill()
-fn on_kill(hunter: &mut Hunter, rng: &mut FastRng, is_boss: bool) {
+fn on_kill(hunter: &mut Hunter, rng: &mut impl SimRng, is_boss: bool) {
     let effective_effect_chance = hunter.get_effective_effect_chance(is_boss);
     
-    // DEBUG: Track how many times on_kill is called
+    // Compared with kills to show the on-kill policy in effect
     hunter.result.on_kill_calls += 1;
     
     // Call Me Lucky Loot proc (not on bosses) - independent RNG, separate from other effect procs
     // Each talent/ability has its own effect_chance roll, so Lucky Loot gets its own counter
-    if !is_boss && hunter.call_me_lucky_loot > 0 {
-        if rng.f64() < effective_effect_chance {
-            hunter.result.lucky_loot_procs += 1;
-        }
+    if !is_boss && hunter.call_me_lucky_loot > 0 && rng.f64() < effective_effect_chance {
+        hunter.result.lucky_loot_procs += 1;
     }
     
     // Unfair Advantage - Python: if random.random() < effect_chance and UA:
     //   heal = max_hp * 0.02 * UA_level
     if hunter.unfair_advantage > 0 && rng.f64() < effective_effect_chance {
-        let heal = hunter.max_hp * 0.02 * hunter.unfair_advantage as f64;
-        hunter.hp = (hunter.hp + heal).min(hunter.max_hp);
-        hunter.result.unfair_advantage_healing += heal;
+        let heal = hunter.max_hp * hunter.per_level(CombatEffect::UnfairAdvantage) * hunter.unfair_advantage as f64;
+        trace_at!(hunter, Procs, proc = "unfair_advantage", heal);
+        hunter.heal(heal, HealSource::UnfairAdvantage);
         hunter.result.effect_procs += 1;
         
-        // Vectid Elixir (Ozzy) - empowered regen for 5 ticks
+        // Vectid Elixir (Ozzy) - empowered regen for 5 ticks, stacked by the build's rules
         if hunter.vectid_elixir > 0 {
-            hunter.empowered_regen += 5;
+            let (left, dropped) = hunter.stacking.empowered_regen_after_proc(hunter.empowered_regen, EMPOWERED_REGEN_TICKS);
+            hunter.empowered_regen = left;
+            hunter.result.empowered_regen_procs += 1;
+            hunter.result.empowered_regen_dropped += dropped;
+            hunter.result.empowered_regen_peak = hunter.result.empowered_regen_peak.max(left);
         }
     }
     
@@ -1003,13 +1884,14 @@ fn on_kill(hunter: &mut Hunter, rng: &mut FastRng, is_boss: bool) {
 }
 
 /// On stage complete - mirrors Python's Simulation.complete_stage()
-fn on_stage_complete(hunter: &mut Hunter, rng: &mut FastRng, is_boss: bool) {
+fn on_stage_complete(hunter: &mut Hunter, rng: &mut impl SimRng, is_boss: bool) {
     let effective_effect_chance = hunter.get_effective_effect_chance(is_boss);
     
     // Calypso's Advantage (Knox) - chance to gain Hundred Souls stack
     if hunter.calypsos_advantage > 0 && rng.f64() < effective_effect_chance * 2.5 {
-        let max_stacks = 100 + hunter.soul_amplification * 10;
+        let max_stacks = hunter.hundred_souls_cap();
         if hunter.hundred_souls_stacks < max_stacks {
+            trace_at!(hunter, Procs, proc = "calypsos_advantage", stacks = hunter.hundred_souls_stacks + 1);
             hunter.hundred_souls_stacks += 1;
             hunter.result.effect_procs += 1;  // Track effect proc
         }
@@ -1018,7 +1900,13 @@ fn on_stage_complete(hunter: &mut Hunter, rng: &mut FastRng, is_boss: bool) {
 
 /// Run multiple simulations in parallel
 pub fn run_simulations_parallel(config: &BuildConfig, count: usize) -> Vec<SimResult> {
-    (0..count)
+    run_seeds_parallel(config, 0..count)
+}
+
+/// Run simulations `seeds` of a parallel batch (simulation i runs with seed
+/// i), so a batch run in pieces matches the batch run whole
+pub fn run_seeds_parallel(config: &BuildConfig, seeds: std::ops::Range<usize>) -> Vec<SimResult> {
+    seeds
         .into_par_iter()
         .map(|i| run_simulation_with_seed(config, i as u64))
         .collect()
@@ -1026,12 +1914,56 @@ pub fn run_simulations_parallel(config: &BuildConfig, count: usize) -> Vec<SimRe
 
 /// Run multiple simulations sequentially
 pub fn run_simulations_sequential(config: &BuildConfig, count: usize) -> Vec<SimResult> {
-    let mut rng = FastRng::new(rand::random::<u64>());
+    run_simulations_with_stream(config, count, &mut FastRng::new(rand::random::<u64>()))
+}
+
+/// Run `count` simulations one after another, each continuing `rng`'s stream
+pub fn run_simulations_with_stream(config: &BuildConfig, count: usize, rng: &mut FastRng) -> Vec<SimResult> {
     (0..count)
-        .map(|_| run_simulation_with_rng(config, &mut rng))
+        .map(|_| run_simulation_with_rng(config, rng))
         .collect()
 }
 
+/// Throughput of one RNG mode in `bench_rng`
+#[derive(Debug, Clone, Serialize)]
+pub struct RngBench {
+    pub batched: bool,
+    pub sims: usize,
+    pub seconds: f64,
+    pub sims_per_sec: f64,
+    pub avg_stage: f64,  // Must match across modes
+    pub avg_loot: f64,
+}
+
+/// Time the same seeded batch with single and batched RNG draws. Leaves batch
+/// RNG off afterwards.
+pub fn bench_rng(config: &BuildConfig, count: usize, parallel: bool) -> Vec<RngBench> {
+    let runs = [false, true]
+        .into_iter()
+        .map(|batched| {
+            set_batch_rng(batched);
+            let start = Instant::now();
+            let results: Vec<SimResult> = if parallel {
+                run_simulations_parallel(config, count)
+            } else {
+                (0..count).map(|i| run_simulation_with_seed(config, i as u64)).collect()
+            };
+            let seconds = start.elapsed().as_secs_f64();
+            let stats = AggregatedStats::from_results(&results);
+            RngBench {
+                batched,
+                sims: count,
+                seconds,
+                sims_per_sec: count as f64 / seconds.max(f64::EPSILON),
+                avg_stage: stats.avg_stage,
+                avg_loot: stats.avg_loot,
+            }
+        })
+        .collect();
+    set_batch_rng(false);
+    runs
+}
+
 /// Run simulations and return aggregated stats - MATCHES WHAT main.rs AND python.rs EXPECT
 pub fn run_and_aggregate(config: &BuildConfig, count: usize, parallel: bool) -> AggregatedStats {
     let results = if parallel {
@@ -1042,3 +1974,18 @@ pub fn run_and_aggregate(config: &BuildConfig, count: usize, parallel: bool) ->
     
     AggregatedStats::from_results(&results)
 }
+
+/// Like `run_and_aggregate`, but rejects an empty batch or a build with unusable
+/// derived stats instead of returning zeroed or NaN stats
+pub fn try_run_and_aggregate(config: &BuildConfig, count: usize, parallel: bool) -> Result<AggregatedStats, SimError> {
+    if count == 0 {
+        return Err(SimError::NoSimulations);
+    }
+    validate_config(config)?;
+    Ok(run_and_aggregate(config, count, parallel))
+}
+
+/// Derive the hunter once and check its stats before committing to a batch
+pub fn validate_config(config: &BuildConfig) -> Result<(), SimError> {
+    Hunter::from_config(config).validate()
+}
@@ -0,0 +1,226 @@
+//! Stable entry point for running simulation batches from Rust
+//!
+//! ```no_run
+//! use rust_sim::{BuildConfig, Simulator};
+//!
+//! let config = BuildConfig::from_file("builds/empty_borge.yaml")?;
+//! let stats = Simulator::builder()
+//!     .config(config)
+//!     .sims(10_000)
+//!     .seed(42)
+//!     .parallel(true)
+//!     .build()?
+//!     .run();
+//! println!("avg stage {:.1}", stats.avg_stage);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::config::BuildConfig;
+use crate::error::SimError;
+use crate::simulation::{run_simulation_with_seed, validate_config};
+use crate::stats::{AggregatedStats, RunningSnapshot, RunningStats, SimResult};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Receives per-run notifications from a [`Simulator`] batch.
+///
+/// Runs may complete on several threads at once when the batch is parallel,
+/// so implementations must be thread-safe and should not assume ordering.
+pub trait Observer: Send + Sync {
+    /// Called before run `index` starts
+    fn on_run_start(&self, _index: usize) {}
+    
+    /// Called after run `index` finishes
+    fn on_run_complete(&self, _index: usize, _result: &SimResult) {}
+}
+
+/// Lets one observer be shared with the code watching it
+impl<O: Observer + ?Sized> Observer for Arc<O> {
+    fn on_run_start(&self, index: usize) {
+        (**self).on_run_start(index)
+    }
+    
+    fn on_run_complete(&self, index: usize, result: &SimResult) {
+        (**self).on_run_complete(index, result)
+    }
+}
+
+/// Observer keeping a count and running aggregates of finished runs, for
+/// progress bars and live convergence views while a batch is in flight
+#[derive(Debug, Default)]
+pub struct BatchProgress {
+    completed: AtomicUsize,
+    running: Mutex<RunningStats>,
+}
+
+impl BatchProgress {
+    /// Runs finished so far
+    pub fn completed(&self) -> usize {
+        self.completed.load(Ordering::Relaxed)
+    }
+    
+    /// Aggregates over the runs finished so far
+    pub fn snapshot(&self) -> RunningSnapshot {
+        self.running.lock().unwrap().snapshot()
+    }
+}
+
+impl Observer for BatchProgress {
+    fn on_run_complete(&self, _index: usize, result: &SimResult) {
+        self.running.lock().unwrap().push(result);
+        self.completed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Shared flag that stops a [`Simulator`] batch early. Runs already in
+/// flight finish; no new run starts once it is set, and the batch returns
+/// the runs that completed.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Ask every batch holding this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    
+    /// Whether `cancel` has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Builder for [`Simulator`]
+#[derive(Default)]
+pub struct SimulatorBuilder {
+    config: Option<BuildConfig>,
+    sims: Option<usize>,
+    seed: Option<u64>,
+    parallel: Option<bool>,
+    observer: Option<Arc<dyn Observer>>,
+    cancel: Option<CancelToken>,
+}
+
+impl SimulatorBuilder {
+    /// Build configuration to simulate (required)
+    pub fn config(mut self, config: BuildConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+    
+    /// Number of runs in the batch (default 100)
+    pub fn sims(mut self, sims: usize) -> Self {
+        self.sims = Some(sims);
+        self
+    }
+    
+    /// Base seed; run `i` uses `seed + i`. Without a seed a random base is drawn.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+    
+    /// Spread runs over the rayon thread pool (default true)
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = Some(parallel);
+        self
+    }
+    
+    /// Attach an observer notified as each run starts and completes
+    pub fn observer<O: Observer + 'static>(mut self, observer: O) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+    
+    /// Stop the batch early when `token` is cancelled
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+    
+    /// Validate the settings and produce a ready-to-run simulator
+    pub fn build(self) -> Result<Simulator, SimError> {
+        let config = self.config.ok_or(SimError::MissingConfig)?;
+        let sims = self.sims.unwrap_or(100);
+        if sims == 0 {
+            return Err(SimError::NoSimulations);
+        }
+        validate_config(&config)?;
+        Ok(Simulator {
+            config,
+            sims,
+            seed: self.seed.unwrap_or_else(rand::random::<u64>),
+            parallel: self.parallel.unwrap_or(true),
+            observer: self.observer,
+            cancel: self.cancel,
+        })
+    }
+}
+
+/// A configured simulation batch.
+///
+/// Every run gets its own seed derived from the base seed, so the same seed
+/// reproduces the same results whether or not the batch runs in parallel.
+pub struct Simulator {
+    config: BuildConfig,
+    sims: usize,
+    seed: u64,
+    parallel: bool,
+    observer: Option<Arc<dyn Observer>>,
+    cancel: Option<CancelToken>,
+}
+
+impl Simulator {
+    /// Start configuring a simulator
+    pub fn builder() -> SimulatorBuilder {
+        SimulatorBuilder::default()
+    }
+    
+    /// The build configuration being simulated
+    pub fn config(&self) -> &BuildConfig {
+        &self.config
+    }
+    
+    /// Number of runs in the batch
+    pub fn sims(&self) -> usize {
+        self.sims
+    }
+    
+    /// Base seed of the batch (drawn at build time if none was given)
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    
+    /// Run the batch and aggregate the results
+    pub fn run(&self) -> AggregatedStats {
+        AggregatedStats::from_results(&self.run_detailed())
+    }
+    
+    /// Run the batch and return every individual result, in run order
+    /// (fewer than `sims` if the batch was cancelled)
+    pub fn run_detailed(&self) -> Vec<SimResult> {
+        let next = |i| (!self.is_cancelled()).then(|| self.run_one(i));
+        if self.parallel {
+            (0..self.sims).into_par_iter().map(next).while_some().collect()
+        } else {
+            (0..self.sims).map_while(next).collect()
+        }
+    }
+    
+    /// Whether the batch's cancel token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+    
+    fn run_one(&self, index: usize) -> SimResult {
+        if let Some(ref obs) = self.observer {
+            obs.on_run_start(index);
+        }
+        let result = run_simulation_with_seed(&self.config, self.seed.wrapping_add(index as u64));
+        if let Some(ref obs) = self.observer {
+            obs.on_run_complete(index, &result);
+        }
+        result
+    }
+}
@@ -0,0 +1,93 @@
+//! How far a batch's headline numbers move with its seeds (`stability`)
+//!
+//! The same batch is run several times, batch b with seeds `b*n..(b+1)*n`,
+//! and each metric's spread between batches is reported next to its mean. A
+//! batch average's spread shrinks with the square root of its size, so the
+//! spread at `n` also says how many runs would bring it down to a target.
+
+use crate::config::BuildConfig;
+use crate::simulation::{run_seeds_parallel, run_simulation_with_seed};
+use crate::stats::AggregatedStats;
+use serde::Serialize;
+
+/// Batches compared when the caller doesn't say
+pub const DEFAULT_BATCHES: usize = 5;
+
+/// Relative spread between batches the sample-size advice aims for
+pub const DEFAULT_TARGET_SPREAD: f64 = 0.01;
+
+/// One metric over the batches
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSpread {
+    pub metric: &'static str,
+    pub mean: f64,
+    pub std_dev: f64,                   // Between batch averages
+    pub relative_spread: f64,           // std_dev / |mean|, 0 when the mean is 0
+    pub min: f64,
+    pub max: f64,
+    pub sims_for_target: Option<usize>, // Runs per batch for the target spread; None when the mean is 0
+}
+
+/// Every headline metric's spread for one build
+#[derive(Debug, Clone, Serialize)]
+pub struct StabilityReport {
+    pub sims: usize,            // Per batch
+    pub batches: usize,
+    pub target_spread: f64,
+    pub metrics: Vec<MetricSpread>,
+    pub recommended_sims: usize,  // The most any metric needs for the target
+}
+
+/// Reads one headline metric off a batch's aggregate
+type Metric = fn(&AggregatedStats) -> f64;
+
+/// Headline metrics compared between batches
+const METRICS: [(&str, Metric); 8] = [
+    ("avg_stage", |s| s.avg_stage),
+    ("avg_kills", |s| s.avg_kills),
+    ("avg_time", |s| s.avg_time),
+    ("avg_damage_taken", |s| s.avg_damage_taken),
+    ("avg_loot", |s| s.avg_loot),
+    ("avg_loot_per_hour", |s| s.avg_loot_per_hour),
+    ("avg_xp_per_hour", |s| s.avg_xp_per_hour),
+    ("boss1_survival", |s| s.boss1_survival),
+];
+
+/// Run `batches` seeded batches of `sims` runs and compare their headline metrics
+pub fn measure(config: &BuildConfig, sims: usize, batches: usize, target_spread: f64, parallel: bool) -> StabilityReport {
+    let sims = sims.max(1);
+    let batches = batches.max(2);
+    let stats: Vec<AggregatedStats> = (0..batches)
+        .map(|b| {
+            let seeds = b * sims..(b + 1) * sims;
+            let results = if parallel {
+                run_seeds_parallel(config, seeds)
+            } else {
+                seeds.map(|i| run_simulation_with_seed(config, i as u64)).collect()
+            };
+            AggregatedStats::from_results(&results)
+        })
+        .collect();
+    let metrics: Vec<MetricSpread> = METRICS
+        .iter()
+        .map(|&(metric, get)| spread(metric, &stats.iter().map(get).collect::<Vec<_>>(), sims, target_spread))
+        .collect();
+    let recommended_sims = metrics.iter().filter_map(|m| m.sims_for_target).max().unwrap_or(sims);
+    StabilityReport { sims, batches, target_spread, metrics, recommended_sims }
+}
+
+fn spread(metric: &'static str, values: &[f64], sims: usize, target: f64) -> MetricSpread {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let relative_spread = if mean == 0.0 { 0.0 } else { std_dev / mean.abs() };
+    MetricSpread {
+        metric,
+        mean,
+        std_dev,
+        relative_spread,
+        min: values.iter().copied().fold(f64::INFINITY, f64::min),
+        max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        sims_for_target: (mean != 0.0 && target > 0.0).then(|| (sims as f64 * (relative_spread / target).powi(2)).ceil().max(1.0) as usize),
+    }
+}
@@ -1,10 +1,14 @@
 //! Simulation result statistics
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Results from a single simulation run
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::pyclass(get_all, frozen, module = "rust_sim"))]
 pub struct SimResult {
+    pub start_stage: i32,    // Stage play began on: 0, or the build's `start.stage` or a snapshot's
     pub final_stage: i32,
     pub elapsed_time: f64,
     pub kills: i32,
@@ -16,6 +20,8 @@ pub struct SimResult {
     pub loot_uncommon: f64,  // Mat2 - Behlium
     pub loot_rare: f64,      // Mat3 - Hellish-Biomatter
     pub total_xp: f64,       // XP earned
+    pub event_loot: f64,     // Part of total_loot from the build's loot events
+    pub event_xp: f64,       // Part of total_xp from XP events
     pub attacks: i32,
     pub crits: i32,
     pub extra_damage_from_crits: f64,
@@ -25,10 +31,17 @@ pub struct SimResult {
     pub enemy_attacks: i32,  // Total incoming enemy attacks
     pub regenerated_hp: f64,
     pub lifesteal: f64,
-    pub mitigated_damage: f64,
+    // Healing that would have gone above max HP
+    pub overheal_regen: f64,
+    pub overheal_lifesteal: f64,
+    pub overheal_procs: f64,          // Life of the Hunt + Unfair Advantage
+    pub mitigated_damage: f64,        // Taken off by main DR, after the hunter's other layers
+    pub total_mitigated_damage: f64,  // Taken off by every layer (Minotaur, Scarab, Weakspot, DR)
     pub effect_procs: i32,
     pub lucky_loot_procs: i32,  // Separate counter for Lucky Loot (independent RNG)
     pub stun_duration_inflicted: f64,
+    pub nominal_speed: f64,           // The hunter's `speed` stat (seconds per attack)
+    pub max_hp: f64,
     // Hunter-specific stats
     pub helltouch_barrier: f64,
     pub helltouch_kills: i32,
@@ -36,15 +49,707 @@ pub struct SimResult {
     pub medusa_kills: i32,
     pub trickster_evades: i32,
     pub echo_bullets: i32,
-    pub unfair_advantage_healing: f64,
-    pub life_of_the_hunt_healing: f64,
+    pub unfair_advantage_healing: f64,            // Attempted: what the procs rolled, overheal included
+    pub life_of_the_hunt_healing: f64,            // Attempted, as above
+    pub unfair_advantage_healing_effective: f64,  // The part that landed below max HP
+    pub life_of_the_hunt_healing_effective: f64,
     // Knox-specific stats
     pub ghost_bullets: i32,           // Extra projectiles from Ghost Bullets talent
     pub extra_salvo_damage: f64,      // Extra damage from ghost bullet projectiles
+    // Exoscarab harden windows (Ozzy stage 200+ boss)
+    pub harden_windows: i32,          // Hardens started
+    pub harden_ticks: i32,            // Regen ticks (seconds) spent hardened
+    pub harden_wasted_damage: f64,    // Damage absorbed by harden DR on top of the boss's base DR
+    pub held_multistrikes: i32,       // Multistrikes held for release after harden (policy)
+    // Vectid Elixir empowered regen (Ozzy)
+    pub empowered_regen_procs: i32,   // Unfair Advantage procs that empowered regen
+    pub empowered_regen_dropped: i32, // Empowered ticks the `stacking` rules dropped
+    pub empowered_regen_peak: i32,    // Most empowered ticks banked at once
+    pub empowered_regen_seconds: f64, // Simulated seconds with empowered ticks banked
+    // Boss enrage
+    pub boss_fights: i32,
+    pub boss_kills: i32,
+    pub enrage_at_boss_kill: i32,     // Sum of enrage stacks each killed boss had when it died
+    pub max_enrage_fights: i32,       // Boss fights where the boss passed 200 stacks
+    pub boss_stages_fought: Vec<i32>, // Boss stages whose fight started, in order
+    pub boss_stages_cleared: Vec<i32>, // Boss stages whose boss was killed, in order
+    pub bands: Vec<BandResult>,       // Per stage band, from stage 0 up to the band the run ended in
+    pub loot_tail: Vec<f64>,          // Share of this run's loot from its last LOOT_TAIL_STAGES[i] stages
+    pub boss_entries: Vec<BossEntry>, // Hunter state as each boss fight started, in order
+    pub sustain: Vec<PhaseSustain>,   // Per FightPhase, in FightPhase::ALL order once anything is recorded
+    pub revives: i32,                 // Revives used
+    pub end: RunEnd,
+    pub seed: Option<u64>,            // None for runs sharing one RNG stream (sequential batches)
+    // Where the run would have died: final_stage if it did, extrapolated if it
+    // stopped at max_stage (see wall), None if that found no wall or too few stages
+    pub wall_stage: Option<i32>,
+    pub wall_unbounded: bool,         // Stopped at max_stage with no wall within wall::MAX_EXTRAPOLATION stages
+    pub stage_time_growth: f64,       // Per-stage growth of clear time near max_stage; 0 if not fitted
     // Debug stats
     pub on_kill_calls: i32,
 }
 
+impl SimResult {
+    /// Stages this run earned loot and XP for. Loot is the geometric series
+    /// over them, so every per-stage and per-hour rate divides the loot of
+    /// exactly the stages the simulated time was spent on
+    pub fn stages_played(&self) -> i32 {
+        (self.final_stage - self.start_stage).max(0)
+    }
+
+    /// Loot over simulated hours; 0 for a run that took no time
+    pub fn loot_per_hour(&self) -> f64 {
+        if self.elapsed_time > 0.0 { self.total_loot / (self.elapsed_time / 3600.0) } else { 0.0 }
+    }
+
+    /// Loot over stages played; 0 for a run that played none
+    pub fn loot_per_stage(&self) -> f64 {
+        let stages = self.stages_played();
+        if stages > 0 { self.total_loot / stages as f64 } else { 0.0 }
+    }
+
+    /// Counters of the stage band containing `stage`, adding bands up to it
+    pub fn band_mut(&mut self, stage: i32) -> &mut BandResult {
+        let index = (stage.max(0) / STAGE_BAND) as usize;
+        while self.bands.len() <= index {
+            let start_stage = self.bands.len() as i32 * STAGE_BAND;
+            self.bands.push(BandResult { start_stage, ..Default::default() });
+        }
+        &mut self.bands[index]
+    }
+
+    /// Sustain counters of `phase`, adding every phase's on first use
+    pub fn sustain_mut(&mut self, phase: FightPhase) -> &mut PhaseSustain {
+        if self.sustain.is_empty() {
+            self.sustain = FightPhase::ALL.iter().map(|&phase| PhaseSustain { phase, ..Default::default() }).collect();
+        }
+        &mut self.sustain[phase as usize]
+    }
+}
+
+/// Why a run ended
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, frozen, module = "rust_sim"))]
+pub enum RunEnd {
+    #[default]
+    Unfinished,    // Still running (a partial result)
+    EnemyAttack,   // Killed, out of revives, by each kind of hit
+    EnemySpecial,
+    BossAttack,
+    BossSpecial,
+    MaxStage,      // Reached the build's max stage
+    StageLimit,    // The engine's stage safety limit
+    Abandoned,     // Early termination: out of revives with no way to reach stage 100 in time
+    TimedOut,      // Played the simulated-time limit (see simulation::set_max_sim_time)
+    Aborted,       // Ran past the wall-clock watchdog (see simulation::set_watchdog)
+}
+
+impl RunEnd {
+    pub fn label(self) -> &'static str {
+        match self {
+            RunEnd::Unfinished => "unfinished",
+            RunEnd::EnemyAttack => "enemy attack",
+            RunEnd::EnemySpecial => "enemy special",
+            RunEnd::BossAttack => "boss attack",
+            RunEnd::BossSpecial => "boss special",
+            RunEnd::MaxStage => "max stage",
+            RunEnd::StageLimit => "stage limit",
+            RunEnd::Abandoned => "abandoned",
+            RunEnd::TimedOut => "timed out",
+            RunEnd::Aborted => "aborted by watchdog",
+        }
+    }
+
+    /// Whether the hunter was killed, rather than stopped
+    pub fn is_death(self) -> bool {
+        matches!(self, RunEnd::EnemyAttack | RunEnd::EnemySpecial | RunEnd::BossAttack | RunEnd::BossSpecial)
+    }
+}
+
+/// Which kind of fight the hunter is in, for the sustain split (`SimResult::sustain`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "python", pyo3::pyclass(eq, eq_int, frozen, module = "rust_sim"))]
+pub enum FightPhase {
+    #[default]
+    Trash,         // Regular enemies
+    Boss,          // A boss at up to 200 enrage stacks
+    BossEnraged,   // A boss past 200 stacks (3x power, 100% crit)
+}
+
+impl FightPhase {
+    pub const ALL: [FightPhase; 3] = [FightPhase::Trash, FightPhase::Boss, FightPhase::BossEnraged];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FightPhase::Trash => "trash",
+            FightPhase::Boss => "boss",
+            FightPhase::BossEnraged => "boss, max enrage",
+        }
+    }
+}
+
+/// Stage counts for the loot concentration shares (`SimResult::loot_tail`)
+pub const LOOT_TAIL_STAGES: [i32; 4] = [10, 20, 50, 100];
+
+/// Share of the batch's loot earned in the last `stages` stages of each run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LootTail {
+    pub stages: i32,
+    pub share: f64,  // Loot-weighted over runs
+}
+
+impl LootTail {
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        let total: f64 = results.iter().map(|r| r.total_loot).sum();
+        LOOT_TAIL_STAGES
+            .iter()
+            .enumerate()
+            .map(|(i, &stages)| {
+                let tail: f64 = results.iter().map(|r| r.total_loot * r.loot_tail.get(i).copied().unwrap_or(0.0)).sum();
+                Self { stages, share: if total > 0.0 { tail / total } else { 0.0 } }
+            })
+            .collect()
+    }
+}
+
+/// How far the batch's runs could go, extrapolating the ones `max_stage` stopped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WallEstimate {
+    pub capped_runs: i32,         // Runs that stopped at max_stage
+    pub extrapolated_runs: i32,   // Of those, runs with a predicted wall
+    pub unbounded_runs: i32,      // Of those, runs whose curves found no wall (see wall::MAX_EXTRAPOLATION)
+    // Over every run with a wall stage, deaths at their final stage and capped
+    // runs at their prediction; unbounded runs rank above them all, so a
+    // percentile landing on one is None
+    pub avg: f64,                 // Unbounded runs excluded
+    pub p10: Option<i32>,
+    pub median: Option<i32>,
+    pub p90: Option<i32>,
+    pub stage_time_growth: f64,   // Mean per-stage growth of clear time near the cap, over capped runs fitted
+}
+
+impl WallEstimate {
+    pub fn from_results(results: &[SimResult]) -> Self {
+        let capped: Vec<&SimResult> = results.iter().filter(|r| r.end == RunEnd::MaxStage).collect();
+        let mut walls: Vec<i32> = results.iter().filter_map(|r| r.wall_stage).collect();
+        walls.sort_unstable();
+        let unbounded = capped.iter().filter(|r| r.wall_unbounded).count();
+        // Nearest rank, over the runs with a wall stage and the unbounded ones
+        let ranked = walls.len() + unbounded;
+        let percentile = |q: f64| {
+            let rank = ((ranked as f64 * q).ceil() as usize).saturating_sub(1);
+            if ranked == 0 { None } else { walls.get(rank).copied() }
+        };
+        let growths: Vec<f64> = capped.iter().map(|r| r.stage_time_growth).filter(|&g| g > 0.0).collect();
+        Self {
+            capped_runs: capped.len() as i32,
+            extrapolated_runs: capped.iter().filter(|r| r.wall_stage.is_some()).count() as i32,
+            unbounded_runs: unbounded as i32,
+            avg: if walls.is_empty() { 0.0 } else { walls.iter().map(|&w| w as f64).sum::<f64>() / walls.len() as f64 },
+            p10: percentile(0.1),
+            median: percentile(0.5),
+            p90: percentile(0.9),
+            stage_time_growth: if growths.is_empty() { 0.0 } else { growths.iter().sum::<f64>() / growths.len() as f64 },
+        }
+    }
+}
+
+/// Stages per band in the per-band breakdowns (every band after the first
+/// starts on a boss stage)
+pub const STAGE_BAND: i32 = 100;
+
+/// One run's counters for the stages `start_stage..start_stage + STAGE_BAND`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::pyclass(get_all, frozen, module = "rust_sim"))]
+pub struct BandResult {
+    pub start_stage: i32,
+    pub kills: i32,
+    pub attacks: i32,
+    pub killing_blows: i32,  // Kills landed by a hunter attack
+    pub overkill: f64,       // Damage past the target's remaining HP on those killing blows
+    pub attack_time: f64,    // Seconds between attacks, summed (realized attack interval x attacks)
+    pub stun_time: f64,      // Seconds of enemy attacks delayed by stuns
+    // Defense, for effective HP
+    pub fights: i32,               // Enemies engaged
+    pub incoming_damage: f64,      // Raw enemy damage, before evade, block and DR
+    pub damage_taken: f64,
+    pub regen: f64,                // Effective regen healing
+    pub boss_incoming_damage: f64, // The same three for the boss fight at `start_stage`
+    pub boss_damage_taken: f64,
+    pub boss_regen: f64,
+    // Loot from the stages cleared in this band
+    pub loot_common: f64,
+    pub loot_uncommon: f64,
+    pub loot_rare: f64,
+}
+
+/// One run's damage taken and healing during one `FightPhase`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::pyclass(get_all, frozen, module = "rust_sim"))]
+pub struct PhaseSustain {
+    pub phase: FightPhase,
+    pub seconds: f64,            // Regen ticks spent in the phase
+    pub damage_taken: f64,
+    // Effective healing by source, and what went past max HP from all of them
+    pub regen: f64,
+    pub lifesteal: f64,
+    pub procs: f64,              // Life of the Hunt + Unfair Advantage
+    pub overheal: f64,
+}
+
+impl PhaseSustain {
+    pub fn healing(&self) -> f64 {
+        self.regen + self.lifesteal + self.procs
+    }
+}
+
+/// The hunter's state at the start of one boss fight
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::pyclass(get_all, frozen, module = "rust_sim"))]
+pub struct BossEntry {
+    pub stage: i32,
+    pub hp: f64,
+    pub max_hp: f64,
+    pub revives_left: i32,
+    pub fires_of_war_buff: f64,     // Borge
+    pub trickster_charges: i32,     // Ozzy
+    pub empowered_regen: i32,       // Ozzy
+    pub hundred_souls_stacks: i32,  // Knox
+    pub charge: f64,                // Knox
+    pub empowered_block_regen: i32, // Knox
+}
+
+/// Effective HP: the raw enemy damage one fight from full HP can absorb,
+/// `(max_hp + regen per fight) * incoming / taken`. Incoming damage is
+/// counted before evade, trickster charges, block and every DR layer, so
+/// each of them raises the number. Infinite if every hit was avoided, 0 if
+/// nothing attacked.
+pub fn effective_hp(max_hp: f64, regen_per_fight: f64, incoming: f64, taken: f64) -> f64 {
+    if incoming <= 0.0 {
+        0.0
+    } else if taken <= 0.0 {
+        f64::INFINITY
+    } else {
+        (max_hp + regen_per_fight) * incoming / taken
+    }
+}
+
+/// Sustain in one `FightPhase`, over every run's time in it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhaseSustainStats {
+    pub phase: FightPhase,
+    pub runs: i32,                  // Runs that spent any time in the phase
+    pub seconds: f64,               // Per such run
+    pub damage_taken_per_second: f64,
+    pub healing_per_second: f64,    // Effective, all sources
+    pub coverage: f64,              // Healing / damage taken; below 1 the phase drains HP
+    // Shares of the effective healing
+    pub regen_share: f64,
+    pub lifesteal_share: f64,
+    pub procs_share: f64,
+    pub overheal_rate: f64,         // Overheal / (healing + overheal)
+}
+
+impl PhaseSustainStats {
+    /// One entry per phase any run spent time in
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+        FightPhase::ALL
+            .iter()
+            .filter_map(|&phase| {
+                let spent: Vec<&PhaseSustain> =
+                    results.iter().filter_map(|r| r.sustain.get(phase as usize)).filter(|p| p.seconds > 0.0).collect();
+                if spent.is_empty() {
+                    return None;
+                }
+                let sum = |value: fn(&PhaseSustain) -> f64| spent.iter().map(|p| value(p)).sum::<f64>();
+                let (seconds, taken, healing, overheal) = (sum(|p| p.seconds), sum(|p| p.damage_taken), sum(PhaseSustain::healing), sum(|p| p.overheal));
+                Some(Self {
+                    phase,
+                    runs: spent.len() as i32,
+                    seconds: seconds / spent.len() as f64,
+                    damage_taken_per_second: ratio(taken, seconds),
+                    healing_per_second: ratio(healing, seconds),
+                    coverage: ratio(healing, taken),
+                    regen_share: ratio(sum(|p| p.regen), healing),
+                    lifesteal_share: ratio(sum(|p| p.lifesteal), healing),
+                    procs_share: ratio(sum(|p| p.procs), healing),
+                    overheal_rate: ratio(overheal, healing + overheal),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Kill speed in one stage band, over the runs that reached it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BandStats {
+    pub start_stage: i32,
+    pub runs: i32,                // Runs that reached this band
+    pub avg_kills: f64,           // Per run reaching the band
+    pub attacks_per_kill: f64,
+    pub avg_overkill: f64,        // Per killing blow
+    pub avg_attack_interval: f64, // Realized seconds per attack (Fires of War, Atlas Protocol, catch-up)
+    pub effective_aps: f64,       // Attacks per second, 1 / avg_attack_interval
+    pub avg_stun_time: f64,       // Seconds of enemy attacks delayed by stuns, per run reaching the band
+    pub ehp: f64,                 // See effective_hp
+    pub loot_share: f64,          // Fraction of all loot (every material) earned in this band
+}
+
+impl BandStats {
+    /// Per-band aggregates, one entry per band any run reached
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        let bands = results.iter().map(|r| r.bands.len()).max().unwrap_or(0);
+        let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+        let total_loot: f64 = results.iter().map(|r| r.total_loot).sum();
+        (0..bands)
+            .map(|i| {
+                let reached: Vec<&BandResult> = results.iter().filter_map(|r| r.bands.get(i)).collect();
+                let kills: f64 = reached.iter().map(|b| b.kills as f64).sum();
+                let attacks: f64 = reached.iter().map(|b| b.attacks as f64).sum();
+                let killing_blows: f64 = reached.iter().map(|b| b.killing_blows as f64).sum();
+                let overkill: f64 = reached.iter().map(|b| b.overkill).sum();
+                let attack_time: f64 = reached.iter().map(|b| b.attack_time).sum();
+                let fights: f64 = reached.iter().map(|b| b.fights as f64).sum();
+                let max_hp = ratio(results.iter().filter(|r| r.bands.len() > i).map(|r| r.max_hp).sum(), reached.len() as f64);
+                Self {
+                    start_stage: i as i32 * STAGE_BAND,
+                    runs: reached.len() as i32,
+                    avg_kills: ratio(kills, reached.len() as f64),
+                    attacks_per_kill: ratio(attacks, kills),
+                    avg_overkill: ratio(overkill, killing_blows),
+                    avg_attack_interval: ratio(attack_time, attacks),
+                    effective_aps: ratio(attacks, attack_time),
+                    avg_stun_time: ratio(reached.iter().map(|b| b.stun_time).sum(), reached.len() as f64),
+                    ehp: effective_hp(
+                        max_hp,
+                        ratio(reached.iter().map(|b| b.regen).sum(), fights),
+                        reached.iter().map(|b| b.incoming_damage).sum(),
+                        reached.iter().map(|b| b.damage_taken).sum(),
+                    ),
+                    loot_share: ratio(reached.iter().map(|b| b.loot_common + b.loot_uncommon + b.loot_rare).sum(), total_loot),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Survival at one boss stage, from the boss encounters of every run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BossSurvival {
+    pub stage: i32,
+    pub reached: f64,    // Fraction of all runs that started this boss fight
+    pub passed: f64,     // Fraction of all runs that killed this boss
+    pub kill_rate: f64,  // Fraction of runs reaching this boss that killed it
+    pub ehp: f64,        // Effective HP in this boss fight, see effective_hp
+}
+
+impl BossSurvival {
+    /// Per-stage survival from the boss stages each run fought and cleared
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        let mut counts: BTreeMap<i32, (usize, usize)> = BTreeMap::new();
+        // Max HP, regen, incoming and taken damage summed over each boss's fights
+        let mut defense: BTreeMap<i32, [f64; 4]> = BTreeMap::new();
+        for r in results {
+            for &stage in &r.boss_stages_fought {
+                if let Some(band) = r.bands.get((stage / STAGE_BAND) as usize) {
+                    let sums = defense.entry(stage).or_default();
+                    sums[0] += r.max_hp;
+                    sums[1] += band.boss_regen;
+                    sums[2] += band.boss_incoming_damage;
+                    sums[3] += band.boss_damage_taken;
+                }
+            }
+            for &stage in &r.boss_stages_fought {
+                counts.entry(stage).or_default().0 += 1;
+            }
+            for &stage in &r.boss_stages_cleared {
+                counts.entry(stage).or_default().1 += 1;
+            }
+        }
+        let n = results.len().max(1) as f64;
+        counts
+            .into_iter()
+            .map(|(stage, (reached, passed))| {
+                let [max_hp, regen, incoming, taken] = defense.get(&stage).copied().unwrap_or_default();
+                let fights = reached.max(1) as f64;
+                Self {
+                    stage,
+                    reached: reached as f64 / n,
+                    passed: passed as f64 / n,
+                    kill_rate: if reached > 0 { passed as f64 / reached as f64 } else { 0.0 },
+                    ehp: effective_hp(max_hp / fights, regen / fights, incoming, taken),
+                }
+            })
+            .collect()
+    }
+}
+
+/// How runs arrived at one boss stage, from the hunter state as each of its
+/// fights started. Separates "can't kill the boss" from "arrives half-dead".
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BossEntryStats {
+    pub stage: i32,
+    pub entries: i32,                 // Runs that started this boss fight
+    pub avg_hp_fraction: f64,         // HP / max HP on entry
+    pub hp_buckets: [f64; 4],         // Fraction of entries with 0-25%, 25-50%, 50-75% and 75-100% HP
+    pub kill_rate_below_half: f64,    // Kill rate of entries below half HP
+    pub kill_rate_above_half: f64,    // Kill rate of entries at half HP or more
+    pub avg_revives_left: f64,
+    pub revives_left: Vec<f64>,       // Fraction of entries with 0, 1, 2, ... revives left
+    // Buff states, per entry
+    pub fires_of_war_rate: f64,       // Borge: Fires of War active
+    pub avg_trickster_charges: f64,   // Ozzy
+    pub empowered_regen_rate: f64,    // Ozzy: empowered regen active
+    pub avg_hundred_souls_stacks: f64, // Knox
+    pub avg_charge: f64,              // Knox
+    pub empowered_block_regen_rate: f64, // Knox: block regen buff active
+}
+
+impl BossEntryStats {
+    /// Per-stage entry state, one entry per boss stage any run fought
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        // Each entry with whether that run went on to kill the boss
+        let mut by_stage: BTreeMap<i32, Vec<(&BossEntry, bool)>> = BTreeMap::new();
+        for r in results {
+            for entry in &r.boss_entries {
+                let killed = r.boss_stages_cleared.contains(&entry.stage);
+                by_stage.entry(entry.stage).or_default().push((entry, killed));
+            }
+        }
+        let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+        by_stage
+            .into_iter()
+            .map(|(stage, entries)| {
+                let n = entries.len() as f64;
+                let fraction = |e: &BossEntry| ratio(e.hp, e.max_hp);
+                let avg = |f: &dyn Fn(&BossEntry) -> f64| entries.iter().map(|(e, _)| f(e)).sum::<f64>() / n;
+                let kill_rate = |below: bool| {
+                    let group: Vec<bool> = entries.iter().filter(|(e, _)| (fraction(e) < 0.5) == below).map(|&(_, k)| k).collect();
+                    ratio(group.iter().filter(|&&k| k).count() as f64, group.len() as f64)
+                };
+                let mut hp_buckets = [0.0; 4];
+                let mut revives_left = Vec::new();
+                for (e, _) in &entries {
+                    hp_buckets[((fraction(e) * 4.0) as usize).min(3)] += 1.0 / n;
+                    let revives = e.revives_left.max(0) as usize;
+                    if revives_left.len() <= revives {
+                        revives_left.resize(revives + 1, 0.0);
+                    }
+                    revives_left[revives] += 1.0 / n;
+                }
+                Self {
+                    stage,
+                    entries: entries.len() as i32,
+                    avg_hp_fraction: avg(&fraction),
+                    hp_buckets,
+                    kill_rate_below_half: kill_rate(true),
+                    kill_rate_above_half: kill_rate(false),
+                    avg_revives_left: avg(&|e| e.revives_left as f64),
+                    revives_left,
+                    fires_of_war_rate: avg(&|e| (e.fires_of_war_buff > 0.0) as i32 as f64),
+                    avg_trickster_charges: avg(&|e| e.trickster_charges as f64),
+                    empowered_regen_rate: avg(&|e| (e.empowered_regen > 0) as i32 as f64),
+                    avg_hundred_souls_stacks: avg(&|e| e.hundred_souls_stacks as f64),
+                    avg_charge: avg(&|e| e.charge),
+                    empowered_block_regen_rate: avg(&|e| (e.empowered_block_regen > 0) as i32 as f64),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Runs a correlation needs before it's reported
+const MIN_CORRELATION_RUNS: usize = 10;
+
+/// Pearson correlation between one in-run metric and an outcome across runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Correlation {
+    pub metric: String,
+    pub outcome: String,  // `final_stage`, or `boss_<stage>_kill` (1 if the run killed that boss)
+    pub r: f64,
+    pub runs: i32,        // Runs with a value for the metric
+}
+
+type RunMetric = fn(&SimResult) -> Option<f64>;
+type EntryMetric = fn(&BossEntry) -> f64;
+
+/// Per-run rates of the random events, named for `Correlation::metric`.
+/// None where the run had no chance for the event. Rates that drift with the
+/// stage (damage per hit, procs per kill) are left out, since they'd track
+/// the final stage whatever the luck; the `early_` metrics cover stages 0-99,
+/// which every run plays.
+const RUN_METRICS: &[(&str, RunMetric)] = &[
+    ("crit_rate", |r| per(r.crits as f64, r.attacks as f64)),
+    ("multistrike_rate", |r| per(r.multistrikes as f64, r.attacks as f64)),
+    ("echo_bullet_rate", |r| per(r.echo_bullets as f64, r.attacks as f64)),
+    ("ghost_bullet_rate", |r| per(r.ghost_bullets as f64, r.attacks as f64)),
+    ("stun_per_attack", |r| per(r.stun_duration_inflicted, r.attacks as f64)),
+    ("evade_rate", |r| per(r.evades as f64, (r.evades + r.trickster_evades + r.enemy_attacks) as f64)),
+    ("trickster_evade_rate", |r| per(r.trickster_evades as f64, (r.evades + r.trickster_evades + r.enemy_attacks) as f64)),
+    ("lucky_loot_per_kill", |r| per(r.lucky_loot_procs as f64, r.kills as f64)),
+    ("early_attacks_per_kill", |r| r.bands.first().and_then(|b| per(b.attacks as f64, b.kills as f64))),
+    ("early_damage_taken_per_fight", |r| r.bands.first().and_then(|b| per(b.damage_taken, b.fights as f64))),
+];
+
+/// Hunter state on entering a boss, named `boss_entry_<name>`
+const ENTRY_METRICS: &[(&str, EntryMetric)] = &[
+    ("hp_fraction", |e| if e.max_hp > 0.0 { e.hp / e.max_hp } else { 0.0 }),
+    ("revives_left", |e| e.revives_left as f64),
+    ("fires_of_war", |e| e.fires_of_war_buff),
+    ("trickster_charges", |e| e.trickster_charges as f64),
+    ("empowered_regen", |e| e.empowered_regen as f64),
+    ("hundred_souls_stacks", |e| e.hundred_souls_stacks as f64),
+    ("charge", |e| e.charge),
+];
+
+fn per(num: f64, den: f64) -> Option<f64> {
+    (den > 0.0).then(|| num / den)
+}
+
+/// Pearson's r; None if either side is constant
+fn pearson(pairs: &[(f64, f64)]) -> Option<f64> {
+    let constant = |side: fn(&(f64, f64)) -> f64| pairs.iter().all(|p| side(p) == side(&pairs[0]));
+    if pairs.is_empty() || constant(|p| p.0) || constant(|p| p.1) {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mx = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let my = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for &(x, y) in pairs {
+        sxy += (x - mx) * (y - my);
+        sxx += (x - mx) * (x - mx);
+        syy += (y - my) * (y - my);
+    }
+    let r = sxy / (sxx * syy).sqrt();
+    r.is_finite().then_some(r)
+}
+
+impl Correlation {
+    /// Every reportable correlation, strongest first: each run metric against
+    /// the final stage, and each boss-entry state against killing that boss
+    pub fn from_results(results: &[SimResult]) -> Vec<Self> {
+        let mut found = Vec::new();
+        let mut push = |metric: String, outcome: String, pairs: Vec<(f64, f64)>| {
+            if pairs.len() >= MIN_CORRELATION_RUNS {
+                if let Some(r) = pearson(&pairs) {
+                    found.push(Self { metric, outcome, r, runs: pairs.len() as i32 });
+                }
+            }
+        };
+        for (name, metric) in RUN_METRICS {
+            let pairs = results.iter().filter_map(|r| Some((metric(r)?, r.final_stage as f64))).collect();
+            push(name.to_string(), "final_stage".to_string(), pairs);
+        }
+
+        let mut entries: BTreeMap<i32, Vec<(&BossEntry, bool)>> = BTreeMap::new();
+        for r in results {
+            for entry in &r.boss_entries {
+                entries.entry(entry.stage).or_default().push((entry, r.boss_stages_cleared.contains(&entry.stage)));
+            }
+        }
+        for (stage, entries) in entries {
+            for (name, metric) in ENTRY_METRICS {
+                let pairs = entries.iter().map(|&(e, killed)| (metric(e), killed as i32 as f64)).collect();
+                push(format!("boss_entry_{}", name), format!("boss_{}_kill", stage), pairs);
+            }
+        }
+
+        found.sort_by(|a, b| b.r.abs().total_cmp(&a.r.abs()));
+        found
+    }
+}
+
+/// Runs listed at each end of `AggregatedStats::best_runs`/`worst_runs`
+pub const TOP_RUNS: usize = 5;
+
+/// Hunter damage over one window of a run, for DPS curves (`replay --dps-curve`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DamageWindow {
+    pub start: f64,    // Run time, in seconds
+    pub end: f64,
+    pub damage: f64,
+    pub dps: f64,
+    pub attacks: i32,
+    pub stage: i32,    // Of the window's last attack, or the last one before it
+    pub boss: bool,    // Any of its attacks was at a boss
+}
+
+impl DamageWindow {
+    /// Bucket `(time, damage, stage)` attack samples into `window`-second
+    /// windows from `start` to `end`, windows without attacks included
+    pub fn bucket(samples: &[(f64, f64, i32)], start: f64, end: f64, window: f64, first_stage: i32) -> Vec<Self> {
+        let count = ((end - start) / window).ceil().max(1.0) as usize;
+        let mut windows: Vec<Self> = (0..count)
+            .map(|i| {
+                let from = start + i as f64 * window;
+                Self { start: from, end: (from + window).min(end.max(from)), ..Default::default() }
+            })
+            .collect();
+        for &(time, damage, stage) in samples {
+            let i = (((time - start) / window).floor().max(0.0) as usize).min(count - 1);
+            let w = &mut windows[i];
+            w.damage += damage;
+            w.attacks += 1;
+            w.stage = stage;
+            w.boss |= stage % 100 == 0 && stage > 0;
+        }
+        let mut stage = first_stage;
+        for w in &mut windows {
+            if w.attacks > 0 {
+                stage = w.stage;
+            } else {
+                w.stage = stage;
+            }
+            let seconds = w.end - w.start;
+            w.dps = if seconds > 0.0 { w.damage / seconds } else { 0.0 };
+        }
+        windows
+    }
+}
+
+/// One run in brief, for the best and worst runs of a batch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub seed: Option<u64>,
+    pub final_stage: i32,
+    pub elapsed_time: f64,
+    pub total_loot: f64,
+    pub revives: i32,
+    pub end: RunEnd,
+}
+
+impl RunSummary {
+    pub fn new(result: &SimResult) -> Self {
+        Self {
+            seed: result.seed,
+            final_stage: result.final_stage,
+            elapsed_time: result.elapsed_time,
+            total_loot: result.total_loot,
+            revives: result.revives,
+            end: result.end,
+        }
+    }
+
+    /// The `TOP_RUNS` best and worst runs, by final stage, then loot, then
+    /// the quicker run
+    pub fn extremes(results: &[SimResult]) -> (Vec<Self>, Vec<Self>) {
+        let mut ranked: Vec<&SimResult> = results.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.final_stage
+                .cmp(&a.final_stage)
+                .then(b.total_loot.total_cmp(&a.total_loot))
+                .then(a.elapsed_time.total_cmp(&b.elapsed_time))
+        });
+        let best = ranked.iter().take(TOP_RUNS).map(|r| Self::new(r)).collect();
+        let worst = ranked.iter().rev().take(TOP_RUNS).map(|r| Self::new(r)).collect();
+        (best, worst)
+    }
+}
+
 /// Aggregated statistics from multiple simulation runs
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AggregatedStats {
@@ -54,8 +759,16 @@ pub struct AggregatedStats {
     pub min_stage: i32,
     pub max_stage: i32,
     pub avg_time: f64,
-    pub avg_loot: f64,
-    pub avg_loot_per_hour: f64,
+    pub avg_loot: f64,              // Loot per run
+    pub avg_loot_per_hour: f64,     // Mean of each run's loot per hour, so every run counts the same
+    pub loot_per_sim_hour: f64,     // All loot over all simulated hours, so longer runs count for more
+    pub loot_per_stage: f64,        // All loot over all stages played
+    // Nearest-rank percentiles of each run's loot per hour: a bad session and a good one
+    pub p10_loot_per_hour: f64,
+    pub p90_loot_per_hour: f64,
+    pub avg_loot_common_per_hour: f64,
+    pub avg_loot_uncommon_per_hour: f64,
+    pub avg_loot_rare_per_hour: f64,
     pub min_loot_common: f64,
     pub max_loot_common: f64,
     pub avg_loot_common: f64,
@@ -67,7 +780,9 @@ pub struct AggregatedStats {
     pub avg_loot_rare: f64,
     pub avg_damage: f64,
     pub avg_damage_taken: f64,
-    pub avg_mitigated: f64,
+    pub avg_mitigated: f64,         // By main DR only, as before the layers were reported
+    pub avg_total_mitigated: f64,   // By every layer
+    pub effective_mitigation: f64,  // Total mitigated / (taken + total mitigated) over all landed hits
     pub avg_lifesteal: f64,
     pub avg_attacks: f64,
     pub avg_crits: f64,
@@ -78,25 +793,66 @@ pub struct AggregatedStats {
     pub avg_effect_procs: f64,
     pub avg_stun_duration: f64,
     pub avg_trample_kills: f64,
-    pub avg_loth_healing: f64,
-    pub avg_ua_healing: f64,
+    pub avg_loth_healing: f64,  // Attempted, overheal included
+    pub avg_ua_healing: f64,    // Attempted, overheal included
+    pub avg_loth_healing_effective: f64,
+    pub avg_ua_healing_effective: f64,
     pub avg_regen: f64,
+    pub avg_overheal_regen: f64,
+    pub avg_overheal_lifesteal: f64,
+    pub avg_overheal_procs: f64,      // Life of the Hunt + Unfair Advantage
     pub survival_rate: f64,  // Legacy: % of runs that didn't die exactly at a boss stage
-    // Boss milestone survival rates - % of runs that PASSED each boss
-    pub boss1_survival: f64,  // % that reached stage > 100
-    pub boss2_survival: f64,  // % that reached stage > 200
-    pub boss3_survival: f64,  // % that reached stage > 300
-    pub boss4_survival: f64,  // % that reached stage > 400
-    pub boss5_survival: f64,  // % that reached stage > 500
+    pub boss_survival: Vec<BossSurvival>,  // One entry per boss stage any run fought, by stage
+    pub boss_entry: Vec<BossEntryStats>,   // Hunter state arriving at each boss stage, by stage
+    pub best_runs: Vec<RunSummary>,        // See TOP_RUNS, best first
+    pub worst_runs: Vec<RunSummary>,       // Worst first
+    pub correlations: Vec<Correlation>,    // What drives the spread between runs, strongest first
+    // Aliases of boss_survival[..].passed for the GUI - % of runs that PASSED each boss
+    pub boss1_survival: f64,  // % that cleared stage 100
+    pub boss2_survival: f64,  // % that cleared stage 200
+    pub boss3_survival: f64,  // % that cleared stage 300
+    pub boss4_survival: f64,  // % that cleared stage 400
+    pub boss5_survival: f64,  // % that cleared stage 500
     pub avg_xp: f64,
+    pub avg_xp_per_hour: f64,
+    // Loot events (the build's `events` section); the same runs without them
+    // earn the averages above less these
+    pub avg_event_loot_per_hour: f64,
+    pub avg_event_xp_per_hour: f64,
     // Hunter-specific aggregated stats
     pub avg_extra_from_crits: f64,    // Borge: extra damage from crits
     pub avg_multistrikes: f64,        // Ozzy: multistrike count
     pub avg_ms_extra_damage: f64,     // Ozzy: extra damage from multistrikes
+    pub avg_echo_bullets: f64,        // Ozzy: echo bullet procs
     pub avg_helltouch: f64,           // Borge: helltouch barrier damage
     pub avg_ghost_bullets: f64,       // Knox: ghost bullet procs
     pub avg_extra_salvo_damage: f64,  // Knox: extra damage from ghost bullets
-    pub avg_on_kill_calls: f64,       // DEBUG: on_kill calls per run
+    pub avg_harden_windows: f64,      // Ozzy: Exoscarab hardens per run
+    pub avg_harden_duration: f64,     // Ozzy: seconds the boss spent hardened
+    pub avg_harden_wasted_damage: f64, // Ozzy: damage lost to harden DR
+    pub avg_held_multistrikes: f64,   // Ozzy: multistrikes held during harden
+    pub avg_empowered_regen_procs: f64,   // Ozzy: Vectid Elixir procs per run
+    pub avg_empowered_regen_dropped: f64, // Ozzy: empowered ticks dropped by the stacking rules
+    pub avg_empowered_regen_peak: f64,    // Ozzy: most ticks banked at once, per run
+    pub empowered_regen_uptime: f64,      // Ozzy: share of simulated time with the buff up
+    pub avg_enrage_at_boss_kill: f64, // Mean enrage stacks of bosses when they died
+    pub max_enrage_rate: f64,         // Fraction of boss fights that reached max enrage (>200 stacks)
+    // Kill speed
+    pub attacks_per_kill: f64,
+    pub avg_overkill: f64,            // Damage past the target's remaining HP, per killing blow
+    // Attack speed (seconds per attack)
+    pub nominal_speed: f64,           // The `speed` stat
+    pub avg_attack_interval: f64,     // Realized, including Fires of War, Atlas Protocol and catch-up
+    pub effective_aps: f64,           // Attacks per second, 1 / avg_attack_interval
+    pub ehp: f64,                     // Effective HP over every fight, see effective_hp
+    pub bands: Vec<BandStats>,        // Per stage band, see STAGE_BAND
+    pub loot_tail: Vec<LootTail>,     // Loot concentration in the last stages of a run
+    pub timed_out_runs: i32,          // Runs ended by the simulated-time limit
+    pub aborted_runs: i32,            // Runs ended by the wall-clock watchdog
+    pub wall: WallEstimate,           // Predicted wall stage, past max_stage where it stops runs
+    pub sustain: Vec<PhaseSustainStats>, // Damage taken vs healing in trash, boss and max-enrage fights
+    pub avg_on_kill_calls: f64,       // on_kill calls per run
+    pub on_kill_calls_per_kill: f64,  // 1.0 unless the on-kill policy double-procs
 }
 
 impl AggregatedStats {
@@ -123,26 +879,38 @@ impl AggregatedStats {
             .sum::<f64>() / n;
         let std_stage = variance.sqrt();
         
-        let loot_per_hours: Vec<f64> = results
-            .iter()
-            .map(|r| {
-                if r.elapsed_time > 0.0 {
-                    r.total_loot / (r.elapsed_time / 3600.0)
-                } else {
-                    0.0
-                }
-            })
-            .collect();
+        let loot_per_hours: Vec<f64> = results.iter().map(SimResult::loot_per_hour).collect();
+        let mut ranked_loot_per_hours = loot_per_hours.clone();
+        ranked_loot_per_hours.sort_by(f64::total_cmp);
+        let loot_per_hour_at = |q: f64| ranked_loot_per_hours[((n * q).ceil() as usize).saturating_sub(1)];
+        let total_hours = times.iter().sum::<f64>() / 3600.0;
+        let total_stages: f64 = results.iter().map(|r| r.stages_played() as f64).sum();
+        let avg_per_hour = |amount: fn(&SimResult) -> f64| {
+            results
+                .iter()
+                .map(|r| if r.elapsed_time > 0.0 { amount(r) / (r.elapsed_time / 3600.0) } else { 0.0 })
+                .sum::<f64>()
+                / n
+        };
         
         // Count boss deaths (died at stage ending in 00) - legacy metric
         let boss_deaths = stages.iter().filter(|&&s| s % 100 == 0 && s > 0).count();
         
         // Boss milestone survival - % of runs that PASSED each boss
-        let boss1_passed = stages.iter().filter(|&&s| s > 100).count();
-        let boss2_passed = stages.iter().filter(|&&s| s > 200).count();
-        let boss3_passed = stages.iter().filter(|&&s| s > 300).count();
-        let boss4_passed = stages.iter().filter(|&&s| s > 400).count();
-        let boss5_passed = stages.iter().filter(|&&s| s > 500).count();
+        let boss_survival = BossSurvival::from_results(results);
+        let (best_runs, worst_runs) = RunSummary::extremes(results);
+        let boss_passed = |k: i32| boss_survival.iter().find(|b| b.stage == 100 * k).map_or(0.0, |b| b.passed);
+        
+        // Enrage aggregates are per boss fight across all runs, not per run
+        // (summed as f64: an i32 total would overflow on a large batch)
+        let total = |count: fn(&SimResult) -> i32| results.iter().map(|r| count(r) as f64).sum::<f64>();
+        let boss_fights = total(|r| r.boss_fights);
+        let boss_kills = total(|r| r.boss_kills);
+        let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+        
+        // Realized attack interval over every attack of every run
+        let attacks = total(|r| r.attacks);
+        let attack_time: f64 = results.iter().flat_map(|r| &r.bands).map(|b| b.attack_time).sum();
         
         Self {
             runs: results.len() as i32,
@@ -153,6 +921,13 @@ impl AggregatedStats {
             avg_time: times.iter().sum::<f64>() / n,
             avg_loot: loots.iter().sum::<f64>() / n,
             avg_loot_per_hour: loot_per_hours.iter().sum::<f64>() / n,
+            loot_per_sim_hour: if total_hours > 0.0 { loots.iter().sum::<f64>() / total_hours } else { 0.0 },
+            p10_loot_per_hour: loot_per_hour_at(0.1),
+            p90_loot_per_hour: loot_per_hour_at(0.9),
+            loot_per_stage: if total_stages > 0.0 { loots.iter().sum::<f64>() / total_stages } else { 0.0 },
+            avg_loot_common_per_hour: avg_per_hour(|r| r.loot_common),
+            avg_loot_uncommon_per_hour: avg_per_hour(|r| r.loot_uncommon),
+            avg_loot_rare_per_hour: avg_per_hour(|r| r.loot_rare),
             min_loot_common: if loots_common.is_empty() { 0.0 } else { loots_common.iter().fold(f64::INFINITY, |a, &b| a.min(b)) },
             max_loot_common: if loots_common.is_empty() { 0.0 } else { loots_common.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)) },
             avg_loot_common: if loots_common.is_empty() { 0.0 } else { loots_common.iter().sum::<f64>() / n },
@@ -165,6 +940,12 @@ impl AggregatedStats {
             avg_damage: results.iter().map(|r| r.damage).sum::<f64>() / n,
             avg_damage_taken: results.iter().map(|r| r.damage_taken).sum::<f64>() / n,
             avg_mitigated: results.iter().map(|r| r.mitigated_damage).sum::<f64>() / n,
+            avg_total_mitigated: results.iter().map(|r| r.total_mitigated_damage).sum::<f64>() / n,
+            effective_mitigation: {
+                let mitigated: f64 = results.iter().map(|r| r.total_mitigated_damage).sum();
+                let taken: f64 = results.iter().map(|r| r.damage_taken).sum();
+                if mitigated + taken > 0.0 { mitigated / (mitigated + taken) } else { 0.0 }
+            },
             avg_lifesteal: results.iter().map(|r| r.lifesteal).sum::<f64>() / n,
             avg_attacks: results.iter().map(|r| r.attacks as f64).sum::<f64>() / n,
             avg_crits: results.iter().map(|r| r.crits as f64).sum::<f64>() / n,
@@ -177,22 +958,207 @@ impl AggregatedStats {
             avg_trample_kills: results.iter().map(|r| r.trample_kills as f64).sum::<f64>() / n,
             avg_loth_healing: results.iter().map(|r| r.life_of_the_hunt_healing).sum::<f64>() / n,
             avg_ua_healing: results.iter().map(|r| r.unfair_advantage_healing).sum::<f64>() / n,
+            avg_loth_healing_effective: results.iter().map(|r| r.life_of_the_hunt_healing_effective).sum::<f64>() / n,
+            avg_ua_healing_effective: results.iter().map(|r| r.unfair_advantage_healing_effective).sum::<f64>() / n,
             avg_regen: results.iter().map(|r| r.regenerated_hp).sum::<f64>() / n,
+            avg_overheal_regen: results.iter().map(|r| r.overheal_regen).sum::<f64>() / n,
+            avg_overheal_lifesteal: results.iter().map(|r| r.overheal_lifesteal).sum::<f64>() / n,
+            avg_overheal_procs: results.iter().map(|r| r.overheal_procs).sum::<f64>() / n,
             survival_rate: 1.0 - (boss_deaths as f64 / n),
-            boss1_survival: boss1_passed as f64 / n,
-            boss2_survival: boss2_passed as f64 / n,
-            boss3_survival: boss3_passed as f64 / n,
-            boss4_survival: boss4_passed as f64 / n,
-            boss5_survival: boss5_passed as f64 / n,
+            boss1_survival: boss_passed(1),
+            boss2_survival: boss_passed(2),
+            boss3_survival: boss_passed(3),
+            boss4_survival: boss_passed(4),
+            boss5_survival: boss_passed(5),
+            boss_survival,
+            boss_entry: BossEntryStats::from_results(results),
+            best_runs,
+            worst_runs,
+            correlations: Correlation::from_results(results),
             avg_xp: results.iter().map(|r| r.total_xp).sum::<f64>() / n,
+            avg_xp_per_hour: avg_per_hour(|r| r.total_xp),
+            avg_event_loot_per_hour: avg_per_hour(|r| r.event_loot),
+            avg_event_xp_per_hour: avg_per_hour(|r| r.event_xp),
             // Hunter-specific stats
             avg_extra_from_crits: results.iter().map(|r| r.extra_damage_from_crits).sum::<f64>() / n,
             avg_multistrikes: results.iter().map(|r| r.multistrikes as f64).sum::<f64>() / n,
             avg_ms_extra_damage: results.iter().map(|r| r.extra_damage_from_ms).sum::<f64>() / n,
+            avg_echo_bullets: results.iter().map(|r| r.echo_bullets as f64).sum::<f64>() / n,
             avg_helltouch: results.iter().map(|r| r.helltouch_barrier).sum::<f64>() / n,
             avg_ghost_bullets: results.iter().map(|r| r.ghost_bullets as f64).sum::<f64>() / n,
             avg_extra_salvo_damage: results.iter().map(|r| r.extra_salvo_damage).sum::<f64>() / n,
+            avg_harden_windows: results.iter().map(|r| r.harden_windows as f64).sum::<f64>() / n,
+            avg_harden_duration: results.iter().map(|r| r.harden_ticks as f64).sum::<f64>() / n,
+            avg_harden_wasted_damage: results.iter().map(|r| r.harden_wasted_damage).sum::<f64>() / n,
+            avg_held_multistrikes: results.iter().map(|r| r.held_multistrikes as f64).sum::<f64>() / n,
+            avg_empowered_regen_procs: results.iter().map(|r| r.empowered_regen_procs as f64).sum::<f64>() / n,
+            avg_empowered_regen_dropped: results.iter().map(|r| r.empowered_regen_dropped as f64).sum::<f64>() / n,
+            avg_empowered_regen_peak: results.iter().map(|r| r.empowered_regen_peak as f64).sum::<f64>() / n,
+            empowered_regen_uptime: {
+                let seconds: f64 = results.iter().map(|r| r.empowered_regen_seconds).sum();
+                if total_hours > 0.0 { seconds / (total_hours * 3600.0) } else { 0.0 }
+            },
+            avg_enrage_at_boss_kill: ratio(total(|r| r.enrage_at_boss_kill), boss_kills),
+            max_enrage_rate: ratio(total(|r| r.max_enrage_fights), boss_fights),
             avg_on_kill_calls: results.iter().map(|r| r.on_kill_calls as f64).sum::<f64>() / n,
+            on_kill_calls_per_kill: ratio(total(|r| r.on_kill_calls), total(|r| r.kills)),
+            attacks_per_kill: ratio(attacks, total(|r| r.kills)),
+            avg_overkill: {
+                let overkill: f64 = results.iter().flat_map(|r| &r.bands).map(|b| b.overkill).sum();
+                let blows: f64 = results.iter().flat_map(|r| &r.bands).map(|b| b.killing_blows as f64).sum();
+                ratio(overkill, blows)
+            },
+            nominal_speed: results.iter().map(|r| r.nominal_speed).sum::<f64>() / n,
+            avg_attack_interval: ratio(attack_time, attacks),
+            effective_aps: ratio(attacks, attack_time),
+            ehp: {
+                let bands = || results.iter().flat_map(|r| &r.bands);
+                let fights: f64 = bands().map(|b| b.fights as f64).sum();
+                let regen_per_fight = ratio(bands().map(|b| b.regen).sum(), fights);
+                effective_hp(
+                    results.iter().map(|r| r.max_hp).sum::<f64>() / n,
+                    regen_per_fight,
+                    bands().map(|b| b.incoming_damage).sum(),
+                    bands().map(|b| b.damage_taken).sum(),
+                )
+            },
+            bands: BandStats::from_results(results),
+            loot_tail: LootTail::from_results(results),
+            timed_out_runs: results.iter().filter(|r| r.end == RunEnd::TimedOut).count() as i32,
+            aborted_runs: results.iter().filter(|r| r.end == RunEnd::Aborted).count() as i32,
+            wall: WallEstimate::from_results(results),
+            sustain: PhaseSustainStats::from_results(results),
+        }
+    }
+}
+
+/// Headline aggregates updated one run at a time, for live progress views
+/// (stage mean and spread via Welford's method, so nothing is stored per run)
+#[derive(Debug, Clone, Default)]
+pub struct RunningStats {
+    runs: usize,
+    stage_mean: f64,
+    stage_m2: f64,
+    min_stage: i32,
+    max_stage: i32,
+    total_time: f64,
+    total_loot: f64,
+    total_loot_per_hour: f64,
+}
+
+/// Point-in-time view of a [`RunningStats`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunningSnapshot {
+    pub runs: usize,
+    pub avg_stage: f64,
+    pub std_stage: f64,
+    pub stderr_stage: f64,  // Standard error of avg_stage; shrinks as the batch converges
+    pub min_stage: i32,
+    pub max_stage: i32,
+    pub avg_time: f64,
+    pub avg_loot: f64,
+    pub avg_loot_per_hour: f64,
+}
+
+impl RunningStats {
+    /// Fold one finished run into the aggregates
+    pub fn push(&mut self, result: &SimResult) {
+        let stage = result.final_stage as f64;
+        self.runs += 1;
+        let delta = stage - self.stage_mean;
+        self.stage_mean += delta / self.runs as f64;
+        self.stage_m2 += delta * (stage - self.stage_mean);
+        if self.runs == 1 {
+            self.min_stage = result.final_stage;
+            self.max_stage = result.final_stage;
+        } else {
+            self.min_stage = self.min_stage.min(result.final_stage);
+            self.max_stage = self.max_stage.max(result.final_stage);
+        }
+        self.total_time += result.elapsed_time;
+        self.total_loot += result.total_loot;
+        self.total_loot_per_hour += result.loot_per_hour();
+    }
+
+    /// Aggregates over the runs pushed so far (population std, like [`AggregatedStats`])
+    pub fn snapshot(&self) -> RunningSnapshot {
+        if self.runs == 0 {
+            return RunningSnapshot::default();
+        }
+        let n = self.runs as f64;
+        let std_stage = (self.stage_m2 / n).sqrt();
+        RunningSnapshot {
+            runs: self.runs,
+            avg_stage: self.stage_mean,
+            std_stage,
+            stderr_stage: std_stage / n.sqrt(),
+            min_stage: self.min_stage,
+            max_stage: self.max_stage,
+            avg_time: self.total_time / n,
+            avg_loot: self.total_loot / n,
+            avg_loot_per_hour: self.total_loot_per_hour / n,
+        }
+    }
+}
+
+/// One metric of a [`PairedDeltas`]: both batch means and the mean per-run difference
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PairedDelta {
+    pub base: f64,
+    pub variant: f64,
+    pub delta: f64,   // Mean of variant - base, run by run
+    pub stderr: f64,  // Standard error of `delta`
+}
+
+impl PairedDelta {
+    fn from_pairs(pairs: impl Iterator<Item = (f64, f64)>) -> Self {
+        let (mut n, mut base, mut variant, mut mean, mut m2) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (b, v) in pairs {
+            n += 1.0;
+            base += b;
+            variant += v;
+            let d = v - b;
+            let step = d - mean;
+            mean += step / n;
+            m2 += step * (d - mean);
+        }
+        if n == 0.0 {
+            return Self::default();
+        }
+        Self {
+            base: base / n,
+            variant: variant / n,
+            delta: mean,
+            stderr: (m2 / n).sqrt() / n.sqrt(),
+        }
+    }
+}
+
+/// Paired differences between two batches run on the same seeds. Run `i` of
+/// each batch shares its random stream, so luck common to both cancels and
+/// `stderr` reflects the change rather than the run-to-run spread.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PairedDeltas {
+    pub runs: usize,
+    pub stage: PairedDelta,
+    pub time: PairedDelta,
+    pub loot: PairedDelta,
+    pub loot_per_hour: PairedDelta,
+    pub xp: PairedDelta,
+}
+
+impl PairedDeltas {
+    /// Pair `base[i]` with `variant[i]`; extra runs on either side are ignored
+    pub fn from_results(base: &[SimResult], variant: &[SimResult]) -> Self {
+        let per_hour = |r: &SimResult, value: f64| if r.elapsed_time > 0.0 { value / (r.elapsed_time / 3600.0) } else { 0.0 };
+        let metric = |f: &dyn Fn(&SimResult) -> f64| PairedDelta::from_pairs(base.iter().zip(variant).map(|(b, v)| (f(b), f(v))));
+        Self {
+            runs: base.len().min(variant.len()),
+            stage: metric(&|r| r.final_stage as f64),
+            time: metric(&|r| r.elapsed_time),
+            loot: metric(&|r| r.total_loot),
+            loot_per_hour: metric(&|r| per_hour(r, r.total_loot)),
+            xp: metric(&|r| r.total_xp),
         }
     }
 }
@@ -0,0 +1,119 @@
+//! Wall-stage estimate for runs that stop at the hunter's `max_stage`
+//!
+//! A run that reaches the cap ends alive, so its final stage says nothing
+//! about how far the build could go. Each run keeps its most recent normal
+//! stages' clear time, damage taken and healing, and when it hits the cap
+//! fits an exponential curve to each. Walking those curves forward from the
+//! HP and revives the run had left, the first stage whose damage outpaces its
+//! healing by more than the HP remaining is the predicted wall. The spread of predictions across runs is the uncertainty.
+//!
+//! The curves only see normal stages, so bosses past the cap are assumed no
+//! harder than the stages around them.
+
+use crate::stats::STAGE_BAND;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Most recent normal stages each run fits its curves to
+pub const FIT_STAGES: usize = 50;
+
+/// Fewest stages a fit needs before it predicts anything
+pub const MIN_FIT_STAGES: usize = 5;
+
+/// Stages past the cap the walk tries before giving up on finding a wall
+pub const MAX_EXTRAPOLATION: i32 = 1000;
+
+/// Share of a batch's runs stopping at the cap above which the cap, not the
+/// build, is what limits it
+pub const CAP_WARNING_RATE: f64 = 0.5;
+
+/// One normal stage's totals
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct StageSample {
+    stage: i32,
+    seconds: f64,
+    damage_taken: f64,
+    healing: f64,  // Including overheal: near the wall there's no headroom to waste it
+}
+
+/// The recent stages of a run, for extrapolating past the cap
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StageFit {
+    samples: VecDeque<StageSample>,
+}
+
+/// `y = exp(intercept + slope * stage)`, least squares on `ln y`
+#[derive(Debug, Clone, Copy)]
+struct Curve {
+    intercept: f64,
+    slope: f64,
+}
+
+impl Curve {
+    /// None with fewer than `MIN_FIT_STAGES` positive points or no spread in stage
+    fn fit(points: impl Iterator<Item = (f64, f64)>) -> Option<Self> {
+        let points: Vec<(f64, f64)> = points.filter(|&(_, y)| y > 0.0).map(|(x, y)| (x, y.ln())).collect();
+        if points.len() < MIN_FIT_STAGES {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mx = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let my = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|p| (p.0 - mx).powi(2)).sum();
+        let sxy: f64 = points.iter().map(|p| (p.0 - mx) * (p.1 - my)).sum();
+        if sxx == 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        Some(Self { intercept: my - slope * mx, slope })
+    }
+
+    fn at(&self, stage: i32) -> f64 {
+        (self.intercept + self.slope * stage as f64).exp()
+    }
+}
+
+impl StageFit {
+    /// Record a cleared normal stage, dropping the oldest past `FIT_STAGES`.
+    /// Enemies step up at each boss stage, so the samples restart in each band
+    pub fn push(&mut self, stage: i32, seconds: f64, damage_taken: f64, healing: f64) {
+        if self.samples.back().is_some_and(|s| s.stage / STAGE_BAND != stage / STAGE_BAND) {
+            self.samples.clear();
+        }
+        if self.samples.len() == FIT_STAGES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(StageSample { stage, seconds, damage_taken, healing });
+    }
+
+    fn curve(&self, value: fn(&StageSample) -> f64) -> Option<Curve> {
+        Curve::fit(self.samples.iter().map(|s| (s.stage as f64, value(s))))
+    }
+
+    /// Growth factor of the stage clear time per stage (1.05 is 5% slower each stage)
+    pub fn stage_time_growth(&self) -> Option<f64> {
+        self.curve(|s| s.seconds).map(|c| c.slope.exp())
+    }
+
+    /// First stage from `stage` on that the hunter wouldn't clear, starting it
+    /// at `hp` with `revives_left` revives at 80% HP. The outer None is too few
+    /// stages to fit; the inner one is no wall within `MAX_EXTRAPOLATION` stages
+    pub fn predict_wall(&self, stage: i32, hp: f64, max_hp: f64, revives_left: i32) -> Option<Option<i32>> {
+        let damage = self.curve(|s| s.damage_taken)?;
+        // A build without healing has nothing to fit and heals nothing
+        let healing = self.curve(|s| s.healing);
+        let (mut hp, mut revives_left) = (hp, revives_left);
+        for s in stage..stage + MAX_EXTRAPOLATION {
+            let net = damage.at(s) - healing.map_or(0.0, |c| c.at(s));
+            hp = (hp - net).min(max_hp);
+            if hp <= 0.0 {
+                if revives_left == 0 {
+                    return Some(Some(s));
+                }
+                revives_left -= 1;
+                hp = max_hp * 0.8;
+            }
+        }
+        Some(None)
+    }
+}
@@ -0,0 +1,75 @@
+//! Browser bindings using wasm-bindgen (`wasm` feature)
+//!
+//! JSON in, JSON out like the Python bindings. A wasm module has no rayon
+//! pool, so every batch runs sequentially on the calling thread; to spread a
+//! large batch over web workers, give each worker a range of run indices with
+//! [`simulate_chunk`] and merge the results with [`aggregate`]. Run `i` of a
+//! batch uses seed `seed + i`, the same as [`Simulator`](crate::Simulator),
+//! so a chunked batch matches the CLI run for run.
+//!
+//! Build with `wasm-pack build --target web -- --no-default-features --features wasm`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::{BuildConfig, HunterType};
+use crate::enemy::StageStats;
+use crate::hunter::HunterStats;
+use crate::simulation::{run_simulation_with_seed, validate_config};
+use crate::simulator::Simulator;
+use crate::stats::{AggregatedStats, SimResult};
+
+fn js_err(e: impl std::fmt::Display) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn parse_config(config_json: &str) -> Result<BuildConfig, JsError> {
+    let config = BuildConfig::from_json(config_json).map_err(js_err)?;
+    validate_config(&config).map_err(js_err)?;
+    Ok(config)
+}
+
+/// Run `num_sims` simulations and return the aggregated stats as JSON
+/// (the same shape as `simulate_json` in Python)
+#[wasm_bindgen]
+pub fn simulate(config_json: &str, num_sims: usize, seed: Option<u64>) -> Result<String, JsError> {
+    let mut builder = Simulator::builder().config(parse_config(config_json)?).sims(num_sims).parallel(false);
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    let stats = builder.build().map_err(js_err)?.run();
+    serde_json::to_string(&stats).map_err(js_err)
+}
+
+/// Run indices `start..start + count` of the batch seeded with `seed` and
+/// return the per-run results as a JSON array (for [`aggregate`])
+#[wasm_bindgen]
+pub fn simulate_chunk(config_json: &str, seed: u64, start: usize, count: usize) -> Result<String, JsError> {
+    let config = parse_config(config_json)?;
+    let results: Vec<SimResult> = (start..start + count)
+        .map(|i| run_simulation_with_seed(&config, seed.wrapping_add(i as u64)))
+        .collect();
+    serde_json::to_string(&results).map_err(js_err)
+}
+
+/// Aggregate per-run results from one or more [`simulate_chunk`] calls
+/// (a JSON array of arrays) into the stats [`simulate`] returns
+#[wasm_bindgen]
+pub fn aggregate(chunks_json: &str) -> Result<String, JsError> {
+    let chunks: Vec<Vec<SimResult>> = serde_json::from_str(chunks_json).map_err(js_err)?;
+    let results: Vec<SimResult> = chunks.into_iter().flatten().collect();
+    serde_json::to_string(&AggregatedStats::from_results(&results)).map_err(js_err)
+}
+
+/// Every derived hunter stat for a build, as JSON
+#[wasm_bindgen]
+pub fn hunter_stats(config_json: &str) -> Result<String, JsError> {
+    let config = BuildConfig::from_json(config_json).map_err(js_err)?;
+    serde_json::to_string(&HunterStats::from_config(&config)).map_err(js_err)
+}
+
+/// Regular enemy and boss stats for one stage of a hunter's path, as JSON
+#[wasm_bindgen]
+pub fn enemy_stats(hunter: &str, stage: i32) -> Result<String, JsError> {
+    let hunter_type: HunterType = serde_json::from_value(serde_json::Value::String(hunter.to_string())).map_err(js_err)?;
+    serde_json::to_string(&StageStats::new(hunter_type, stage)).map_err(js_err)
+}
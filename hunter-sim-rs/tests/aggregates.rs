@@ -0,0 +1,23 @@
+//! Batch aggregates stay exact when per-run counts sum past i32
+
+use rust_sim::stats::{AggregatedStats, SimResult};
+
+#[test]
+fn counts_summed_across_runs_do_not_overflow() {
+    let big = i32::MAX / 2 + 1;
+    let run = SimResult {
+        boss_fights: big,
+        boss_kills: big,
+        enrage_at_boss_kill: big,
+        max_enrage_fights: big / 2,
+        kills: big,
+        attacks: big,
+        on_kill_calls: big,
+        ..Default::default()
+    };
+    let stats = AggregatedStats::from_results(&[run.clone(), run.clone(), run]);
+    assert_eq!(stats.avg_enrage_at_boss_kill, 1.0);
+    assert_eq!(stats.max_enrage_rate, 0.5);
+    assert_eq!(stats.on_kill_calls_per_kill, 1.0);
+    assert_eq!(stats.attacks_per_kill, 1.0);
+}
@@ -0,0 +1,23 @@
+//! Boss previews play the real engine's fight, from a fresh hunter
+
+use rust_sim::boss_preview::{fight, preview};
+use rust_sim::examples;
+
+#[test]
+fn boss_preview_separates_beaten_and_walling_bosses() {
+    let config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let easy = preview(&config, 100, 4, false);
+    assert_eq!((easy.fights, easy.wins), (4, 4));
+    assert!(easy.losses.is_empty());
+    assert!(easy.win_seconds.p10 > 0.0 && easy.win_seconds.p10 <= easy.win_seconds.p90);
+    assert!(easy.avg_hp_left > 0.0 && easy.avg_hp_left <= 1.0);
+
+    let wall = preview(&config, 200, 4, false);
+    assert_eq!(wall.wins, 0);
+    assert_eq!(wall.losses.iter().map(|(_, n)| n).sum::<usize>(), 4);
+    assert!(wall.losses.iter().all(|(end, _)| end.is_death()));
+
+    // Seeded, so a fight replays exactly
+    let (a, b) = (fight(&config, 200, 3), fight(&config, 200, 3));
+    assert_eq!((a.seconds, a.hp_left, a.end), (b.seconds, b.hp_left, b.end));
+}
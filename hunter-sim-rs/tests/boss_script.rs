@@ -0,0 +1,75 @@
+//! Boss scripts: the built-in table as data, and scripts installed on top of it
+//!
+//! Installing is once per process, so this file is its own test binary.
+
+use rust_sim::boss_script::{self, BossEffect, Cooldown};
+use rust_sim::config::HunterType;
+use rust_sim::enemy::Enemy;
+use rust_sim::examples;
+use rust_sim::scenario::{self, Case};
+
+const SCRIPTS: &str = r#"
+- hunter: Ozzy
+  min_stage: 200
+  name: Exoscarab
+  cooldown: { seconds: 30 }
+  effects:
+    - { effect: harden, ticks: 5, dr: 0.95, regen_mult: 3, end_enrage: 5 }
+    - { effect: heal, fraction: 0.5 }
+  verified: true
+- hunter: Knox
+  min_stage: 250
+  name: Bombard
+  cooldown: { speed_factor: 2 }
+  effects:
+    - { effect: hit, damage_mult: 3 }
+    - { effect: enrage, stacks: 4 }
+"#;
+
+#[test]
+fn installed_scripts_replace_and_extend_the_built_in_ones() {
+    let built_in = boss_script::scripts();
+    let exoscarab = boss_script::for_boss(HunterType::Ozzy, 250).unwrap();
+    assert_eq!((exoscarab.name.as_ref(), exoscarab.cooldown), ("Exoscarab", Cooldown::Seconds(60.0)));
+    assert!(boss_script::for_boss(HunterType::Knox, 350).is_none());
+    // Provisional scripts are opt-in: the verified ones hold past stage 300
+    assert_eq!(boss_script::for_boss(HunterType::Borge, 350).unwrap().name, "Gothmorgor");
+    assert!(built_in.iter().all(|s| s.verified));
+
+    // Bad scripts are rejected at the offending entry
+    let bad = SCRIPTS.replace("fraction: 0.5", "fraction: 1.5");
+    let err = boss_script::from_str(&bad).unwrap_err();
+    assert!(err.to_string().contains("[0].effects[1]"), "{}", err);
+    assert!(boss_script::from_str(&SCRIPTS.replace("Knox", "Ozzy").replace("250", "200")).is_err());
+
+    let provisional = boss_script::from_str(boss_script::PROVISIONAL).unwrap();
+    assert!(provisional.iter().all(|s| !s.verified && s.min_stage >= 300));
+    let mut scripts = boss_script::from_str(SCRIPTS).unwrap();
+    scripts.extend(provisional.iter().cloned());
+    boss_script::install(scripts).unwrap();
+    assert!(boss_script::install(Vec::new()).is_err());
+
+    // One Ozzy script replaced; one Knox script and the provisional ones added
+    assert_eq!(boss_script::scripts().len(), built_in.len() + 1 + provisional.len());
+    assert_eq!(boss_script::for_boss(HunterType::Ozzy, 250).unwrap().cooldown, Cooldown::Seconds(30.0));
+    assert_eq!(boss_script::for_boss(HunterType::Ozzy, 300).unwrap().name, "Devastate");
+    let bombard = boss_script::for_boss(HunterType::Knox, 260).unwrap();
+    assert_eq!(bombard.effects[0], BossEffect::Hit { damage_mult: 3.0 });
+
+    // The boss plays its effects in order: harden, then a heal from a quarter HP
+    let boss = Enemy::new_boss(200, HunterType::Ozzy);
+    assert_eq!(boss.speed2, 30.0);
+    let case: Case = serde_yaml::from_str(&format!(
+        "{{name: exoscarab, stage: 200, boss: true, enemy_hp: {}, steps: [enemy_special], expect: {{enemy_hp: {}, harden_windows: 1}}}}",
+        boss.max_hp * 0.25,
+        boss.max_hp * 0.75,
+    ))
+    .unwrap();
+    let ozzy = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    let report = scenario::run_case(&ozzy, &case, 0).unwrap();
+    assert!(report.passed(), "{:?}", report.checks);
+
+    let knox = Enemy::new_boss(260, HunterType::Knox);
+    assert_eq!(knox.secondary.unwrap().name, "Bombard");
+    assert_eq!(knox.speed2, knox.speed * 2.0);
+}
@@ -0,0 +1,16 @@
+//! Boss scripts can't be installed once a simulation has built the bosses
+//!
+//! Its own test binary, so no other test has installed scripts first.
+
+use rust_sim::boss_script;
+use rust_sim::config::HunterType;
+use rust_sim::enemy::Enemy;
+
+#[test]
+fn installing_after_the_first_boss_is_refused() {
+    let boss = Enemy::new_boss(200, HunterType::Borge);
+    let scripts = boss_script::from_str(boss_script::PROVISIONAL).unwrap();
+    let err = boss_script::install(scripts).unwrap_err();
+    assert!(err.to_string().contains("before the first simulation"), "{}", err);
+    assert_eq!(boss.secondary.unwrap().name, "Gothmorgor");
+}
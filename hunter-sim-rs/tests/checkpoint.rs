@@ -0,0 +1,36 @@
+//! Checkpointed batches: appended chunks, interrupted writes and resuming
+
+use rust_sim::checkpoint::{run_checkpointed, Checkpoint};
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+use std::io::Write;
+
+#[test]
+fn resumed_batches_match_uninterrupted_ones() {
+    let configs = [examples::find("borge_lvl24").unwrap().config().unwrap(), examples::find("ozzy_lvl45").unwrap().config().unwrap()];
+    let path = std::env::temp_dir().join(format!("hunter-sim-checkpoint-{}.hsck", std::process::id()));
+    let stages = |runs: &[Vec<rust_sim::stats::SimResult>]| -> Vec<Vec<i32>> {
+        runs.iter().map(|r| r.iter().map(|r| r.final_stage).collect()).collect()
+    };
+
+    // Stop after the first config, then tear the last chunk as a crash would
+    let first = run_checkpointed(&configs[..1], 5, true, &path, 2, None).unwrap();
+    assert_eq!(stages(&first)[0], (0..5).map(|i| run_simulation_with_seed(&configs[0], i).final_stage).collect::<Vec<_>>());
+    let full = run_checkpointed(&configs, 5, false, &path, 2, None).unwrap();
+    let len = std::fs::metadata(&path).unwrap().len();
+    let torn = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &torn[..torn.len() - 10]).unwrap();
+    std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"garbage").unwrap();
+
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    assert_eq!(checkpoint.completed(), 9);
+    let resumed = run_checkpointed(&configs, 5, true, &path, 2, Some(checkpoint)).unwrap();
+    assert_eq!(stages(&resumed), stages(&full));
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+    assert_eq!(Checkpoint::load(&path).unwrap().completed(), 10);
+
+    // A different batch can't resume from it
+    let other = Checkpoint::load(&path).unwrap();
+    assert!(run_checkpointed(&configs, 6, true, &path, 2, Some(other)).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,40 @@
+//! Mid-fight coefficients come from the upgrade registry
+
+use rust_sim::config::HunterType;
+use rust_sim::examples;
+use rust_sim::game_data::{self, UPGRADES};
+use rust_sim::hunter::{CombatEffect, Hunter};
+use rust_sim::simulation::Snapshot;
+
+const HUNTERS: [HunterType; 3] = [HunterType::Borge, HunterType::Ozzy, HunterType::Knox];
+
+#[test]
+fn every_coefficient_the_engine_reads_is_declared() {
+    for effect in CombatEffect::ALL {
+        let (key, target) = effect.source();
+        let owners: Vec<_> = HUNTERS.into_iter().filter(|&h| UPGRADES.iter().any(|r| r.hunter == h && r.key == key)).collect();
+        assert!(!owners.is_empty(), "{:?}: no hunter has `{}`", effect, key);
+        for hunter in owners {
+            assert!(game_data::coefficient(hunter, key, target).is_some(), "{:?} `{}` has no `{}` effect", hunter, key, target);
+        }
+    }
+    // The stat formulas assert their own lookups in debug builds
+    for hunter in HUNTERS {
+        Hunter::from_config(&examples::template(hunter).unwrap());
+    }
+}
+
+#[test]
+fn the_fight_applies_the_registry_coefficients() {
+    let config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let hunter = Hunter::from_config(&config);
+    let reflect = game_data::coefficient(HunterType::Borge, "helltouch_barrier", "reflect").unwrap();
+    assert_eq!(hunter.per_level(CombatEffect::HelltouchBarrier), reflect);
+
+    // The snapshot carries the hunter's coefficients: doubling one changes the rest of the run
+    let snapshot = Snapshot::take(&config, 0, 10).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+    json["state"]["hunter"]["coefficients"][CombatEffect::HelltouchBarrier as usize] = (reflect * 2.0).into();
+    let doubled = Snapshot::from_json(&json.to_string()).unwrap();
+    assert!(doubled.resume().helltouch_barrier > snapshot.resume().helltouch_barrier);
+}
@@ -0,0 +1,18 @@
+//! The stage table `enemies` prints, and its CSV form
+
+use rust_sim::config::HunterType;
+use rust_sim::enemy::StageStats;
+
+#[test]
+fn stage_table_rows_line_up_with_the_header() {
+    let rows = StageStats::range(HunterType::Ozzy, 100, 400, 10);
+    assert_eq!(rows.len(), 31);
+    assert_eq!((rows[0].stage, rows[30].stage), (100, 400));
+    let columns = StageStats::csv_header().split(',').count();
+    for row in &rows {
+        assert_eq!(row.csv_row().split(',').count(), columns, "stage {}", row.stage);
+    }
+    // Full precision, so spreadsheets get the engine's exact numbers back
+    let hp: f64 = rows[10].csv_row().split(',').nth(1).unwrap().parse().unwrap();
+    assert_eq!(hp, rows[10].enemy.hp);
+}
@@ -0,0 +1,73 @@
+//! Engine flags: each disputed mechanic maps onto the rule it stands for
+
+use rust_sim::config::StackMode;
+use rust_sim::engine_flags::{EngineFlag, FLAGS};
+use rust_sim::examples;
+use rust_sim::hunter::{Hunter, OnKillPolicy};
+use rust_sim::simulation::run_simulation_with_seed;
+use rust_sim::BuildConfig;
+
+fn flagged(example: &str, flags: &[&str]) -> BuildConfig {
+    let mut config = examples::find(example).unwrap().config().unwrap();
+    for name in flags {
+        config.set_value(&format!("engine_flags.{}", name), &true.into()).unwrap();
+    }
+    config.normalize().unwrap();
+    config
+}
+
+#[test]
+fn unset_flags_keep_the_engine_defaults() {
+    let config = flagged("ozzy_lvl45", &[]);
+    for info in FLAGS {
+        assert_eq!(EngineFlag::parse(info.name), Some(info.flag));
+        assert_eq!(config.get_engine_flag(info.flag), info.default);
+    }
+    assert_eq!(config.mitigation_rules(), config.mitigation);
+    assert_eq!(config.stacking_rules(), config.stacking);
+}
+
+#[test]
+fn flags_map_onto_their_rules() {
+    let names: Vec<&str> = FLAGS.iter().map(|f| f.name).collect();
+    let ozzy = flagged("ozzy_lvl45", &names);
+    assert_eq!(OnKillPolicy::for_config(&ozzy), OnKillPolicy::AttackAndDeath);
+    assert_eq!(ozzy.mitigation_rules().dr_cap, Some(0.95));
+    assert_eq!(ozzy.stacking_rules().empowered_regen, Some(StackMode::Refresh));
+    assert!(Hunter::from_config(&ozzy).stun_affects_specials);
+    assert!(Hunter::from_config(&ozzy).enemy_abilities);
+
+    // The Ozzy flag leaves other hunters alone
+    let borge = flagged("borge_lvl35", &["ozzy_double_on_kill"]);
+    assert_eq!(OnKillPolicy::for_config(&borge), OnKillPolicy::Single);
+    let r = run_simulation_with_seed(&borge, 3);
+    assert_eq!(r.on_kill_calls, r.kills);
+
+    // A rule set in its own section wins over the flag
+    let mut capped = flagged("borge_lvl35", &["dr_cap_95"]);
+    capped.mitigation.dr_cap = Some(0.5);
+    assert_eq!(capped.mitigation_rules().dr_cap, Some(0.5));
+}
+
+#[test]
+fn unknown_flags_are_rejected() {
+    let mut config = flagged("borge_lvl35", &[]);
+    config.engine_flags.insert("stun_everything".to_string(), true);
+    let err = config.normalize().unwrap_err();
+    assert!(err.to_string().contains("engine_flags.stun_everything"));
+    assert!(config.set_value("engine_flags.dr_cap_95", &1.into()).is_err());
+}
+
+#[test]
+fn enemy_abilities_are_off_unless_flagged() {
+    let at_301 = |flags: &[&str]| {
+        let mut config = flagged("borge_lvl35", flags);
+        config.max_stage = Some(400);
+        config.set_value("start.stage", &301.into()).unwrap();
+        config.normalize().unwrap();
+        run_simulation_with_seed(&config, 7)
+    };
+    let (off, on) = (at_301(&[]), at_301(&["enemy_abilities"]));
+    assert!(off.enemy_attacks > 0);
+    assert_ne!(off.regenerated_hp, on.regenerated_hp);
+}
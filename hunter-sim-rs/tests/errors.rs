@@ -0,0 +1,30 @@
+//! Errors carry a stable code and the offending field for `--output json`
+
+use rust_sim::config::BuildConfig;
+use rust_sim::error::{ErrorReport, SimError};
+use rust_sim::examples;
+
+#[test]
+fn error_reports_keep_the_code_and_field() {
+    let parse = BuildConfig::from_json(r#"{"hunter": "Borge", "level": "high"}"#).unwrap_err();
+    let report = ErrorReport::from(parse);
+    assert_eq!(report.code, "config_parse");
+    assert_eq!(report.field.as_deref(), Some("level"));
+
+    let mut config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    config.max_stage = Some(0);
+    let report = ErrorReport::from(config.normalize().unwrap_err());
+    assert_eq!((report.code, report.field.as_deref()), ("config_invalid", Some("max_stage")));
+
+    // A bonuses alias of an integer input takes whole numbers only, rather than dropping the rest
+    let mut config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    config.set_value("bonuses.attraction_loot_borge", &2.0.into()).unwrap();
+    config.normalize().unwrap();
+    assert_eq!(config.gems.get("attraction_loot_borge"), Some(&2));
+    config.set_value("bonuses.attraction_loot_borge", &2.5.into()).unwrap();
+    let report = ErrorReport::from(config.normalize().unwrap_err());
+    assert_eq!(report.field.as_deref(), Some("bonuses.attraction_loot_borge"));
+
+    let report = ErrorReport::from(SimError::NoSimulations).context("running");
+    assert_eq!((report.code, report.field, report.message.as_str()), ("no_simulations", None, "running: number of simulations must be at least 1"));
+}
@@ -0,0 +1,38 @@
+//! Loot events change the loot model only, never the fight
+
+use rust_sim::events::LootEvent;
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+
+fn event(loot: f64, xp: f64, duration: Option<f64>) -> LootEvent {
+    LootEvent { name: String::new(), loot, xp, start: 0.0, duration }
+}
+
+#[test]
+fn whole_run_events_scale_loot_and_leave_combat_alone() {
+    let base = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let mut boosted = base.clone();
+    boosted.events = vec![event(2.0, 1.0, None), event(1.5, 3.0, None)];
+    for seed in 0..3 {
+        let plain = run_simulation_with_seed(&base, seed);
+        let run = run_simulation_with_seed(&boosted, seed);
+        assert_eq!(run.final_stage, plain.final_stage);
+        assert_eq!(run.damage_taken, plain.damage_taken);
+        assert!((run.total_loot / plain.total_loot - 3.0).abs() < 1e-9);
+        assert!((run.total_loot - run.event_loot - plain.total_loot).abs() < 1e-6 * plain.total_loot);
+        assert!((run.total_xp / plain.total_xp - 3.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn timed_events_only_cover_their_window() {
+    let base = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let mut boosted = base.clone();
+    boosted.events = vec![event(2.0, 2.0, Some(600.0))];
+    let plain = run_simulation_with_seed(&base, 0);
+    let run = run_simulation_with_seed(&boosted, 0);
+    assert!(run.event_loot > 0.0 && run.event_loot < plain.total_loot);
+    assert!(run.event_xp > 0.0 && run.event_xp < plain.total_xp);
+    boosted.events[0].duration = Some(0.0);
+    assert!(boosted.normalize().is_err());
+}
@@ -0,0 +1,30 @@
+//! C API: panics come back as errors, and the committed header is current
+#![cfg(feature = "ffi")]
+
+use rust_sim::ffi::{hunter_sim_debug_panic, hunter_sim_enemy_stats, hunter_sim_free, hunter_sim_last_error};
+use std::ffi::CStr;
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(hunter_sim_last_error()) }.to_string_lossy().into_owned()
+}
+
+#[test]
+fn panics_return_null_with_the_message() {
+    assert!(hunter_sim_debug_panic().is_null());
+    assert!(last_error().contains("internal error: hunter_sim_debug_panic called"), "{}", last_error());
+
+    // The library keeps working after a caught panic
+    let stats = unsafe { hunter_sim_enemy_stats(c"borge".as_ptr(), 100) };
+    assert!(!stats.is_null(), "{}", last_error());
+    unsafe { hunter_sim_free(stats) };
+}
+
+#[test]
+fn committed_header_matches_the_generated_one() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/hunter_sim.h"));
+    let committed = include_str!("../include/hunter_sim.h");
+    assert!(
+        generated == committed,
+        "include/hunter_sim.h is stale; run `cbindgen --config cbindgen.toml --output include/hunter_sim.h`"
+    );
+}
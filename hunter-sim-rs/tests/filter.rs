@@ -0,0 +1,74 @@
+//! `--filter` and `--objective` expressions: precedence, parentheses and field checking
+
+use rust_sim::filter::{Objective, RunFilter, MAX_DEPTH};
+use rust_sim::stats::{AggregatedStats, SimResult};
+
+fn run(final_stage: i32, boss_kills: i32, damage_taken: f64) -> SimResult {
+    SimResult { final_stage, boss_kills, damage_taken, max_hp: 100.0, ..Default::default() }
+}
+
+#[test]
+fn filters_follow_precedence_and_parentheses() {
+    let runs = [run(150, 1, 500.0), run(250, 2, 2000.0), run(90, 0, 50.0)];
+    let stages = |expr: &str| -> Vec<i32> {
+        RunFilter::parse(expr).unwrap().apply(&runs).iter().map(|r| r.final_stage).collect()
+    };
+    assert_eq!(stages("final_stage<200"), [150, 90]);
+    assert_eq!(stages("boss_kills >= 1 && damage_taken / max_hp > 10"), [250]);
+    assert_eq!(stages("final_stage < 100 or boss_kills == 2 and damage_taken > 1e3"), [250, 90]);
+    assert_eq!(stages("!(final_stage < 100 || boss_kills == 2)"), [150]);
+    assert_eq!(stages("(final_stage - 50) * 2 >= 200"), [150, 250]);
+}
+
+#[test]
+fn filters_reject_unknown_fields_and_bad_syntax() {
+    for expr in ["final_stag < 200", "bands > 1", "final_stage", "final_stage < 200 &&", "(final_stage < 200", "final_stage = 1"] {
+        assert!(RunFilter::parse(expr).is_err(), "{}", expr);
+    }
+}
+
+#[test]
+fn objectives_score_and_rank_batch_stats() {
+    let batch = |avg_stage: f64, p10_loot_per_hour: f64, survival_rate: f64| AggregatedStats {
+        avg_stage,
+        p10_loot_per_hour,
+        survival_rate,
+        ..Default::default()
+    };
+    let objective = Objective::parse("0.7 * p10_loot_per_hour / 1e6 + 0.3 * survival_rate").unwrap();
+    assert_eq!(objective.score(&batch(0.0, 2e6, 1.0)), 1.7);
+
+    // Highest first; a score that isn't a number ranks last
+    let batches = [batch(150.0, 0.0, 0.5), batch(0.0, 0.0, 0.0), batch(300.0, 0.0, 0.5)];
+    let per_stage = Objective::parse("survival_rate / avg_stage").unwrap();
+    let order: Vec<usize> = per_stage.rank(&batches).into_iter().map(|(i, _)| i).collect();
+    assert_eq!(order, [0, 2, 1]);
+
+    for expr in ["avg_stag", "final_stage", "avg_stage > 100", "bands", "avg_stage *"] {
+        assert!(Objective::parse(expr).is_err(), "{}", expr);
+    }
+}
+
+#[test]
+fn deep_or_long_expressions_are_errors_not_overflows() {
+    let nest = |depth: usize, inner: &str| format!("{}{}{}", "(".repeat(depth), inner, ")".repeat(depth));
+    assert!(Objective::parse(&nest(MAX_DEPTH, "avg_stage")).is_ok());
+    let err = Objective::parse(&nest(MAX_DEPTH + 1, "avg_stage")).unwrap_err();
+    assert!(err.to_string().contains("levels deep"), "{}", err);
+    assert!(RunFilter::parse(&nest(MAX_DEPTH + 1, "final_stage < 1")).unwrap_err().to_string().contains("levels deep"));
+    for expr in [nest(60_000, "avg_stage"), nest(60_000, "final_stage < 1"), format!("{}final_stage < 1", "!".repeat(60_000)), format!("final_stage < {}1", "-".repeat(60_000))] {
+        assert!(RunFilter::parse(&expr).is_err() && Objective::parse(&expr).is_err());
+    }
+    let chain = vec!["avg_stage"; 60_000].join(" + ");
+    assert!(Objective::parse(&chain).unwrap_err().to_string().contains("tokens"));
+}
+
+#[test]
+fn filters_can_name_every_numeric_run_field() {
+    let fields = serde_json::to_value(SimResult { wall_unbounded: true, ..run(150, 1, 500.0) }).unwrap();
+    for (name, value) in fields.as_object().unwrap() {
+        let Some(n) = value.as_f64().or(value.as_bool().map(|b| b as i32 as f64)) else { continue };
+        let filter = RunFilter::parse(&format!("{} == {}", name, n)).unwrap();
+        assert!(filter.matches(&serde_json::from_value(fields.clone()).unwrap()), "{}", name);
+    }
+}
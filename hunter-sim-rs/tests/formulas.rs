@@ -0,0 +1,90 @@
+//! Shared game formulas against hand-computed values and the Python port's
+
+use rust_sim::config::HunterType;
+use rust_sim::examples;
+use rust_sim::formulas::{self, gadget_loot_multiplier, gadget_multiplier};
+use rust_sim::hunter::HunterStats;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+fn near(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+}
+
+/// From the Python port's `Enemy.fetch_stats`/`Boss.fetch_stats`:
+/// (hunter, stage, enemy [hp, power, regen, speed], boss [hp, power, regen, speed])
+const STAGE_TABLE: &[(HunterType, i32, [f64; 4], [f64; 4])] = &[
+    (HunterType::Borge, 1, [13.0, 3.2, 0.0, 4.524], [1170.0, 11.616, 0.0, 10.94808]),
+    (HunterType::Borge, 150, [1746.0639, 308.21325, 12.61507904, 3.63], [157145.751, 1118.8140975, 24.2209517568, 8.7846]),
+    (HunterType::Borge, 300, [8757.46404, 1539.2565, 71.06268416, 2.73], [788171.7636, 5587.501095, 136.4403535872, 6.6066]),
+    (HunterType::Borge, 420, [81693.40632135392, 14341.086426454372, 598.4560270992191, 2.01], [7352406.568921853, 52058.14372802937, 1149.0355720305006, 4.8642]),
+    (HunterType::Ozzy, 1, [17.0, 2.1, 0.0, 3.196], [816.0, 6.3, 0.0, 7.8302]),
+    (HunterType::Ozzy, 150, [2657.7514, 309.23937, 18.73675, 2.6], [127572.0672, 927.71811, 112.4205, 6.37]),
+    (HunterType::Ozzy, 300, [40430.3026256, 4380.26689224, 131.93375, 2.0], [1940654.5260288, 13140.80067672, 791.6025, 4.9]),
+    (HunterType::Ozzy, 420, [3038061.996791237, 285322.0862001042, 1736.067353743112, 1.52], [145826975.8459794, 855966.2586003126, 10416.404122458673, 3.724]),
+    (HunterType::Knox, 1, [15.0, 2.15, 0.0, 3.795], [1800.0, 8.6, 0.0, 10.81575]),
+    (HunterType::Knox, 150, [2142.896, 259.2018, 15.5294505, 3.05], [257147.52, 1036.8072, 31.058901, 8.6925]),
+    (HunterType::Knox, 300, [20286.732827265823, 2451.3935197374904, 148.48708075661818, 2.3], [2434407.939271899, 9805.574078949961, 296.97416151323637, 6.555]),
+    (HunterType::Knox, 420, [574712.0252019247, 69426.53561046271, 4218.5594178942565, 1.7], [68965443.02423097, 277706.14244185085, 8437.118835788513, 4.845]),
+];
+
+#[test]
+fn enemy_stats_match_the_python_port() {
+    for &(hunter, stage, enemy, boss) in STAGE_TABLE {
+        for (is_boss, expected) in [(false, enemy), (true, boss)] {
+            let s = formulas::enemy_stats(stage, hunter, is_boss);
+            let got = [s.hp, s.power, s.regen, s.speed];
+            for (name, (g, e)) in ["hp", "power", "regen", "speed"].iter().zip(got.into_iter().zip(expected)) {
+                assert!(near(g, e), "{:?} stage {} boss={} {}: {} != {}", hunter, stage, is_boss, name, g, e);
+            }
+        }
+    }
+}
+
+#[test]
+fn hunter_curves_match_known_values() {
+    // (hunter, stat level, [hp, power, regen])
+    let table = [
+        (HunterType::Borge, 0.0, [43.0, 3.0, 0.02]),
+        (HunterType::Borge, 30.0, [43.0 + 30.0 * 2.56, 3.0 + 30.0 * 0.53, 0.02 + 30.0 * 0.04]),
+        (HunterType::Ozzy, 0.0, [16.0, 2.0, 0.1]),
+        (HunterType::Ozzy, 30.0, [16.0 + 30.0 * 2.18, 2.0 + 30.0 * 0.33, 0.1 + 30.0 * 0.06]),
+        (HunterType::Knox, 0.0, [20.0, 1.2, 0.05]),
+        (HunterType::Knox, 50.0, [20.0 + 50.0 * 3.0, 1.2 + 50.0 * 0.11, 0.05 + 50.0 * 0.0475]),
+    ];
+    for (hunter, level, [hp, power, regen]) in table {
+        assert!(near(formulas::base_hp(hunter, level), hp), "{:?} hp {}", hunter, level);
+        assert!(near(formulas::base_power(hunter, level), power), "{:?} power {}", hunter, level);
+        assert!(near(formulas::base_regen(hunter, level), regen), "{:?} regen {}", hunter, level);
+    }
+    // In game: 6.40s between salvos at reload_time 20
+    assert!(close(formulas::knox_reload_interval(20), 6.40));
+    assert_eq!(formulas::stage_scaling(149), 1.0);
+    assert_eq!(formulas::knox_stage_scaling(149), 1.0);
+    assert!(close(formulas::ozzy_level_multiplier(10), 1.001_f64.powi(10) * 1.02));
+    assert_eq!(formulas::creation_node_scaler(0.015, 39, 1), 1.0);
+}
+
+#[test]
+fn gadget_multipliers_step_every_ten_levels() {
+    assert_eq!(gadget_multiplier(0), 1.0);
+    assert!(close(gadget_multiplier(9), 1.027));
+    assert!(close(gadget_multiplier(10), 1.03 * 1.002));
+    assert!(close(gadget_multiplier(25), 1.075 * 1.002 * 1.002));
+    assert_eq!(gadget_loot_multiplier(0), 1.0);
+    assert!(close(gadget_loot_multiplier(10), 1.005_f64.powi(10) * 1.02));
+}
+
+#[test]
+fn gadgets_scale_every_hunters_stats() {
+    for name in ["empty_borge", "empty_ozzy", "empty_knox"] {
+        let mut config = examples::find(name).unwrap().config().unwrap();
+        let before = HunterStats::from_config(&config);
+        config.gadgets.insert("anchor_of_ages".to_string(), 20);
+        let after = HunterStats::from_config(&config);
+        assert!(close(after.max_hp / before.max_hp, gadget_multiplier(20)), "{}", name);
+        assert!(close(after.power / before.power, gadget_multiplier(20)), "{}", name);
+    }
+}
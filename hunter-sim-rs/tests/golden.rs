@@ -0,0 +1,20 @@
+//! Seeded batches reproduce results recorded from the engine before event
+//! times became integer ticks (seeds 0..50, in parallel, default switches)
+
+use rust_sim::examples;
+use rust_sim::simulation::run_and_aggregate;
+
+#[test]
+fn tick_times_match_the_recorded_float_time_runs() {
+    // (example, avg stage, avg damage taken); the Ozzy builds stun every few attacks
+    let recorded = [
+        ("ozzy_lvl29", 55.06, 7837.267402137601),
+        ("ozzy_lvl45", 136.44, 354680.5725892808),
+        ("ozzy_lvl45_ut", 140.04, 409963.1936033443),
+    ];
+    for (name, stage, damage_taken) in recorded {
+        let config = examples::find(name).unwrap().config().unwrap();
+        let stats = run_and_aggregate(&config, 50, true);
+        assert_eq!((stats.avg_stage, stats.avg_damage_taken), (stage, damage_taken), "{}", name);
+    }
+}
@@ -0,0 +1,24 @@
+//! `introspect` lists the keys the engine actually reads, with their effects
+//! and their rows of the talent and attribute registry
+
+use rust_sim::config::HunterType;
+use rust_sim::introspect::Capabilities;
+
+#[test]
+fn introspection_finds_read_keys_and_their_effects() {
+    let caps = Capabilities::new(Vec::new(), Vec::new());
+    let borge = caps.hunters.iter().find(|h| h.hunter == HunterType::Borge).unwrap();
+    let key = |section: &str, name: &str| borge.keys.iter().find(|k| k.section == section && k.key == name);
+
+    let hp = key("stats", "hp").unwrap();
+    assert!(hp.measured.iter().any(|d| d.stat == "max_hp" && d.delta > 0.0));
+    let companion = key("talents", "death_is_my_companion").unwrap();
+    assert_eq!((companion.cost, companion.max), (Some(1), Some(2)));
+    assert!(companion.measured.iter().any(|d| d.stat == "max_revives"));
+    // Registry rows travel with the key, combat-only effects included
+    assert!(companion.effects.iter().any(|e| e.target == "max_revives" && e.per_level == 1.0));
+    let impacts = key("talents", "impeccable_impacts").unwrap();
+    assert!(impacts.effects.iter().any(|e| e.target == "stun"));
+    // Ozzy's talents don't show up on Borge
+    assert!(key("talents", "echo_bullets").is_none());
+}
@@ -0,0 +1,16 @@
+//! Per-run limits end runs early and say so
+
+use rust_sim::examples;
+use rust_sim::simulation::{run_simulation_with_seed, set_max_sim_time};
+use rust_sim::stats::RunEnd;
+
+#[test]
+fn runs_stop_at_the_simulated_time_limit() {
+    let config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    set_max_sim_time(Some(600));
+    let run = run_simulation_with_seed(&config, 0);
+    set_max_sim_time(None);
+    assert_eq!((run.end, run.elapsed_time), (RunEnd::TimedOut, 600.0));
+    assert!(run.final_stage > 0 && run.total_loot > 0.0);
+    assert_ne!(run_simulation_with_seed(&config, 0).end, RunEnd::TimedOut);
+}
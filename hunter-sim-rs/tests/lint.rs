@@ -0,0 +1,76 @@
+//! The linter flags allocations that can't do anything for the hunter, and
+//! points the hunter's level doesn't give
+
+use rust_sim::config::{self, HunterType};
+use rust_sim::examples;
+use rust_sim::game_data::{Coverage, CATALOG};
+use rust_sim::hunter::HunterStats;
+use rust_sim::lint::{lint, point_budgets, LintKind};
+
+#[test]
+fn lint_flags_unused_and_over_cap_keys() {
+    let mut knox = examples::find("empty_knox").unwrap().config().unwrap();
+    knox.stats.insert("evade_chance".to_string(), 10);
+    knox.talents.insert("death_is_my_companion".to_string(), 5);
+    knox.relics.insert("book_of_mephisto".to_string(), 3);
+    let lints = lint(&knox);
+    let kind = |path: &str| lints.iter().find(|l| l.path == path).map(|l| l.kind);
+    assert_eq!(kind("stats.evade_chance"), Some(LintKind::NoEffect));
+    assert_eq!(kind("talents.death_is_my_companion"), Some(LintKind::AboveCap));
+    // Book of Mephisto is Borge's
+    assert_eq!(kind("relics.book_of_mephisto"), Some(LintKind::NoEffect));
+
+    let mut borge = examples::find("borge_lvl24").unwrap().config().unwrap();
+    assert!(lint(&borge).is_empty());
+    borge.relics.insert("book_of_mephisto".to_string(), 10);
+    assert_eq!(lint(&borge)[0].kind, LintKind::Clamped);
+}
+
+#[test]
+fn lint_flags_points_beyond_the_level() {
+    let mut config = examples::find("borge_lvl24").unwrap().config().unwrap();
+    let budgets = point_budgets(&config);
+    assert!(budgets.iter().all(|b| b.spent == b.available));
+    *config.talents.get_mut("presence_of_god").unwrap() += 1;
+    let lints = lint(&config);
+    assert_eq!((lints[0].path.as_str(), lints[0].kind), ("talents", LintKind::OverBudget));
+}
+
+#[test]
+fn catalog_matches_what_the_engine_reads() {
+    for (hunter, example) in [(HunterType::Borge, "empty_borge"), (HunterType::Ozzy, "empty_ozzy"), (HunterType::Knox, "empty_knox")] {
+        for item in CATALOG {
+            let mut config = examples::find(example).unwrap().config().unwrap();
+            let path = format!("{}.{}", item.section, item.key);
+            config.set_value(&path, &1.into()).unwrap();
+            let reads = config::record_key_reads(|| {
+                HunterStats::from_config(&config);
+            });
+            let read = reads.iter().any(|r| r.section == item.section && r.key == item.key);
+            let simulated = matches!(item.coverage(hunter), Coverage::Modeled | Coverage::Partial);
+            assert_eq!(read, simulated, "{:?} {}", hunter, path);
+        }
+    }
+}
+
+#[test]
+fn lint_flags_items_the_simulator_skips() {
+    let mut knox = examples::find("empty_knox").unwrap().config().unwrap();
+    knox.gadgets.insert("anchor_of_ages".to_string(), 10);
+    knox.gadgets.insert("trident_of_tides".to_string(), 10);
+    knox.bonuses.insert("wastarian_relic_loot_bonus".to_string(), 3.into());
+    let lints = lint(&knox);
+    let kind = |path: &str| lints.iter().find(|l| l.path == path).map(|l| l.kind);
+    assert_eq!(kind("gadgets.anchor_of_ages"), None);
+    assert_eq!(kind("gadgets.trident_of_tides"), Some(LintKind::Partial));
+    assert_eq!(kind("bonuses.wastarian_relic_loot_bonus"), Some(LintKind::Inert));
+}
+
+#[test]
+fn bundled_examples_lint_clean() {
+    for example in examples::EXAMPLES {
+        let config = example.config().unwrap();
+        assert_eq!(config.get_hunter_type(), example.hunter, "{}", example.name);
+        assert!(lint(&config).is_empty(), "{}: {:?}", example.name, lint(&config));
+    }
+}
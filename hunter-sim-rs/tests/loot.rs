@@ -0,0 +1,56 @@
+//! What each loot rate divides by
+
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+use rust_sim::stats::{AggregatedStats, SimResult};
+
+fn run(start_stage: i32, final_stage: i32, hours: f64, loot: f64) -> SimResult {
+    SimResult { start_stage, final_stage, elapsed_time: hours * 3600.0, total_loot: loot, ..Default::default() }
+}
+
+#[test]
+fn loot_rates_are_per_run_per_hour_and_per_stage() {
+    // A short run at 100/hr and a long one at 50/hr
+    let results = [run(0, 10, 1.0, 100.0), run(20, 60, 3.0, 150.0)];
+    let stats = AggregatedStats::from_results(&results);
+    assert_eq!(stats.avg_loot, 125.0);
+    assert_eq!(stats.avg_loot_per_hour, 75.0);   // Each run's rate, averaged
+    assert_eq!(stats.loot_per_sim_hour, 62.5);   // 250 loot over 4 hours
+    assert_eq!(stats.loot_per_stage, 5.0);       // 250 loot over 50 stages played
+    assert_eq!((results[1].stages_played(), results[1].loot_per_stage()), (40, 3.75));
+
+    // A run that played nothing earns no rate rather than dividing by zero
+    let empty = run(5, 5, 0.0, 0.0);
+    assert_eq!((empty.loot_per_hour(), empty.loot_per_stage()), (0.0, 0.0));
+}
+
+#[test]
+fn real_runs_report_the_stages_their_loot_covers() {
+    let mut config = examples::find("borge_lvl24").unwrap().config().unwrap();
+    let fresh = run_simulation_with_seed(&config, 1);
+    assert_eq!(fresh.start_stage, 0);
+    assert_eq!(fresh.stages_played(), fresh.final_stage);
+
+    config.start.stage = Some(50);
+    let mid = run_simulation_with_seed(&config, 1);
+    assert_eq!(mid.start_stage, 50);
+    assert_eq!(mid.stages_played(), mid.final_stage - 50);
+    assert!(mid.loot_per_stage() > 0.0);
+}
+
+#[test]
+fn loot_per_hour_percentiles_rank_the_runs() {
+    // Ten one-hour runs earning 10, 20, ..., 100
+    let results: Vec<SimResult> = (1..=10).map(|i| run(0, 10, 1.0, i as f64 * 10.0)).collect();
+    let stats = AggregatedStats::from_results(&results);
+    assert_eq!((stats.p10_loot_per_hour, stats.p90_loot_per_hour), (10.0, 90.0));
+
+    // A glass cannon: a higher mean, and a much worse bad session
+    let mut risky: Vec<SimResult> = (0..9).map(|_| run(0, 10, 1.0, 80.0)).collect();
+    risky.push(run(0, 10, 1.0, 0.0));
+    risky.rotate_right(3);
+    let steady: Vec<SimResult> = (0..10).map(|_| run(0, 10, 1.0, 70.0)).collect();
+    let (risky, steady) = (AggregatedStats::from_results(&risky), AggregatedStats::from_results(&steady));
+    assert!(risky.avg_loot_per_hour > steady.avg_loot_per_hour);
+    assert!(risky.p10_loot_per_hour < steady.p10_loot_per_hour);
+}
@@ -0,0 +1,22 @@
+//! Mitigated damage: main DR alone, and every layer together
+
+use rust_sim::examples;
+use rust_sim::hunter::Hunter;
+use rust_sim::simulation::run_simulation_with_seed;
+
+#[test]
+fn mitigated_damage_stays_main_dr_and_the_total_adds_the_other_layers() {
+    let mut config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    config.set_input("attributes.soul_of_the_minotaur", 5).unwrap();
+    config.normalize().unwrap();
+    let mitigation = Hunter::from_config(&config).mitigation;
+    assert!(mitigation.minotaur > 0.0 && mitigation.damage_reduction > 0.0);
+
+    let r = run_simulation_with_seed(&config, 11);
+    assert!(r.mitigated_damage > 0.0);
+    assert!(r.total_mitigated_damage > r.mitigated_damage);
+    // DR is the last layer, so what it took is the share of what reached it
+    let dr = mitigation.damage_reduction;
+    let reached_dr = r.damage_taken / (1.0 - dr);
+    assert!((r.mitigated_damage - reached_dr * dr).abs() <= 1e-9 * reached_dr);
+}
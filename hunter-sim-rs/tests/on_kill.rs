@@ -0,0 +1,72 @@
+//! On-kill pipeline: how often on-kill effects run per kill under each policy
+
+use rust_sim::examples;
+use rust_sim::hunter::{Hunter, KillCause, OnKillPolicy};
+use rust_sim::simulation::{resolve_kill, run_simulation_with_seed, FastRng};
+use rust_sim::BuildConfig;
+
+fn ozzy(double_on_kill: bool) -> BuildConfig {
+    let mut config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    config.mods.insert("double_on_kill".to_string(), double_on_kill);
+    config
+}
+
+#[test]
+fn policy_defaults_to_single() {
+    let config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    assert_eq!(OnKillPolicy::for_config(&config), OnKillPolicy::Single);
+    assert_eq!(OnKillPolicy::for_config(&ozzy(true)), OnKillPolicy::AttackAndDeath);
+}
+
+#[test]
+fn only_attack_kills_double_proc() {
+    for cause in [KillCause::Attack, KillCause::Trample, KillCause::Reflect] {
+        assert_eq!(OnKillPolicy::Single.calls(cause), 1);
+    }
+    assert_eq!(OnKillPolicy::AttackAndDeath.calls(KillCause::Attack), 2);
+    assert_eq!(OnKillPolicy::AttackAndDeath.calls(KillCause::Trample), 1);
+    assert_eq!(OnKillPolicy::AttackAndDeath.calls(KillCause::Reflect), 1);
+}
+
+#[test]
+fn resolve_kill_counts_one_kill_per_call() {
+    let mut rng = FastRng::new(1);
+    let mut hunter = Hunter::from_config(&ozzy(true));
+
+    resolve_kill(&mut hunter, &mut rng, false, KillCause::Attack);
+    assert_eq!((hunter.result.kills, hunter.result.on_kill_calls), (1, 2));
+
+    resolve_kill(&mut hunter, &mut rng, false, KillCause::Trample);
+    assert_eq!((hunter.result.kills, hunter.result.on_kill_calls), (2, 3));
+
+    let mut single = Hunter::from_config(&ozzy(false));
+    resolve_kill(&mut single, &mut rng, true, KillCause::Attack);
+    assert_eq!((single.result.kills, single.result.on_kill_calls), (1, 1));
+}
+
+#[test]
+fn full_run_matches_policy() {
+    // Ozzy only kills with attacks, so every kill double-procs under the Python policy
+    for seed in 0..5 {
+        let single = run_simulation_with_seed(&ozzy(false), seed);
+        assert!(single.kills > 0);
+        assert_eq!(single.on_kill_calls, single.kills);
+
+        let double = run_simulation_with_seed(&ozzy(true), seed);
+        assert_eq!(double.on_kill_calls, 2 * double.kills);
+    }
+}
+
+#[test]
+fn double_proc_raises_unfair_advantage_healing() {
+    // ozzy_lvl45 has Unfair Advantage, so twice the rolls heal more over a batch
+    let heal = |config: &BuildConfig| -> f64 {
+        (0..20)
+            .map(|seed| {
+                let r = run_simulation_with_seed(config, seed);
+                r.unfair_advantage_healing / r.kills as f64
+            })
+            .sum()
+    };
+    assert!(heal(&ozzy(true)) > 1.5 * heal(&ozzy(false)));
+}
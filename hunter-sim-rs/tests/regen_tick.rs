@@ -0,0 +1,35 @@
+//! Regen tick granularity: finer ticks heal the same per second, and timed
+//! buffs last the same seconds. Alone in its binary, since the tick is global
+
+use rust_sim::examples;
+use rust_sim::hunter::Hunter;
+use rust_sim::simulation::{run_simulation_with_seed, set_regen_tick};
+
+#[test]
+fn regen_ticks_scale_to_their_length() {
+    let config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    let mut whole = Hunter::from_config(&config);
+    whole.hp = whole.max_hp / 2.0;
+    whole.empowered_regen = 2;
+    let mut quarters = whole.clone();
+    whole.regen_hp();
+    for whole_seconds in [0, 0, 0, 1] {
+        quarters.regen_hp_over(0.25, whole_seconds);
+        // The buff holds until the clock passes the whole second
+        assert_eq!(quarters.empowered_regen, 2 - whole_seconds);
+    }
+    assert!((quarters.hp - whole.hp).abs() <= 1e-9 * whole.hp);
+    assert_eq!(whole.empowered_regen, 1);
+
+    let default = serde_json::to_string(&run_simulation_with_seed(&config, 3)).unwrap();
+    set_regen_tick(Some(250));
+    let fine = run_simulation_with_seed(&config, 3);
+    set_regen_tick(None);
+    assert_eq!(serde_json::to_string(&run_simulation_with_seed(&config, 3)).unwrap(), default);
+
+    // The clock moves in quarter seconds and every one of them is accounted for
+    assert_eq!((fine.elapsed_time * 4.0).fract(), 0.0);
+    let seconds: f64 = fine.sustain.iter().map(|p| p.seconds).sum();
+    assert!((seconds - fine.elapsed_time).abs() < 1e-6);
+    assert!(fine.regenerated_hp > 0.0);
+}
@@ -0,0 +1,46 @@
+//! Scripted combat scenarios: every file in tests/scenarios must pass
+
+use rust_sim::scenario::{self, ScenarioFile};
+use rust_sim::simulation::{FastRng, Roll, ScriptedRng, SimRng};
+use std::path::Path;
+
+#[test]
+fn scenario_files_pass() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    let mut files = 0;
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "yaml") {
+            continue;
+        }
+        files += 1;
+        let report = ScenarioFile::from_file(&path).and_then(|f| scenario::run_file(&f)).unwrap();
+        for case in report.cases.iter().filter(|c| !c.passed()) {
+            for check in case.checks.iter().filter(|c| !c.passed) {
+                eprintln!("{}: {}: {} expected {}, got {:?}", path.display(), case.name, check.key, check.expected, check.actual);
+            }
+        }
+        assert_eq!(report.failures(), 0, "{} has failing cases", path.display());
+    }
+    assert!(files > 0);
+}
+
+#[test]
+fn scripted_rng_continues_with_the_seeded_stream() {
+    let mut scripted = ScriptedRng::new(Roll::parse_script("crit, no-proc, 0.25").unwrap(), 7);
+    let mut seeded = FastRng::new(7);
+    assert_eq!(scripted.f64(), 0.0);
+    assert!(scripted.f64() > 0.999_999);
+    assert_eq!(scripted.f64(), 0.25);
+    assert_eq!(scripted.remaining(), 0);
+    for _ in 0..40 {
+        assert_eq!(SimRng::f64(&mut scripted), seeded.f64());
+    }
+}
+
+#[test]
+fn rolls_reject_unknown_outcomes() {
+    assert!(Roll::parse_script("crit, critt").is_err());
+    assert!(Roll::parse_script("1.5").is_err());
+    assert_eq!(Roll::parse_script("no_evade").unwrap(), vec![Roll::Fail]);
+}
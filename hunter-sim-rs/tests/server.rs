@@ -0,0 +1,53 @@
+//! API server: request limits and cross-origin headers
+
+use rust_sim::server;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+fn start(origins: &[&str]) -> SocketAddr {
+    let api = server::bind("127.0.0.1:0").unwrap().allow_origins(origins.iter().map(|o| o.to_string()).collect());
+    let addr = api.local_addr().unwrap();
+    std::thread::spawn(move || api.run());
+    addr
+}
+
+/// Send one request and return the raw response
+fn send(addr: SocketAddr, method: &str, path: &str, origin: &str, body: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nOrigin: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        origin,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn oversized_jobs_are_rejected_and_cors_is_opt_in() {
+    let closed = start(&[]);
+    let health = send(closed, "GET", "/health", "https://example.com", "");
+    assert!(health.starts_with("HTTP/1.1 200"), "{}", health);
+    assert!(!health.contains("Access-Control-Allow-Origin"), "{}", health);
+
+    let open = start(&["https://example.com"]);
+    let allowed = send(open, "OPTIONS", "/simulate", "https://example.com", "");
+    assert!(allowed.contains("Access-Control-Allow-Origin: https://example.com"), "{}", allowed);
+    let other = send(open, "OPTIONS", "/simulate", "https://elsewhere.example", "");
+    assert!(!other.contains("Access-Control-Allow-Origin"), "{}", other);
+
+    // Too many simulations, alone or multiplied out, is a 400 rather than an overflow
+    let config = rust_sim::examples::find("borge_lvl35").unwrap().config().unwrap();
+    let config = serde_json::to_string(&config).unwrap();
+    let sims = send(open, "POST", "/simulate", "", &format!("{{\"config\": {}, \"num_sims\": {}}}", config, usize::MAX));
+    assert!(sims.starts_with("HTTP/1.1 400"), "{}", sims);
+    let configs = vec![config.as_str(); 200].join(",");
+    let compare = send(open, "POST", "/compare", "", &format!("{{\"configs\": [{}], \"num_sims\": 1000000}}", configs));
+    assert!(compare.starts_with("HTTP/1.1 400") && compare.contains("a job may run"), "{}", compare);
+}
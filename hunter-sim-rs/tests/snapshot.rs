@@ -0,0 +1,104 @@
+//! Mid-run snapshots: resuming with the saved RNG must finish the run exactly.
+//! Configured starting states and carry-over begin a run mid-way without one
+
+use rust_sim::examples;
+use rust_sim::simulation::{run_simulation_with_seed, Snapshot};
+use serde_json::json;
+
+#[test]
+fn resumed_snapshot_matches_uninterrupted_run() {
+    for name in ["borge_lvl35", "ozzy_lvl45"] {
+        let config = examples::find(name).unwrap().config().unwrap();
+        for seed in 0..3 {
+            let full = serde_json::to_string(&run_simulation_with_seed(&config, seed)).unwrap();
+            for stage in [0, 37, 100] {
+                let snapshot = Snapshot::take(&config, seed, stage).unwrap();
+                let restored = Snapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+                assert_eq!(serde_json::to_string(&restored.resume()).unwrap(), full, "{} seed {} stage {}", name, seed, stage);
+            }
+        }
+    }
+}
+
+#[test]
+fn replays_start_at_the_snapshot_stage() {
+    let config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let snapshot = Snapshot::take(&config, 0, 100).unwrap();
+    for result in snapshot.replay(20, false) {
+        assert_eq!(result.boss_stages_fought.first(), Some(&100));
+    }
+}
+
+#[test]
+fn hit_audit_finishes_the_run_unchanged_and_accounts_for_every_hit() {
+    let config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    let snapshot = Snapshot::take(&config, 1, 100).unwrap();
+    let before = snapshot.hunter().result.damage_taken;
+    let (result, hits) = snapshot.audit_hits();
+    assert_eq!(serde_json::to_string(&result).unwrap(), serde_json::to_string(&snapshot.resume()).unwrap());
+    let taken: f64 = hits.iter().map(|h| h.taken).sum();
+    assert!((before + taken - result.damage_taken).abs() <= 1e-6 * result.damage_taken);
+    for hit in &hits {
+        assert_eq!(hit.steps.last().map_or(hit.raw - hit.blocked, |s| s.remaining), hit.taken);
+    }
+}
+
+#[test]
+fn damage_curve_finishes_the_run_unchanged_and_accounts_for_every_attack() {
+    let config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    let snapshot = Snapshot::take(&config, 1, 90).unwrap();
+    let before = snapshot.hunter().result.clone();
+    let (result, curve) = snapshot.damage_curve(10.0);
+    assert_eq!(serde_json::to_string(&result).unwrap(), serde_json::to_string(&snapshot.resume()).unwrap());
+
+    // Back to back from the snapshot to the end, with every attack in one window
+    assert_eq!(curve.first().map(|w| w.start), Some(snapshot.elapsed_time()));
+    assert_eq!(curve.last().map(|w| w.end), Some(result.elapsed_time));
+    assert!(curve.windows(2).all(|w| w[0].end == w[1].start));
+    let damage: f64 = curve.iter().map(|w| w.damage).sum();
+    assert!((before.damage + damage - result.damage).abs() <= 1e-9 * result.damage);
+    assert_eq!(before.attacks + curve.iter().map(|w| w.attacks).sum::<i32>(), result.attacks);
+    assert!(curve.iter().any(|w| w.boss) && curve.iter().all(|w| w.stage >= 90));
+}
+
+#[test]
+fn configured_start_begins_mid_run_and_earns_only_what_it_plays() {
+    let mut config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    config.set_value("start.stage", &json!(180)).unwrap();
+    config.set_value("start.hp_fraction", &json!(0.5)).unwrap();
+    config.normalize().unwrap();
+    let snapshot = Snapshot::take(&config, 0, 180).unwrap();
+    assert_eq!(snapshot.elapsed_time(), 0.0);
+    assert_eq!(snapshot.hunter().hp, snapshot.hunter().max_hp * 0.5);
+
+    let result = run_simulation_with_seed(&config, 0);
+    assert!(result.final_stage >= 180);
+    assert!(result.boss_stages_fought.iter().all(|&s| s >= 200));
+    // Only the stages played earn: XP per stage is the fresh run's
+    let fresh = run_simulation_with_seed(&examples::find("borge_lvl35").unwrap().config().unwrap(), 0);
+    let per_stage = result.total_xp / (result.final_stage - 180) as f64;
+    let fresh_per_stage = fresh.total_xp / fresh.final_stage as f64;
+    assert!((per_stage - fresh_per_stage).abs() <= 1e-9 * fresh_per_stage);
+
+    config.start.stage = Some(config.get_max_stage());
+    assert_eq!(config.normalize().unwrap_err().field(), "start.stage");
+}
+
+#[test]
+fn persistence_carries_stacks_into_the_reported_run() {
+    let mut config = examples::find("empty_knox").unwrap().config().unwrap();
+    config.set_value("talents.calypsos_advantage", &json!(5)).unwrap();
+    for stat in ["hp", "power", "regen", "damage_reduction", "block_chance"] {
+        config.set_value(&format!("stats.{}", stat), &json!(60)).unwrap();
+    }
+    let cold = Snapshot::take(&config, 0, 0).unwrap();
+    assert_eq!(cold.hunter().hundred_souls_stacks, 0);
+
+    config.set_value("persistence.hundred_souls", &json!(true)).unwrap();
+    config.normalize().unwrap();
+    let warm = Snapshot::take(&config, 0, 0).unwrap();
+    assert!(warm.hunter().hundred_souls_stacks > 0);
+    assert_eq!(warm.elapsed_time(), 0.0);
+    // The reported run is the one after the warm-ups, resumed exactly
+    assert_eq!(serde_json::to_string(&warm.resume()).unwrap(), serde_json::to_string(&run_simulation_with_seed(&config, 0)).unwrap());
+}
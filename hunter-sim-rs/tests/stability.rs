@@ -0,0 +1,23 @@
+//! Stability batches are seeded apart, so their spread is the sampling noise
+
+use rust_sim::examples;
+use rust_sim::stability::measure;
+
+#[test]
+fn stability_sizes_the_batch_from_the_spread_between_seeds() {
+    let config = examples::find("borge_lvl24").unwrap().config().unwrap();
+    let report = measure(&config, 4, 3, 0.01, false);
+    assert_eq!((report.sims, report.batches, report.metrics.len()), (4, 3, 8));
+    for m in &report.metrics {
+        assert!(m.std_dev.is_finite() && m.relative_spread >= 0.0, "{}", m.metric);
+        assert!(m.min <= m.mean && m.mean <= m.max, "{}", m.metric);
+    }
+    // Runs differ in length, so the batches can't all agree
+    let time = report.metrics.iter().find(|m| m.metric == "avg_time").unwrap();
+    assert!(time.std_dev > 0.0);
+    assert!(report.recommended_sims >= time.sims_for_target.unwrap());
+
+    // A looser target never asks for more runs
+    let loose = measure(&config, 4, 3, 0.5, false);
+    assert!(loose.recommended_sims <= report.recommended_sims);
+}
@@ -0,0 +1,54 @@
+//! Vectid Elixir stacking: accumulate by default, refresh or a cap from the build's `stacking` section
+
+use rust_sim::config::{StackMode, StackingRules, EMPOWERED_REGEN_TICKS};
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+use rust_sim::BuildConfig;
+
+fn ozzy(rules: &[(&str, serde_json::Value)]) -> BuildConfig {
+    let mut config = examples::find("ozzy_lvl45").unwrap().config().unwrap();
+    config.attributes.insert("vectid_elixir".to_string(), 3);
+    for (key, value) in rules {
+        config.set_value(key, value).unwrap();
+    }
+    config
+}
+
+#[test]
+fn rules_combine_procs() {
+    let accumulate = StackingRules::default();
+    assert_eq!(accumulate.empowered_regen_after_proc(3, EMPOWERED_REGEN_TICKS), (8, 0));
+
+    let refresh = StackingRules { empowered_regen: Some(StackMode::Refresh), ..Default::default() };
+    assert_eq!(refresh.empowered_regen_after_proc(3, EMPOWERED_REGEN_TICKS), (5, 3));
+    assert_eq!(refresh.empowered_regen_after_proc(0, EMPOWERED_REGEN_TICKS), (5, 0));
+
+    let capped = StackingRules { empowered_regen_cap: Some(7), ..Default::default() };
+    assert_eq!(capped.empowered_regen_after_proc(4, EMPOWERED_REGEN_TICKS), (7, 2));
+}
+
+#[test]
+fn runs_report_uptime_under_each_rule() {
+    let accumulate = run_simulation_with_seed(&ozzy(&[]), 1);
+    assert!(accumulate.empowered_regen_procs > 0);
+    assert_eq!(accumulate.empowered_regen_dropped, 0);
+    assert!(accumulate.empowered_regen_peak > EMPOWERED_REGEN_TICKS);
+
+    let refresh = run_simulation_with_seed(&ozzy(&[("stacking.empowered_regen", "refresh".into())]), 1);
+    assert_eq!(refresh.empowered_regen_peak, EMPOWERED_REGEN_TICKS);
+    assert!(refresh.empowered_regen_dropped > 0);
+
+    let capped = run_simulation_with_seed(&ozzy(&[("stacking.empowered_regen_cap", 10.into())]), 1);
+    assert!(capped.empowered_regen_peak <= 10);
+    for r in [&accumulate, &refresh, &capped] {
+        assert!(r.empowered_regen_seconds > 0.0 && r.empowered_regen_seconds <= r.elapsed_time);
+    }
+}
+
+#[test]
+fn bad_rules_are_rejected() {
+    let mut config = ozzy(&[]);
+    assert!(config.set_value("stacking.empowered_regen", &"stack".into()).is_err());
+    config.stacking.empowered_regen_cap = Some(0);
+    assert!(config.normalize().is_err());
+}
@@ -0,0 +1,18 @@
+//! The per-phase sustain split accounts for all of a run's damage and healing
+
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+
+#[test]
+fn phases_add_up_to_the_run_totals() {
+    let config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    let run = run_simulation_with_seed(&config, 0);
+    let close = |a: f64, b: f64| (a - b).abs() <= 1e-6 * b.max(1.0);
+    let sum = |value: fn(&rust_sim::stats::PhaseSustain) -> f64| run.sustain.iter().map(value).sum::<f64>();
+    assert!(close(sum(|p| p.damage_taken), run.damage_taken));
+    assert!(close(sum(|p| p.regen), run.regenerated_hp));
+    assert!(close(sum(|p| p.lifesteal), run.lifesteal));
+    assert!(close(sum(|p| p.procs), run.life_of_the_hunt_healing_effective + run.unfair_advantage_healing_effective));
+    assert!(close(sum(|p| p.overheal), run.overheal_regen + run.overheal_lifesteal + run.overheal_procs));
+    assert!(close(sum(|p| p.seconds), run.elapsed_time));
+}
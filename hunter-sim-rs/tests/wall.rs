@@ -0,0 +1,40 @@
+//! Wall-stage extrapolation from a run's recent stages
+
+use rust_sim::examples;
+use rust_sim::simulation::run_simulation_with_seed;
+use rust_sim::stats::RunEnd;
+use rust_sim::wall::StageFit;
+
+fn fit(stages: std::ops::Range<i32>, damage: f64, healing: f64) -> StageFit {
+    let mut fit = StageFit::default();
+    for stage in stages {
+        fit.push(stage, 60.0, damage, healing);
+    }
+    fit
+}
+
+#[test]
+fn walls_come_from_the_current_band_and_spend_revives() {
+    // 10 HP lost per stage from 35: down at 113, revived to 80, down again at 121
+    let mut flat = fit(90..100, 1000.0, 0.0);
+    for stage in 101..106 {
+        flat.push(stage, 60.0, 10.0, 0.0);
+    }
+    assert_eq!(flat.predict_wall(110, 35.0, 100.0, 1), Some(Some(121)));
+    assert_eq!(flat.stage_time_growth(), Some(1.0));
+
+    assert_eq!(fit(101..111, 10.0, 20.0).predict_wall(111, 50.0, 100.0, 0), Some(None));
+    assert_eq!(fit(101..104, 10.0, 0.0).predict_wall(104, 50.0, 100.0, 0), None);
+}
+
+#[test]
+fn a_build_max_stage_caps_its_runs_and_gets_a_prediction() {
+    let mut config = examples::find("borge_lvl35").unwrap().config().unwrap();
+    assert_eq!(config.get_max_stage(), 300);
+    config.max_stage = Some(150);
+    let run = run_simulation_with_seed(&config, 0);
+    assert_eq!((run.final_stage, run.end), (150, RunEnd::MaxStage));
+    assert!(run.wall_stage.is_some_and(|w| w >= 150));
+    config.max_stage = Some(0);
+    assert!(config.normalize().is_err());
+}